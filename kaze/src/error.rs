@@ -0,0 +1,410 @@
+use crate::graph::{
+    MAX_SIGNAL_BIT_WIDTH, MIN_SIGNAL_BIT_WIDTH, RESERVED_NAME_PREFIXES, RUST_KEYWORDS,
+};
+
+use std::error;
+use std::fmt;
+use std::panic::Location;
+
+/// An error describing an invalid graph construction or an invalid [`Module`](crate::Module) hierarchy.
+///
+/// Graph construction methods that can fail (eg. [`Module::input`](crate::Module::input)) panic with this error's message, and have `try_*` counterparts (eg. [`Module::try_input`](crate::Module::try_input)) that return it instead.
+/// Likewise, generators panic if a [`Module`](crate::Module) hierarchy is invalid, which can be checked up front with [`Module::validate`](crate::Module::validate).
+///
+/// Each variant carries the path of the [`Module`](crate::Module) instance the error occurred in, where each instance name in the hierarchy is separated by `.` (eg. `"top.cpu.alu"`), as well as the names and bit widths involved.
+/// Where an error refers to a previously-declared item, it also carries the source location that item was declared at, which is included in the error's message.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// match m.try_input("i", 0) {
+///     Err(Error::InputBitWidthOutOfRange { module_path, name, bit_width }) => {
+///         assert_eq!(module_path, "m");
+///         assert_eq!(name, "i");
+///         assert_eq!(bit_width, 0);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An input was created with a bit width less than [`MIN_SIGNAL_BIT_WIDTH`] or greater than [`MAX_SIGNAL_BIT_WIDTH`].
+    InputBitWidthOutOfRange {
+        module_path: String,
+        name: String,
+        bit_width: u32,
+    },
+    /// An item was created with the same name as another item in the same module.
+    DuplicateName {
+        module_path: String,
+        module_name: String,
+        name: String,
+        kind: ItemKind,
+        existing_kind: ItemKind,
+        existing_location: &'static Location<'static>,
+    },
+    /// An item was created with a name that's reserved for names in generated code.
+    ///
+    /// Names beginning with `__` are always reserved. Except for [`Reset`](crate::Reset)s (whose names are always prefixed in generated code), names beginning with `posedge_` are also reserved, as are `clk` and `reset_n` (the implicit clock and reset ports of generated Verilog modules) and `new`, `reset`, `prop` and `update_trace` (methods of generated Rust simulators).
+    ReservedName {
+        module_path: String,
+        module_name: String,
+        name: String,
+        kind: ItemKind,
+    },
+    /// An item was created with a name that is a Rust keyword or (except for [`Reset`](crate::Reset)s) a Verilog keyword, and therefore can't be used in generated code.
+    KeywordName {
+        module_path: String,
+        module_name: String,
+        name: String,
+        kind: ItemKind,
+    },
+    /// Signals that belong to different modules were combined.
+    SignalsFromDifferentModules { module_path: String },
+    /// A multiplexer was created with a conditional that isn't 1 bit wide.
+    MuxCondBitWidth { module_path: String, bit_width: u32 },
+    /// A multiplexer was created with inputs that have different bit widths.
+    MuxBitWidthMismatch {
+        module_path: String,
+        when_true_bit_width: u32,
+        when_false_bit_width: u32,
+    },
+    /// A range of bits was specified with a lower bound outside of the source signal.
+    BitsRangeLowOutOfBounds {
+        module_path: String,
+        bit_width: u32,
+        range_low: u32,
+    },
+    /// A range of bits was specified with an upper bound outside of the source signal.
+    BitsRangeHighOutOfBounds {
+        module_path: String,
+        bit_width: u32,
+        range_high: u32,
+    },
+    /// A range of bits was specified with a lower bound greater than its upper bound.
+    BitsRangeLowGreaterThanHigh {
+        module_path: String,
+        range_high: u32,
+        range_low: u32,
+    },
+    /// A write port was specified for a memory that already has one.
+    MemWritePortAlreadySpecified {
+        module_path: String,
+        module_name: String,
+        mem_name: String,
+    },
+    /// A write port was specified for a memory with an address signal whose bit width doesn't match the memory's address bit width.
+    MemWritePortAddressBitWidth {
+        module_path: String,
+        module_name: String,
+        mem_name: String,
+        bit_width: u32,
+        address_bit_width: u32,
+    },
+    /// A write port was specified for a memory with a value signal whose bit width doesn't match the memory's element bit width.
+    MemWritePortValueBitWidth {
+        module_path: String,
+        module_name: String,
+        mem_name: String,
+        bit_width: u32,
+        element_bit_width: u32,
+    },
+    /// A write port was specified for a memory with an enable signal that isn't 1 bit wide.
+    MemWritePortEnableBitWidth {
+        module_path: String,
+        module_name: String,
+        mem_name: String,
+        bit_width: u32,
+    },
+    /// A register in the hierarchy isn't driven.
+    UndrivenRegister {
+        root_module_name: String,
+        module_path: String,
+        module_name: String,
+        register_name: String,
+        location: &'static Location<'static>,
+    },
+    /// A register in the hierarchy is assigned to a reset but doesn't have a default value.
+    ResetRegisterWithoutDefaultValue {
+        root_module_name: String,
+        module_path: String,
+        module_name: String,
+        register_name: String,
+        reset_name: String,
+        location: &'static Location<'static>,
+    },
+    /// An input of a module instance in the hierarchy isn't driven.
+    UndrivenInstanceInput {
+        root_module_name: String,
+        module_path: String,
+        module_name: String,
+        instance_module_name: String,
+        instance_name: String,
+        input_name: String,
+        location: &'static Location<'static>,
+    },
+    /// A clock of a module instance in the hierarchy isn't driven.
+    UndrivenInstanceClock {
+        root_module_name: String,
+        module_path: String,
+        module_name: String,
+        instance_module_name: String,
+        instance_name: String,
+        clock_name: String,
+    },
+    /// A memory in the hierarchy doesn't have any read ports.
+    MemWithoutReadPorts {
+        root_module_name: String,
+        module_path: String,
+        module_name: String,
+        mem_name: String,
+        location: &'static Location<'static>,
+    },
+    /// A memory in the hierarchy has neither initial contents nor a write port.
+    MemWithoutInitialContentsOrWritePort {
+        root_module_name: String,
+        module_path: String,
+        module_name: String,
+        mem_name: String,
+        location: &'static Location<'static>,
+    },
+    /// A combinational path in the hierarchy forms a loop, which necessarily passes through an output of a module instance.
+    ///
+    /// `cycle` lists the named signals along the loop in the order values flow through them, starting and ending with the reported output, where each signal is qualified with the path of the module it belongs to (eg. `["top.inner.o", "top.inner.i", "top.inner.o"]`).
+    CombinationalLoop {
+        root_module_name: String,
+        module_path: String,
+        module_name: String,
+        output_name: String,
+        location: &'static Location<'static>,
+        cycle: Vec<String>,
+    },
+    /// Two modules in the hierarchy have the same module name but aren't instances of the same definition, so they can't share a generated Verilog module.
+    ConflictingModuleDefinitions {
+        root_module_name: String,
+        module_name: String,
+        module_path: String,
+        existing_module_path: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InputBitWidthOutOfRange { bit_width, .. } => {
+                if *bit_width < MIN_SIGNAL_BIT_WIDTH {
+                    write!(f, "Cannot create an input with {} bit(s). Signals must not be narrower than {} bit(s).", bit_width, MIN_SIGNAL_BIT_WIDTH)
+                } else {
+                    write!(f, "Cannot create an input with {} bit(s). Signals must not be wider than {} bit(s).", bit_width, MAX_SIGNAL_BIT_WIDTH)
+                }
+            }
+            Error::DuplicateName {
+                module_name,
+                name,
+                kind,
+                existing_kind,
+                existing_location,
+                ..
+            } => write!(f, "Cannot create {} {} called \"{}\" in module \"{}\" because {} {} with the same name already exists in this module. The existing {} was declared at {}:{}.", kind.article(), kind, name, module_name, existing_kind.article(), existing_kind, existing_kind, existing_location.file(), existing_location.line()),
+            Error::ReservedName {
+                module_name,
+                name,
+                kind,
+                ..
+            } => match RESERVED_NAME_PREFIXES
+                .iter()
+                .find(|prefix| name.starts_with(*prefix))
+            {
+                Some(prefix) => write!(f, "Cannot create {} {} called \"{}\" in module \"{}\" because names beginning with \"{}\" are reserved for generated code.", kind.article(), kind, name, module_name, prefix),
+                None => write!(f, "Cannot create {} {} called \"{}\" in module \"{}\" because \"{}\" is reserved for generated code.", kind.article(), kind, name, module_name, name),
+            },
+            Error::KeywordName {
+                module_name,
+                name,
+                kind,
+                ..
+            } => write!(f, "Cannot create {} {} called \"{}\" in module \"{}\" because \"{}\" is a {} keyword.", kind.article(), kind, name, module_name, name, if RUST_KEYWORDS.contains(&name.as_str()) { "Rust" } else { "Verilog" }),
+            Error::SignalsFromDifferentModules { .. } => {
+                write!(f, "Attempted to combine signals from different modules.")
+            }
+            Error::MuxCondBitWidth { .. } => {
+                write!(f, "Multiplexer conditionals can only be 1 bit wide.")
+            }
+            Error::MuxBitWidthMismatch {
+                when_true_bit_width,
+                when_false_bit_width,
+                ..
+            } => write!(
+                f,
+                "Cannot multiplex signals with different bit widths ({} and {}, respectively).",
+                when_true_bit_width, when_false_bit_width
+            ),
+            Error::BitsRangeLowOutOfBounds {
+                bit_width,
+                range_low,
+                ..
+            } => write!(f, "Cannot specify a range of bits where the lower bound is greater than or equal to the number of bits in the source signal. The bounds must be in the range [0, {}] for a signal with a width of {} bits, but a lower bound of {} was given.", bit_width - 1, bit_width, range_low),
+            Error::BitsRangeHighOutOfBounds {
+                bit_width,
+                range_high,
+                ..
+            } => write!(f, "Cannot specify a range of bits where the upper bound is greater than or equal to the number of bits in the source signal. The bounds must be in the range [0, {}] for a signal with a width of {} bits, but an upper bound of {} was given.", bit_width - 1, bit_width, range_high),
+            Error::BitsRangeLowGreaterThanHigh { .. } => write!(
+                f,
+                "Cannot specify a range of bits where the lower bound is greater than the upper bound."
+            ),
+            Error::MemWritePortAlreadySpecified {
+                module_name,
+                mem_name,
+                ..
+            } => write!(f, "Attempted to specify a write port for memory \"{}\" in module \"{}\", but this memory already has a write port.", mem_name, module_name),
+            Error::MemWritePortAddressBitWidth {
+                module_name,
+                mem_name,
+                bit_width,
+                address_bit_width,
+                ..
+            } => write!(f, "Attempted to specify a write port for memory \"{}\" in module \"{}\" with an address signal with {} bit(s), but this memory has {} address bit(s).", mem_name, module_name, bit_width, address_bit_width),
+            Error::MemWritePortValueBitWidth {
+                module_name,
+                mem_name,
+                bit_width,
+                element_bit_width,
+                ..
+            } => write!(f, "Attempted to specify a write port for memory \"{}\" in module \"{}\" with a value signal with {} bit(s), but this memory has {} element bit(s).", mem_name, module_name, bit_width, element_bit_width),
+            Error::MemWritePortEnableBitWidth {
+                module_name,
+                mem_name,
+                bit_width,
+                ..
+            } => write!(f, "Attempted to specify a write port for memory \"{}\" in module \"{}\" with an enable signal with {} bit(s), but memory read/write ports are required to be 1 bit wide.", mem_name, module_name, bit_width),
+            Error::UndrivenRegister {
+                root_module_name,
+                module_name,
+                register_name,
+                location,
+                ..
+            } => write!(f, "Cannot generate code for module \"{}\" because module \"{}\" contains a register called \"{}\" which is not driven. The register was declared at {}:{}.", root_module_name, module_name, register_name, location.file(), location.line()),
+            Error::ResetRegisterWithoutDefaultValue {
+                root_module_name,
+                module_name,
+                register_name,
+                reset_name,
+                location,
+                ..
+            } => write!(f, "Cannot generate code for module \"{}\" because module \"{}\" contains a register called \"{}\" which is assigned to reset \"{}\" but doesn't have a default value. The register was declared at {}:{}.", root_module_name, module_name, register_name, reset_name, location.file(), location.line()),
+            Error::UndrivenInstanceInput {
+                root_module_name,
+                module_name,
+                instance_module_name,
+                instance_name,
+                input_name,
+                location,
+                ..
+            } => write!(f, "Cannot generate code for module \"{}\" because module \"{}\" contains an instance of module \"{}\" called \"{}\" whose input \"{}\" is not driven. The input was declared at {}:{}.", root_module_name, module_name, instance_module_name, instance_name, input_name, location.file(), location.line()),
+            Error::UndrivenInstanceClock {
+                root_module_name,
+                module_name,
+                instance_module_name,
+                instance_name,
+                clock_name,
+                ..
+            } => write!(f, "Cannot generate code for module \"{}\" because module \"{}\" contains an instance of module \"{}\" called \"{}\" whose clock \"{}\" is not driven.", root_module_name, module_name, instance_module_name, instance_name, clock_name),
+            Error::MemWithoutReadPorts {
+                root_module_name,
+                module_name,
+                mem_name,
+                location,
+                ..
+            } => write!(f, "Cannot generate code for module \"{}\" because module \"{}\" contains a memory called \"{}\" which doesn't have any read ports. The memory was declared at {}:{}.", root_module_name, module_name, mem_name, location.file(), location.line()),
+            Error::MemWithoutInitialContentsOrWritePort {
+                root_module_name,
+                module_name,
+                mem_name,
+                location,
+                ..
+            } => write!(f, "Cannot generate code for module \"{}\" because module \"{}\" contains a memory called \"{}\" which doesn't have initial contents or a write port specified. At least one of the two is required. The memory was declared at {}:{}.", root_module_name, module_name, mem_name, location.file(), location.line()),
+            Error::CombinationalLoop {
+                root_module_name,
+                module_name,
+                output_name,
+                location,
+                cycle,
+                ..
+            } => write!(f, "Cannot generate code for module \"{}\" because module \"{}\" contains an output called \"{}\" which forms a combinational loop with itself. The loop passes through {}. The output's source signal was created at {}:{}.", root_module_name, module_name, output_name, cycle.join(" -> "), location.file(), location.line()),
+            Error::ConflictingModuleDefinitions {
+                root_module_name,
+                module_name,
+                module_path,
+                existing_module_path,
+            } => write!(f, "Cannot generate Verilog code for module \"{}\" because module instances \"{}\" and \"{}\" are both called \"{}\" but aren't instances of the same definition. Modules with the same name must be instantiated from a single definition with `Module::instance`.", root_module_name, existing_module_path, module_path, module_name),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+pub(crate) trait UnwrapOrPanic<T> {
+    fn unwrap_or_panic(self) -> T;
+}
+
+impl<T> UnwrapOrPanic<T> for Result<T, Error> {
+    #[track_caller]
+    fn unwrap_or_panic(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+/// The kind of a named item in a [`Module`](crate::Module), used to describe name collisions in [`Error`]s.
+///
+/// All of these items share a single namespace within their [`Module`](crate::Module).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ItemKind {
+    /// An [`Input`](crate::Input).
+    Input,
+    /// An [`Output`](crate::Output).
+    Output,
+    /// A [`Register`](crate::Register).
+    Register,
+    /// A [`Mem`](crate::Mem).
+    Mem,
+    /// A [`Module`](crate::Module) instance.
+    Instance,
+    /// A [`Clock`](crate::Clock).
+    Clock,
+    /// A [`Reset`](crate::Reset).
+    Reset,
+}
+
+impl ItemKind {
+    fn article(&self) -> &'static str {
+        match self {
+            ItemKind::Input | ItemKind::Output => "an",
+            _ => "a",
+        }
+    }
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ItemKind::Input => "input",
+            ItemKind::Output => "output",
+            ItemKind::Register => "register",
+            ItemKind::Mem => "memory",
+            ItemKind::Instance => "module instance",
+            ItemKind::Clock => "clock",
+            ItemKind::Reset => "reset",
+        })
+    }
+}
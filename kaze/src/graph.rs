@@ -1,16 +1,37 @@
+mod bundle;
+mod clock;
 mod constant;
 mod context;
+mod generator;
+mod instance;
 pub(crate) mod internal_signal;
 mod mem;
 mod module;
+mod node;
 mod register;
+mod reset;
 mod signal;
+mod signal_vec;
+mod signed;
+mod stream;
 mod sugar;
 
+pub use bundle::*;
+pub use clock::*;
 pub use constant::*;
 pub use context::*;
+pub use generator::*;
+pub use internal_signal::{
+    AdditiveBinOp, ComparisonBinOp, CountOp, DivisionBinOp, ExtendOp, ReduceOp, RotateBinOp,
+    ShiftBinOp, SimpleBinOp, UnOp,
+};
 pub use mem::*;
 pub use module::*;
+pub use node::*;
 pub use register::*;
+pub use reset::*;
 pub use signal::*;
+pub use signal_vec::*;
+pub use signed::*;
+pub use stream::*;
 pub use sugar::*;
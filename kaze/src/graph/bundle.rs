@@ -0,0 +1,299 @@
+use super::module::*;
+use super::signal::*;
+
+/// A named collection of [`Signal`]s with fixed bit widths, usually declared with the [`bundle!`](crate::bundle!) macro.
+///
+/// A `Bundle` groups related signals (such as the fields of a pipeline register) so that they can be passed around as a single value.
+/// It can be packed into a single [`Signal`] with [`pack`](Self::pack), unpacked from one with [`unpack`](Self::unpack), and a matching set of inputs or outputs can be created for a [`Module`] with [`Module::bundle_input`] and [`Module::bundle_output`].
+///
+/// When packed, fields are concatenated in declaration order, so the first field occupies the most significant bits.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// bundle! {
+///     pub struct Stage {
+///         pub valid: 1,
+///         pub pc: 32,
+///     }
+/// }
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let stage = Stage {
+///     valid: m.input("valid", 1),
+///     pc: m.input("pc", 32),
+/// };
+/// let packed = stage.pack();
+/// assert_eq!(packed.bit_width(), Stage::bit_width());
+///
+/// let unpacked = Stage::unpack(packed);
+/// m.output("pc_out", unpacked.pc);
+/// ```
+pub trait Bundle<'a>: Sized {
+    /// Returns the names and bit widths of this `Bundle`'s fields, in declaration order.
+    fn fields() -> Vec<(&'static str, u32)>;
+
+    /// Creates a `Bundle` from one [`Signal`] per field, in declaration order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of signals doesn't match the number of fields.
+    fn from_signals(signals: Vec<&'a dyn Signal<'a>>) -> Self;
+
+    /// Returns this `Bundle`'s fields' [`Signal`]s, in declaration order.
+    fn signals(&self) -> Vec<&'a dyn Signal<'a>>;
+
+    /// Returns the total bit width of this `Bundle`'s fields.
+    fn bit_width() -> u32 {
+        Self::fields().iter().map(|&(_, bit_width)| bit_width).sum()
+    }
+
+    /// Packs this `Bundle`'s fields into a single [`Signal`] by concatenating them in declaration order, with the first field occupying the most significant bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any field's bit width doesn't match its declared bit width, or if the fields belong to different [`Module`]s.
+    #[track_caller]
+    fn pack(&self) -> &'a dyn Signal<'a> {
+        check_field_bit_widths(self);
+        self.signals()
+            .into_iter()
+            .reduce(|packed, signal| packed.concat(signal))
+            .expect("Attempted to pack a bundle without any fields.")
+    }
+
+    /// Unpacks a `Bundle` from `signal`, which should have been created by [`pack`](Self::pack) or have an equivalent layout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `signal`'s bit width doesn't match this `Bundle`'s [`bit_width`](Self::bit_width).
+    #[track_caller]
+    fn unpack(signal: &'a dyn Signal<'a>) -> Self {
+        let bit_width = Self::bit_width();
+        if signal.bit_width() != bit_width {
+            panic!(
+                "Attempted to unpack a {}-bit signal into a bundle with {} bit(s).",
+                signal.bit_width(),
+                bit_width
+            );
+        }
+        let mut high = bit_width;
+        Self::from_signals(
+            Self::fields()
+                .into_iter()
+                .map(|(_, field_bit_width)| {
+                    let field = signal.bits(high - 1, high - field_bit_width);
+                    high -= field_bit_width;
+                    field
+                })
+                .collect(),
+        )
+    }
+}
+
+#[track_caller]
+pub(crate) fn check_field_bit_widths<'a, B: Bundle<'a>>(bundle: &B) {
+    for ((name, bit_width), signal) in B::fields().into_iter().zip(bundle.signals()) {
+        if signal.bit_width() != bit_width {
+            panic!("Attempted to use a {}-bit signal for bundle field \"{}\", but this field is declared with {} bit(s).", signal.bit_width(), name, bit_width);
+        }
+    }
+}
+
+/// A set of [`Input`]s created for each field of a [`Bundle`], created by the [`Module::bundle_input`] method.
+#[must_use]
+pub struct BundleInput<'a, B> {
+    pub(crate) inputs: Vec<&'a Input<'a>>,
+    pub(crate) value: B,
+}
+
+impl<'a, B: Bundle<'a>> BundleInput<'a, B> {
+    /// Returns the [`Bundle`] whose fields are this `BundleInput`'s [`Input`]s, for use inside their [`Module`].
+    pub fn value(&self) -> &B {
+        &self.value
+    }
+
+    /// Returns this `BundleInput`'s [`Input`]s, in field declaration order.
+    pub fn inputs(&self) -> &[&'a Input<'a>] {
+        &self.inputs
+    }
+
+    /// Drives each of this `BundleInput`'s [`Input`]s with the corresponding field of `source`, which belongs to the parent of the [`Input`]s' [`Module`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any field of `source` doesn't match its declared bit width, or under the same conditions as [`Input::drive`] for any field. All fields are checked before any [`Input`] is driven, so a panic leaves every [`Input`] undriven.
+    #[track_caller]
+    pub fn drive(&self, source: &B) {
+        self.check_drive(source);
+        for (input, signal) in self.inputs.iter().zip(source.signals()) {
+            input.drive(signal);
+        }
+    }
+
+    #[track_caller]
+    pub(crate) fn check_drive(&self, source: &B) {
+        check_field_bit_widths(source);
+        for (input, signal) in self.inputs.iter().zip(source.signals()) {
+            input.check_drive(signal.internal_signal());
+        }
+    }
+}
+
+/// Declares a struct implementing [`Bundle`], whose fields are [`Signal`]s with the specified bit widths.
+///
+/// Each field is declared as `name: bit_width`, where `bit_width` is any expression that evaluates to a `u32`.
+/// The generated struct has a single lifetime parameter, and each of its fields is a `&'a dyn Signal<'a>`.
+/// It also derives [`Clone`] and [`Copy`].
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// const XLEN: u32 = 32;
+///
+/// bundle! {
+///     /// A decoded instruction
+///     pub struct Decoded {
+///         pub valid: 1,
+///         pub rd: 5,
+///         /// Sign-extended immediate
+///         pub imm: XLEN,
+///     }
+/// }
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let i = m.bundle_input::<Decoded>("i");
+/// let o = m.bundle_output("o", i.value());
+/// ```
+#[macro_export]
+macro_rules! bundle {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident: $bit_width:expr
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy)]
+        $vis struct $name<'a> {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: &'a dyn $crate::Signal<'a>,
+            )+
+        }
+
+        impl<'a> $crate::Bundle<'a> for $name<'a> {
+            fn fields() -> ::std::vec::Vec<(&'static str, u32)> {
+                ::std::vec![$((::std::stringify!($field), $bit_width)),+]
+            }
+
+            fn from_signals(signals: ::std::vec::Vec<&'a dyn $crate::Signal<'a>>) -> Self {
+                let field_count = <Self as $crate::Bundle<'a>>::fields().len();
+                if signals.len() != field_count {
+                    ::std::panic!(
+                        "Attempted to create a bundle with {} field(s) from {} signal(s).",
+                        field_count,
+                        signals.len()
+                    );
+                }
+                let mut signals = signals.into_iter();
+                $name {
+                    $($field: signals.next().unwrap(),)+
+                }
+            }
+
+            fn signals(&self) -> ::std::vec::Vec<&'a dyn $crate::Signal<'a>> {
+                ::std::vec![$(self.$field),+]
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    bundle! {
+        struct TestBundle {
+            a: 1,
+            b: 8,
+            c: 3,
+        }
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let c = Context::new();
+
+        let m = c.module("m", "M");
+        let i = m.bundle_input::<TestBundle>("i");
+
+        assert_eq!(TestBundle::bit_width(), 12);
+        assert_eq!(
+            i.inputs().iter().map(|i| i.name()).collect::<Vec<_>>(),
+            vec!["i_a", "i_b", "i_c"]
+        );
+
+        let packed = i.value().pack();
+        assert_eq!(packed.bit_width(), 12);
+
+        let unpacked = TestBundle::unpack(packed);
+        assert_eq!(unpacked.a.bit_width(), 1);
+        assert_eq!(unpacked.b.bit_width(), 8);
+        assert_eq!(unpacked.c.bit_width(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to unpack a 11-bit signal into a bundle with 12 bit(s).")]
+    fn unpack_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("m", "M");
+        let i = m.input("i", 11);
+
+        // Panic
+        let _ = TestBundle::unpack(i);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to use a 2-bit signal for bundle field \"c\", but this field is declared with 3 bit(s)."
+    )]
+    fn pack_field_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("m", "M");
+        let b = TestBundle {
+            a: m.input("a", 1),
+            b: m.input("b", 8),
+            c: m.input("c", 2),
+        };
+
+        // Panic
+        let _ = b.pack();
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to create a bundle with 3 field(s) from 2 signal(s).")]
+    fn from_signals_count_error() {
+        let c = Context::new();
+
+        let m = c.module("m", "M");
+
+        // Panic
+        let _ = TestBundle::from_signals(vec![m.low(), m.high()]);
+    }
+}
@@ -0,0 +1,155 @@
+use super::module::*;
+
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+use std::ptr;
+
+/// An explicitly-declared clock, created by the [`Module::clock`] method.
+///
+/// All [`Register`](crate::Register)s and [`Mem`](crate::Mem)s in kaze are driven by their [`Module`]'s implicit clock unless they're bound to a `Clock` with [`Register::clock`](crate::Register::clock) or [`Mem::clock`](crate::Mem::clock), respectively.
+/// Each `Clock` forms a separate clock domain, which makes it possible to describe designs with more than one clock (for example, a system clock and a pixel clock).
+///
+/// A `Clock` declared in a top-level [`Module`] is an additional clock input for that `Module`, and generated code exposes it alongside the implicit clock (for example, the generated Rust simulator has a `posedge_<name>` method for each clock domain).
+/// A `Clock` declared in a [`Module`] that has a parent must be driven by one of its parent's `Clock`s using the [`drive`](Self::drive) method.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let pixel_clk = m.clock("pixel_clk");
+///
+/// let my_reg = m.reg("my_reg", 32);
+/// my_reg.clock(pixel_clk); // my_reg is updated on positive edges of pixel_clk
+/// my_reg.drive_next(!my_reg);
+/// m.output("my_output", my_reg);
+/// ```
+#[must_use]
+pub struct Clock<'a> {
+    pub(crate) module: &'a Module<'a>,
+
+    pub(crate) name: String,
+    pub(crate) driven_clock: RefCell<Option<&'a Clock<'a>>>,
+}
+
+impl<'a> Clock<'a> {
+    /// Returns this `Clock`'s name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the `Clock` driving this `Clock` from its [`Module`]'s parent, if it's driven.
+    pub fn driver(&self) -> Option<&'a Clock<'a>> {
+        *self.driven_clock.borrow()
+    }
+
+    /// Drives this `Clock` with `clock`, which must belong to the parent of this `Clock`'s [`Module`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `Clock`'s [`Module`] doesn't have a parent, if `clock` doesn't belong to that parent, or if this `Clock` is already driven.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    /// let pixel_clk = m.clock("pixel_clk");
+    ///
+    /// let inner = m.module("inner", "Inner");
+    /// let inner_clk = inner.clock("inner_clk");
+    /// inner_clk.drive(pixel_clk);
+    /// ```
+    #[track_caller]
+    pub fn drive(&'a self, clock: &'a Clock<'a>) {
+        self.module.check_modifiable();
+        if let Some(parent) = self.module.parent {
+            if !ptr::eq(parent, clock.module) {
+                panic!("Attempted to drive a clock called \"{}\" on an instance of \"{}\" with a clock from a different module than that instance's parent module.", self.name, self.module.name);
+            }
+        } else {
+            panic!("Attempted to drive a clock called \"{}\" in module \"{}\", but this module doesn't have a parent module.", self.name, self.module.name);
+        }
+        let mut driven_clock = self.driven_clock.borrow_mut();
+        if driven_clock.is_some() {
+            panic!("Attempted to drive a clock called \"{}\" on an instance of \"{}\", but this clock is already driven for this instance.", self.name, self.module.name);
+        }
+        *driven_clock = Some(clock);
+    }
+}
+
+impl<'a> Eq for &'a Clock<'a> {}
+
+impl<'a> Hash for &'a Clock<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(*self as *const _ as usize)
+    }
+}
+
+impl<'a> PartialEq for &'a Clock<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(*self, *other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to drive a clock called \"clk2\" in module \"A\", but this module doesn't have a parent module."
+    )]
+    fn drive_without_parent_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let clk1 = m.clock("clk1");
+        let clk2 = m.clock("clk2");
+
+        // Panic
+        clk2.drive(clk1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to drive a clock called \"inner_clk\" on an instance of \"Inner\" with a clock from a different module than that instance's parent module."
+    )]
+    fn drive_from_non_parent_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let inner = m.module("inner", "Inner");
+        let inner_clk = inner.clock("inner_clk");
+        let other_clk = inner.clock("other_clk");
+
+        // Panic
+        inner_clk.drive(other_clk);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to drive a clock called \"inner_clk\" on an instance of \"Inner\", but this clock is already driven for this instance."
+    )]
+    fn drive_already_driven_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let clk1 = m.clock("clk1");
+        let clk2 = m.clock("clk2");
+        let inner = m.module("inner", "Inner");
+        let inner_clk = inner.clock("inner_clk");
+
+        inner_clk.drive(clk1);
+
+        // Panic
+        inner_clk.drive(clk2);
+    }
+}
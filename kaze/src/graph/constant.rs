@@ -1,7 +1,11 @@
+use crate::runtime::WideUint;
+
 /// A container for different types of integer constant values.
 ///
 /// This type isn't typically used explicitly, as the graph API always takes `Constant` parameters as `Into<Constant>`, and `Constant` implements `From` for most of Rust's unsigned integer types. If an API entry point requires a `Constant`, prefer passing integer values/literals directly.
 ///
+/// Values wider than 128 bits can be specified as arrays of 64-bit words in little-endian order (or as [`WideUint`](crate::runtime::WideUint)s), which are stored in the [`Wide`](Self::Wide) variant.
+///
 /// # Examples
 ///
 /// ```
@@ -16,7 +20,10 @@
 /// let c = m.reg("data", 20);
 /// c.default_value(5u32);
 /// let d = m.lit(42u32, 8);
+/// let e = m.lit([0xdeadbeefu64, 0, 0, 0xfadebabe], 256);
 /// ```
+#[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Constant {
     /// Contains a boolean value
     Bool(bool),
@@ -26,6 +33,8 @@ pub enum Constant {
     U64(u64),
     /// Contains an unsigned, 128-bit value
     U128(u128),
+    /// Contains an unsigned value of arbitrary width, stored as 64-bit words in little-endian order
+    Wide(Vec<u64>),
 }
 
 impl Constant {
@@ -36,15 +45,51 @@ impl Constant {
             Constant::U32(value) => 32 - value.leading_zeros(),
             Constant::U64(value) => 64 - value.leading_zeros(),
             Constant::U128(value) => 128 - value.leading_zeros(),
+            Constant::Wide(ref words) => words
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(_, &word)| word != 0)
+                .map_or(0, |(i, word)| (i as u32 + 1) * 64 - word.leading_zeros()),
         }
     }
 
-    pub(crate) fn numeric_value(&self) -> u128 {
-        match *self {
-            Constant::Bool(value) => value.into(),
-            Constant::U32(value) => value.into(),
-            Constant::U64(value) => value.into(),
-            Constant::U128(value) => value,
+    /// Returns this value as 64-bit words in little-endian order, truncated or zero-extended to `word_count` words.
+    pub(crate) fn words(&self, word_count: usize) -> Vec<u64> {
+        let mut ret = match *self {
+            Constant::Bool(value) => vec![value as u64],
+            Constant::U32(value) => vec![value as u64],
+            Constant::U64(value) => vec![value],
+            Constant::U128(value) => vec![value as u64, (value >> 64) as u64],
+            Constant::Wide(ref words) => words.clone(),
+        };
+        ret.resize(word_count, 0);
+        ret
+    }
+
+    /// Returns the least significant 128 bits of this value.
+    pub(crate) fn low_u128(&self) -> u128 {
+        let words = self.words(2);
+        words[0] as u128 | (words[1] as u128) << 64
+    }
+
+    /// Formats this value as a hexadecimal string with no prefix, for use in generated code and diagnostics.
+    pub(crate) fn hex_string(&self) -> String {
+        let words = self.words(self.required_bits().div_ceil(64).max(1) as usize);
+        let mut words = words.iter().rev();
+        let mut ret = format!("{:x}", words.next().unwrap());
+        for word in words {
+            ret.push_str(&format!("{:016x}", word));
+        }
+        ret
+    }
+
+    /// Formats this value for diagnostics; in decimal if it fits in 128 bits, otherwise in hexadecimal.
+    pub(crate) fn value_string(&self) -> String {
+        if self.required_bits() <= 128 {
+            format!("{}", self.low_u128())
+        } else {
+            format!("0x{}", self.hex_string())
         }
     }
 }
@@ -84,3 +129,15 @@ impl From<u128> for Constant {
         Constant::U128(value)
     }
 }
+
+impl<const N: usize> From<[u64; N]> for Constant {
+    fn from(value: [u64; N]) -> Self {
+        Constant::Wide(value.to_vec())
+    }
+}
+
+impl<const N: usize> From<WideUint<N>> for Constant {
+    fn from(value: WideUint<N>) -> Self {
+        Constant::Wide(value.words().to_vec())
+    }
+}
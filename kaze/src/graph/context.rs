@@ -1,7 +1,10 @@
+use super::clock::*;
 use super::internal_signal::*;
 use super::mem::*;
 use super::module::*;
 use super::register::*;
+use super::reset::*;
+use super::signed::*;
 
 use typed_arena::Arena;
 
@@ -9,14 +12,14 @@ use std::cell::RefCell;
 
 // TODO: Move, doc
 pub trait ModuleParent<'a> {
-    // TODO: Update doc
-    /// Creates a new [`Module`] called `name` in this `Context`.
+    /// Creates a new [`Module`] instance called `instance_name` whose definition is called `name` in this parent.
     ///
-    /// Conventionally, `name` should be `CamelCase`, though this is not enforced.
+    /// Conventionally, `instance_name` should be `snake_case` and `name` should be `CamelCase`, though this is not enforced.
     ///
     /// # Panics
     ///
-    /// Panics if a [`Module`] with the same `name` already exists in this `Context`.
+    /// Panics if a [`Module`] instance with the same `instance_name` already exists in this parent.
+    /// If this parent is a [`Module`], also panics if `instance_name` is already used by any other item in it, is reserved for names in generated code (see [`Error::ReservedName`](crate::Error::ReservedName)), or is a Rust or Verilog keyword.
     ///
     /// # Examples
     ///
@@ -29,7 +32,7 @@ pub trait ModuleParent<'a> {
     /// let another_mod = c.module("another_mod", "AnotherMod");
     /// ```
     ///
-    /// The following example panics by creating a `Module` with the same `name` as a previously-created `Module` in the same `Context`:
+    /// The following example panics by creating a `Module` with the same `instance_name` as a previously-created `Module` in the same `Context`:
     ///
     /// ```should_panic
     /// use kaze::*;
@@ -41,7 +44,12 @@ pub trait ModuleParent<'a> {
     ///
     /// let _ = c.module("a", "A"); // Non-unique name, panic!
     /// ```
-    fn module(&'a self, instance_name: impl Into<String>, name: impl Into<String>) -> &Module;
+    #[track_caller]
+    fn module(
+        &'a self,
+        instance_name: impl Into<String>,
+        name: impl Into<String>,
+    ) -> &'a Module<'a>;
 }
 
 /// A top-level container/owner object for a [`Module`] graph.
@@ -56,7 +64,7 @@ pub trait ModuleParent<'a> {
 /// let c = Context::new();
 ///
 /// let m = c.module("m", "MyModule");
-/// m.output("out", m.input("in", 1));
+/// m.output("o", m.input("i", 1));
 /// ```
 #[must_use]
 pub struct Context<'a> {
@@ -66,11 +74,17 @@ pub struct Context<'a> {
     pub(super) output_data_arena: Arena<OutputData<'a>>,
     pub(super) output_arena: Arena<Output<'a>>,
     pub(super) signal_arena: Arena<InternalSignal<'a>>,
+    pub(super) signed_signal_arena: Arena<SignedSignal<'a>>,
     pub(super) register_data_arena: Arena<RegisterData<'a>>,
     pub(super) register_arena: Arena<Register<'a>>,
     pub(super) mem_arena: Arena<Mem<'a>>,
+    pub(super) clock_arena: Arena<Clock<'a>>,
+    pub(super) reset_arena: Arena<Reset<'a>>,
 
     pub(super) modules: RefCell<Vec<&'a Module<'a>>>,
+
+    // Guards of the currently-open `when` blocks, innermost last
+    pub(super) when_guards: RefCell<Vec<&'a InternalSignal<'a>>>,
 }
 
 impl<'a> Context<'a> {
@@ -91,23 +105,50 @@ impl<'a> Context<'a> {
             output_data_arena: Arena::new(),
             output_arena: Arena::new(),
             signal_arena: Arena::new(),
+            signed_signal_arena: Arena::new(),
             register_data_arena: Arena::new(),
             register_arena: Arena::new(),
             mem_arena: Arena::new(),
+            clock_arena: Arena::new(),
+            reset_arena: Arena::new(),
 
             modules: RefCell::new(Vec::new()),
+
+            when_guards: RefCell::new(Vec::new()),
         }
     }
 }
 
+impl<'a> Default for Context<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> ModuleParent<'a> for Context<'a> {
     // TODO: Docs, error handling
-    fn module(&'a self, instance_name: impl Into<String>, name: impl Into<String>) -> &Module {
+    #[track_caller]
+    fn module(
+        &'a self,
+        instance_name: impl Into<String>,
+        name: impl Into<String>,
+    ) -> &'a Module<'a> {
         let instance_name = instance_name.into();
         let name = name.into();
+        if self
+            .modules
+            .borrow()
+            .iter()
+            .any(|module| module.instance_name == instance_name)
+        {
+            panic!(
+                "A module instance called \"{}\" already exists in this context.",
+                instance_name
+            );
+        }
         let module = self
             .module_arena
-            .alloc(Module::new(self, None, instance_name, name));
+            .alloc(Module::new(self, None, instance_name, name, None));
         self.modules.borrow_mut().push(module);
         module
     }
@@ -123,4 +164,15 @@ mod tests {
 
         assert!(c.modules.borrow().is_empty());
     }
+
+    #[test]
+    #[should_panic(expected = "A module instance called \"a\" already exists in this context.")]
+    fn duplicate_instance_name_error() {
+        let c = Context::new();
+
+        let _ = c.module("a", "A");
+
+        // Panic
+        let _ = c.module("a", "B");
+    }
 }
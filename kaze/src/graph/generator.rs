@@ -0,0 +1,329 @@
+use super::context::*;
+use super::module::*;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The parameters of a [`ModuleGenerator`], which select a specialization of its definition.
+///
+/// Parameters are compared and hashed to find previously-generated definitions, and their [`params`](Self::params) are used to derive each specialization's name.
+pub trait ModuleParams: Clone + Eq + Hash {
+    /// Returns the names and values of these parameters, in the order they should appear in derived module names (eg. `[("W", "32"), ("D", "16")]`).
+    fn params(&self) -> Vec<(&'static str, String)>;
+}
+
+type BuildFn<'a, P> = dyn Fn(&'a Module<'a>, &P) + 'a;
+
+/// A parameterised [`Module`] definition, which builds and caches one specialization per distinct set of parameters.
+///
+/// A generator wraps a function that builds a [`Module`] from a [`ModuleParams`] value.
+/// The first time a set of parameters is requested, a new top-level [`Module`] is built with that function; subsequent requests with equal parameters reuse the same definition, and instances are created from it with [`Module::instance`].
+///
+/// Each specialization is named after the generator and its parameters, eg. a generator called `"Fifo"` with parameters `[("W", "32"), ("D", "16")]` produces a definition called `Fifo_W32_D16`. Characters in parameter names and values that aren't valid in identifiers are replaced with `_`.
+/// The parameters are also recorded on the definition (see [`Module::parameters`]) and emitted as comments in generated Verilog code, with characters such as line breaks escaped.
+/// The limitations of [`Module::instance`] apply to the instances created by a generator as well: each of them is a copy of its definition's graph, while generated Verilog and Rust simulator code share the definition between them. As with any other definition, a specialization can't be modified once it's been instantiated.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// #[derive(Clone, PartialEq, Eq, Hash)]
+/// struct DelayParams {
+///     width: u32,
+///     depth: u32,
+/// }
+///
+/// impl ModuleParams for DelayParams {
+///     fn params(&self) -> Vec<(&'static str, String)> {
+///         vec![("W", self.width.to_string()), ("D", self.depth.to_string())]
+///     }
+/// }
+///
+/// let c = Context::new();
+///
+/// let delay = ModuleGenerator::new(&c, "Delay", |m, p: &DelayParams| {
+///     let mut value: &dyn Signal = m.input("i", p.width);
+///     for stage in 0..p.depth {
+///         let r = m.reg(format!("stage{}", stage), p.width);
+///         r.drive_next(value);
+///         value = r;
+///     }
+///     m.output("o", value);
+/// });
+///
+/// let top = c.module("top", "Top");
+/// let a = delay.instance(top, "a", DelayParams { width: 32, depth: 16 });
+/// let b = delay.instance(top, "b", DelayParams { width: 32, depth: 16 }); // Reuses a's definition
+/// let d = delay.instance(top, "d", DelayParams { width: 8, depth: 2 });
+/// assert_eq!(a.name(), "Delay_W32_D16");
+/// assert_eq!(b.name(), "Delay_W32_D16");
+/// assert_eq!(d.name(), "Delay_W8_D2");
+/// ```
+#[must_use]
+pub struct ModuleGenerator<'a, P> {
+    context: &'a Context<'a>,
+
+    name: String,
+    build: Box<BuildFn<'a, P>>,
+
+    definitions: RefCell<HashMap<P, &'a Module<'a>>>,
+}
+
+impl<'a, P: ModuleParams> ModuleGenerator<'a, P> {
+    /// Creates a new `ModuleGenerator` called `name` in `context`, which builds each specialization by calling `build` with an empty top-level [`Module`] and the specialization's parameters.
+    pub fn new(
+        context: &'a Context<'a>,
+        name: impl Into<String>,
+        build: impl Fn(&'a Module<'a>, &P) + 'a,
+    ) -> ModuleGenerator<'a, P> {
+        ModuleGenerator {
+            context,
+
+            name: name.into(),
+            build: Box::new(build),
+
+            definitions: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns this `ModuleGenerator`'s name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the definition specialized for `params`, building it first if it doesn't exist yet.
+    ///
+    /// The returned [`Module`] is a top-level [`Module`] whose definition and instance names are both the derived specialization name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `params` derives the same name as a different set of parameters that was previously used with this `ModuleGenerator`, or if any [`Module`] in this `ModuleGenerator`'s [`Context`] (at any level of the hierarchy, including definitions built by other `ModuleGenerator`s) already has the derived name as its definition name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// #[derive(Clone, PartialEq, Eq, Hash)]
+    /// struct Width(u32);
+    ///
+    /// impl ModuleParams for Width {
+    ///     fn params(&self) -> Vec<(&'static str, String)> {
+    ///         vec![("W", self.0.to_string())]
+    ///     }
+    /// }
+    ///
+    /// let c = Context::new();
+    ///
+    /// let inverter = ModuleGenerator::new(&c, "Inverter", |m, p: &Width| {
+    ///     m.output("o", !m.input("i", p.0));
+    /// });
+    ///
+    /// let def = inverter.definition(Width(8));
+    /// assert_eq!(def.name(), "Inverter_W8");
+    /// assert_eq!(def.parameters(), [("W".to_string(), "8".to_string())]);
+    /// assert!(std::ptr::eq(def, inverter.definition(Width(8))));
+    /// ```
+    #[track_caller]
+    pub fn definition(&self, params: P) -> &'a Module<'a> {
+        if let Some(&definition) = self.definitions.borrow().get(&params) {
+            return definition;
+        }
+
+        let params_list = params
+            .params()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect::<Vec<_>>();
+        let name = params_list
+            .iter()
+            .fold(self.name.clone(), |name, (param_name, value)| {
+                format!("{}_{}{}", name, sanitize(param_name), sanitize(value))
+            });
+        if self
+            .definitions
+            .borrow()
+            .values()
+            .any(|definition| definition.name == name)
+        {
+            panic!("Module generator \"{}\" derived the name \"{}\" for two different sets of parameters.", self.name, name);
+        }
+        if definition_exists(&self.context.modules.borrow(), &name) {
+            panic!("Module generator \"{}\" derived the name \"{}\", but a module definition with this name already exists in this context.", self.name, name);
+        }
+
+        // `build` may use this generator for other specializations, so the cache must not be borrowed while it runs
+        let definition = self.context.module(name.clone(), name);
+        *definition.parameters.borrow_mut() = params_list;
+        (self.build)(definition, &params);
+        self.definitions.borrow_mut().insert(params, definition);
+        definition
+    }
+
+    /// Creates a new [`Module`] instance called `instance_name` in `parent` from the definition specialized for `params`, building that definition first if it doesn't exist yet.
+    ///
+    /// This is equivalent to `parent.instance(instance_name, self.definition(params))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`definition`](Self::definition) and [`Module::instance`].
+    #[track_caller]
+    pub fn instance(
+        &self,
+        parent: &'a Module<'a>,
+        instance_name: impl Into<String>,
+        params: P,
+    ) -> &'a Module<'a> {
+        parent.instance(instance_name, self.definition(params))
+    }
+}
+
+// Returns true if any of `modules` or the `Module`s they contain has the definition name `name`
+fn definition_exists<'a>(modules: &[&'a Module<'a>], name: &str) -> bool {
+    modules
+        .iter()
+        .any(|module| module.name == name || definition_exists(&module.modules.borrow(), name))
+}
+
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    struct TestParams {
+        name: &'static str,
+        width: u32,
+    }
+
+    impl ModuleParams for TestParams {
+        fn params(&self) -> Vec<(&'static str, String)> {
+            vec![("N", self.name.to_string()), ("W", self.width.to_string())]
+        }
+    }
+
+    #[test]
+    fn definitions_are_cached() {
+        let c = Context::new();
+
+        let generator = ModuleGenerator::new(&c, "Gen", |m, p: &TestParams| {
+            m.output("o", m.input("i", p.width));
+        });
+
+        let top = c.module("top", "Top");
+        let a = generator.instance(
+            top,
+            "a",
+            TestParams {
+                name: "x",
+                width: 4,
+            },
+        );
+        let b = generator.instance(
+            top,
+            "b",
+            TestParams {
+                name: "x",
+                width: 4,
+            },
+        );
+        let d = generator.instance(
+            top,
+            "d",
+            TestParams {
+                name: "x-y",
+                width: 8,
+            },
+        );
+
+        assert_eq!(a.name(), "Gen_Nx_W4");
+        assert_eq!(b.name(), "Gen_Nx_W4");
+        assert_eq!(d.name(), "Gen_Nx_y_W8");
+        assert_eq!(
+            d.parameters(),
+            [
+                ("N".to_string(), "x-y".to_string()),
+                ("W".to_string(), "8".to_string())
+            ]
+        );
+        // Top, plus one definition per distinct set of parameters
+        assert_eq!(c.modules.borrow().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Module generator \"Gen\" derived the name \"Gen_Nx_y_W4\" for two different sets of parameters."
+    )]
+    fn name_collision_error() {
+        let c = Context::new();
+
+        let generator = ModuleGenerator::new(&c, "Gen", |m, p: &TestParams| {
+            m.output("o", m.input("i", p.width));
+        });
+
+        let _ = generator.definition(TestParams {
+            name: "x-y",
+            width: 4,
+        });
+
+        // Panic
+        let _ = generator.definition(TestParams {
+            name: "x_y",
+            width: 4,
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Module generator \"Gen\" derived the name \"Gen_Nx_W4\", but a module definition with this name already exists in this context."
+    )]
+    fn existing_definition_name_error() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let _ = top.module("inner", "Gen_Nx_W4");
+
+        let generator = ModuleGenerator::new(&c, "Gen", |m, p: &TestParams| {
+            m.output("o", m.input("i", p.width));
+        });
+
+        // Panic
+        let _ = generator.definition(TestParams {
+            name: "x",
+            width: 4,
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Module generator \"Gen\" derived the name \"Gen_Nx_W4\", but a module definition with this name already exists in this context."
+    )]
+    fn other_generator_definition_name_error() {
+        let c = Context::new();
+
+        let other = ModuleGenerator::new(&c, "Gen", |m, p: &TestParams| {
+            m.output("o", !m.input("i", p.width));
+        });
+        let _ = other.definition(TestParams {
+            name: "x",
+            width: 4,
+        });
+
+        let generator = ModuleGenerator::new(&c, "Gen", |m, p: &TestParams| {
+            m.output("o", m.input("i", p.width));
+        });
+
+        // Panic
+        let _ = generator.definition(TestParams {
+            name: "x",
+            width: 4,
+        });
+    }
+}
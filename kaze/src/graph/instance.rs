@@ -0,0 +1,561 @@
+use super::clock::*;
+use super::context::*;
+use super::internal_signal::*;
+use super::mem::*;
+use super::module::*;
+use super::register::*;
+use super::reset::*;
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+
+// Creates a copy of `definition`'s graph (including the Modules it contains) as a new Module instance called `instance_name` in `parent`
+pub(super) fn instantiate<'a>(
+    parent: &'a Module<'a>,
+    instance_name: String,
+    definition: &'a Module<'a>,
+) -> &'a Module<'a> {
+    let mut instantiator = Instantiator {
+        context: parent.context,
+
+        modules: HashMap::new(),
+        inputs: HashMap::new(),
+        outputs: HashMap::new(),
+        mems: HashMap::new(),
+        clocks: HashMap::new(),
+        resets: HashMap::new(),
+        signals: HashMap::new(),
+    };
+
+    let module = instantiator.create_module(definition, parent, instance_name, Some(definition));
+    instantiator.copy_outputs(definition);
+    instantiator.connect_module(definition);
+
+    // Instances share `definition`'s generated code, so it must not change from now on
+    definition.instantiated.set(true);
+
+    module
+}
+
+struct Instantiator<'a> {
+    context: &'a Context<'a>,
+
+    // Maps items in the definition's hierarchy to the corresponding items in the new instance's hierarchy
+    modules: HashMap<&'a Module<'a>, &'a Module<'a>>,
+    inputs: HashMap<*const InputData<'a>, &'a Input<'a>>,
+    outputs: HashMap<*const OutputData<'a>, &'a OutputData<'a>>,
+    mems: HashMap<*const Mem<'a>, &'a Mem<'a>>,
+    clocks: HashMap<*const Clock<'a>, &'a Clock<'a>>,
+    resets: HashMap<*const Reset<'a>, &'a Reset<'a>>,
+    signals: HashMap<&'a InternalSignal<'a>, &'a InternalSignal<'a>>,
+}
+
+impl<'a> Instantiator<'a> {
+    // Creates a copy of `definition` and its inputs, registers, memories and clocks (but not anything that refers to other signals), then does the same for the Modules it contains.
+    //  `shared_definition` is the definition whose generated code the copy shares, if any.
+    fn create_module(
+        &mut self,
+        definition: &'a Module<'a>,
+        parent: &'a Module<'a>,
+        instance_name: String,
+        shared_definition: Option<&'a Module<'a>>,
+    ) -> &'a Module<'a> {
+        let module = self.context.module_arena.alloc(Module::new(
+            self.context,
+            Some(parent),
+            instance_name,
+            definition.name.clone(),
+            shared_definition,
+        ));
+        parent.modules.borrow_mut().push(module);
+        self.modules.insert(definition, module);
+
+        *module.names.borrow_mut() = definition.names.borrow().clone();
+        *module.incomplete_switches.borrow_mut() = definition.incomplete_switches.borrow().clone();
+        *module.parameters.borrow_mut() = definition.parameters.borrow().clone();
+
+        for (name, input) in definition.inputs.borrow().iter() {
+            let data = self.context.input_data_arena.alloc(InputData {
+                name: name.clone(),
+                location: input.data.location,
+                bit_width: input.data.bit_width,
+                signed: input.data.signed,
+                driven_value: RefCell::new(None),
+            });
+            let value = self.context.signal_arena.alloc(InternalSignal {
+                context: self.context,
+                module,
+                location: input.value.location,
+
+                data: SignalData::Input { data },
+            });
+            let copy = self.context.input_arena.alloc(Input {
+                module,
+
+                data,
+                value,
+            });
+            module.inputs.borrow_mut().insert(name.clone(), copy);
+            self.inputs.insert(input.data, copy);
+            self.signals.insert(input.value, value);
+        }
+
+        for &register in definition.registers.borrow().iter() {
+            let data = match register.data {
+                SignalData::Reg { data } => data,
+                _ => unreachable!(),
+            };
+            let copy_data = self.context.register_data_arena.alloc(RegisterData {
+                module,
+
+                name: data.name.clone(),
+                location: data.location,
+                initial_value: RefCell::new(data.initial_value.borrow().clone()),
+                bit_width: data.bit_width,
+                signed: data.signed,
+                next: RefCell::new(None),
+                clock: RefCell::new(None),
+                reset: RefCell::new(None),
+            });
+            let value = self.context.signal_arena.alloc(InternalSignal {
+                context: self.context,
+                module,
+                location: register.location,
+
+                data: SignalData::Reg { data: copy_data },
+            });
+            module.registers.borrow_mut().push(value);
+            self.signals.insert(register, value);
+        }
+
+        for &mem in definition.mems.borrow().iter() {
+            let copy = self.context.mem_arena.alloc(Mem {
+                context: self.context,
+                module,
+
+                name: mem.name.clone(),
+                location: mem.location,
+                address_bit_width: mem.address_bit_width,
+                element_bit_width: mem.element_bit_width,
+
+                initial_contents: RefCell::new(mem.initial_contents.borrow().clone()),
+
+                read_ports: RefCell::new(Vec::new()),
+                write_port: RefCell::new(None),
+
+                clock: RefCell::new(None),
+            });
+            module.mems.borrow_mut().push(copy);
+            self.mems.insert(mem, copy);
+        }
+
+        for (name, &clock) in definition.clocks.borrow().iter() {
+            // The parent's Clocks have already been copied, so this Clock's driver can be copied right away
+            let driven_clock = clock
+                .driven_clock
+                .borrow()
+                .map(|driver| self.clocks[&(driver as *const _)]);
+            let copy = self.context.clock_arena.alloc(Clock {
+                module,
+
+                name: name.clone(),
+                driven_clock: RefCell::new(driven_clock),
+            });
+            module.clocks.borrow_mut().insert(name.clone(), copy);
+            self.clocks.insert(clock, copy);
+        }
+
+        for &child in definition.modules.borrow().iter() {
+            self.create_module(child, module, child.instance_name.clone(), child.definition);
+        }
+
+        module
+    }
+
+    // Copies the outputs of `definition` and the Modules it contains, innermost first, so that each output's source can refer to the outputs of inner Modules
+    fn copy_outputs(&mut self, definition: &'a Module<'a>) {
+        for &child in definition.modules.borrow().iter() {
+            self.copy_outputs(child);
+        }
+
+        let module = self.modules[&definition];
+        let mut outputs = BTreeMap::new();
+        for (name, output) in definition.outputs.borrow().iter() {
+            let data = self.context.output_data_arena.alloc(OutputData {
+                module,
+
+                name: name.clone(),
+                source: self.signal(output.data.source),
+                bit_width: output.data.bit_width,
+                signed: output.data.signed,
+            });
+            outputs.insert(
+                name.clone(),
+                &*self.context.output_arena.alloc(Output { data }),
+            );
+            self.outputs.insert(output.data, data);
+        }
+        *module.outputs.borrow_mut() = outputs;
+    }
+
+    // Copies everything else in `definition` and the Modules it contains that refers to other signals
+    fn connect_module(&mut self, definition: &'a Module<'a>) {
+        let module = self.modules[&definition];
+
+        for &reset in definition.resets.borrow().iter() {
+            let copy = self.context.reset_arena.alloc(Reset {
+                module,
+
+                name: reset.name.clone(),
+                location: reset.location,
+                source: self.signal(reset.source),
+                kind: reset.kind,
+                polarity: reset.polarity,
+            });
+            module.resets.borrow_mut().push(copy);
+            self.resets.insert(reset, copy);
+        }
+
+        for &register in definition.registers.borrow().iter() {
+            let (data, copy_data) = match (&register.data, &self.signals[&register].data) {
+                (SignalData::Reg { data }, SignalData::Reg { data: copy_data }) => {
+                    (*data, *copy_data)
+                }
+                _ => unreachable!(),
+            };
+            if let Some(next) = *data.next.borrow() {
+                *copy_data.next.borrow_mut() = Some(self.signal(next));
+            }
+            *copy_data.clock.borrow_mut() = data
+                .clock
+                .borrow()
+                .map(|clock| self.clocks[&(clock as *const _)]);
+            *copy_data.reset.borrow_mut() = data
+                .reset
+                .borrow()
+                .map(|reset| self.resets[&(reset as *const _)]);
+        }
+
+        for &mem in definition.mems.borrow().iter() {
+            let copy = self.mems[&(mem as *const _)];
+            for &(address, enable) in mem.read_ports.borrow().iter() {
+                let port = (self.signal(address), self.signal(enable));
+                copy.read_ports.borrow_mut().push(port);
+            }
+            if let Some((address, value, enable)) = *mem.write_port.borrow() {
+                let port = (
+                    self.signal(address),
+                    self.signal(value),
+                    self.signal(enable),
+                );
+                *copy.write_port.borrow_mut() = Some(port);
+            }
+            *copy.clock.borrow_mut() = mem
+                .clock
+                .borrow()
+                .map(|clock| self.clocks[&(clock as *const _)]);
+        }
+
+        for &child in definition.modules.borrow().iter() {
+            for input in child.inputs.borrow().values() {
+                if let Some(driver) = *input.data.driven_value.borrow() {
+                    let driver = self.signal(driver);
+                    *self.inputs[&(input.data as *const _)]
+                        .data
+                        .driven_value
+                        .borrow_mut() = Some(driver);
+                }
+            }
+
+            self.connect_module(child);
+        }
+    }
+
+    // Returns the copy of `signal`, copying it and any signals it depends on that haven't been copied yet
+    fn signal(&mut self, signal: &'a InternalSignal<'a>) -> &'a InternalSignal<'a> {
+        // Signal graphs can be arbitrarily deep, so we use an explicit stack instead of recursion
+        let mut stack = vec![signal];
+        while let Some(&signal) = stack.last() {
+            if self.signals.contains_key(&signal) {
+                stack.pop();
+                continue;
+            }
+
+            let pending = sources(&signal.data)
+                .into_iter()
+                .filter(|source| !self.signals.contains_key(source))
+                .collect::<Vec<_>>();
+            if !pending.is_empty() {
+                stack.extend(pending);
+                continue;
+            }
+
+            stack.pop();
+            let data = self.copy_data(&signal.data);
+            let copy = self.context.signal_arena.alloc(InternalSignal {
+                context: self.context,
+                module: self.modules[&signal.module],
+                location: signal.location,
+
+                data,
+            });
+            self.signals.insert(signal, copy);
+        }
+
+        self.signals[&signal]
+    }
+
+    // Copies `data`, whose sources must have been copied already
+    fn copy_data(&self, data: &SignalData<'a>) -> SignalData<'a> {
+        let s = |signal: &&'a InternalSignal<'a>| self.signals[signal];
+        match *data {
+            SignalData::Lit {
+                ref value,
+                bit_width,
+            } => SignalData::Lit {
+                value: value.clone(),
+                bit_width,
+            },
+
+            // Inputs and registers are copied along with their Modules
+            SignalData::Input { .. } | SignalData::Reg { .. } => unreachable!(),
+            SignalData::Output { data } => SignalData::Output {
+                data: self.outputs[&(data as *const _)],
+            },
+
+            SignalData::UnOp {
+                ref source,
+                op,
+                bit_width,
+            } => SignalData::UnOp {
+                source: s(source),
+                op,
+                bit_width,
+            },
+            SignalData::ReduceOp { ref source, op } => SignalData::ReduceOp {
+                source: s(source),
+                op,
+            },
+            SignalData::CountOp {
+                ref source,
+                op,
+                bit_width,
+            } => SignalData::CountOp {
+                source: s(source),
+                op,
+                bit_width,
+            },
+            SignalData::OneHot {
+                ref source,
+                bit_width,
+            } => SignalData::OneHot {
+                source: s(source),
+                bit_width,
+            },
+            SignalData::PriorityEncode {
+                ref source,
+                bit_width,
+            } => SignalData::PriorityEncode {
+                source: s(source),
+                bit_width,
+            },
+            SignalData::SimpleBinOp {
+                ref lhs,
+                ref rhs,
+                op,
+                bit_width,
+            } => SignalData::SimpleBinOp {
+                lhs: s(lhs),
+                rhs: s(rhs),
+                op,
+                bit_width,
+            },
+            SignalData::AdditiveBinOp {
+                ref lhs,
+                ref rhs,
+                op,
+                bit_width,
+            } => SignalData::AdditiveBinOp {
+                lhs: s(lhs),
+                rhs: s(rhs),
+                op,
+                bit_width,
+            },
+            SignalData::ComparisonBinOp {
+                ref lhs,
+                ref rhs,
+                op,
+            } => SignalData::ComparisonBinOp {
+                lhs: s(lhs),
+                rhs: s(rhs),
+                op,
+            },
+            SignalData::ShiftBinOp {
+                ref lhs,
+                ref rhs,
+                op,
+                bit_width,
+            } => SignalData::ShiftBinOp {
+                lhs: s(lhs),
+                rhs: s(rhs),
+                op,
+                bit_width,
+            },
+            SignalData::RotateBinOp {
+                ref lhs,
+                ref rhs,
+                op,
+                bit_width,
+            } => SignalData::RotateBinOp {
+                lhs: s(lhs),
+                rhs: s(rhs),
+                op,
+                bit_width,
+            },
+
+            SignalData::Mul {
+                ref lhs,
+                ref rhs,
+                bit_width,
+            } => SignalData::Mul {
+                lhs: s(lhs),
+                rhs: s(rhs),
+                bit_width,
+            },
+            SignalData::MulSigned {
+                ref lhs,
+                ref rhs,
+                bit_width,
+            } => SignalData::MulSigned {
+                lhs: s(lhs),
+                rhs: s(rhs),
+                bit_width,
+            },
+            SignalData::DivisionBinOp {
+                ref lhs,
+                ref rhs,
+                op,
+                bit_width,
+            } => SignalData::DivisionBinOp {
+                lhs: s(lhs),
+                rhs: s(rhs),
+                op,
+                bit_width,
+            },
+
+            SignalData::Bits {
+                ref source,
+                range_high,
+                range_low,
+            } => SignalData::Bits {
+                source: s(source),
+                range_high,
+                range_low,
+            },
+            SignalData::Extend {
+                ref source,
+                op,
+                bit_width,
+            } => SignalData::Extend {
+                source: s(source),
+                op,
+                bit_width,
+            },
+
+            SignalData::Repeat {
+                ref source,
+                count,
+                bit_width,
+            } => SignalData::Repeat {
+                source: s(source),
+                count,
+                bit_width,
+            },
+            SignalData::Concat {
+                ref lhs,
+                ref rhs,
+                bit_width,
+            } => SignalData::Concat {
+                lhs: s(lhs),
+                rhs: s(rhs),
+                bit_width,
+            },
+
+            SignalData::Mux {
+                ref cond,
+                ref when_true,
+                ref when_false,
+                bit_width,
+            } => SignalData::Mux {
+                cond: s(cond),
+                when_true: s(when_true),
+                when_false: s(when_false),
+                bit_width,
+            },
+            SignalData::Index {
+                ref elements,
+                ref index,
+                bit_width,
+            } => SignalData::Index {
+                elements: elements.iter().map(s).collect(),
+                index: s(index),
+                bit_width,
+            },
+
+            SignalData::MemReadPortOutput {
+                mem,
+                ref address,
+                ref enable,
+            } => SignalData::MemReadPortOutput {
+                mem: self.mems[&(mem as *const _)],
+                address: s(address),
+                enable: s(enable),
+            },
+        }
+    }
+}
+
+// Returns the signals within the same Module that `data` refers to directly
+fn sources<'a>(data: &SignalData<'a>) -> Vec<&'a InternalSignal<'a>> {
+    match *data {
+        SignalData::Lit { .. }
+        | SignalData::Input { .. }
+        | SignalData::Output { .. }
+        | SignalData::Reg { .. } => Vec::new(),
+
+        SignalData::UnOp { source, .. }
+        | SignalData::ReduceOp { source, .. }
+        | SignalData::CountOp { source, .. }
+        | SignalData::OneHot { source, .. }
+        | SignalData::PriorityEncode { source, .. }
+        | SignalData::Bits { source, .. }
+        | SignalData::Extend { source, .. }
+        | SignalData::Repeat { source, .. } => vec![source],
+
+        SignalData::SimpleBinOp { lhs, rhs, .. }
+        | SignalData::AdditiveBinOp { lhs, rhs, .. }
+        | SignalData::ComparisonBinOp { lhs, rhs, .. }
+        | SignalData::ShiftBinOp { lhs, rhs, .. }
+        | SignalData::RotateBinOp { lhs, rhs, .. }
+        | SignalData::Mul { lhs, rhs, .. }
+        | SignalData::MulSigned { lhs, rhs, .. }
+        | SignalData::DivisionBinOp { lhs, rhs, .. }
+        | SignalData::Concat { lhs, rhs, .. } => vec![lhs, rhs],
+
+        SignalData::Mux {
+            cond,
+            when_true,
+            when_false,
+            ..
+        } => vec![cond, when_true, when_false],
+        SignalData::Index {
+            ref elements,
+            index,
+            ..
+        } => elements.iter().copied().chain([index]).collect(),
+
+        SignalData::MemReadPortOutput {
+            address, enable, ..
+        } => vec![address, enable],
+    }
+}
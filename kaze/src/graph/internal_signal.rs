@@ -5,11 +5,13 @@ use super::module::*;
 use super::register::*;
 
 use std::hash::{Hash, Hasher};
+use std::panic::Location;
 use std::ptr;
 
 pub struct InternalSignal<'a> {
     pub(super) context: &'a Context<'a>,
     pub(crate) module: &'a Module<'a>,
+    pub(crate) location: &'static Location<'static>,
 
     pub(crate) data: SignalData<'a>,
 }
@@ -23,20 +25,28 @@ impl<'a> InternalSignal<'a> {
             SignalData::Output { data } => data.bit_width,
             SignalData::Reg { data } => data.bit_width,
             SignalData::UnOp { bit_width, .. } => bit_width,
+            SignalData::ReduceOp { .. } => 1,
+            SignalData::CountOp { bit_width, .. } => bit_width,
+            SignalData::OneHot { bit_width, .. } => bit_width,
+            SignalData::PriorityEncode { bit_width, .. } => bit_width,
             SignalData::SimpleBinOp { bit_width, .. } => bit_width,
             SignalData::AdditiveBinOp { bit_width, .. } => bit_width,
             SignalData::ComparisonBinOp { .. } => 1,
             SignalData::ShiftBinOp { bit_width, .. } => bit_width,
+            SignalData::RotateBinOp { bit_width, .. } => bit_width,
             SignalData::Mul { bit_width, .. } => bit_width,
             SignalData::MulSigned { bit_width, .. } => bit_width,
+            SignalData::DivisionBinOp { bit_width, .. } => bit_width,
             SignalData::Bits {
                 range_high,
                 range_low,
                 ..
             } => range_high - range_low + 1,
+            SignalData::Extend { bit_width, .. } => bit_width,
             SignalData::Repeat { bit_width, .. } => bit_width,
             SignalData::Concat { bit_width, .. } => bit_width,
             SignalData::Mux { bit_width, .. } => bit_width,
+            SignalData::Index { bit_width, .. } => bit_width,
             SignalData::MemReadPortOutput { mem, .. } => mem.element_bit_width,
         }
     }
@@ -79,6 +89,23 @@ pub(crate) enum SignalData<'a> {
         op: UnOp,
         bit_width: u32,
     },
+    ReduceOp {
+        source: &'a InternalSignal<'a>,
+        op: ReduceOp,
+    },
+    CountOp {
+        source: &'a InternalSignal<'a>,
+        op: CountOp,
+        bit_width: u32,
+    },
+    OneHot {
+        source: &'a InternalSignal<'a>,
+        bit_width: u32,
+    },
+    PriorityEncode {
+        source: &'a InternalSignal<'a>,
+        bit_width: u32,
+    },
     SimpleBinOp {
         lhs: &'a InternalSignal<'a>,
         rhs: &'a InternalSignal<'a>,
@@ -102,6 +129,12 @@ pub(crate) enum SignalData<'a> {
         op: ShiftBinOp,
         bit_width: u32,
     },
+    RotateBinOp {
+        lhs: &'a InternalSignal<'a>,
+        rhs: &'a InternalSignal<'a>,
+        op: RotateBinOp,
+        bit_width: u32,
+    },
 
     Mul {
         lhs: &'a InternalSignal<'a>,
@@ -113,12 +146,23 @@ pub(crate) enum SignalData<'a> {
         rhs: &'a InternalSignal<'a>,
         bit_width: u32,
     },
+    DivisionBinOp {
+        lhs: &'a InternalSignal<'a>,
+        rhs: &'a InternalSignal<'a>,
+        op: DivisionBinOp,
+        bit_width: u32,
+    },
 
     Bits {
         source: &'a InternalSignal<'a>,
         range_high: u32,
         range_low: u32,
     },
+    Extend {
+        source: &'a InternalSignal<'a>,
+        op: ExtendOp,
+        bit_width: u32,
+    },
 
     Repeat {
         source: &'a InternalSignal<'a>,
@@ -137,6 +181,11 @@ pub(crate) enum SignalData<'a> {
         when_false: &'a InternalSignal<'a>,
         bit_width: u32,
     },
+    Index {
+        elements: Vec<&'a InternalSignal<'a>>,
+        index: &'a InternalSignal<'a>,
+        bit_width: u32,
+    },
 
     MemReadPortOutput {
         mem: &'a Mem<'a>,
@@ -145,20 +194,57 @@ pub(crate) enum SignalData<'a> {
     },
 }
 
-#[derive(Clone, Copy)]
-pub(crate) enum UnOp {
+/// A unary operation, as described by [`SignalNode::UnOp`](crate::SignalNode::UnOp).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UnOp {
+    /// Bitwise NOT.
     Not,
 }
 
-#[derive(Clone, Copy)]
-pub(crate) enum SimpleBinOp {
+/// A reduction of all of a signal's bits to a single bit, as described by [`SignalNode::ReduceOp`](crate::SignalNode::ReduceOp).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ReduceOp {
+    /// High if all bits are high.
+    And,
+    /// High if any bit is high.
+    Or,
+    /// High if an odd number of bits are high.
+    Xor,
+}
+
+/// A count of a signal's bits, as described by [`SignalNode::CountOp`](crate::SignalNode::CountOp).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CountOp {
+    /// The number of low bits above the most significant high bit.
+    LeadingZeros,
+    /// The number of low bits below the least significant high bit.
+    TrailingZeros,
+    /// The number of high bits.
+    CountOnes,
+}
+
+/// A bitwise binary operation, as described by [`SignalNode::SimpleBinOp`](crate::SignalNode::SimpleBinOp).
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SimpleBinOp {
+    /// Bitwise AND.
     BitAnd,
+    /// Bitwise OR.
     BitOr,
+    /// Bitwise XOR.
     BitXor,
 }
 
-#[derive(Clone, Copy)]
-pub(crate) enum ComparisonBinOp {
+/// A comparison, as described by [`SignalNode::ComparisonBinOp`](crate::SignalNode::ComparisonBinOp).
+///
+/// Variants with a `Signed` suffix interpret their operands as signed, two's complement integers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ComparisonBinOp {
     Equal,
     GreaterThan,
     GreaterThanEqual,
@@ -171,22 +257,76 @@ pub(crate) enum ComparisonBinOp {
     NotEqual,
 }
 
-#[derive(Clone, Copy)]
-pub(crate) enum AdditiveBinOp {
+/// An addition or subtraction, as described by [`SignalNode::AdditiveBinOp`](crate::SignalNode::AdditiveBinOp).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AdditiveBinOp {
+    /// Wrapping addition.
     Add,
+    /// Wrapping subtraction.
     Sub,
 }
 
-#[derive(Clone, Copy)]
-pub(crate) enum ShiftBinOp {
+/// A shift, as described by [`SignalNode::ShiftBinOp`](crate::SignalNode::ShiftBinOp).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ShiftBinOp {
+    /// Logical left shift.
     Shl,
+    /// Logical right shift.
     Shr,
+    /// Arithmetic right shift.
     ShrArithmetic,
 }
 
+/// A widening of a signal, as described by [`SignalNode::Extend`](crate::SignalNode::Extend).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ExtendOp {
+    /// Fill the new upper bits with `0`.
+    Zero,
+    /// Fill the new upper bits with the source's most significant bit.
+    Sign,
+}
+
+/// A rotation, as described by [`SignalNode::RotateBinOp`](crate::SignalNode::RotateBinOp).
+///
+/// The rotation amount is taken modulo the bit width of the rotated signal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum RotateBinOp {
+    /// Rotate towards the most significant bit.
+    Left,
+    /// Rotate towards the least significant bit.
+    Right,
+}
+
+/// A division or remainder, as described by [`SignalNode::DivisionBinOp`](crate::SignalNode::DivisionBinOp).
+///
+/// Variants with a `Signed` suffix interpret their operands as signed, two's complement integers.
+/// Dividing by zero produces a quotient with all bits set and a remainder equal to the dividend, and signed overflow wraps.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DivisionBinOp {
+    /// Unsigned division, rounding towards zero.
+    Div,
+    /// Signed division, rounding towards zero.
+    DivSigned,
+    /// Unsigned remainder.
+    Rem,
+    /// Signed remainder, whose sign matches the dividend's.
+    RemSigned,
+}
+
 pub trait GetInternalSignal<'a> {
     // TODO: Rename to `get_internal_signal` ?
+    #[track_caller]
     fn internal_signal(&'a self) -> &'a InternalSignal<'a>;
+
+    /// Whether this value should be treated as a signed quantity when it's driven onto a port (see [`SignedSignal`](super::SignedSignal)).
+    fn is_signed(&self) -> bool {
+        false
+    }
 }
 
 impl<'a> GetInternalSignal<'a> for InternalSignal<'a> {
@@ -1,11 +1,15 @@
+use super::clock::*;
 use super::constant::*;
 use super::context::*;
 use super::internal_signal::*;
 use super::module::*;
 use super::signal::*;
 
+use crate::error::*;
+
 use std::cell::RefCell;
 use std::hash::{Hash, Hasher};
+use std::panic::Location;
 use std::ptr;
 
 /// A synchronous memory, created by the [`Module::mem`] method.
@@ -42,6 +46,7 @@ pub struct Mem<'a> {
     pub(crate) module: &'a Module<'a>,
 
     pub(crate) name: String,
+    pub(crate) location: &'static Location<'static>,
     pub(crate) address_bit_width: u32,
     pub(crate) element_bit_width: u32,
 
@@ -55,9 +60,78 @@ pub struct Mem<'a> {
             &'a InternalSignal<'a>,
         )>,
     >,
+
+    pub(crate) clock: RefCell<Option<&'a Clock<'a>>>,
 }
 
 impl<'a> Mem<'a> {
+    /// Returns this `Mem`'s name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns this `Mem`'s address bit width.
+    pub fn address_bit_width(&self) -> u32 {
+        self.address_bit_width
+    }
+
+    /// Returns this `Mem`'s element bit width.
+    pub fn element_bit_width(&self) -> u32 {
+        self.element_bit_width
+    }
+
+    /// Returns the initial contents specified for this `Mem` with [`initial_contents`](Self::initial_contents), if any.
+    pub fn initial_values(&self) -> Option<Vec<Constant>> {
+        self.initial_contents.borrow().clone()
+    }
+
+    /// Returns the address and enable [`Signal`]s of each of this `Mem`'s read ports, in the order they were created with [`read_port`](Self::read_port).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let my_mem = m.mem("my_mem", 1, 32);
+    /// my_mem.initial_contents(&[0xfadebabeu32, 0xdeadbeefu32]);
+    /// m.output("my_output", my_mem.read_port(m.input("addr", 1), m.high()));
+    ///
+    /// let (address, enable) = my_mem.read_port_signals()[0];
+    /// assert!(matches!(address.node(), SignalNode::Input { input } if input.name() == "addr"));
+    /// assert!(matches!(enable.node(), SignalNode::Lit { .. }));
+    /// assert!(my_mem.write_port_signals().is_none());
+    /// ```
+    pub fn read_port_signals(&self) -> Vec<(&'a dyn Signal<'a>, &'a dyn Signal<'a>)> {
+        self.read_ports
+            .borrow()
+            .iter()
+            .map(|&(address, enable)| (address as &dyn Signal, enable as &dyn Signal))
+            .collect()
+    }
+
+    /// Returns the address, value, and enable [`Signal`]s of this `Mem`'s write port, if one is specified with [`write_port`](Self::write_port).
+    #[allow(clippy::type_complexity)]
+    pub fn write_port_signals(
+        &self,
+    ) -> Option<(&'a dyn Signal<'a>, &'a dyn Signal<'a>, &'a dyn Signal<'a>)> {
+        self.write_port.borrow().map(|(address, value, enable)| {
+            (
+                address as &dyn Signal,
+                value as &dyn Signal,
+                enable as &dyn Signal,
+            )
+        })
+    }
+
+    /// Returns the [`Clock`] this `Mem` is bound to with [`clock`](Self::clock), or `None` if it's driven by its [`Module`]'s implicit clock.
+    pub fn clock_domain(&self) -> Option<&'a Clock<'a>> {
+        *self.clock.borrow()
+    }
+
     /// Specifies the initial contents for this `Mem`.
     ///
     /// Reads from this `Mem` will reflect the values specified unless writes have overwritten them (if the `Mem` has a write port).
@@ -87,7 +161,9 @@ impl<'a> Mem<'a> {
     /// my_mem.write_port(m.high(), m.lit(0xabad1deau32, 32), m.high());
     /// m.output("my_output", my_mem.read_port(m.high(), m.high()));
     /// ```
+    #[track_caller]
     pub fn initial_contents<C: Clone + Into<Constant>>(&'a self, contents: &[C]) {
+        self.module.check_modifiable();
         if self.initial_contents.borrow().is_some() {
             panic!("Attempted to specify initial contents for memory \"{}\" in module \"{}\", but this memory already has initial contents.", self.name, self.module.name);
         }
@@ -98,7 +174,7 @@ impl<'a> Mem<'a> {
         *self.initial_contents.borrow_mut() = Some(contents.iter().cloned().enumerate().map(|(i, x)| {
             let ret = x.into();
             if ret.required_bits() > self.element_bit_width {
-                panic!("Attempted to specify initial contents for memory \"{}\" in module \"{}\", but this memory has an element width of {} bit(s), and these initial contents specify element {} with value {} which requires {} bit(s).", self.name, self.module.name, self.element_bit_width, i, ret.numeric_value(), ret.required_bits());
+                panic!("Attempted to specify initial contents for memory \"{}\" in module \"{}\", but this memory has an element width of {} bit(s), and these initial contents specify element {} with value {} which requires {} bit(s).", self.name, self.module.name, self.element_bit_width, i, ret.value_string(), ret.required_bits());
             }
             ret
         }).collect());
@@ -133,11 +209,13 @@ impl<'a> Mem<'a> {
     /// my_mem.write_port(m.high(), m.lit(0xabad1deau32, 32), m.high());
     /// m.output("my_output", my_mem.read_port(m.high(), m.high()));
     /// ```
+    #[track_caller]
     pub fn read_port(
         &'a self,
         address: &'a dyn Signal<'a>,
         enable: &'a dyn Signal<'a>,
-    ) -> &dyn Signal<'a> {
+    ) -> &'a dyn Signal<'a> {
+        self.module.check_modifiable();
         let address = address.internal_signal();
         let enable = enable.internal_signal();
         // TODO: Limit amount of read ports added?
@@ -150,6 +228,7 @@ impl<'a> Mem<'a> {
         let ret = self.context.signal_arena.alloc(InternalSignal {
             context: self.context,
             module: self.module,
+            location: Location::caller(),
 
             data: SignalData::MemReadPortOutput {
                 mem: self,
@@ -172,6 +251,7 @@ impl<'a> Mem<'a> {
     /// # Panics
     ///
     /// Panics if this `Mem` already has a write port specified, if `address`'s bit width doesn't match this `Mem`'s address bit width, if `value`'s bit width doesn't match this `Mem`'s element bit width, or if `enable`'s bit width is not `1`.
+    /// See [`try_write_port`](Self::try_write_port) for a variant that returns an [`Error`] instead.
     ///
     /// # Examples
     ///
@@ -190,28 +270,192 @@ impl<'a> Mem<'a> {
     /// m.output("my_output", my_mem.read_port(m.high(), m.high()));
     /// ```
     // TODO: byte/word enable? How might that interface look?
+    #[track_caller]
     pub fn write_port(
         &'a self,
         address: &'a dyn Signal<'a>,
         value: &'a dyn Signal<'a>,
         enable: &'a dyn Signal<'a>,
     ) {
+        self.try_write_port(address, value, enable)
+            .unwrap_or_panic()
+    }
+
+    /// Same as [`write_port`](Self::write_port), except that an [`Error`] is returned instead of panicking if the write port can't be specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let my_mem = m.mem("my_mem", 1, 32);
+    /// assert!(matches!(
+    ///     my_mem.try_write_port(m.high(), m.lit(0u32, 31), m.high()),
+    ///     Err(Error::MemWritePortValueBitWidth { bit_width: 31, element_bit_width: 32, .. })
+    /// ));
+    /// ```
+    #[track_caller]
+    pub fn try_write_port(
+        &'a self,
+        address: &'a dyn Signal<'a>,
+        value: &'a dyn Signal<'a>,
+        enable: &'a dyn Signal<'a>,
+    ) -> Result<(), Error> {
+        self.module.check_modifiable();
         let address = address.internal_signal();
         let value = value.internal_signal();
         let enable = enable.internal_signal();
         if self.write_port.borrow().is_some() {
-            panic!("Attempted to specify a write port for memory \"{}\" in module \"{}\", but this memory already has a write port.", self.name, self.module.name);
+            return Err(Error::MemWritePortAlreadySpecified {
+                module_path: self.module.path(),
+                module_name: self.module.name.clone(),
+                mem_name: self.name.clone(),
+            });
         }
+        self.check_write_port_bit_widths(address, value, enable)?;
+        *self.write_port.borrow_mut() = Some((address, value, enable));
+        Ok(())
+    }
+
+    /// Writes `value` to the location specified by `address`, taking any enclosing [`when`](crate::when) blocks into account.
+    ///
+    /// Outside of a [`when`](crate::when) block, this replaces this `Mem`'s write port with one that writes `value` to `address` on every cycle. Inside a [`when`](crate::when) block, the write only occurs while the block's condition holds.
+    /// Writes are merged into a single write port which is enabled whenever any of the writes occur, and later writes take priority over earlier ones (including a write port specified with [`write_port`](Self::write_port)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `address`'s bit width doesn't match this `Mem`'s address bit width, if `value`'s bit width doesn't match this `Mem`'s element bit width, or if the condition of an enclosing [`when`](crate::when) block belongs to a different [`Module`] than `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let address = m.input("address", 4);
+    /// let value = m.input("value", 32);
+    /// let write_enable = m.input("write_enable", 1);
+    /// let clear = m.input("clear", 1);
+    ///
+    /// let my_mem = m.mem("my_mem", 4, 32);
+    /// when(write_enable, || {
+    ///     my_mem.write(address, value);
+    /// });
+    /// when(clear, || {
+    ///     my_mem.write(address, m.lit(0u32, 32)); // Takes priority over the other write
+    /// });
+    /// m.output("my_output", my_mem.read_port(address, m.high()));
+    /// ```
+    #[track_caller]
+    pub fn write(&'a self, address: &'a dyn Signal<'a>, value: &'a dyn Signal<'a>) {
+        self.module.check_modifiable();
+        let address = address.internal_signal();
+        let value = value.internal_signal();
+        let guard = self.context.when_guards.borrow().last().copied();
+        let enable = match guard {
+            Some(guard) => {
+                if !ptr::eq(self.module, guard.module) {
+                    panic!("Attempted to write to memory \"{}\" inside a when block whose condition belongs to another module.", self.name);
+                }
+                guard
+            }
+            None => self.module.high().internal_signal(),
+        };
+        self.check_write_port_bit_widths(address, value, enable)
+            .unwrap_or_panic();
+        let write_port = *self.write_port.borrow();
+        let write_port = match (guard, write_port) {
+            (Some(guard), Some((prev_address, prev_value, prev_enable))) => (
+                self.module
+                    .mux(guard, address, prev_address)
+                    .internal_signal(),
+                self.module.mux(guard, value, prev_value).internal_signal(),
+                (guard as &'a dyn Signal<'a> | prev_enable).internal_signal(),
+            ),
+            _ => (address, value, enable),
+        };
+        *self.write_port.borrow_mut() = Some(write_port);
+    }
+
+    fn check_write_port_bit_widths(
+        &self,
+        address: &'a InternalSignal<'a>,
+        value: &'a InternalSignal<'a>,
+        enable: &'a InternalSignal<'a>,
+    ) -> Result<(), Error> {
         if address.bit_width() != self.address_bit_width {
-            panic!("Attempted to specify a write port for memory \"{}\" in module \"{}\" with an address signal with {} bit(s), but this memory has {} address bit(s).", self.name, self.module.name, address.bit_width(), self.address_bit_width);
+            return Err(Error::MemWritePortAddressBitWidth {
+                module_path: self.module.path(),
+                module_name: self.module.name.clone(),
+                mem_name: self.name.clone(),
+                bit_width: address.bit_width(),
+                address_bit_width: self.address_bit_width,
+            });
         }
         if value.bit_width() != self.element_bit_width {
-            panic!("Attempted to specify a write port for memory \"{}\" in module \"{}\" with a value signal with {} bit(s), but this memory has {} element bit(s).", self.name, self.module.name, value.bit_width(), self.element_bit_width);
+            return Err(Error::MemWritePortValueBitWidth {
+                module_path: self.module.path(),
+                module_name: self.module.name.clone(),
+                mem_name: self.name.clone(),
+                bit_width: value.bit_width(),
+                element_bit_width: self.element_bit_width,
+            });
         }
         if enable.bit_width() != 1 {
-            panic!("Attempted to specify a write port for memory \"{}\" in module \"{}\" with an enable signal with {} bit(s), but memory read/write ports are required to be 1 bit wide.", self.name, self.module.name, enable.bit_width());
+            return Err(Error::MemWritePortEnableBitWidth {
+                module_path: self.module.path(),
+                module_name: self.module.name.clone(),
+                mem_name: self.name.clone(),
+                bit_width: enable.bit_width(),
+            });
         }
-        *self.write_port.borrow_mut() = Some((address, value, enable));
+        Ok(())
+    }
+
+    /// Specifies the [`Clock`] that drives this `Mem`'s read and write ports.
+    ///
+    /// By default, a `Mem` is driven by its [`Module`]'s implicit clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `clock` belongs to a different [`Module`] than `self`, or if this `Mem` already has a clock specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let pixel_clk = m.clock("pixel_clk");
+    ///
+    /// let my_mem = m.mem("my_mem", 1, 32);
+    /// my_mem.clock(pixel_clk);
+    /// my_mem.initial_contents(&[0xfadebabeu32, 0xdeadbeefu32]);
+    /// m.output("my_output", my_mem.read_port(m.high(), m.high()));
+    /// ```
+    #[track_caller]
+    pub fn clock(&'a self, clock: &'a Clock<'a>) {
+        self.module.check_modifiable();
+        if !ptr::eq(self.module, clock.module) {
+            panic!(
+                "Attempted to bind memory \"{}\" to a clock from another module.",
+                self.name
+            );
+        }
+        if self.clock.borrow().is_some() {
+            panic!("Attempted to specify a clock for memory \"{}\" in module \"{}\", but this memory already has a clock specified.", self.name, self.module.name);
+        }
+        *self.clock.borrow_mut() = Some(clock);
     }
 }
 
@@ -362,4 +606,69 @@ mod tests {
         // Panic
         mem.write_port(m.low(), m.low(), m.lit(0u32, 2));
     }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to specify a write port for memory \"mem\" in module \"A\" with a value signal with 2 bit(s), but this memory has 1 element bit(s)."
+    )]
+    fn write_value_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let mem = m.mem("mem", 1, 1);
+
+        // Panic
+        mem.write(m.low(), m.lit(0u32, 2));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to write to memory \"mem\" inside a when block whose condition belongs to another module."
+    )]
+    fn write_when_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let cond = m1.input("cond", 1);
+
+        let m2 = c.module("b", "B");
+        let mem = m2.mem("mem", 1, 1);
+
+        when(cond, || {
+            // Panic
+            mem.write(m2.low(), m2.high());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to bind memory \"mem\" to a clock from another module.")]
+    fn clock_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let mem = m1.mem("mem", 1, 1);
+
+        let m2 = c.module("b", "B");
+        let clk = m2.clock("clk2");
+
+        // Panic
+        mem.clock(clk);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to specify a clock for memory \"mem\" in module \"A\", but this memory already has a clock specified."
+    )]
+    fn clock_already_specified_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let mem = m.mem("mem", 1, 1);
+        let clk = m.clock("clk2");
+
+        mem.clock(clk);
+
+        // Panic
+        mem.clock(clk);
+    }
 }
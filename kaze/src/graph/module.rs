@@ -1,20 +1,30 @@
+use super::bundle::*;
+use super::clock::*;
 use super::constant::*;
 use super::context::*;
+use super::instance;
 use super::internal_signal::*;
 use super::mem::*;
 use super::register::*;
+use super::reset::*;
 use super::signal::*;
+use super::stream::*;
+use super::sugar::IncompleteSwitch;
 
-use std::cell::RefCell;
+use crate::error::*;
+use crate::validation;
+
+use std::cell::{Cell, RefCell};
 use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
+use std::panic::Location;
 use std::ptr;
 
 /// A self-contained and potentially-reusable hardware design unit, created by the [`Context::module`] method.
 ///
-/// Once a `Module` is specified, it can be [instantiated](Self::instance) in another `Module` to form a hierarchy, or it can be used to generate [Rust simulator code](crate::sim::generate) or a [Verilog module](crate::verilog::generate).
+/// `Module`s can be created inside other `Module`s with [`ModuleParent::module`] (or instantiated from an existing definition with [`Module::instance`]) to form a hierarchy, and a top-level `Module` can be used to generate [Rust simulator code](crate::sim::generate) or a [Verilog module](crate::verilog::generate).
 ///
-/// All `Module`s in kaze have an implicit reset and clock. These are only visible in generated code. It's assumed that all kaze modules operate in the same clock domain.
+/// All `Module`s in kaze have an implicit reset and clock. These are only visible in generated code. Additional clock domains and resets can be declared explicitly with [`Module::clock`] and [`Module::reset`], respectively.
 ///
 /// # Examples
 ///
@@ -24,25 +34,36 @@ use std::ptr;
 /// let c = Context::new();
 ///
 /// let m = c.module("m", "MyModule");
-/// m.output("out", m.input("in", 1));
+/// m.output("o", m.input("i", 1));
 /// ```
 // TODO: Validation error if a module has no inputs/outputs
 // TODO: Document composing modules (even if it's really basic)
 #[must_use]
 pub struct Module<'a> {
-    context: &'a Context<'a>,
+    pub(super) context: &'a Context<'a>,
 
     pub(crate) parent: Option<&'a Module<'a>>,
 
     pub(crate) instance_name: String,
     pub(crate) name: String,
 
+    // The definition this `Module` was instantiated from with `Module::instance`, whose generated code it shares
+    pub(crate) definition: Option<&'a Module<'a>>,
+    // Set once this `Module` has been instantiated, after which its hierarchy can no longer be modified
+    pub(super) instantiated: Cell<bool>,
+
     // TODO: Do we need to duplicate the input/output names here?
     pub(crate) inputs: RefCell<BTreeMap<String, &'a Input<'a>>>,
     pub(crate) outputs: RefCell<BTreeMap<String, &'a Output<'a>>>,
     pub(crate) registers: RefCell<Vec<&'a InternalSignal<'a>>>,
     pub(crate) modules: RefCell<Vec<&'a Module<'a>>>,
     pub(crate) mems: RefCell<Vec<&'a Mem<'a>>>,
+    pub(crate) clocks: RefCell<BTreeMap<String, &'a Clock<'a>>>,
+    pub(crate) resets: RefCell<Vec<&'a Reset<'a>>>,
+    pub(crate) incomplete_switches: RefCell<Vec<IncompleteSwitch>>,
+    pub(crate) parameters: RefCell<Vec<(String, String)>>,
+
+    pub(super) names: RefCell<BTreeMap<String, (ItemKind, &'static Location<'static>)>>,
 }
 
 impl<'a> Module<'a> {
@@ -51,6 +72,7 @@ impl<'a> Module<'a> {
         parent: Option<&'a Module<'a>>,
         instance_name: String,
         name: String,
+        definition: Option<&'a Module<'a>>,
     ) -> Module<'a> {
         Module {
             context,
@@ -60,11 +82,20 @@ impl<'a> Module<'a> {
             instance_name,
             name,
 
+            definition,
+            instantiated: Cell::new(false),
+
             inputs: RefCell::new(BTreeMap::new()),
             outputs: RefCell::new(BTreeMap::new()),
             registers: RefCell::new(Vec::new()),
             modules: RefCell::new(Vec::new()),
             mems: RefCell::new(Vec::new()),
+            clocks: RefCell::new(BTreeMap::new()),
+            resets: RefCell::new(Vec::new()),
+            incomplete_switches: RefCell::new(Vec::new()),
+            parameters: RefCell::new(Vec::new()),
+
+            names: RefCell::new(BTreeMap::new()),
         }
     }
 
@@ -89,7 +120,8 @@ impl<'a> Module<'a> {
     /// let one_bit_const = m.lit(0u32, 1);
     /// let twenty_seven_bit_const = m.lit(true, 27);
     /// ```
-    pub fn lit(&'a self, value: impl Into<Constant>, bit_width: u32) -> &dyn Signal<'a> {
+    #[track_caller]
+    pub fn lit(&'a self, value: impl Into<Constant>, bit_width: u32) -> &'a dyn Signal<'a> {
         if bit_width < MIN_SIGNAL_BIT_WIDTH {
             panic!(
                 "Cannot create a literal with {} bit(s). Signals must not be narrower than {} bit(s).",
@@ -105,12 +137,13 @@ impl<'a> Module<'a> {
         let value = value.into();
         let required_bits = value.required_bits();
         if required_bits > bit_width {
-            let numeric_value = value.numeric_value();
-            panic!("Cannot fit the specified value '{}' into the specified bit width '{}'. The value '{}' requires a bit width of at least {} bit(s).", numeric_value, bit_width, numeric_value, required_bits);
+            let value_string = value.value_string();
+            panic!("Cannot fit the specified value '{}' into the specified bit width '{}'. The value '{}' requires a bit width of at least {} bit(s).", value_string, bit_width, value_string, required_bits);
         }
         self.context.signal_arena.alloc(InternalSignal {
             context: self.context,
             module: self,
+            location: Location::caller(),
 
             data: SignalData::Lit { value, bit_width },
         })
@@ -131,7 +164,8 @@ impl<'a> Module<'a> {
     /// let low1 = m.low();
     /// let low2 = m.lit(false, 1);
     /// ```
-    pub fn low(&'a self) -> &dyn Signal<'a> {
+    #[track_caller]
+    pub fn low(&'a self) -> &'a dyn Signal<'a> {
         self.lit(false, 1)
     }
 
@@ -150,7 +184,8 @@ impl<'a> Module<'a> {
     /// let high1 = m.high();
     /// let high2 = m.lit(true, 1);
     /// ```
-    pub fn high(&'a self) -> &dyn Signal<'a> {
+    #[track_caller]
+    pub fn high(&'a self) -> &'a dyn Signal<'a> {
         self.lit(true, 1)
     }
 
@@ -158,7 +193,9 @@ impl<'a> Module<'a> {
     ///
     /// # Panics
     ///
-    /// Panics if `bit_width` is less than [`MIN_SIGNAL_BIT_WIDTH`] or greater than [`MAX_SIGNAL_BIT_WIDTH`], respectively.
+    /// Panics if `name` is already used by another item in this `Module`, is reserved for names in generated code (see [`Error::ReservedName`]), or is a Rust or Verilog keyword.
+    /// Also panics if `bit_width` is less than [`MIN_SIGNAL_BIT_WIDTH`] or greater than [`MAX_SIGNAL_BIT_WIDTH`], respectively.
+    /// See [`try_input`](Self::try_input) for a variant that returns an [`Error`] instead.
     ///
     /// # Examples
     ///
@@ -171,47 +208,78 @@ impl<'a> Module<'a> {
     ///
     /// let my_input = m.input("my_input", 80);
     /// ```
-    pub fn input(&'a self, name: impl Into<String>, bit_width: u32) -> &Input<'a> {
-        let name = name.into();
-        // TODO: Error if name already exists in this context
-        if bit_width < MIN_SIGNAL_BIT_WIDTH {
-            panic!(
-                "Cannot create an input with {} bit(s). Signals must not be narrower than {} bit(s).",
-                bit_width, MIN_SIGNAL_BIT_WIDTH
-            );
-        }
-        if bit_width > MAX_SIGNAL_BIT_WIDTH {
-            panic!(
-                "Cannot create an input with {} bit(s). Signals must not be wider than {} bit(s).",
-                bit_width, MAX_SIGNAL_BIT_WIDTH
-            );
-        }
-        let data = self.context.input_data_arena.alloc(InputData {
-            name: name.clone(),
-            bit_width,
-            driven_value: RefCell::new(None),
-        });
-        let value = self.context.signal_arena.alloc(InternalSignal {
-            context: self.context,
-            module: self,
+    #[track_caller]
+    pub fn input(&'a self, name: impl Into<String>, bit_width: u32) -> &'a Input<'a> {
+        self.try_input(name, bit_width).unwrap_or_panic()
+    }
 
-            data: SignalData::Input { data },
-        });
-        let input = self.context.input_arena.alloc(Input {
-            module: self,
+    /// Same as [`input`](Self::input), except that an [`Error`] is returned instead of panicking if the input can't be created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// assert!(m.try_input("my_input", 80).is_ok());
+    /// assert!(m.try_input("my_other_input", 0).is_err());
+    /// ```
+    #[track_caller]
+    pub fn try_input(
+        &'a self,
+        name: impl Into<String>,
+        bit_width: u32,
+    ) -> Result<&'a Input<'a>, Error> {
+        self.try_input_impl(name.into(), bit_width, false)
+    }
 
-            data,
-            value,
-        });
-        self.inputs.borrow_mut().insert(name, input);
-        input
+    /// Creates a signed input for this `Module` called `name` with `bit_width` bits, and returns a [`Signal`] that represents the value of this input.
+    ///
+    /// This is the same as [`input`](Self::input), except that the input is declared as `signed` in generated Verilog code and is reported as signed to [`Trace`](crate::runtime::tracing::Trace) implementations. [`Output`]s driven directly by a signed input are signed as well.
+    /// The returned [`Input`] is still a plain `Signal`, so [`as_signed`](Signal::as_signed) should be used to pick the signed variants of comparisons and arithmetic on its value.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`input`](Self::input).
+    /// See [`try_signed_input`](Self::try_signed_input) for a variant that returns an [`Error`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let my_input = m.signed_input("my_input", 8);
+    /// assert!(my_input.is_signed());
+    /// m.output("my_output", my_input.as_signed() >> m.lit(1u32, 3));
+    /// ```
+    #[track_caller]
+    pub fn signed_input(&'a self, name: impl Into<String>, bit_width: u32) -> &'a Input<'a> {
+        self.try_signed_input(name, bit_width).unwrap_or_panic()
+    }
+
+    /// Same as [`signed_input`](Self::signed_input), except that an [`Error`] is returned instead of panicking if the input can't be created.
+    #[track_caller]
+    pub fn try_signed_input(
+        &'a self,
+        name: impl Into<String>,
+        bit_width: u32,
+    ) -> Result<&'a Input<'a>, Error> {
+        self.try_input_impl(name.into(), bit_width, true)
     }
 
     /// Creates an output for this `Module` called `name` with the same number of bits as `source`, and drives this output with `source`.
     ///
     /// # Panics
     ///
-    /// Panics of `source` doesn't belong to this `Module`.
+    /// Panics if `name` is already used by another item in this `Module`, is reserved for names in generated code (see [`Error::ReservedName`]), or is a Rust or Verilog keyword.
+    /// Also panics if `source` doesn't belong to this `Module`.
     ///
     /// # Examples
     ///
@@ -224,31 +292,205 @@ impl<'a> Module<'a> {
     ///
     /// let some_signal = m.high();
     /// m.output("my_output", some_signal);
+    ///
+    /// // Outputs driven by a signed view are declared as signed
+    /// let signed_output = m.output("my_signed_output", m.lit(0xffu32, 8).as_signed());
+    /// assert!(signed_output.is_signed());
     /// ```
-    pub fn output(&'a self, name: impl Into<String>, source: &'a dyn Signal<'a>) -> &Output<'a> {
+    #[track_caller]
+    pub fn output(&'a self, name: impl Into<String>, source: &'a dyn Signal<'a>) -> &'a Output<'a> {
         let name = name.into();
+        self.claim_name(&name, ItemKind::Output);
+        let signed = source.is_signed();
         let source = source.internal_signal();
         if !ptr::eq(self, source.module) {
             panic!("Cannot output a signal from another module.");
         }
-        // TODO: Error if name already exists in this context
         let data = self.context.output_data_arena.alloc(OutputData {
             module: self,
 
             name: name.clone(),
             source,
             bit_width: source.bit_width(),
+            signed,
         });
         let output = self.context.output_arena.alloc(Output { data });
         self.outputs.borrow_mut().insert(name, output);
         output
     }
 
+    /// Creates an [`Input`] for each field of the [`Bundle`] `B` in this `Module`, called `prefix` followed by an underscore and the field's name, with the field's bit width.
+    ///
+    /// The returned [`BundleInput`] provides the inputs as a `B` for use within this `Module`, and can be driven from this `Module`'s parent with [`BundleInput::drive`].
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`input`](Self::input) for any of the inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// bundle! {
+    ///     pub struct Stage {
+    ///         pub valid: 1,
+    ///         pub pc: 32,
+    ///     }
+    /// }
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let stage = m.bundle_input::<Stage>("stage"); // Creates "stage_valid" and "stage_pc" inputs
+    /// m.output("pc", stage.value().pc);
+    /// ```
+    #[track_caller]
+    pub fn bundle_input<B: Bundle<'a>>(&'a self, prefix: impl Into<String>) -> BundleInput<'a, B> {
+        let prefix = prefix.into();
+        let inputs = B::fields()
+            .into_iter()
+            .map(|(name, bit_width)| self.input(format!("{}_{}", prefix, name), bit_width))
+            .collect::<Vec<_>>();
+        let value = B::from_signals(inputs.iter().map(|&input| input as &dyn Signal).collect());
+        BundleInput { inputs, value }
+    }
+
+    /// Creates an [`Output`] for each field of `source` in this `Module`, called `prefix` followed by an underscore and the field's name, and drives each output with the corresponding field.
+    ///
+    /// Returns a `B` whose fields are the created outputs, for use in this `Module`'s parent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any field of `source` doesn't match its declared bit width, or under the same conditions as [`output`](Self::output) for any of the outputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// bundle! {
+    ///     pub struct Stage {
+    ///         pub valid: 1,
+    ///         pub pc: 32,
+    ///     }
+    /// }
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let stage_in = m.bundle_input::<Stage>("stage_in");
+    /// let stage_out = Stage {
+    ///     valid: stage_in.value().valid,
+    ///     pc: stage_in.value().pc + 4u32,
+    /// };
+    /// m.bundle_output("stage_out", &stage_out); // Creates "stage_out_valid" and "stage_out_pc" outputs
+    /// ```
+    #[track_caller]
+    pub fn bundle_output<B: Bundle<'a>>(&'a self, prefix: impl Into<String>, source: &B) -> B {
+        let prefix = prefix.into();
+        check_field_bit_widths(source);
+        B::from_signals(
+            B::fields()
+                .into_iter()
+                .zip(source.signals())
+                .map(|((name, _), signal)| {
+                    self.output(format!("{}_{}", prefix, name), signal) as &dyn Signal
+                })
+                .collect(),
+        )
+    }
+
+    /// Creates the producing end of a valid/ready stream called `name` in this `Module`, carrying a payload of type `B`.
+    ///
+    /// This immediately creates an [`Input`] called `name` followed by `_ready`. The `valid` and payload [`Output`]s are created when the returned [`StreamProducer`] is driven with [`StreamProducer::drive`].
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`input`](Self::input) for the `ready` input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// bundle! {
+    ///     pub struct Packet {
+    ///         pub data: 8,
+    ///     }
+    /// }
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let out = m.stream_producer::<Packet>("out");
+    /// out.drive(m.input("valid", 1), &Packet { data: m.input("data", 8) });
+    /// ```
+    #[track_caller]
+    pub fn stream_producer<B: Bundle<'a>>(
+        &'a self,
+        name: impl Into<String>,
+    ) -> StreamProducer<'a, B> {
+        let name = name.into();
+        let ready = self.input(format!("{}_ready", name), 1);
+        StreamProducer {
+            name,
+            ready,
+            outputs: RefCell::new(None),
+        }
+    }
+
+    /// Creates the consuming end of a valid/ready stream called `name` in this `Module`, carrying a payload of type `B`.
+    ///
+    /// This immediately creates an [`Input`] called `name` followed by `_valid`, along with an [`Input`] for each field of the payload, as described in [`bundle_input`](Self::bundle_input). The `ready` [`Output`] is created when the returned [`StreamConsumer`]'s ready signal is driven with [`StreamConsumer::drive_ready`].
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`input`](Self::input) for any of the inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// bundle! {
+    ///     pub struct Packet {
+    ///         pub data: 8,
+    ///     }
+    /// }
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let input = m.stream_consumer::<Packet>("in");
+    /// input.drive_ready(!input.payload().data.bit(0)); // Only accept even values
+    /// ```
+    #[track_caller]
+    pub fn stream_consumer<B: Bundle<'a>>(
+        &'a self,
+        name: impl Into<String>,
+    ) -> StreamConsumer<'a, B> {
+        let name = name.into();
+        let valid = self.input(format!("{}_valid", name), 1);
+        let payload = self.bundle_input(name.clone());
+        StreamConsumer {
+            name,
+            valid,
+            payload,
+            ready: RefCell::new(None),
+        }
+    }
+
     /// Creates a [`Register`] in this `Module` called `name` with `bit_width` bits.
     ///
     /// # Panics
     ///
-    /// Panics if `bit_width` is less than [`MIN_SIGNAL_BIT_WIDTH`] or greater than [`MAX_SIGNAL_BIT_WIDTH`], respectively.
+    /// Panics if `name` is already used by another item in this `Module`, is reserved for names in generated code (see [`Error::ReservedName`]), or is a Rust or Verilog keyword.
+    /// Also panics if `bit_width` is less than [`MIN_SIGNAL_BIT_WIDTH`] or greater than [`MAX_SIGNAL_BIT_WIDTH`], respectively.
     ///
     /// # Examples
     ///
@@ -264,36 +506,38 @@ impl<'a> Module<'a> {
     /// my_reg.drive_next(!my_reg);
     /// m.output("my_output", my_reg);
     /// ```
-    pub fn reg(&'a self, name: impl Into<String>, bit_width: u32) -> &Register<'a> {
-        // TODO: Error if name already exists in this context and update docs for Signal::reg_next and Signal::reg_next_with_default to reflect this
-        if bit_width < MIN_SIGNAL_BIT_WIDTH {
-            panic!(
-                "Cannot create a register with {} bit(s). Signals must not be narrower than {} bit(s).",
-                bit_width, MIN_SIGNAL_BIT_WIDTH
-            );
-        }
-        if bit_width > MAX_SIGNAL_BIT_WIDTH {
-            panic!(
-                "Cannot create a register with {} bit(s). Signals must not be wider than {} bit(s).",
-                bit_width, MAX_SIGNAL_BIT_WIDTH
-            );
-        }
-        let data = self.context.register_data_arena.alloc(RegisterData {
-            module: self,
-
-            name: name.into(),
-            initial_value: RefCell::new(None),
-            bit_width,
-            next: RefCell::new(None),
-        });
-        let value = self.context.signal_arena.alloc(InternalSignal {
-            context: self.context,
-            module: self,
+    #[track_caller]
+    pub fn reg(&'a self, name: impl Into<String>, bit_width: u32) -> &'a Register<'a> {
+        self.reg_impl(name.into(), bit_width, false)
+    }
 
-            data: SignalData::Reg { data },
-        });
-        self.registers.borrow_mut().push(value);
-        self.context.register_arena.alloc(Register { data, value })
+    /// Creates a signed [`Register`] in this `Module` called `name` with `bit_width` bits.
+    ///
+    /// This is the same as [`reg`](Self::reg), except that the register is declared as `signed` in generated Verilog code and is reported as signed to [`Trace`](crate::runtime::tracing::Trace) implementations. [`Output`]s driven directly by a signed register are signed as well.
+    /// The returned [`Register`] is still a plain `Signal`, so [`as_signed`](Signal::as_signed) should be used to pick the signed variants of comparisons and arithmetic on its value.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`reg`](Self::reg).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let acc = m.signed_reg("acc", 16);
+    /// acc.default_value(0u32);
+    /// acc.drive_next(acc.as_signed() + m.input("delta", 16));
+    /// assert!(acc.is_signed());
+    /// m.output("sum", acc);
+    /// ```
+    #[track_caller]
+    pub fn signed_reg(&'a self, name: impl Into<String>, bit_width: u32) -> &'a Register<'a> {
+        self.reg_impl(name.into(), bit_width, true)
     }
 
     /// Creates a 2:1 [multiplexer](https://en.wikipedia.org/wiki/Multiplexer) that represents `when_true`'s value when `cond` is high, and `when_false`'s value when `cond` is low.
@@ -301,6 +545,7 @@ impl<'a> Module<'a> {
     /// # Panics
     ///
     /// Panics if `cond`, `when_true`, or `when_false` belong to a different `Module` than `self`, if `cond`'s bit width is not 1, or if the bit widths of `when_true` and `when_false` aren't equal.
+    /// See [`try_mux`](Self::try_mux) for a variant that returns an [`Error`] instead.
     ///
     /// # Examples
     ///
@@ -316,43 +561,73 @@ impl<'a> Module<'a> {
     /// let b = m.input("b", 8);
     /// m.output("my_output", m.mux(cond, a, b)); // Outputs a when cond is high, b otherwise
     /// ```
+    #[track_caller]
     pub fn mux(
         &'a self,
         cond: &'a dyn Signal<'a>,
         when_true: &'a dyn Signal<'a>,
         when_false: &'a dyn Signal<'a>,
-    ) -> &dyn Signal<'a> {
+    ) -> &'a dyn Signal<'a> {
+        self.try_mux(cond, when_true, when_false).unwrap_or_panic()
+    }
+
+    /// Same as [`mux`](Self::mux), except that an [`Error`] is returned instead of panicking if the multiplexer can't be created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let cond = m.input("cond", 1);
+    /// let a = m.input("a", 8);
+    /// let b = m.input("b", 4);
+    /// assert!(matches!(m.try_mux(cond, a, b), Err(Error::MuxBitWidthMismatch { .. })));
+    /// ```
+    #[track_caller]
+    pub fn try_mux(
+        &'a self,
+        cond: &'a dyn Signal<'a>,
+        when_true: &'a dyn Signal<'a>,
+        when_false: &'a dyn Signal<'a>,
+    ) -> Result<&'a dyn Signal<'a>, Error> {
         let cond = cond.internal_signal();
         let when_true = when_true.internal_signal();
         let when_false = when_false.internal_signal();
 
         // TODO: This is an optimization to support sugar; if that doesn't go well, remove this
         if when_true == when_false {
-            return when_true;
+            return Ok(when_true);
         }
 
-        if !ptr::eq(self, cond.module) {
-            panic!("Attempted to combine signals from different modules.");
-        }
-        if !ptr::eq(self, when_true.module) {
-            panic!("Attempted to combine signals from different modules.");
-        }
-        if !ptr::eq(self, when_false.module) {
-            panic!("Attempted to combine signals from different modules.");
+        if !ptr::eq(self, cond.module)
+            || !ptr::eq(self, when_true.module)
+            || !ptr::eq(self, when_false.module)
+        {
+            return Err(Error::SignalsFromDifferentModules {
+                module_path: self.path(),
+            });
         }
         if cond.bit_width() != 1 {
-            panic!("Multiplexer conditionals can only be 1 bit wide.");
+            return Err(Error::MuxCondBitWidth {
+                module_path: self.path(),
+                bit_width: cond.bit_width(),
+            });
         }
         if when_true.bit_width() != when_false.bit_width() {
-            panic!(
-                "Cannot multiplex signals with different bit widths ({} and {}, respectively).",
-                when_true.bit_width(),
-                when_false.bit_width()
-            );
+            return Err(Error::MuxBitWidthMismatch {
+                module_path: self.path(),
+                when_true_bit_width: when_true.bit_width(),
+                when_false_bit_width: when_false.bit_width(),
+            });
         }
-        self.context.signal_arena.alloc(InternalSignal {
+        Ok(self.context.signal_arena.alloc(InternalSignal {
             context: self.context,
             module: self,
+            location: Location::caller(),
 
             data: SignalData::Mux {
                 cond,
@@ -360,7 +635,7 @@ impl<'a> Module<'a> {
                 when_false,
                 bit_width: when_true.bit_width(),
             },
-        })
+        }))
     }
 
     /// Creates a [`Mem`] in this `Module` called `name` with `address_bit_width` address bits and `element_bit_width` element bits.
@@ -369,7 +644,8 @@ impl<'a> Module<'a> {
     ///
     /// # Panics
     ///
-    /// Panics if `address_bit_width` or `element_bit_width` is less than [`MIN_SIGNAL_BIT_WIDTH`] or greater than [`MAX_SIGNAL_BIT_WIDTH`], respectively.
+    /// Panics if `name` is already used by another item in this `Module`, is reserved for names in generated code (see [`Error::ReservedName`]), or is a Rust or Verilog keyword.
+    /// Also panics if `address_bit_width` or `element_bit_width` is less than [`MIN_SIGNAL_BIT_WIDTH`] or greater than [`MAX_SIGNAL_BIT_WIDTH`], respectively.
     ///
     /// # Examples
     ///
@@ -387,13 +663,14 @@ impl<'a> Module<'a> {
     /// my_mem.write_port(m.high(), m.lit(0xabad1deau32, 32), m.high());
     /// m.output("my_output", my_mem.read_port(m.high(), m.high()));
     /// ```
+    #[track_caller]
     pub fn mem(
         &'a self,
         name: impl Into<String>,
         address_bit_width: u32,
         element_bit_width: u32,
-    ) -> &Mem<'a> {
-        // TODO: Error if name already exists in this context
+    ) -> &'a Mem<'a> {
+        let name = name.into();
         if address_bit_width < MIN_SIGNAL_BIT_WIDTH {
             panic!(
                 "Cannot create a memory with {} address bit(s). Signals must not be narrower than {} bit(s).",
@@ -418,11 +695,13 @@ impl<'a> Module<'a> {
                 element_bit_width, MAX_SIGNAL_BIT_WIDTH
             );
         }
+        self.claim_name(&name, ItemKind::Mem);
         let ret = self.context.mem_arena.alloc(Mem {
             context: self.context,
             module: self,
 
-            name: name.into(),
+            name,
+            location: Location::caller(),
             address_bit_width,
             element_bit_width,
 
@@ -430,32 +709,731 @@ impl<'a> Module<'a> {
 
             read_ports: RefCell::new(Vec::new()),
             write_port: RefCell::new(None),
+
+            clock: RefCell::new(None),
         });
         self.mems.borrow_mut().push(ret);
         ret
     }
-}
 
-impl<'a> ModuleParent<'a> for Module<'a> {
-    // TODO: Docs, error handling
-    fn module(&'a self, instance_name: impl Into<String>, name: impl Into<String>) -> &Module {
-        let instance_name = instance_name.into();
+    /// Creates a [`Clock`] in this `Module` called `name`.
+    ///
+    /// [`Register`]s and [`Mem`]s in this `Module` can be bound to the returned [`Clock`] with [`Register::clock`] and [`Mem::clock`], respectively. Otherwise, they're driven by this `Module`'s implicit clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is `"clk"`, which is reserved for this `Module`'s implicit clock.
+    /// Also panics if `name` is already used by another item in this `Module`, is reserved for names in generated code (see [`Error::ReservedName`]), or is a Rust or Verilog keyword.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let pixel_clk = m.clock("pixel_clk");
+    /// ```
+    #[track_caller]
+    pub fn clock(&'a self, name: impl Into<String>) -> &'a Clock<'a> {
         let name = name.into();
-        let module = self.context.module_arena.alloc(Module::new(
-            self.context,
-            Some(self),
-            instance_name,
-            name,
-        ));
-        self.modules.borrow_mut().push(module);
-        module
-    }
-}
+        if name == "clk" {
+            panic!("Cannot create a clock called \"clk\" in module \"{}\" because this name is reserved for the module's implicit clock.", self.name);
+        }
+        self.claim_name(&name, ItemKind::Clock);
+        let clock = self.context.clock_arena.alloc(Clock {
+            module: self,
 
-impl<'a> Eq for &'a Module<'a> {}
+            name: name.clone(),
+            driven_clock: RefCell::new(None),
+        });
+        self.clocks.borrow_mut().insert(name, clock);
+        clock
+    }
 
-impl<'a> Hash for &'a Module<'a> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
+    /// Creates a [`Reset`] in this `Module` called `name` which is asserted according to `source`'s value and `polarity`, and takes effect according to `kind`.
+    ///
+    /// [`Register`]s in this `Module` can be assigned to the returned [`Reset`] with [`Register::reset`]. Otherwise, they're reset by this `Module`'s implicit reset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another `Reset` called `name` already exists in this `Module`, or if `name` begins with `__` (which is reserved for names in generated code) or is a Rust keyword.
+    /// Resets don't share a namespace with this `Module`'s other items, so a `Reset` can have the same name as the [`Input`] that drives it.
+    /// Also panics if `source` doesn't belong to this `Module`, or if `source`'s bit width is not `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let sync_rst = m.reset("sync_rst", m.input("sync_rst", 1), ResetKind::Sync, ResetPolarity::ActiveHigh);
+    /// ```
+    #[track_caller]
+    pub fn reset(
+        &'a self,
+        name: impl Into<String>,
+        source: &'a dyn Signal<'a>,
+        kind: ResetKind,
+        polarity: ResetPolarity,
+    ) -> &'a Reset<'a> {
+        let name = name.into();
+        let source = source.internal_signal();
+        if !ptr::eq(self, source.module) {
+            panic!("Cannot create a reset from a signal from another module.");
+        }
+        if source.bit_width() != 1 {
+            panic!("Cannot create a reset called \"{}\" in module \"{}\" from a signal with {} bit(s). Reset signals are required to be 1 bit wide.", name, self.name, source.bit_width());
+        }
+        self.check_name(&name, ItemKind::Reset).unwrap_or_panic();
+        let reset = self.context.reset_arena.alloc(Reset {
+            module: self,
+
+            name,
+            location: Location::caller(),
+            source,
+            kind,
+            polarity,
+        });
+        self.resets.borrow_mut().push(reset);
+        reset
+    }
+
+    /// Creates a new `Module` instance called `instance_name` in this `Module` from `definition`, a top-level `Module` (created with [`Context::module`]) that has already been built.
+    ///
+    /// Unlike [`ModuleParent::module`], which creates an empty `Module` that has to be built signal by signal, this copies `definition`'s inputs, outputs, registers, memories, clocks, resets and the `Module`s it contains into the new instance, so a definition only needs to be built once no matter how many times it's instantiated.
+    /// The new instance has the same definition name as `definition`, and its [`Input`]s are undriven, so they can be driven with [`Input::drive`] as usual. Its ports can be looked up with [`get_input`](Self::get_input) and [`get_output`](Self::get_output).
+    ///
+    /// Instances share the code generated for `definition`, so `definition` must be fully built before it's instantiated; modifying it (or any `Module` it contains) afterwards panics.
+    ///
+    /// # Limitations
+    ///
+    /// Building the definition and the generated code are shared between instances, but the graph isn't:
+    ///
+    /// - Each instance is a copy of `definition`'s graph, so the memory used by the graph still grows with the number of instances.
+    /// - [`verilog::generate`](crate::verilog::generate) generates `definition`'s Verilog module once, and instantiates it for each instance.
+    /// - [`sim::generate`](crate::sim::generate) generates a single struct for `definition`, and each instance becomes a member of that struct's type in the generated simulator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `definition` isn't a top-level `Module`, belongs to a different [`Context`], or is the top-level `Module` containing this `Module`.
+    /// Also panics if `instance_name` is already used by another item in this `Module`, is reserved for names in generated code (see [`Error::ReservedName`]), or is a Rust or Verilog keyword, or if this `Module` belongs to a definition that has already been instantiated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// // Build the definition once
+    /// let stage = c.module("stage", "Stage");
+    /// let i = stage.input("i", 8);
+    /// let r = stage.reg("r", 8);
+    /// r.default_value(0u32);
+    /// r.drive_next(i + 1u32);
+    /// stage.output("o", r);
+    ///
+    /// // ... then instantiate it as many times as needed
+    /// let pipeline = c.module("pipeline", "Pipeline");
+    /// let mut value: &dyn Signal = pipeline.input("i", 8);
+    /// for index in 0..4 {
+    ///     let instance = pipeline.instance(format!("stage{}", index), stage);
+    ///     instance.get_input("i").unwrap().drive(value);
+    ///     value = instance.get_output("o").unwrap();
+    /// }
+    /// pipeline.output("o", value);
+    /// ```
+    #[track_caller]
+    pub fn instance(
+        &'a self,
+        instance_name: impl Into<String>,
+        definition: &'a Module<'a>,
+    ) -> &'a Module<'a> {
+        let instance_name = instance_name.into();
+        if definition.parent.is_some() {
+            panic!("Attempted to instantiate module \"{}\", but only top-level modules can be used as definitions.", definition.name);
+        }
+        if !ptr::eq(self.context, definition.context) {
+            panic!(
+                "Attempted to instantiate module \"{}\" from another context.",
+                definition.name
+            );
+        }
+        let mut root = self;
+        while let Some(parent) = root.parent {
+            root = parent;
+        }
+        if ptr::eq(root, definition) {
+            panic!(
+                "Attempted to instantiate module \"{}\" inside itself.",
+                definition.name
+            );
+        }
+        self.claim_name(&instance_name, ItemKind::Instance);
+        instance::instantiate(self, instance_name, definition)
+    }
+
+    /// Checks that this `Module` and all of the `Module`s it contains form a valid hierarchy that code can be generated for.
+    ///
+    /// Generators such as [`sim::generate`](crate::sim::generate) and [`verilog::generate`](crate::verilog::generate) perform the same checks and panic if they fail, so this method can be used to report these errors gracefully instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let my_reg = m.reg("my_reg", 32);
+    /// m.output("my_output", my_reg);
+    ///
+    /// assert!(matches!(m.validate(), Err(Error::UndrivenRegister { .. })));
+    ///
+    /// my_reg.drive_next(!my_reg);
+    ///
+    /// assert_eq!(m.validate(), Ok(()));
+    /// ```
+    pub fn validate(&'a self) -> Result<(), Error> {
+        validation::validate_module_hierarchy(self)
+    }
+
+    /// Returns this `Module`'s definition name (eg. `"MyModule"`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns this `Module`'s instance name (eg. `"my_module"`).
+    pub fn instance_name(&self) -> &str {
+        &self.instance_name
+    }
+
+    /// Returns the `Module` containing this `Module`, or `None` if this is a top-level `Module`.
+    pub fn parent(&self) -> Option<&'a Module<'a>> {
+        self.parent
+    }
+
+    /// Returns this `Module`'s [`Input`]s, ordered by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let _ = m.input("b", 4);
+    /// let _ = m.input("a", 1);
+    ///
+    /// let names: Vec<_> = m.inputs().iter().map(|i| (i.name(), i.bit_width())).collect();
+    /// assert_eq!(names, [("a", 1), ("b", 4)]);
+    /// ```
+    pub fn inputs(&self) -> Vec<&'a Input<'a>> {
+        self.inputs.borrow().values().copied().collect()
+    }
+
+    /// Returns this `Module`'s [`Input`] called `name`, or `None` if there isn't one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let _ = m.input("a", 1);
+    ///
+    /// assert_eq!(m.get_input("a").unwrap().bit_width(), 1);
+    /// assert!(m.get_input("b").is_none());
+    /// ```
+    pub fn get_input(&self, name: &str) -> Option<&'a Input<'a>> {
+        self.inputs.borrow().get(name).copied()
+    }
+
+    /// Returns this `Module`'s [`Output`]s, ordered by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let i = m.input("i", 4);
+    /// m.output("o", i);
+    ///
+    /// let o = m.outputs()[0];
+    /// assert_eq!(o.name(), "o");
+    /// assert_eq!(o.bit_width(), 4);
+    /// assert!(matches!(o.source().node(), SignalNode::Input { .. }));
+    /// ```
+    pub fn outputs(&self) -> Vec<&'a Output<'a>> {
+        self.outputs.borrow().values().copied().collect()
+    }
+
+    /// Returns this `Module`'s [`Output`] called `name`, or `None` if there isn't one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// m.output("o", m.input("i", 4));
+    ///
+    /// assert_eq!(m.get_output("o").unwrap().bit_width(), 4);
+    /// assert!(m.get_output("i").is_none());
+    /// ```
+    pub fn get_output(&self, name: &str) -> Option<&'a Output<'a>> {
+        self.outputs.borrow().get(name).copied()
+    }
+
+    /// Returns the current values of this `Module`'s [`Register`]s, in the order they were created.
+    ///
+    /// Each returned [`Signal`]'s [node](Signal::node) is a [`SignalNode::Reg`] describing the [`Register`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let r = m.reg("r", 8);
+    /// r.default_value(0u32);
+    /// r.drive_next(!r);
+    ///
+    /// match m.registers()[0].node() {
+    ///     SignalNode::Reg { name, bit_width, default_value, next, .. } => {
+    ///         assert_eq!(name, "r");
+    ///         assert_eq!(bit_width, 8);
+    ///         assert!(default_value.is_some());
+    ///         assert!(matches!(next.unwrap().node(), SignalNode::UnOp { op: UnOp::Not, .. }));
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn registers(&self) -> Vec<&'a dyn Signal<'a>> {
+        self.registers
+            .borrow()
+            .iter()
+            .map(|&register| register as &dyn Signal)
+            .collect()
+    }
+
+    /// Returns this `Module`'s [`Mem`]s, in the order they were created.
+    pub fn mems(&self) -> Vec<&'a Mem<'a>> {
+        self.mems.borrow().clone()
+    }
+
+    /// Returns the `Module` instances contained in this `Module`, in the order they were created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let inner = m.module("inner", "Inner");
+    ///
+    /// let modules = m.modules();
+    /// assert_eq!(modules[0].instance_name(), "inner");
+    /// assert_eq!(modules[0].name(), "Inner");
+    /// assert_eq!(modules[0].parent().unwrap().name(), "MyModule");
+    /// ```
+    pub fn modules(&self) -> Vec<&'a Module<'a>> {
+        self.modules.borrow().clone()
+    }
+
+    /// Returns this `Module`'s explicitly-declared [`Clock`]s, ordered by name.
+    pub fn clocks(&self) -> Vec<&'a Clock<'a>> {
+        self.clocks.borrow().values().copied().collect()
+    }
+
+    /// Returns this `Module`'s explicitly-declared [`Reset`]s, in the order they were created.
+    pub fn resets(&self) -> Vec<&'a Reset<'a>> {
+        self.resets.borrow().clone()
+    }
+
+    /// Returns the names and values of the parameters this `Module`'s definition was built with by a [`ModuleGenerator`](crate::ModuleGenerator), in declaration order.
+    ///
+    /// This is empty for `Module`s that weren't built by a [`ModuleGenerator`](crate::ModuleGenerator). Instances created from a definition with [`Module::instance`] have the same parameters as that definition.
+    pub fn parameters(&self) -> Vec<(String, String)> {
+        self.parameters.borrow().clone()
+    }
+
+    pub(crate) fn path(&self) -> String {
+        match self.parent {
+            Some(parent) => format!("{}.{}", parent.path(), self.instance_name),
+            None => self.instance_name.clone(),
+        }
+    }
+
+    #[track_caller]
+    fn try_input_impl(
+        &'a self,
+        name: String,
+        bit_width: u32,
+        signed: bool,
+    ) -> Result<&'a Input<'a>, Error> {
+        self.check_name(&name, ItemKind::Input)?;
+        if !(MIN_SIGNAL_BIT_WIDTH..=MAX_SIGNAL_BIT_WIDTH).contains(&bit_width) {
+            return Err(Error::InputBitWidthOutOfRange {
+                module_path: self.path(),
+                name,
+                bit_width,
+            });
+        }
+        let data = self.context.input_data_arena.alloc(InputData {
+            name: name.clone(),
+            location: Location::caller(),
+            bit_width,
+            signed,
+            driven_value: RefCell::new(None),
+        });
+        let value = self.context.signal_arena.alloc(InternalSignal {
+            context: self.context,
+            module: self,
+            location: Location::caller(),
+
+            data: SignalData::Input { data },
+        });
+        let input = self.context.input_arena.alloc(Input {
+            module: self,
+
+            data,
+            value,
+        });
+        self.names
+            .borrow_mut()
+            .insert(name.clone(), (ItemKind::Input, data.location));
+        self.inputs.borrow_mut().insert(name, input);
+        Ok(input)
+    }
+
+    #[track_caller]
+    fn reg_impl(&'a self, name: String, bit_width: u32, signed: bool) -> &'a Register<'a> {
+        if bit_width < MIN_SIGNAL_BIT_WIDTH {
+            panic!(
+                "Cannot create a register with {} bit(s). Signals must not be narrower than {} bit(s).",
+                bit_width, MIN_SIGNAL_BIT_WIDTH
+            );
+        }
+        if bit_width > MAX_SIGNAL_BIT_WIDTH {
+            panic!(
+                "Cannot create a register with {} bit(s). Signals must not be wider than {} bit(s).",
+                bit_width, MAX_SIGNAL_BIT_WIDTH
+            );
+        }
+        self.claim_name(&name, ItemKind::Register);
+        let data = self.context.register_data_arena.alloc(RegisterData {
+            module: self,
+
+            name: name.clone(),
+            location: Location::caller(),
+            initial_value: RefCell::new(None),
+            bit_width,
+            signed,
+            next: RefCell::new(None),
+            clock: RefCell::new(None),
+            reset: RefCell::new(None),
+        });
+        let value = self.context.signal_arena.alloc(InternalSignal {
+            context: self.context,
+            module: self,
+            location: Location::caller(),
+
+            data: SignalData::Reg { data },
+        });
+        self.registers.borrow_mut().push(value);
+        self.context.register_arena.alloc(Register { data, value })
+    }
+
+    #[track_caller]
+    fn check_name(&self, name: &str, kind: ItemKind) -> Result<(), Error> {
+        let existing = match kind {
+            ItemKind::Reset => self
+                .resets
+                .borrow()
+                .iter()
+                .find(|reset| reset.name == name)
+                .map(|reset| (ItemKind::Reset, reset.location)),
+            _ => self.names.borrow().get(name).copied(),
+        };
+        if let Some((existing_kind, existing_location)) = existing {
+            return Err(Error::DuplicateName {
+                module_path: self.path(),
+                module_name: self.name.clone(),
+                name: name.into(),
+                kind,
+                existing_kind,
+                existing_location,
+            });
+        }
+        // Resets are always prefixed in generated code, so only the `__` prefix (and keywords) are off-limits for their names
+        let is_reserved = if kind == ItemKind::Reset {
+            name.starts_with("__")
+        } else {
+            RESERVED_NAME_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+                || RESERVED_NAMES.contains(&name)
+        };
+        if is_reserved {
+            return Err(Error::ReservedName {
+                module_path: self.path(),
+                module_name: self.name.clone(),
+                name: name.into(),
+                kind,
+            });
+        }
+        if RUST_KEYWORDS.contains(&name)
+            || (kind != ItemKind::Reset && VERILOG_KEYWORDS.contains(&name))
+        {
+            return Err(Error::KeywordName {
+                module_path: self.path(),
+                module_name: self.name.clone(),
+                name: name.into(),
+                kind,
+            });
+        }
+        Ok(())
+    }
+
+    // Checks all of `names` for new items of the same kind (including against each other), so that a group of items can be validated before any of them is created
+    #[track_caller]
+    pub(crate) fn check_names(&self, names: &[String], kind: ItemKind) {
+        for (index, name) in names.iter().enumerate() {
+            self.check_name(name, kind).unwrap_or_panic();
+            if names[..index].contains(name) {
+                panic!(
+                    "{}",
+                    Error::DuplicateName {
+                        module_path: self.path(),
+                        module_name: self.name.clone(),
+                        name: name.clone(),
+                        kind,
+                        existing_kind: kind,
+                        existing_location: Location::caller(),
+                    }
+                );
+            }
+        }
+    }
+
+    // Instances share the generated code of their definition, so a definition's hierarchy can't be modified once it's been instantiated
+    #[track_caller]
+    pub(crate) fn check_modifiable(&self) {
+        let mut root = self;
+        while let Some(parent) = root.parent {
+            root = parent;
+        }
+        if root.instantiated.get() {
+            panic!("Attempted to modify module \"{}\", but module \"{}\" has already been instantiated. Definitions must be fully built before they're instantiated.", self.name, root.name);
+        }
+    }
+
+    #[track_caller]
+    fn claim_name(&self, name: &str, kind: ItemKind) {
+        self.check_modifiable();
+        self.check_name(name, kind).unwrap_or_panic();
+        self.names
+            .borrow_mut()
+            .insert(name.into(), (kind, Location::caller()));
+    }
+}
+
+// Prefixes of names used in generated code: `__` for internal signals and `posedge_` for generated Rust simulators' clock methods
+pub(crate) const RESERVED_NAME_PREFIXES: &[&str] = &["__", "posedge_"];
+
+// Verilog ports that every generated Verilog module has, and other members of generated Rust simulators
+pub(crate) const RESERVED_NAMES: &[&str] =
+    &["clk", "reset_n", "new", "reset", "prop", "update_trace"];
+
+// Strict and reserved keywords as of the 2018 edition, which can't be used as identifiers in generated code
+pub(crate) const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+// Keywords as of IEEE 1364-2005, which can't be used as identifiers in generated Verilog code
+pub(crate) const VERILOG_KEYWORDS: &[&str] = &[
+    "always",
+    "and",
+    "assign",
+    "automatic",
+    "begin",
+    "buf",
+    "bufif0",
+    "bufif1",
+    "case",
+    "casex",
+    "casez",
+    "cell",
+    "cmos",
+    "config",
+    "deassign",
+    "default",
+    "defparam",
+    "design",
+    "disable",
+    "edge",
+    "else",
+    "end",
+    "endcase",
+    "endconfig",
+    "endfunction",
+    "endgenerate",
+    "endmodule",
+    "endprimitive",
+    "endspecify",
+    "endtable",
+    "endtask",
+    "event",
+    "for",
+    "force",
+    "forever",
+    "fork",
+    "function",
+    "generate",
+    "genvar",
+    "highz0",
+    "highz1",
+    "if",
+    "ifnone",
+    "incdir",
+    "include",
+    "initial",
+    "inout",
+    "input",
+    "instance",
+    "integer",
+    "join",
+    "large",
+    "liblist",
+    "library",
+    "localparam",
+    "macromodule",
+    "medium",
+    "module",
+    "nand",
+    "negedge",
+    "nmos",
+    "nor",
+    "noshowcancelled",
+    "not",
+    "notif0",
+    "notif1",
+    "or",
+    "output",
+    "parameter",
+    "pmos",
+    "posedge",
+    "primitive",
+    "pull0",
+    "pull1",
+    "pulldown",
+    "pullup",
+    "pulsestyle_ondetect",
+    "pulsestyle_onevent",
+    "rcmos",
+    "real",
+    "realtime",
+    "reg",
+    "release",
+    "repeat",
+    "rnmos",
+    "rpmos",
+    "rtran",
+    "rtranif0",
+    "rtranif1",
+    "scalared",
+    "showcancelled",
+    "signed",
+    "small",
+    "specify",
+    "specparam",
+    "strong0",
+    "strong1",
+    "supply0",
+    "supply1",
+    "table",
+    "task",
+    "time",
+    "tran",
+    "tranif0",
+    "tranif1",
+    "tri",
+    "tri0",
+    "tri1",
+    "triand",
+    "trior",
+    "trireg",
+    "unsigned",
+    "use",
+    "uwire",
+    "vectored",
+    "wait",
+    "wand",
+    "weak0",
+    "weak1",
+    "while",
+    "wire",
+    "wor",
+    "xnor",
+    "xor",
+];
+
+impl<'a> ModuleParent<'a> for Module<'a> {
+    // TODO: Docs, error handling
+    fn module(
+        &'a self,
+        instance_name: impl Into<String>,
+        name: impl Into<String>,
+    ) -> &'a Module<'a> {
+        let instance_name = instance_name.into();
+        let name = name.into();
+        self.claim_name(&instance_name, ItemKind::Instance);
+        let module = self.context.module_arena.alloc(Module::new(
+            self.context,
+            Some(self),
+            instance_name,
+            name,
+            None,
+        ));
+        self.modules.borrow_mut().push(module);
+        module
+    }
+}
+
+impl<'a> Eq for &'a Module<'a> {}
+
+impl<'a> Hash for &'a Module<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
         state.write_usize(*self as *const _ as usize)
     }
 }
@@ -479,11 +1457,38 @@ pub struct Input<'a> {
 }
 
 impl<'a> Input<'a> {
+    /// Returns this `Input`'s name.
+    pub fn name(&self) -> &str {
+        &self.data.name
+    }
+
+    /// Returns `true` if this `Input` was created with [`Module::signed_input`], in which case it's declared as `signed` in generated Verilog code and traced as a signed value.
+    pub fn is_signed(&self) -> bool {
+        self.data.signed
+    }
+
+    /// Returns the [`Signal`] driving this `Input` from its [`Module`]'s parent, if it's driven.
+    pub fn driver(&self) -> Option<&'a dyn Signal<'a>> {
+        self.data
+            .driven_value
+            .borrow()
+            .map(|driver| driver as &dyn Signal)
+    }
+
     // TODO: Doc
     // TODO: Merge error cases with Instance::drive_input?
     // TODO: Rename i?
+    #[track_caller]
     pub fn drive(&'a self, i: &'a dyn Signal<'a>) {
         let i = i.internal_signal();
+        self.check_drive(i);
+        *self.data.driven_value.borrow_mut() = Some(i);
+    }
+
+    /// Panics under the same conditions as [`drive`](Self::drive), without driving this `Input`. Used to validate several inputs before driving any of them.
+    #[track_caller]
+    pub(crate) fn check_drive(&self, i: &'a InternalSignal<'a>) {
+        self.module.check_modifiable();
         // TODO: Change text from instance -> module in appropriate places?
         if let Some(parent) = self.module.parent {
             if !ptr::eq(parent, i.module) {
@@ -494,14 +1499,12 @@ impl<'a> Input<'a> {
             // TODO: Proper panic + test!
             panic!("OH NOES");
         }
-        let mut driven_value = self.data.driven_value.borrow_mut();
-        if driven_value.is_some() {
+        if self.data.driven_value.borrow().is_some() {
             panic!("Attempted to drive an input called \"{}\" on an instance of \"{}\", but this input is already driven for this instance.", self.data.name, self.module.name);
         }
         if self.data.bit_width != i.bit_width() {
             panic!("Attempted to drive an input called \"{}\" on an instance of \"{}\", but this input and the provided signal have different bit widths ({} and {}, respectively).", self.data.name, self.module.name, self.data.bit_width, i.bit_width());
         }
-        *driven_value = Some(i);
     }
 }
 
@@ -509,6 +1512,10 @@ impl<'a> GetInternalSignal<'a> for Input<'a> {
     fn internal_signal(&'a self) -> &'a InternalSignal<'a> {
         self.value
     }
+
+    fn is_signed(&self) -> bool {
+        self.data.signed
+    }
 }
 
 impl<'a> GetInternalSignal<'a> for Output<'a> {
@@ -517,16 +1524,23 @@ impl<'a> GetInternalSignal<'a> for Output<'a> {
         self.data.module.context.signal_arena.alloc(InternalSignal {
             context: self.data.module.context,
             module: parent,
+            location: Location::caller(),
 
             data: SignalData::Output { data: self.data },
         })
     }
+
+    fn is_signed(&self) -> bool {
+        self.data.signed
+    }
 }
 
 pub(crate) struct InputData<'a> {
     // TODO: Do we need this stored here too?
     pub name: String,
+    pub location: &'static Location<'static>,
     pub bit_width: u32,
+    pub signed: bool,
     // TODO: Rename?
     pub driven_value: RefCell<Option<&'a InternalSignal<'a>>>,
 }
@@ -539,344 +1553,937 @@ pub struct Output<'a> {
     pub(crate) data: &'a OutputData<'a>,
 }
 
+impl<'a> Output<'a> {
+    /// Returns this `Output`'s name.
+    pub fn name(&self) -> &str {
+        &self.data.name
+    }
+
+    /// Returns this `Output`'s bit width.
+    pub fn bit_width(&self) -> u32 {
+        self.data.bit_width
+    }
+
+    /// Returns `true` if this `Output` was driven by a [`SignedSignal`](crate::SignedSignal), in which case it's declared as `signed` in generated Verilog code and traced as a signed value.
+    pub fn is_signed(&self) -> bool {
+        self.data.signed
+    }
+
+    /// Returns the [`Signal`] driving this `Output`, which belongs to this `Output`'s [`Module`].
+    pub fn source(&self) -> &'a dyn Signal<'a> {
+        self.data.source
+    }
+
+    /// Returns the [`Module`] this `Output` belongs to.
+    pub fn module(&self) -> &'a Module<'a> {
+        self.data.module
+    }
+}
+
 pub(crate) struct OutputData<'a> {
     // TODO: Do we need this?
     pub module: &'a Module<'a>,
 
-    // TODO: Do we need this stored here too?
-    pub name: String,
-    pub source: &'a InternalSignal<'a>,
-    pub bit_width: u32,
-}
+    // TODO: Do we need this stored here too?
+    pub name: String,
+    pub source: &'a InternalSignal<'a>,
+    pub bit_width: u32,
+    pub signed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SignalNode;
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot create a literal with 0 bit(s). Signals must not be narrower than 1 bit(s)."
+    )]
+    fn lit_bit_width_lt_min_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.lit(false, 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot create a literal with 65537 bit(s). Signals must not be wider than 65536 bit(s)."
+    )]
+    fn lit_bit_width_gt_max_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.lit(false, 65537);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot fit the specified value '128' into the specified bit width '7'. The value '128' requires a bit width of at least 8 bit(s)."
+    )]
+    fn lit_value_cannot_bit_into_bit_width_error_1() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.lit(128u32, 7);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot fit the specified value '128' into the specified bit width '2'. The value '128' requires a bit width of at least 8 bit(s)."
+    )]
+    fn lit_value_cannot_bit_into_bit_width_error_2() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.lit(128u64, 2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot fit the specified value '1023' into the specified bit width '4'. The value '1023' requires a bit width of at least 10 bit(s)."
+    )]
+    fn lit_value_cannot_bit_into_bit_width_error_3() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.lit(1023u128, 4);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot fit the specified value '65536' into the specified bit width '1'. The value '65536' requires a bit width of at least 17 bit(s)."
+    )]
+    fn lit_value_cannot_bit_into_bit_width_error_4() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.lit(65536u32, 1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot fit the specified value '0x100000000000000000000000000000000' into the specified bit width '128'. The value '0x100000000000000000000000000000000' requires a bit width of at least 129 bit(s)."
+    )]
+    fn lit_value_cannot_bit_into_bit_width_error_5() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.lit([0u64, 0, 1], 128);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot create an input with 0 bit(s). Signals must not be narrower than 1 bit(s)."
+    )]
+    fn input_width_lt_min_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.input("i", 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot create an input with 65537 bit(s). Signals must not be wider than 65536 bit(s)."
+    )]
+    fn input_width_gt_max_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.input("i", 65537);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot output a signal from another module.")]
+    fn output_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+
+        let m2 = c.module("b", "B");
+        let i = m2.high();
+
+        // Panic
+        m1.output("a", i);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot create a register with 0 bit(s). Signals must not be narrower than 1 bit(s)."
+    )]
+    fn reg_bit_width_lt_min_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.reg("r", 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot create a register with 65537 bit(s). Signals must not be wider than 65536 bit(s)."
+    )]
+    fn reg_bit_width_gt_max_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+
+        // Panic
+        let _ = m.reg("r", 65537);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn mux_cond_separate_module_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(false, 1);
+
+        let b = c.module("b", "B");
+        let l2 = b.lit(32u8, 8);
+        let l3 = b.lit(32u8, 8);
+
+        // Panic
+        let _ = b.mux(l1, l2, l3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn mux_when_true_separate_module_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(32u8, 8);
+
+        let b = c.module("b", "B");
+        let l2 = b.lit(true, 1);
+        let l3 = b.lit(32u8, 8);
+
+        // Panic
+        let _ = b.mux(l2, l1, l3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn mux_when_false_separate_module_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(32u8, 8);
+
+        let b = c.module("b", "B");
+        let l2 = b.lit(true, 1);
+        let l3 = b.lit(32u8, 8);
+
+        // Panic
+        let _ = b.mux(l2, l3, l1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Multiplexer conditionals can only be 1 bit wide.")]
+    fn mux_cond_bit_width_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(2u8, 2);
+        let l2 = a.lit(32u8, 8);
+        let l3 = a.lit(32u8, 8);
+
+        // Panic
+        let _ = a.mux(l1, l2, l3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot multiplex signals with different bit widths (3 and 5, respectively)."
+    )]
+    fn mux_true_false_bit_width_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(false, 1);
+        let l2 = a.lit(3u8, 3);
+        let l3 = a.lit(3u8, 5);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // Panic
+        let _ = a.mux(l1, l2, l3);
+    }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create a literal with 0 bit(s). Signals must not be narrower than 1 bit(s)."
+        expected = "Cannot create a memory with 0 address bit(s). Signals must not be narrower than 1 bit(s)."
     )]
-    fn lit_bit_width_lt_min_error() {
+    fn mem_address_bit_width_lt_min_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
         // Panic
-        let _ = m.lit(false, 0);
+        let _ = m.mem("mem", 0, 1);
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create a literal with 129 bit(s). Signals must not be wider than 128 bit(s)."
+        expected = "Cannot create a memory with 65537 address bit(s). Signals must not be wider than 65536 bit(s)."
     )]
-    fn lit_bit_width_gt_max_error() {
+    fn mem_address_bit_width_gt_max_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
         // Panic
-        let _ = m.lit(false, 129);
+        let _ = m.mem("mem", 65537, 1);
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot fit the specified value '128' into the specified bit width '7'. The value '128' requires a bit width of at least 8 bit(s)."
+        expected = "Cannot create a memory with 0 element bit(s). Signals must not be narrower than 1 bit(s)."
     )]
-    fn lit_value_cannot_bit_into_bit_width_error_1() {
+    fn mem_element_bit_width_lt_min_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
         // Panic
-        let _ = m.lit(128u32, 7);
+        let _ = m.mem("mem", 1, 0);
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot fit the specified value '128' into the specified bit width '2'. The value '128' requires a bit width of at least 8 bit(s)."
+        expected = "Cannot create a memory with 65537 element bit(s). Signals must not be wider than 65536 bit(s)."
     )]
-    fn lit_value_cannot_bit_into_bit_width_error_2() {
+    fn mem_element_bit_width_gt_max_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
         // Panic
-        let _ = m.lit(128u64, 2);
+        let _ = m.mem("mem", 1, 65537);
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot fit the specified value '1023' into the specified bit width '4'. The value '1023' requires a bit width of at least 10 bit(s)."
+        expected = "Attempted to drive an instance input with a signal from a different module than that instance's parent module."
     )]
-    fn lit_value_cannot_bit_into_bit_width_error_3() {
+    fn input_drive_different_module_than_parent_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+
+        let inner = m2.module("inner", "Inner");
+        let a = inner.input("a", 1);
+
+        // Panic
+        a.drive(i1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to drive an input called \"a\" on an instance of \"Inner\", but this input is already driven for this instance."
+    )]
+    fn input_drive_already_driven_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
+        let inner = m.module("inner", "Inner");
+        let a = inner.input("a", 1);
+
+        a.drive(m.input("i1", 1));
+
         // Panic
-        let _ = m.lit(1023u128, 4);
+        a.drive(m.input("i2", 1));
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot fit the specified value '65536' into the specified bit width '1'. The value '65536' requires a bit width of at least 17 bit(s)."
+        expected = "Attempted to drive an input called \"a\" on an instance of \"Inner\", but this input and the provided signal have different bit widths (1 and 32, respectively)."
     )]
-    fn lit_value_cannot_bit_into_bit_width_error_4() {
+    fn input_drive_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
+        let inner = m.module("inner", "Inner");
+        let a = inner.input("a", 1);
+
         // Panic
-        let _ = m.lit(65536u32, 1);
+        a.drive(m.input("i1", 32));
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create an input with 0 bit(s). Signals must not be narrower than 1 bit(s)."
+        expected = "Cannot create a module instance called \"inner\" in module \"A\" because a module instance with the same name already exists in this module."
     )]
-    fn input_width_lt_min_error() {
+    fn duplicate_instance_name_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
+        let _ = m.module("inner", "Inner");
+
         // Panic
-        let _ = m.input("i", 0);
+        let _ = m.module("inner", "Inner");
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create an input with 129 bit(s). Signals must not be wider than 128 bit(s)."
+        expected = "Cannot create a clock called \"clk\" in module \"A\" because this name is reserved for the module's implicit clock."
     )]
-    fn input_width_gt_max_error() {
+    fn clock_reserved_name_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
         // Panic
-        let _ = m.input("i", 129);
+        let _ = m.clock("clk");
     }
 
     #[test]
-    #[should_panic(expected = "Cannot output a signal from another module.")]
-    fn output_separate_module_error() {
+    #[should_panic(expected = "Cannot create a reset from a signal from another module.")]
+    fn reset_source_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
+        let i = m1.input("i", 1);
 
         let m2 = c.module("b", "B");
-        let i = m2.high();
 
         // Panic
-        m1.output("a", i);
+        let _ = m2.reset("rst", i, ResetKind::Sync, ResetPolarity::ActiveHigh);
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create a register with 0 bit(s). Signals must not be narrower than 1 bit(s)."
+        expected = "Cannot create a reset called \"rst\" in module \"A\" from a signal with 2 bit(s). Reset signals are required to be 1 bit wide."
     )]
-    fn reg_bit_width_lt_min_error() {
+    fn reset_source_bit_width_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
+        let i = m.input("i", 2);
 
         // Panic
-        let _ = m.reg("r", 0);
+        let _ = m.reset("rst", i, ResetKind::Async, ResetPolarity::ActiveLow);
+    }
+
+    #[test]
+    fn try_input_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let inner = m.module("b", "B");
+
+        assert_eq!(
+            inner.try_input("i", MAX_SIGNAL_BIT_WIDTH + 1).err(),
+            Some(Error::InputBitWidthOutOfRange {
+                module_path: "a.b".into(),
+                name: "i".into(),
+                bit_width: MAX_SIGNAL_BIT_WIDTH + 1,
+            })
+        );
+    }
+
+    #[test]
+    fn try_mux_cond_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let cond = m.input("cond", 2);
+        let a = m.input("a", 8);
+        let b = m.input("b", 8);
+
+        assert_eq!(
+            m.try_mux(cond, a, b).err(),
+            Some(Error::MuxCondBitWidth {
+                module_path: "a".into(),
+                bit_width: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn validate_undriven_instance_input_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let inner = m.module("b", "B");
+        let (i, i_line) = (inner.input("i", 1), line!());
+        m.output("o", inner.output("o", i));
+
+        match m.validate() {
+            Err(Error::UndrivenInstanceInput {
+                root_module_name,
+                module_path,
+                module_name,
+                instance_module_name,
+                instance_name,
+                input_name,
+                location,
+            }) => {
+                assert_eq!(root_module_name, "A");
+                assert_eq!(module_path, "a");
+                assert_eq!(module_name, "A");
+                assert_eq!(instance_module_name, "B");
+                assert_eq!(instance_name, "b");
+                assert_eq!(input_name, "i");
+                assert_eq!(location.file(), file!());
+                assert_eq!(location.line(), i_line);
+            }
+            _ => panic!("Expected an undriven instance input error."),
+        }
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create a register with 129 bit(s). Signals must not be wider than 128 bit(s)."
+        expected = "Cannot create a register called \"a\" in module \"A\" because an input with the same name already exists in this module."
     )]
-    fn reg_bit_width_gt_max_error() {
+    fn duplicate_name_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
+        let _ = m.input("a", 1);
+
         // Panic
-        let _ = m.reg("r", 129);
+        let _ = m.reg("a", 1);
     }
 
     #[test]
-    #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn mux_cond_separate_module_error() {
+    #[should_panic(
+        expected = "Cannot create an output called \"inner\" in module \"A\" because a module instance with the same name already exists in this module."
+    )]
+    fn duplicate_instance_and_output_name_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(false, 1);
+        let m = c.module("a", "A");
 
-        let b = c.module("b", "B");
-        let l2 = b.lit(32u8, 8);
-        let l3 = b.lit(32u8, 8);
+        let _ = m.module("inner", "Inner");
 
         // Panic
-        let _ = b.mux(l1, l2, l3);
+        let _ = m.output("inner", m.high());
     }
 
     #[test]
-    #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn mux_when_true_separate_module_error() {
+    #[should_panic(
+        expected = "Cannot create a memory called \"__temp_0\" in module \"A\" because names beginning with \"__\" are reserved for generated code."
+    )]
+    fn reserved_name_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(32u8, 8);
-
-        let b = c.module("b", "B");
-        let l2 = b.lit(true, 1);
-        let l3 = b.lit(32u8, 8);
+        let m = c.module("a", "A");
 
         // Panic
-        let _ = b.mux(l2, l1, l3);
+        let _ = m.mem("__temp_0", 1, 1);
     }
 
     #[test]
-    #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn mux_when_false_separate_module_error() {
+    #[should_panic(
+        expected = "Cannot create an output called \"type\" in module \"A\" because \"type\" is a Rust keyword."
+    )]
+    fn keyword_name_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(32u8, 8);
-
-        let b = c.module("b", "B");
-        let l2 = b.lit(true, 1);
-        let l3 = b.lit(32u8, 8);
+        let m = c.module("a", "A");
 
         // Panic
-        let _ = b.mux(l2, l3, l1);
+        let _ = m.output("type", m.high());
     }
 
     #[test]
-    #[should_panic(expected = "Multiplexer conditionals can only be 1 bit wide.")]
-    fn mux_cond_bit_width_error() {
+    #[should_panic(
+        expected = "Cannot create an input called \"clk\" in module \"A\" because \"clk\" is reserved for generated code."
+    )]
+    fn reserved_clk_name_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(2u8, 2);
-        let l2 = a.lit(32u8, 8);
-        let l3 = a.lit(32u8, 8);
+        let m = c.module("a", "A");
 
         // Panic
-        let _ = a.mux(l1, l2, l3);
+        let _ = m.input("clk", 1);
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot multiplex signals with different bit widths (3 and 5, respectively)."
+        expected = "Cannot create an output called \"reset_n\" in module \"A\" because \"reset_n\" is reserved for generated code."
     )]
-    fn mux_true_false_bit_width_error() {
+    fn reserved_reset_n_name_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(false, 1);
-        let l2 = a.lit(3u8, 3);
-        let l3 = a.lit(3u8, 5);
+        let m = c.module("a", "A");
 
         // Panic
-        let _ = a.mux(l1, l2, l3);
+        let _ = m.output("reset_n", m.high());
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create a memory with 0 address bit(s). Signals must not be narrower than 1 bit(s)."
+        expected = "Cannot create a register called \"reset\" in module \"A\" because \"reset\" is reserved for generated code."
     )]
-    fn mem_address_bit_width_lt_min_error() {
+    fn reserved_reset_name_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
         // Panic
-        let _ = m.mem("mem", 0, 1);
+        let _ = m.reg("reset", 1);
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create a memory with 129 address bit(s). Signals must not be wider than 128 bit(s)."
+        expected = "Cannot create a memory called \"prop\" in module \"A\" because \"prop\" is reserved for generated code."
     )]
-    fn mem_address_bit_width_gt_max_error() {
+    fn reserved_prop_name_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
         // Panic
-        let _ = m.mem("mem", 129, 1);
+        let _ = m.mem("prop", 1, 1);
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create a memory with 0 element bit(s). Signals must not be narrower than 1 bit(s)."
+        expected = "Cannot create a clock called \"posedge_pixel_clk\" in module \"A\" because names beginning with \"posedge_\" are reserved for generated code."
     )]
-    fn mem_element_bit_width_lt_min_error() {
+    fn reserved_posedge_prefix_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
         // Panic
-        let _ = m.mem("mem", 1, 0);
+        let _ = m.clock("posedge_pixel_clk");
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot create a memory with 129 element bit(s). Signals must not be wider than 128 bit(s)."
+        expected = "Cannot create a module instance called \"wire\" in module \"A\" because \"wire\" is a Verilog keyword."
     )]
-    fn mem_element_bit_width_gt_max_error() {
+    fn verilog_keyword_name_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
 
         // Panic
-        let _ = m.mem("mem", 1, 129);
+        let _ = m.module("wire", "Wire");
+    }
+
+    #[test]
+    fn reset_generated_names() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 1);
+
+        // Reset names are always prefixed in generated code, so only `__` is reserved for them
+        let _ = m.reset("reset", i, ResetKind::Sync, ResetPolarity::ActiveHigh);
+        let _ = m.reset("reset_n", i, ResetKind::Async, ResetPolarity::ActiveLow);
+        let _ = m.reset(
+            "posedge_reset",
+            i,
+            ResetKind::Sync,
+            ResetPolarity::ActiveHigh,
+        );
+        let _ = m.reset("reg", i, ResetKind::Sync, ResetPolarity::ActiveHigh);
+        assert_eq!(
+            m.try_input("reset", 1).err(),
+            Some(Error::ReservedName {
+                module_path: "a".into(),
+                module_name: "A".into(),
+                name: "reset".into(),
+                kind: ItemKind::Input,
+            })
+        );
+    }
+
+    #[test]
+    fn try_input_duplicate_name_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let (_, clock_line) = (m.clock("i"), line!());
+
+        match m.try_input("i", 1) {
+            Err(Error::DuplicateName {
+                module_path,
+                module_name,
+                name,
+                kind,
+                existing_kind,
+                existing_location,
+            }) => {
+                assert_eq!(module_path, "a");
+                assert_eq!(module_name, "A");
+                assert_eq!(name, "i");
+                assert_eq!(kind, ItemKind::Input);
+                assert_eq!(existing_kind, ItemKind::Clock);
+                assert_eq!(existing_location.file(), file!());
+                assert_eq!(existing_location.line(), clock_line);
+            }
+            _ => panic!("Expected a duplicate name error."),
+        }
+    }
+
+    #[test]
+    fn signal_location() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 1);
+        let (eq, eq_line) = (i.eq(m.high()), line!());
+        let (reg, reg_line) = (eq.reg_next("r"), line!());
+
+        let eq = eq.internal_signal();
+        assert_eq!(eq.location.file(), file!());
+        assert_eq!(eq.location.line(), eq_line);
+        match reg.internal_signal().data {
+            SignalData::Reg { data } => {
+                assert_eq!(data.location.file(), file!());
+                assert_eq!(data.location.line(), reg_line);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn introspection() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let clk = m.clock("pixel_clk");
+        let inner = m.module("inner", "Inner");
+        let inner_clk = inner.clock("inner_clk");
+        inner_clk.drive(clk);
+        let inner_i = inner.input("i", 4);
+        inner_i.drive(m.input("i", 4));
+        let inner_o = inner.output("o", inner_i);
+        let mem = m.mem("mem", 4, 8);
+        mem.initial_contents(&[0u32; 16]);
+        m.output("o", mem.read_port(inner_o, m.high()));
+
+        assert_eq!(m.modules().len(), 1);
+        assert!(ptr::eq(m.modules()[0], inner));
+        assert_eq!(inner.clocks()[0].driver().unwrap().name(), "pixel_clk");
+        assert!(
+            matches!(inner.inputs()[0].driver().unwrap().node(), SignalNode::Input { input } if input.name() == "i")
+        );
+        assert!(m.inputs()[0].driver().is_none());
+        assert_eq!(m.mems()[0].name(), "mem");
+        assert_eq!(m.mems()[0].initial_values().unwrap().len(), 16);
+
+        match m.outputs()[0].source().node() {
+            SignalNode::MemReadPortOutput { mem, address, .. } => {
+                assert_eq!(mem.address_bit_width(), 4);
+                assert_eq!(mem.element_bit_width(), 8);
+                match address.node() {
+                    SignalNode::Output { output } => {
+                        assert_eq!(output.name(), "o");
+                        assert!(ptr::eq(output.module(), inner));
+                    }
+                    _ => panic!("Expected an instance output."),
+                }
+            }
+            _ => panic!("Expected a memory read port output."),
+        }
+    }
+
+    #[test]
+    fn validate_combinational_loop_between_instances_error() {
+        let c = Context::new();
+
+        let m = c.module("top", "Top");
+        let x = m.module("x", "X");
+        let x_i = x.input("i", 1);
+        let x_o = x.output("o", !x_i);
+        let y = m.module("y", "Y");
+        let y_i = y.input("i", 1);
+        let y_o = y.output("o", y_i);
+        x_i.drive(y_o);
+        y_i.drive(x_o);
+
+        match m.validate() {
+            Err(Error::CombinationalLoop {
+                module_path,
+                output_name,
+                cycle,
+                ..
+            }) => {
+                assert_eq!(module_path, "top.x");
+                assert_eq!(output_name, "o");
+                assert_eq!(
+                    cycle,
+                    ["top.x.o", "top.y.i", "top.y.o", "top.x.i", "top.x.o"]
+                );
+            }
+            _ => panic!("Expected a combinational loop error."),
+        }
+    }
+
+    #[test]
+    fn validate_combinational_loop_in_nested_instance_error() {
+        let c = Context::new();
+
+        let m = c.module("top", "Top");
+        let a = m.module("a", "A");
+        let b = a.module("b", "B");
+        let b_i = b.input("i", 1);
+        let b_o = b.output("o", b_i);
+        b_i.drive(b_o & a.high());
+        let r = m.reg("r", 1);
+        r.drive_next(r);
+        m.output("o", r);
+
+        match m.validate() {
+            Err(Error::CombinationalLoop { cycle, .. }) => {
+                assert_eq!(cycle, ["top.a.b.o", "top.a.b.i", "top.a.b.o"]);
+            }
+            _ => panic!("Expected a combinational loop error."),
+        }
+    }
+
+    #[test]
+    fn instance_copies_definition() {
+        let c = Context::new();
+
+        let def = c.module("def", "Def");
+        let i = def.input("i", 8);
+        let r = def.reg("r", 8);
+        r.default_value(0u32);
+        r.drive_next(i + r);
+        let inner = def.module("inner", "Inner");
+        let inner_i = inner.input("i", 8);
+        let inner_o = inner.output("o", !inner_i);
+        inner_i.drive(r);
+        def.output("o", inner_o);
+
+        let top = c.module("top", "Top");
+        let a = top.instance("a", def);
+        let b = top.instance("b", def);
+        a.get_input("i").unwrap().drive(top.input("i", 8));
+        b.get_input("i").unwrap().drive(a.get_output("o").unwrap());
+        top.output("o", b.get_output("o").unwrap());
+
+        assert_eq!(top.validate(), Ok(()));
+        for instance in [a, b] {
+            assert_eq!(instance.name(), "Def");
+            assert!(ptr::eq(instance.parent().unwrap(), top));
+            assert!(ptr::eq(instance.definition.unwrap(), def));
+            assert_eq!(instance.registers().len(), 1);
+            let inner = instance.modules()[0];
+            assert_eq!(inner.instance_name(), "inner");
+            assert!(ptr::eq(inner.parent().unwrap(), instance));
+            assert!(inner.definition.is_none());
+            assert!(matches!(
+                inner.get_input("i").unwrap().driver().unwrap().node(),
+                SignalNode::Reg { .. }
+            ));
+        }
+        assert!(!ptr::eq(a.registers()[0], b.registers()[0]));
+
+        // The definition itself is unchanged
+        assert!(i.driver().is_none());
+        assert_eq!(def.modules().len(), 1);
     }
 
     #[test]
     #[should_panic(
-        expected = "Attempted to drive an instance input with a signal from a different module than that instance's parent module."
+        expected = "Attempted to modify module \"Def\", but module \"Def\" has already been instantiated. Definitions must be fully built before they're instantiated."
     )]
-    fn input_drive_different_module_than_parent_module_error() {
+    fn instance_modify_definition_error() {
         let c = Context::new();
 
-        let m1 = c.module("a", "A");
-        let i1 = m1.input("a", 1);
-
-        let m2 = c.module("b", "B");
+        let def = c.module("def", "Def");
+        let i = def.input("i", 8);
 
-        let inner = m2.module("inner", "Inner");
-        let a = inner.input("a", 1);
+        let top = c.module("top", "Top");
+        let _ = top.instance("def", def);
 
         // Panic
-        a.drive(i1);
+        let _ = def.output("o", i);
     }
 
     #[test]
     #[should_panic(
-        expected = "Attempted to drive an input called \"a\" on an instance of \"Inner\", but this input is already driven for this instance."
+        expected = "Attempted to modify module \"Inner\", but module \"Def\" has already been instantiated. Definitions must be fully built before they're instantiated."
     )]
-    fn input_drive_already_driven_error() {
+    fn instance_modify_nested_definition_error() {
         let c = Context::new();
 
-        let m = c.module("a", "A");
-
-        let inner = m.module("inner", "Inner");
-        let a = inner.input("a", 1);
+        let def = c.module("def", "Def");
+        let inner = def.module("inner", "Inner");
+        let r = inner.reg("r", 8);
 
-        a.drive(m.input("i1", 1));
+        let top = c.module("top", "Top");
+        let _ = top.instance("def", def);
 
         // Panic
-        a.drive(m.input("i2", 1));
+        r.drive_next(!r);
     }
 
     #[test]
     #[should_panic(
-        expected = "Attempted to drive an input called \"a\" on an instance of \"Inner\", but this input and the provided signal have different bit widths (1 and 32, respectively)."
+        expected = "Attempted to instantiate module \"Inner\", but only top-level modules can be used as definitions."
     )]
-    fn input_drive_incompatible_bit_widths_error() {
+    fn instance_non_top_level_definition_error() {
         let c = Context::new();
 
-        let m = c.module("a", "A");
+        let def = c.module("def", "Def");
+        let inner = def.module("inner", "Inner");
 
-        let inner = m.module("inner", "Inner");
-        let a = inner.input("a", 1);
+        let top = c.module("top", "Top");
 
         // Panic
-        a.drive(m.input("i1", 32));
+        let _ = top.instance("inner", inner);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to instantiate module \"Top\" inside itself.")]
+    fn instance_inside_itself_error() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let inner = top.module("inner", "Inner");
+
+        // Panic
+        let _ = inner.instance("top", top);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to instantiate module \"Def\" from another context.")]
+    fn instance_separate_context_error() {
+        let c1 = Context::new();
+        let def = c1.module("def", "Def");
+
+        let c2 = Context::new();
+        let top = c2.module("top", "Top");
+
+        // Panic
+        let _ = top.instance("def", def);
     }
 }
@@ -0,0 +1,397 @@
+use super::clock::*;
+use super::constant::*;
+use super::internal_signal::*;
+use super::mem::*;
+use super::module::*;
+use super::reset::*;
+use super::signal::*;
+
+/// A read-only view of the node behind a [`Signal`], returned by [`Signal::node`].
+///
+/// Each [`Signal`] in kaze is a node in a directed acyclic graph of expressions whose leaves are literals, [`Input`]s, instance [`Output`]s, [`Register`](crate::Register)s and [`Mem`] read ports. A `SignalNode` describes a single node along with its operands, which are themselves [`Signal`]s, so the whole graph can be visited recursively. This is useful for writing custom analyses or exporters.
+///
+/// New variants may be added as kaze supports more kinds of expressions, so matches on `SignalNode` should include a wildcard arm.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// fn count_nodes<'a>(s: &'a dyn Signal<'a>) -> u32 {
+///     1 + match s.node() {
+///         SignalNode::SimpleBinOp { lhs, rhs, .. } => count_nodes(lhs) + count_nodes(rhs),
+///         SignalNode::UnOp { source, .. } => count_nodes(source),
+///         _ => 0,
+///     }
+/// }
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let a = m.input("a", 8);
+/// let b = m.input("b", 8);
+/// assert_eq!(count_nodes(!(a & b)), 4);
+///
+/// match (a & b).node() {
+///     SignalNode::SimpleBinOp { op: SimpleBinOp::BitAnd, lhs, bit_width, .. } => {
+///         assert!(matches!(lhs.node(), SignalNode::Input { input } if input.name() == "a"));
+///         assert_eq!(bit_width, 8);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[non_exhaustive]
+pub enum SignalNode<'a> {
+    /// A literal created with [`Module::lit`], [`Module::low`], or [`Module::high`].
+    Lit { value: &'a Constant, bit_width: u32 },
+
+    /// The value of an [`Input`] of this [`Signal`]'s [`Module`].
+    Input { input: &'a Input<'a> },
+    /// The value of an [`Output`] of an instance contained in this [`Signal`]'s [`Module`].
+    Output { output: &'a Output<'a> },
+
+    /// The current value of a [`Register`](crate::Register).
+    Reg {
+        name: &'a str,
+        bit_width: u32,
+        /// The [`Register`](crate::Register)'s default value, if one is specified.
+        default_value: Option<Constant>,
+        /// The [`Register`](crate::Register)'s next value, if it's driven.
+        next: Option<&'a dyn Signal<'a>>,
+        /// The [`Clock`] the [`Register`](crate::Register) is bound to, if it isn't driven by its [`Module`]'s implicit clock.
+        clock: Option<&'a Clock<'a>>,
+        /// The [`Reset`] the [`Register`](crate::Register) is assigned to, if it isn't reset by its [`Module`]'s implicit reset.
+        reset: Option<&'a Reset<'a>>,
+    },
+
+    /// A unary operation.
+    UnOp {
+        source: &'a dyn Signal<'a>,
+        op: UnOp,
+        bit_width: u32,
+    },
+    /// A reduction of all of `source`'s bits, whose result is always 1 bit wide.
+    ReduceOp {
+        source: &'a dyn Signal<'a>,
+        op: ReduceOp,
+    },
+    /// A count of `source`'s bits, wide enough to represent `source`'s bit width.
+    CountOp {
+        source: &'a dyn Signal<'a>,
+        op: CountOp,
+        bit_width: u32,
+    },
+    /// A one-hot decoding of `source`, with only the bit at the index represented by `source` high.
+    OneHot {
+        source: &'a dyn Signal<'a>,
+        bit_width: u32,
+    },
+    /// The index of `source`'s least significant high bit, or `0` if no bits are high.
+    PriorityEncode {
+        source: &'a dyn Signal<'a>,
+        bit_width: u32,
+    },
+    /// A bitwise binary operation.
+    SimpleBinOp {
+        lhs: &'a dyn Signal<'a>,
+        rhs: &'a dyn Signal<'a>,
+        op: SimpleBinOp,
+        bit_width: u32,
+    },
+    /// An addition or subtraction.
+    AdditiveBinOp {
+        lhs: &'a dyn Signal<'a>,
+        rhs: &'a dyn Signal<'a>,
+        op: AdditiveBinOp,
+        bit_width: u32,
+    },
+    /// A comparison, whose result is always 1 bit wide.
+    ComparisonBinOp {
+        lhs: &'a dyn Signal<'a>,
+        rhs: &'a dyn Signal<'a>,
+        op: ComparisonBinOp,
+    },
+    /// A shift.
+    ShiftBinOp {
+        lhs: &'a dyn Signal<'a>,
+        rhs: &'a dyn Signal<'a>,
+        op: ShiftBinOp,
+        bit_width: u32,
+    },
+    /// A rotation.
+    RotateBinOp {
+        lhs: &'a dyn Signal<'a>,
+        rhs: &'a dyn Signal<'a>,
+        op: RotateBinOp,
+        bit_width: u32,
+    },
+
+    /// An unsigned multiplication.
+    Mul {
+        lhs: &'a dyn Signal<'a>,
+        rhs: &'a dyn Signal<'a>,
+        bit_width: u32,
+    },
+    /// A signed multiplication.
+    MulSigned {
+        lhs: &'a dyn Signal<'a>,
+        rhs: &'a dyn Signal<'a>,
+        bit_width: u32,
+    },
+    /// A division or remainder.
+    DivisionBinOp {
+        lhs: &'a dyn Signal<'a>,
+        rhs: &'a dyn Signal<'a>,
+        op: DivisionBinOp,
+        bit_width: u32,
+    },
+
+    /// An inclusive range of bits of `source`.
+    Bits {
+        source: &'a dyn Signal<'a>,
+        range_high: u32,
+        range_low: u32,
+    },
+    /// `source` widened to `bit_width` bits.
+    Extend {
+        source: &'a dyn Signal<'a>,
+        op: ExtendOp,
+        bit_width: u32,
+    },
+
+    /// `source` repeated `count` times.
+    Repeat {
+        source: &'a dyn Signal<'a>,
+        count: u32,
+        bit_width: u32,
+    },
+    /// `lhs` concatenated with `rhs`, where `lhs` forms the most significant bits.
+    Concat {
+        lhs: &'a dyn Signal<'a>,
+        rhs: &'a dyn Signal<'a>,
+        bit_width: u32,
+    },
+
+    /// A 2:1 multiplexer.
+    Mux {
+        cond: &'a dyn Signal<'a>,
+        when_true: &'a dyn Signal<'a>,
+        when_false: &'a dyn Signal<'a>,
+        bit_width: u32,
+    },
+    /// The element of `elements` selected by `index`, created by [`SignalVec::get`](crate::SignalVec::get). Indices past the last element select the last element.
+    Index {
+        elements: Vec<&'a dyn Signal<'a>>,
+        index: &'a dyn Signal<'a>,
+        bit_width: u32,
+    },
+
+    /// The output of one of a [`Mem`]'s read ports.
+    MemReadPortOutput {
+        mem: &'a Mem<'a>,
+        address: &'a dyn Signal<'a>,
+        enable: &'a dyn Signal<'a>,
+    },
+}
+
+impl<'a> SignalNode<'a> {
+    pub(super) fn new(s: &'a InternalSignal<'a>) -> SignalNode<'a> {
+        match s.data {
+            SignalData::Lit {
+                ref value,
+                bit_width,
+            } => SignalNode::Lit { value, bit_width },
+
+            SignalData::Input { data } => SignalNode::Input {
+                input: s.module.inputs.borrow()[&data.name],
+            },
+            SignalData::Output { data } => SignalNode::Output {
+                output: data.module.outputs.borrow()[&data.name],
+            },
+
+            SignalData::Reg { data } => SignalNode::Reg {
+                name: &data.name,
+                bit_width: data.bit_width,
+                default_value: data.initial_value.borrow().clone(),
+                next: data.next.borrow().map(|next| next as &dyn Signal),
+                clock: *data.clock.borrow(),
+                reset: *data.reset.borrow(),
+            },
+
+            SignalData::UnOp {
+                source,
+                op,
+                bit_width,
+            } => SignalNode::UnOp {
+                source,
+                op,
+                bit_width,
+            },
+            SignalData::ReduceOp { source, op } => SignalNode::ReduceOp { source, op },
+            SignalData::CountOp {
+                source,
+                op,
+                bit_width,
+            } => SignalNode::CountOp {
+                source,
+                op,
+                bit_width,
+            },
+            SignalData::OneHot { source, bit_width } => SignalNode::OneHot { source, bit_width },
+            SignalData::PriorityEncode { source, bit_width } => {
+                SignalNode::PriorityEncode { source, bit_width }
+            }
+            SignalData::SimpleBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            } => SignalNode::SimpleBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            },
+            SignalData::AdditiveBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            } => SignalNode::AdditiveBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            },
+            SignalData::ComparisonBinOp { lhs, rhs, op } => {
+                SignalNode::ComparisonBinOp { lhs, rhs, op }
+            }
+            SignalData::ShiftBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            } => SignalNode::ShiftBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            },
+            SignalData::RotateBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            } => SignalNode::RotateBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            },
+
+            SignalData::Mul {
+                lhs,
+                rhs,
+                bit_width,
+            } => SignalNode::Mul {
+                lhs,
+                rhs,
+                bit_width,
+            },
+            SignalData::MulSigned {
+                lhs,
+                rhs,
+                bit_width,
+            } => SignalNode::MulSigned {
+                lhs,
+                rhs,
+                bit_width,
+            },
+            SignalData::DivisionBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            } => SignalNode::DivisionBinOp {
+                lhs,
+                rhs,
+                op,
+                bit_width,
+            },
+
+            SignalData::Bits {
+                source,
+                range_high,
+                range_low,
+            } => SignalNode::Bits {
+                source,
+                range_high,
+                range_low,
+            },
+
+            SignalData::Extend {
+                source,
+                op,
+                bit_width,
+            } => SignalNode::Extend {
+                source,
+                op,
+                bit_width,
+            },
+
+            SignalData::Repeat {
+                source,
+                count,
+                bit_width,
+            } => SignalNode::Repeat {
+                source,
+                count,
+                bit_width,
+            },
+            SignalData::Concat {
+                lhs,
+                rhs,
+                bit_width,
+            } => SignalNode::Concat {
+                lhs,
+                rhs,
+                bit_width,
+            },
+
+            SignalData::Mux {
+                cond,
+                when_true,
+                when_false,
+                bit_width,
+            } => SignalNode::Mux {
+                cond,
+                when_true,
+                when_false,
+                bit_width,
+            },
+            SignalData::Index {
+                ref elements,
+                index,
+                bit_width,
+            } => SignalNode::Index {
+                elements: elements
+                    .iter()
+                    .map(|&element| element as &dyn Signal)
+                    .collect(),
+                index,
+                bit_width,
+            },
+
+            SignalData::MemReadPortOutput {
+                mem,
+                address,
+                enable,
+            } => SignalNode::MemReadPortOutput {
+                mem,
+                address,
+                enable,
+            },
+        }
+    }
+}
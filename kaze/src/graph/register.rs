@@ -1,9 +1,12 @@
+use super::clock::*;
 use super::constant::*;
 use super::internal_signal::*;
 use super::module::*;
+use super::reset::*;
 use super::signal::*;
 
 use std::cell::RefCell;
+use std::panic::Location;
 use std::ptr;
 
 /// A hardware register, created by the [`Module::reg`] method.
@@ -11,9 +14,9 @@ use std::ptr;
 /// A `Register` is a stateful component that behaves like a [D flip-flop](https://en.wikipedia.org/wiki/Flip-flop_(electronics)#D_flip-flop) (more precisely as a [positive-edge-triggered D flip-flop](https://en.wikipedia.org/wiki/Flip-flop_(electronics)#Classical_positive-edge-triggered_D_flip-flop)).
 ///
 /// It always has a current value represented by the [`value`] field (often referred to as `Q`) and a next value specified by the [`drive_next`] method (often referred to as `D`).
-/// It will hold its [`value`] until a positive edge of its [`Module`]'s implicit clock occurs, at which point [`value`] will be updated to reflect the next value.
+/// It will hold its [`value`] until a positive edge of its [`Module`]'s implicit clock (or its [`Clock`], if one is specified with the [`clock`] method) occurs, at which point [`value`] will be updated to reflect the next value.
 ///
-/// Optionally, it also has a default value specified by the [`default_value`] method. If at any time its [`Module`]'s implicit reset is driven low, the register's [`value`] will reflect the default value.
+/// Optionally, it also has a default value specified by the [`default_value`] method. If at any time its [`Module`]'s implicit reset is driven low (or its [`Reset`] is asserted, if one is specified with the [`reset`] method), the register's [`value`] will reflect the default value.
 /// Default values are used to provide a known register state on system power-on and reset, but are often omitted to reduce combinational logic (which ultimately is how default values are typically implemented), especially for registers on timing-critical data paths.
 ///
 /// # Examples
@@ -31,8 +34,10 @@ use std::ptr;
 /// m.output("my_output", my_reg);
 /// ```
 ///
+/// [`clock`]: Self::clock
 /// [`default_value`]: Self::default_value
 /// [`drive_next`]: Self::drive_next
+/// [`reset`]: Self::reset
 /// [`value`]: Self::value
 #[must_use]
 pub struct Register<'a> {
@@ -44,7 +49,7 @@ pub struct Register<'a> {
 impl<'a> Register<'a> {
     /// Specifies the default value for this `Register`.
     ///
-    /// This `Register`'s [`value`] will reflect this default value when this `Register`'s [`Module`]'s implicit reset is asserted.
+    /// This `Register`'s [`value`] will reflect this default value when this `Register`'s [`Module`]'s implicit reset is asserted, or when its [`Reset`] is asserted if one is specified with the [`reset`] method.
     ///
     /// By default, a `Register` does not have a default value, and it is not required to specify one. If a default value is not specified, then this `Register`'s [`value`] will not change when its [`Module`]'s implicit reset is asserted.
     ///
@@ -67,23 +72,26 @@ impl<'a> Register<'a> {
     /// m.output("my_output", my_reg);
     /// ```
     ///
+    /// [`reset`]: Self::reset
     /// [`value`]: Self::value
+    #[track_caller]
     pub fn default_value(&'a self, value: impl Into<Constant>) {
+        self.data.module.check_modifiable();
         if self.data.initial_value.borrow().is_some() {
             panic!("Attempted to specify a default value for register \"{}\" in module \"{}\", but this register already has a default value.", self.data.name, self.data.module.name);
         }
         let value = value.into();
         let required_bits = value.required_bits();
         if required_bits > self.data.bit_width {
-            let numeric_value = value.numeric_value();
-            panic!("Cannot fit the specified value '{}' into register \"{}\"'s bit width '{}'. The value '{}' requires a bit width of at least {} bit(s).", numeric_value, self.data.name, self.data.bit_width, numeric_value, required_bits);
+            let value_string = value.value_string();
+            panic!("Cannot fit the specified value '{}' into register \"{}\"'s bit width '{}'. The value '{}' requires a bit width of at least {} bit(s).", value_string, self.data.name, self.data.bit_width, value_string, required_bits);
         }
         *self.data.initial_value.borrow_mut() = Some(value);
     }
 
     /// Specifies the next value for this `Register`.
     ///
-    /// A `Register` will hold its [`value`] until a positive edge of its [`Module`]'s implicit clock occurs, at which point [`value`] will be updated to reflect this next value.
+    /// A `Register` will hold its [`value`] until a positive edge of its clock occurs, at which point [`value`] will be updated to reflect this next value.
     ///
     /// # Panics
     ///
@@ -105,7 +113,9 @@ impl<'a> Register<'a> {
     /// ```
     ///
     /// [`value`]: Self::value
+    #[track_caller]
     pub fn drive_next(&'a self, n: &'a dyn Signal<'a>) {
+        self.data.module.check_modifiable();
         let n = n.internal_signal();
         if !ptr::eq(self.data.module, n.module) {
             panic!("Attempted to drive register \"{}\"'s next value with a signal from another module.", self.data.name);
@@ -118,21 +128,176 @@ impl<'a> Register<'a> {
         }
         *self.data.next.borrow_mut() = Some(n);
     }
+
+    /// Assigns `value` as this `Register`'s next value, taking any enclosing [`when`](crate::when) blocks into account.
+    ///
+    /// Outside of a [`when`](crate::when) block, this simply replaces this `Register`'s next value with `value`. Inside a [`when`](crate::when) block, `value` only becomes the next value while the block's condition holds; otherwise, the next value is the one specified by any earlier assignment, or this `Register`'s current [`value`] if there is none (so the `Register` holds its value by default).
+    /// Later assignments take priority over earlier ones, and unlike [`drive_next`], a `Register` can be assigned any number of times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `value` belong to different [`Module`]s, if the bit widths of `self` and `value` aren't equal, or if the condition of an enclosing [`when`](crate::when) block belongs to a different [`Module`] than `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let clear = m.input("clear", 1);
+    /// let inc = m.input("inc", 1);
+    ///
+    /// let counter = m.reg("counter", 8);
+    /// counter.default_value(0u32);
+    /// when(inc, || {
+    ///     counter.assign(counter + 1);
+    /// });
+    /// when(clear, || {
+    ///     counter.assign(m.lit(0u32, 8)); // Takes priority over the increment
+    /// });
+    /// m.output("count", counter);
+    /// ```
+    ///
+    /// [`drive_next`]: Self::drive_next
+    /// [`value`]: Self::value
+    #[track_caller]
+    pub fn assign(&'a self, value: &'a dyn Signal<'a>) {
+        self.data.module.check_modifiable();
+        let value = value.internal_signal();
+        if !ptr::eq(self.data.module, value.module) {
+            panic!(
+                "Attempted to assign a signal from another module to register \"{}\".",
+                self.data.name
+            );
+        }
+        if value.bit_width() != self.data.bit_width {
+            panic!("Attempted to assign a signal to register \"{}\" that has a different bit width than the register ({} and {}, respectively).", self.data.name, value.bit_width(), self.data.bit_width);
+        }
+        let guard = self.value.context.when_guards.borrow().last().copied();
+        let next = match guard {
+            Some(guard) => {
+                if !ptr::eq(self.data.module, guard.module) {
+                    panic!("Attempted to assign register \"{}\" inside a when block whose condition belongs to another module.", self.data.name);
+                }
+                let prev = self.data.next.borrow().unwrap_or(self.value);
+                self.data.module.mux(guard, value, prev).internal_signal()
+            }
+            None => value,
+        };
+        *self.data.next.borrow_mut() = Some(next);
+    }
+
+    /// Specifies the [`Clock`] that drives this `Register`.
+    ///
+    /// By default, a `Register` is driven by its [`Module`]'s implicit clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `clock` belongs to a different [`Module`] than `self`, or if this `Register` already has a clock specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let pixel_clk = m.clock("pixel_clk");
+    ///
+    /// let my_reg = m.reg("my_reg", 32);
+    /// my_reg.clock(pixel_clk); // my_reg's value will toggle with each positive edge of pixel_clk
+    /// my_reg.drive_next(!my_reg);
+    /// m.output("my_output", my_reg);
+    /// ```
+    #[track_caller]
+    pub fn clock(&'a self, clock: &'a Clock<'a>) {
+        self.data.module.check_modifiable();
+        if !ptr::eq(self.data.module, clock.module) {
+            panic!(
+                "Attempted to bind register \"{}\" to a clock from another module.",
+                self.data.name
+            );
+        }
+        if self.data.clock.borrow().is_some() {
+            panic!("Attempted to specify a clock for register \"{}\" in module \"{}\", but this register already has a clock specified.", self.data.name, self.data.module.name);
+        }
+        *self.data.clock.borrow_mut() = Some(clock);
+    }
+
+    /// Assigns this `Register` to `reset`, which will be used instead of its [`Module`]'s implicit reset.
+    ///
+    /// A `Register` assigned to a [`Reset`] is required to have a [default value](Self::default_value), which its [`value`] will reflect whenever `reset` is asserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reset` belongs to a different [`Module`] than `self`, or if this `Register` is already assigned to a reset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let sync_rst = m.reset("sync_rst", m.input("sync_rst", 1), ResetKind::Sync, ResetPolarity::ActiveHigh);
+    ///
+    /// let my_reg = m.reg("my_reg", 32);
+    /// my_reg.default_value(0xfadebabeu32);
+    /// my_reg.reset(sync_rst); // my_reg's value will be reset on positive clock edges while sync_rst is high
+    /// my_reg.drive_next(!my_reg);
+    /// m.output("my_output", my_reg);
+    /// ```
+    ///
+    /// [`value`]: Self::value
+    #[track_caller]
+    pub fn reset(&'a self, reset: &'a Reset<'a>) {
+        self.data.module.check_modifiable();
+        if !ptr::eq(self.data.module, reset.module) {
+            panic!(
+                "Attempted to assign register \"{}\" to a reset from another module.",
+                self.data.name
+            );
+        }
+        if self.data.reset.borrow().is_some() {
+            panic!("Attempted to assign register \"{}\" in module \"{}\" to a reset, but this register is already assigned to a reset.", self.data.name, self.data.module.name);
+        }
+        *self.data.reset.borrow_mut() = Some(reset);
+    }
+
+    /// Returns `true` if this `Register` was created with [`Module::signed_reg`], in which case it's declared as `signed` in generated Verilog code and traced as a signed value.
+    pub fn is_signed(&self) -> bool {
+        self.data.signed
+    }
 }
 
 pub(crate) struct RegisterData<'a> {
     pub module: &'a Module<'a>,
 
     pub name: String,
+    pub location: &'static Location<'static>,
     pub initial_value: RefCell<Option<Constant>>,
     pub bit_width: u32,
+    pub signed: bool,
     pub next: RefCell<Option<&'a InternalSignal<'a>>>,
+    pub clock: RefCell<Option<&'a Clock<'a>>>,
+    pub reset: RefCell<Option<&'a Reset<'a>>>,
 }
 
 impl<'a> GetInternalSignal<'a> for Register<'a> {
     fn internal_signal(&'a self) -> &'a InternalSignal<'a> {
         self.value
     }
+
+    fn is_signed(&self) -> bool {
+        self.data.signed
+    }
 }
 
 #[cfg(test)]
@@ -259,4 +424,129 @@ mod tests {
         // Panic
         r.drive_next(i);
     }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to assign a signal from another module to register \"r\"."
+    )]
+    fn assign_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let l = m1.lit(true, 1);
+
+        let m2 = c.module("b", "B");
+        let r = m2.reg("r", 1);
+
+        // Panic
+        r.assign(l);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to assign a signal to register \"r\" that has a different bit width than the register (5 and 3, respectively)."
+    )]
+    fn assign_incompatible_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let r = m.reg("r", 3);
+        let i = m.input("i", 5);
+
+        // Panic
+        r.assign(i);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to assign register \"r\" inside a when block whose condition belongs to another module."
+    )]
+    fn assign_when_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let cond = m1.input("cond", 1);
+
+        let m2 = c.module("b", "B");
+        let r = m2.reg("r", 1);
+
+        when(cond, || {
+            // Panic
+            r.assign(m2.high());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to bind register \"r\" to a clock from another module.")]
+    fn clock_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let r = m1.reg("r", 32);
+
+        let m2 = c.module("b", "B");
+        let clk = m2.clock("clk2");
+
+        // Panic
+        r.clock(clk);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to specify a clock for register \"r\" in module \"A\", but this register already has a clock specified."
+    )]
+    fn clock_already_specified_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let r = m.reg("r", 32);
+        let clk = m.clock("clk2");
+
+        r.clock(clk);
+
+        // Panic
+        r.clock(clk);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to assign register \"r\" to a reset from another module.")]
+    fn reset_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let r = m1.reg("r", 32);
+
+        let m2 = c.module("b", "B");
+        let rst = m2.reset(
+            "rst",
+            m2.input("rst", 1),
+            ResetKind::Sync,
+            ResetPolarity::ActiveHigh,
+        );
+
+        // Panic
+        r.reset(rst);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to assign register \"r\" in module \"A\" to a reset, but this register is already assigned to a reset."
+    )]
+    fn reset_already_specified_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let r = m.reg("r", 32);
+        let rst = m.reset(
+            "rst",
+            m.input("rst", 1),
+            ResetKind::Sync,
+            ResetPolarity::ActiveHigh,
+        );
+
+        r.reset(rst);
+
+        // Panic
+        r.reset(rst);
+    }
 }
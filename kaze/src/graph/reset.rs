@@ -0,0 +1,98 @@
+use super::internal_signal::*;
+use super::module::*;
+use super::signal::*;
+
+use std::hash::{Hash, Hasher};
+use std::panic::Location;
+use std::ptr;
+
+/// Specifies when a [`Reset`] takes effect.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ResetKind {
+    /// The reset only takes effect on a positive edge of a [`Register`](crate::Register)'s clock.
+    Sync,
+    /// The reset takes effect as soon as it's asserted, regardless of a [`Register`](crate::Register)'s clock.
+    Async,
+}
+
+/// Specifies which level of a [`Reset`]'s source signal asserts the reset.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ResetPolarity {
+    /// The reset is asserted when its source signal is high.
+    ActiveHigh,
+    /// The reset is asserted when its source signal is low.
+    ActiveLow,
+}
+
+/// An explicitly-declared reset, created by the [`Module::reset`] method.
+///
+/// By default, a [`Register`](crate::Register) with a [default value](crate::Register::default_value) is reset by its [`Module`]'s implicit reset.
+/// A `Reset` describes an additional reset domain with its own source signal, [kind](ResetKind), and [polarity](ResetPolarity), and [`Register`](crate::Register)s can be assigned to it with [`Register::reset`](crate::Register::reset).
+///
+/// Because a `Reset`'s source can be any 1-bit [`Signal`](crate::Signal) in its [`Module`] (including [`Input`]s and [`Register`](crate::Register)s), reset sequencing logic such as reset synchronizers can be described and simulated directly.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let rst_n = m.reset("rst_n", m.input("rst_n", 1), ResetKind::Async, ResetPolarity::ActiveLow);
+///
+/// let my_reg = m.reg("my_reg", 32);
+/// my_reg.default_value(0xfadebabeu32);
+/// my_reg.reset(rst_n);
+/// my_reg.drive_next(!my_reg);
+/// m.output("my_output", my_reg);
+/// ```
+#[must_use]
+pub struct Reset<'a> {
+    pub(crate) module: &'a Module<'a>,
+
+    pub(crate) name: String,
+    pub(crate) location: &'static Location<'static>,
+    pub(crate) source: &'a InternalSignal<'a>,
+    pub(crate) kind: ResetKind,
+    pub(crate) polarity: ResetPolarity,
+}
+
+impl<'a> Reset<'a> {
+    /// Returns this `Reset`'s name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the [`Signal`] this `Reset` is asserted by.
+    pub fn source(&self) -> &'a dyn Signal<'a> {
+        self.source
+    }
+
+    /// Returns this `Reset`'s [kind](ResetKind).
+    pub fn kind(&self) -> ResetKind {
+        self.kind
+    }
+
+    /// Returns this `Reset`'s [polarity](ResetPolarity).
+    pub fn polarity(&self) -> ResetPolarity {
+        self.polarity
+    }
+}
+
+impl<'a> Eq for &'a Reset<'a> {}
+
+impl<'a> Hash for &'a Reset<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(*self as *const _ as usize)
+    }
+}
+
+impl<'a> PartialEq for &'a Reset<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(*self, *other)
+    }
+}
@@ -1,7 +1,12 @@
 use super::constant::*;
 use super::internal_signal::*;
+use super::node::*;
+use super::signed::*;
 
-use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Not, Shl, Shr, Sub};
+use crate::error::*;
+
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Not, Rem, Shl, Shr, Sub};
+use std::panic::Location;
 use std::ptr;
 
 /// The minimum allowed bit width for any given [`Signal`].
@@ -10,8 +15,10 @@ use std::ptr;
 pub const MIN_SIGNAL_BIT_WIDTH: u32 = 1;
 /// The maximum allowed bit width for any given [`Signal`].
 ///
-/// This is currently set to `128` to simplify simulator code generation, since it allows the generated code to rely purely on native integer types provided by Rust's standard library for storage, arithmetic, etc. Larger widths may be supported in a future version of this library.
-pub const MAX_SIGNAL_BIT_WIDTH: u32 = 128;
+/// This is currently set to `65536`, which is the smallest maximum vector width that Verilog implementations are required to support.
+///
+/// Signals up to 128 bits wide are represented with Rust's native integer types in generated simulator code, and wider signals are represented with [`WideUint`](crate::runtime::WideUint).
+pub const MAX_SIGNAL_BIT_WIDTH: u32 = 65536;
 
 /// A collection of 1 or more bits driven by some source.
 ///
@@ -55,14 +62,23 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     /// assert_eq!((m.lit(25u8, 8) + m.lit(42u8, 8)).bit_width(), 8);
     /// assert_eq!((m.lit(1u8, 1) * m.lit(2u8, 2)).bit_width(), 3);
     /// assert_eq!(m.lit(1u8, 1).mul_signed(m.lit(2u8, 2)).bit_width(), 3);
+    /// assert_eq!((m.lit(25u8, 8) / m.lit(3u8, 8)).bit_width(), 8);
+    /// assert_eq!((m.lit(25u8, 8) % m.lit(3u8, 8)).bit_width(), 8);
+    /// assert_eq!(m.lit(25u8, 8).div_signed(m.lit(3u8, 8)).bit_width(), 8);
+    /// assert_eq!(m.lit(25u8, 8).rem_signed(m.lit(3u8, 8)).bit_width(), 8);
     /// assert_eq!(m.lit(false, 1).reg_next("some_other_reg").bit_width(), 1);
     /// assert_eq!(m.lit(true, 1).reg_next_with_default("yet_another_reg", false).bit_width(), 1);
     /// assert_eq!((m.high() & m.low()).bit_width(), 1);
     /// assert_eq!((m.high() | m.low()).bit_width(), 1);
     /// assert_eq!(m.lit(12u32, 100).bit(30).bit_width(), 1);
     /// assert_eq!(m.lit(1u32, 99).bits(37, 29).bit_width(), 9);
+    /// assert_eq!(m.lit(12u32, 100).bit_dyn(m.lit(30u32, 7)).bit_width(), 1);
+    /// assert_eq!(m.lit(1u32, 99).bits_dyn(m.lit(29u32, 7), 9).bit_width(), 9);
     /// assert_eq!(m.high().repeat(35).bit_width(), 35);
     /// assert_eq!(m.lit(1u32, 20).concat(m.high()).bit_width(), 21);
+    /// assert_eq!(m.lit(0xaau32, 8).and_reduce().bit_width(), 1);
+    /// assert_eq!(m.lit(0xaau32, 8).or_reduce().bit_width(), 1);
+    /// assert_eq!(m.lit(0xaau32, 8).xor_reduce().bit_width(), 1);
     /// assert_eq!((m.lit(0x80u32, 8) << m.lit(true, 1)).bit_width(), 8);
     /// assert_eq!((m.lit(0x80u32, 8) >> m.lit(true, 1)).bit_width(), 8);
     /// assert_eq!(m.lit(0x80u32, 8).shr_arithmetic(m.lit(true, 1)).bit_width(), 8);
@@ -80,6 +96,37 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
         s.bit_width()
     }
 
+    /// Returns a read-only view of the node behind this `Signal`, which describes the kind of this `Signal` along with its operands.
+    ///
+    /// This can be used to visit the expression graph behind a `Signal` in order to implement custom analyses or exporters. See [`SignalNode`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.input("a", 8);
+    /// let sum = a + m.lit(1u32, 8);
+    ///
+    /// match sum.node() {
+    ///     SignalNode::AdditiveBinOp { lhs, rhs, op, bit_width } => {
+    ///         assert!(matches!(lhs.node(), SignalNode::Input { .. }));
+    ///         assert!(matches!(rhs.node(), SignalNode::Lit { bit_width: 8, .. }));
+    ///         assert_eq!(op, AdditiveBinOp::Add);
+    ///         assert_eq!(bit_width, 8);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    #[must_use]
+    fn node(&'a self) -> SignalNode<'a> {
+        SignalNode::new(self.internal_signal())
+    }
+
     /// Creates a `Signal` that represents the value of the single bit of this `Signal` at index `index`, where `index` equal to `0` represents this `Signal`'s least significant bit.
     ///
     /// # Panics
@@ -101,6 +148,7 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     /// let bit_2 = lit.bit(2); // Represents 1
     /// let bit_3 = lit.bit(3); // Represents 0
     /// ```
+    #[track_caller]
     fn bit(&'a self, index: u32) -> &'a dyn Signal<'a> {
         let s = self.internal_signal();
         if index >= s.bit_width() {
@@ -109,6 +157,7 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
         s.context.signal_arena.alloc(InternalSignal {
             context: s.context,
             module: s.module,
+            location: Location::caller(),
 
             data: SignalData::Bits {
                 source: s,
@@ -123,6 +172,7 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     /// # Panics
     ///
     /// Panics if either `range_low` or `range_high` is greater than or equal to the bit width of this `Signal`, or if `range_low` is greater than `range_high`.
+    /// See [`try_bits`](Self::try_bits) for a variant that returns an [`Error`] instead.
     ///
     /// # Examples
     ///
@@ -140,27 +190,117 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     /// let bits_32 = lit.bits(3, 2); // Represents 0b01
     /// let bits_2 = lit.bits(2, 2); // Represents 1, equivalent to lit.bit(2)
     /// ```
+    #[track_caller]
     fn bits(&'a self, range_high: u32, range_low: u32) -> &'a dyn Signal<'a> {
+        self.try_bits(range_high, range_low).unwrap_or_panic()
+    }
+
+    /// Same as [`bits`](Self::bits), except that an [`Error`] is returned instead of panicking if the range of bits is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit = m.lit(0b0110u32, 4);
+    /// assert!(lit.try_bits(2, 0).is_ok());
+    /// assert!(matches!(lit.try_bits(4, 0), Err(Error::BitsRangeHighOutOfBounds { range_high: 4, .. })));
+    /// ```
+    #[track_caller]
+    fn try_bits(&'a self, range_high: u32, range_low: u32) -> Result<&'a dyn Signal<'a>, Error> {
         let s = self.internal_signal();
         if range_low >= s.bit_width() {
-            panic!("Cannot specify a range of bits where the lower bound is greater than or equal to the number of bits in the source signal. The bounds must be in the range [0, {}] for a signal with a width of {} bits, but a lower bound of {} was given.", s.bit_width() - 1, s.bit_width(), range_low);
+            return Err(Error::BitsRangeLowOutOfBounds {
+                module_path: s.module.path(),
+                bit_width: s.bit_width(),
+                range_low,
+            });
         }
         if range_high >= s.bit_width() {
-            panic!("Cannot specify a range of bits where the upper bound is greater than or equal to the number of bits in the source signal. The bounds must be in the range [0, {}] for a signal with a width of {} bits, but an upper bound of {} was given.", s.bit_width() - 1, s.bit_width(), range_high);
+            return Err(Error::BitsRangeHighOutOfBounds {
+                module_path: s.module.path(),
+                bit_width: s.bit_width(),
+                range_high,
+            });
         }
         if range_low > range_high {
-            panic!("Cannot specify a range of bits where the lower bound is greater than the upper bound.");
+            return Err(Error::BitsRangeLowGreaterThanHigh {
+                module_path: s.module.path(),
+                range_high,
+                range_low,
+            });
         }
-        s.context.signal_arena.alloc(InternalSignal {
+        Ok(s.context.signal_arena.alloc(InternalSignal {
             context: s.context,
             module: s.module,
+            location: Location::caller(),
 
             data: SignalData::Bits {
                 source: s,
                 range_high,
                 range_low,
             },
-        })
+        }))
+    }
+
+    /// Creates a `Signal` that represents the value of the single bit of this `Signal` at the index represented by `index`, where an index of `0` represents this `Signal`'s least significant bit.
+    ///
+    /// This is the same as [`bit`](Self::bit), except that the index is selected by a `Signal` at runtime rather than a fixed value. If `index` is greater than or equal to this `Signal`'s `bit_width`, the resulting value is `0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `index` belong to different [`Module`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit = m.lit(0b0110u32, 4);
+    /// let index = m.input("index", 3);
+    /// let bit = lit.bit_dyn(index); // Represents 1 when index is 1 or 2, and 0 otherwise
+    /// ```
+    #[track_caller]
+    fn bit_dyn(&'a self, index: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        self.bits_dyn(index, 1)
+    }
+
+    /// Creates a `Signal` that represents `width` contiguous bits of this `Signal`, starting at the bit index represented by `offset` as the least significant bit.
+    ///
+    /// This is the same as [`bits`](Self::bits), except that the lowest bit index is selected by a `Signal` at runtime rather than a fixed value, and is equivalent to `(self >> offset).bits(width - 1, 0)`. Any bits of the range that lie at or above this `Signal`'s `bit_width` are `0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `offset` belong to different [`Module`]s, or if `width` is `0` or greater than this `Signal`'s `bit_width`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit = m.lit(0xfadebabeu32, 32);
+    /// let offset = m.input("offset", 5);
+    /// let byte = lit.bits_dyn(offset, 8); // Represents 0xbe when offset is 0, 0xba when offset is 8, 0x0f when offset is 28, etc.
+    /// ```
+    #[track_caller]
+    fn bits_dyn(&'a self, offset: &'a dyn Signal<'a>, width: u32) -> &'a dyn Signal<'a> {
+        let s: &'a dyn Signal<'a> = self.internal_signal();
+        if width == 0 || width > s.bit_width() {
+            panic!("Attempted to take {} bit(s) from a signal with a width of {} bits. The number of bits must be in the range [1, {}] for a signal with a width of {} bits.", width, s.bit_width(), s.bit_width(), s.bit_width());
+        }
+        (s >> offset).bits(width - 1, 0)
     }
 
     /// Creates a `Signal` that represents this `Signal` repeated `count` times.
@@ -184,6 +324,7 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     /// let repeat_5 = lit.repeat(5); // Equivalent to 20-bit lit with value 0xaaaaa
     /// let repeat_8 = lit.repeat(8); // Equivalent to 32-bit lit with value 0xaaaaaaaa
     /// ```
+    #[track_caller]
     fn repeat(&'a self, count: u32) -> &'a dyn Signal<'a> {
         let s = self.internal_signal();
         let bit_width = s.bit_width() * count;
@@ -196,6 +337,7 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
         s.context.signal_arena.alloc(InternalSignal {
             context: s.context,
             module: s.module,
+            location: Location::caller(),
 
             data: SignalData::Repeat {
                 source: s,
@@ -228,7 +370,8 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     /// let concat_2 = lit_b.concat(lit_a); // Equivalent to 12-bit lit with value 0xffa
     /// let concat_3 = lit_a.concat(lit_a); // Equivalent to 8-bit lit with value 0xaa
     /// ```
-    fn concat(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
+    #[track_caller]
+    fn concat(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
         let lhs = self.internal_signal();
         let rhs = rhs.internal_signal();
         if !ptr::eq(lhs.module, rhs.module) {
@@ -241,6 +384,7 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
         lhs.context.signal_arena.alloc(InternalSignal {
             context: lhs.context,
             module: lhs.module,
+            location: Location::caller(),
 
             data: SignalData::Concat {
                 lhs,
@@ -250,11 +394,13 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
         })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of a bitwise boolean equality comparison between `self` and `rhs`.
+    /// Creates a `Signal` that represents this `Signal` zero-extended to `bit_width` bits, with all of the new upper bits low.
+    ///
+    /// If `bit_width` is equal to this `Signal`'s `bit_width`, this `Signal` is returned unchanged.
     ///
     /// # Panics
     ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    /// Panics if `bit_width` is less than this `Signal`'s `bit_width` or greater than [`MAX_SIGNAL_BIT_WIDTH`].
     ///
     /// # Examples
     ///
@@ -265,43 +411,43 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let eq_1 = lit_a.eq(lit_a); // Equivalent to m.high()
-    /// let eq_2 = lit_b.eq(lit_b); // Equivalent to m.high()
-    /// let eq_3 = lit_a.eq(lit_b); // Equivalent to m.low()
-    /// let eq_4 = lit_b.eq(lit_a); // Equivalent to m.low()
+    /// let lit = m.lit(0xau32, 4);
+    /// let zext_4 = lit.zext(4); // Equivalent to just lit
+    /// let zext_8 = lit.zext(8); // Equivalent to 8-bit lit with value 0x0a
+    /// let zext_12 = lit.zext(12); // Equivalent to 12-bit lit with value 0x00a
     /// ```
-    fn eq(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
-        let lhs = self.internal_signal();
-        let rhs = rhs.internal_signal();
-        if !ptr::eq(lhs.module, rhs.module) {
-            panic!("Attempted to combine signals from different modules.");
+    #[track_caller]
+    fn zext(&'a self, bit_width: u32) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        if bit_width < s.bit_width() {
+            panic!("Attempted to zero-extend a {}-bit signal to {} bit(s), which is less than the signal's bit width.", s.bit_width(), bit_width);
         }
-        if lhs.bit_width() != rhs.bit_width() {
-            panic!(
-                "Signals have different bit widths ({} and {}, respectively).",
-                lhs.bit_width(),
-                rhs.bit_width()
-            );
+        if bit_width > MAX_SIGNAL_BIT_WIDTH {
+            panic!("Attempted to zero-extend a {}-bit signal to {} bit(s), which is greater than the maximum signal bit width of {} bit(s).", s.bit_width(), bit_width, MAX_SIGNAL_BIT_WIDTH);
         }
-        lhs.context.signal_arena.alloc(InternalSignal {
-            context: lhs.context,
-            module: lhs.module,
+        if bit_width == s.bit_width() {
+            return s;
+        }
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
 
-            data: SignalData::ComparisonBinOp {
-                lhs,
-                rhs,
-                op: ComparisonBinOp::Equal,
+            data: SignalData::Extend {
+                source: s,
+                op: ExtendOp::Zero,
+                bit_width,
             },
         })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of a bitwise boolean inequality comparison between `self` and `rhs`.
+    /// Creates a `Signal` that represents this `Signal` sign-extended to `bit_width` bits, with all of the new upper bits equal to this `Signal`'s most significant bit.
+    ///
+    /// If `bit_width` is equal to this `Signal`'s `bit_width`, this `Signal` is returned unchanged.
     ///
     /// # Panics
     ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    /// Panics if `bit_width` is less than this `Signal`'s `bit_width` or greater than [`MAX_SIGNAL_BIT_WIDTH`].
     ///
     /// # Examples
     ///
@@ -313,42 +459,40 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     /// let m = c.module("m", "MyModule");
     ///
     /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let ne_1 = lit_a.ne(lit_a); // Equivalent to m.low()
-    /// let ne_2 = lit_b.ne(lit_b); // Equivalent to m.low()
-    /// let ne_3 = lit_a.ne(lit_b); // Equivalent to m.high()
-    /// let ne_4 = lit_b.ne(lit_a); // Equivalent to m.high()
+    /// let lit_b = m.lit(0x5u32, 4);
+    /// let sext_a = lit_a.sext(8); // Equivalent to 8-bit lit with value 0xfa
+    /// let sext_b = lit_b.sext(8); // Equivalent to 8-bit lit with value 0x05
     /// ```
-    fn ne(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
-        let lhs = self.internal_signal();
-        let rhs = rhs.internal_signal();
-        if !ptr::eq(lhs.module, rhs.module) {
-            panic!("Attempted to combine signals from different modules.");
+    #[track_caller]
+    fn sext(&'a self, bit_width: u32) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        if bit_width < s.bit_width() {
+            panic!("Attempted to sign-extend a {}-bit signal to {} bit(s), which is less than the signal's bit width.", s.bit_width(), bit_width);
         }
-        if lhs.bit_width() != rhs.bit_width() {
-            panic!(
-                "Signals have different bit widths ({} and {}, respectively).",
-                lhs.bit_width(),
-                rhs.bit_width()
-            );
+        if bit_width > MAX_SIGNAL_BIT_WIDTH {
+            panic!("Attempted to sign-extend a {}-bit signal to {} bit(s), which is greater than the maximum signal bit width of {} bit(s).", s.bit_width(), bit_width, MAX_SIGNAL_BIT_WIDTH);
         }
-        lhs.context.signal_arena.alloc(InternalSignal {
-            context: lhs.context,
-            module: lhs.module,
+        if bit_width == s.bit_width() {
+            return s;
+        }
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
 
-            data: SignalData::ComparisonBinOp {
-                lhs,
-                rhs,
-                op: ComparisonBinOp::NotEqual,
+            data: SignalData::Extend {
+                source: s,
+                op: ExtendOp::Sign,
+                bit_width,
             },
         })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of an unsigned `<` comparison between `self` and `rhs`.
+    /// Creates a `Signal` that represents this `Signal` resized to `bit_width` bits, either by discarding its upper bits (if `bit_width` is less than this `Signal`'s `bit_width`) or by [zero-extending](Self::zext) it.
     ///
     /// # Panics
     ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    /// Panics if `bit_width` is less than [`MIN_SIGNAL_BIT_WIDTH`] or greater than [`MAX_SIGNAL_BIT_WIDTH`].
     ///
     /// # Examples
     ///
@@ -359,43 +503,28 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let lt_1 = lit_a.lt(lit_a); // Equivalent to m.low()
-    /// let lt_2 = lit_b.lt(lit_b); // Equivalent to m.low()
-    /// let lt_3 = lit_a.lt(lit_b); // Equivalent to m.high()
-    /// let lt_4 = lit_b.lt(lit_a); // Equivalent to m.low()
+    /// let lit = m.lit(0xfau32, 8);
+    /// let resize_4 = lit.resize(4); // Equivalent to 4-bit lit with value 0xa
+    /// let resize_8 = lit.resize(8); // Equivalent to just lit
+    /// let resize_12 = lit.resize(12); // Equivalent to 12-bit lit with value 0x0fa
     /// ```
-    fn lt(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
-        let lhs = self.internal_signal();
-        let rhs = rhs.internal_signal();
-        if !ptr::eq(lhs.module, rhs.module) {
-            panic!("Attempted to combine signals from different modules.");
+    #[track_caller]
+    fn resize(&'a self, bit_width: u32) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        if bit_width < MIN_SIGNAL_BIT_WIDTH {
+            panic!("Attempted to resize a {}-bit signal to {} bit(s), which is less than the minimal signal bit width of {} bit(s).", s.bit_width(), bit_width, MIN_SIGNAL_BIT_WIDTH);
         }
-        if lhs.bit_width() != rhs.bit_width() {
-            panic!(
-                "Signals have different bit widths ({} and {}, respectively).",
-                lhs.bit_width(),
-                rhs.bit_width()
-            );
+        if bit_width > MAX_SIGNAL_BIT_WIDTH {
+            panic!("Attempted to resize a {}-bit signal to {} bit(s), which is greater than the maximum signal bit width of {} bit(s).", s.bit_width(), bit_width, MAX_SIGNAL_BIT_WIDTH);
+        }
+        if bit_width < s.bit_width() {
+            s.bits(bit_width - 1, 0)
+        } else {
+            s.zext(bit_width)
         }
-        lhs.context.signal_arena.alloc(InternalSignal {
-            context: lhs.context,
-            module: lhs.module,
-
-            data: SignalData::ComparisonBinOp {
-                lhs,
-                rhs,
-                op: ComparisonBinOp::LessThan,
-            },
-        })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of an unsigned `<=` comparison between `self` and `rhs`.
-    ///
-    /// # Panics
-    ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    /// Creates a 1-bit `Signal` that represents whether all of this `Signal`'s bits are high, equivalent to `&`-ing all of its bits together.
     ///
     /// # Examples
     ///
@@ -406,43 +535,25 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let le_1 = lit_a.le(lit_a); // Equivalent to m.high()
-    /// let le_2 = lit_b.le(lit_b); // Equivalent to m.high()
-    /// let le_3 = lit_a.le(lit_b); // Equivalent to m.high()
-    /// let le_4 = lit_b.le(lit_a); // Equivalent to m.low()
+    /// let a = m.lit(0xfu32, 4).and_reduce(); // Equivalent to m.high()
+    /// let b = m.lit(0xeu32, 4).and_reduce(); // Equivalent to m.low()
     /// ```
-    fn le(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
-        let lhs = self.internal_signal();
-        let rhs = rhs.internal_signal();
-        if !ptr::eq(lhs.module, rhs.module) {
-            panic!("Attempted to combine signals from different modules.");
-        }
-        if lhs.bit_width() != rhs.bit_width() {
-            panic!(
-                "Signals have different bit widths ({} and {}, respectively).",
-                lhs.bit_width(),
-                rhs.bit_width()
-            );
-        }
-        lhs.context.signal_arena.alloc(InternalSignal {
-            context: lhs.context,
-            module: lhs.module,
+    #[track_caller]
+    fn and_reduce(&'a self) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
 
-            data: SignalData::ComparisonBinOp {
-                lhs,
-                rhs,
-                op: ComparisonBinOp::LessThanEqual,
+            data: SignalData::ReduceOp {
+                source: s,
+                op: ReduceOp::And,
             },
         })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of an unsigned `>` comparison between `self` and `rhs`.
-    ///
-    /// # Panics
-    ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    /// Creates a 1-bit `Signal` that represents whether any of this `Signal`'s bits are high, equivalent to `|`-ing all of its bits together.
     ///
     /// # Examples
     ///
@@ -453,43 +564,25 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let gt_1 = lit_a.gt(lit_a); // Equivalent to m.low()
-    /// let gt_2 = lit_b.gt(lit_b); // Equivalent to m.low()
-    /// let gt_3 = lit_a.gt(lit_b); // Equivalent to m.low()
-    /// let gt_4 = lit_b.gt(lit_a); // Equivalent to m.high()
+    /// let a = m.lit(0x4u32, 4).or_reduce(); // Equivalent to m.high()
+    /// let b = m.lit(0x0u32, 4).or_reduce(); // Equivalent to m.low()
     /// ```
-    fn gt(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
-        let lhs = self.internal_signal();
-        let rhs = rhs.internal_signal();
-        if !ptr::eq(lhs.module, rhs.module) {
-            panic!("Attempted to combine signals from different modules.");
-        }
-        if lhs.bit_width() != rhs.bit_width() {
-            panic!(
-                "Signals have different bit widths ({} and {}, respectively).",
-                lhs.bit_width(),
-                rhs.bit_width()
-            );
-        }
-        lhs.context.signal_arena.alloc(InternalSignal {
-            context: lhs.context,
-            module: lhs.module,
+    #[track_caller]
+    fn or_reduce(&'a self) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
 
-            data: SignalData::ComparisonBinOp {
-                lhs,
-                rhs,
-                op: ComparisonBinOp::GreaterThan,
+            data: SignalData::ReduceOp {
+                source: s,
+                op: ReduceOp::Or,
             },
         })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of an unsigned `>=` comparison between `self` and `rhs`.
-    ///
-    /// # Panics
-    ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    /// Creates a 1-bit `Signal` that represents the parity of this `Signal`'s bits (high if an odd number of them are high), equivalent to `^`-ing all of its bits together.
     ///
     /// # Examples
     ///
@@ -500,43 +593,127 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let ge_1 = lit_a.ge(lit_a); // Equivalent to m.high()
-    /// let ge_2 = lit_b.ge(lit_b); // Equivalent to m.high()
-    /// let ge_3 = lit_a.ge(lit_b); // Equivalent to m.low()
-    /// let ge_4 = lit_b.ge(lit_a); // Equivalent to m.high()
+    /// let a = m.lit(0x7u32, 4).xor_reduce(); // Equivalent to m.high()
+    /// let b = m.lit(0x5u32, 4).xor_reduce(); // Equivalent to m.low()
     /// ```
-    fn ge(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
-        let lhs = self.internal_signal();
-        let rhs = rhs.internal_signal();
-        if !ptr::eq(lhs.module, rhs.module) {
-            panic!("Attempted to combine signals from different modules.");
-        }
-        if lhs.bit_width() != rhs.bit_width() {
-            panic!(
-                "Signals have different bit widths ({} and {}, respectively).",
-                lhs.bit_width(),
-                rhs.bit_width()
-            );
-        }
-        lhs.context.signal_arena.alloc(InternalSignal {
-            context: lhs.context,
-            module: lhs.module,
+    #[track_caller]
+    fn xor_reduce(&'a self) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
 
-            data: SignalData::ComparisonBinOp {
-                lhs,
-                rhs,
-                op: ComparisonBinOp::GreaterThanEqual,
+            data: SignalData::ReduceOp {
+                source: s,
+                op: ReduceOp::Xor,
             },
         })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of a signed `<` comparison between `self` and `rhs`.
+    /// Creates a `Signal` that represents the number of low bits above this `Signal`'s most significant high bit.
+    ///
+    /// The result is just wide enough to represent this `Signal`'s `bit_width`, so a 32-bit `Signal` produces a 6-bit count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(0x1u32, 8).leading_zeros(); // Equivalent to 4-bit lit with value 7
+    /// let b = m.lit(0x0u32, 8).leading_zeros(); // Equivalent to 4-bit lit with value 8
+    /// ```
+    #[track_caller]
+    fn leading_zeros(&'a self) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
+
+            data: SignalData::CountOp {
+                source: s,
+                op: CountOp::LeadingZeros,
+                bit_width: 32 - s.bit_width().leading_zeros(),
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the number of low bits below this `Signal`'s least significant high bit.
+    ///
+    /// The result is just wide enough to represent this `Signal`'s `bit_width`, so a 32-bit `Signal` produces a 6-bit count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(0x80u32, 8).trailing_zeros(); // Equivalent to 4-bit lit with value 7
+    /// let b = m.lit(0x0u32, 8).trailing_zeros(); // Equivalent to 4-bit lit with value 8
+    /// ```
+    #[track_caller]
+    fn trailing_zeros(&'a self) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
+
+            data: SignalData::CountOp {
+                source: s,
+                op: CountOp::TrailingZeros,
+                bit_width: 32 - s.bit_width().leading_zeros(),
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the number of this `Signal`'s bits that are high.
+    ///
+    /// The result is just wide enough to represent this `Signal`'s `bit_width`, so a 32-bit `Signal` produces a 6-bit count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(0xa5u32, 8).count_ones(); // Equivalent to 4-bit lit with value 4
+    /// let b = m.lit(0xffu32, 8).count_ones(); // Equivalent to 4-bit lit with value 8
+    /// ```
+    #[track_caller]
+    fn count_ones(&'a self) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
+
+            data: SignalData::CountOp {
+                source: s,
+                op: CountOp::CountOnes,
+                bit_width: 32 - s.bit_width().leading_zeros(),
+            },
+        })
+    }
+
+    /// Creates a `Signal` with `2^self.bit_width()` bits, where only the bit at the index represented by this `Signal` is high.
+    ///
+    /// This is the inverse of [`priority_encode`](Self::priority_encode) for `Signal`s with exactly one bit high.
     ///
     /// # Panics
     ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    /// Panics if `2^self.bit_width()` is greater than [`MAX_SIGNAL_BIT_WIDTH`].
     ///
     /// # Examples
     ///
@@ -547,46 +724,93 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let lt_signed_1 = lit_a.lt_signed(lit_a); // Equivalent to m.low()
-    /// let lt_signed_2 = lit_b.lt_signed(lit_b); // Equivalent to m.low()
-    /// let lt_signed_3 = lit_a.lt_signed(lit_b); // Equivalent to m.high()
-    /// let lt_signed_4 = lit_b.lt_signed(lit_a); // Equivalent to m.low()
+    /// let a = m.lit(0u32, 3).one_hot(); // Equivalent to 8-bit lit with value 0x01
+    /// let b = m.lit(5u32, 3).one_hot(); // Equivalent to 8-bit lit with value 0x20
     /// ```
-    fn lt_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
-        let lhs = self.internal_signal();
-        let rhs = rhs.internal_signal();
-        if !ptr::eq(lhs.module, rhs.module) {
-            panic!("Attempted to combine signals from different modules.");
-        }
-        if lhs.bit_width() != rhs.bit_width() {
-            panic!(
-                "Signals have different bit widths ({} and {}, respectively).",
-                lhs.bit_width(),
-                rhs.bit_width()
-            );
-        }
-        if lhs.bit_width() == 1 {
-            panic!("Cannot perform signed comparison of 1-bit signals.");
+    #[track_caller]
+    fn one_hot(&'a self) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        if s.bit_width() > MAX_SIGNAL_BIT_WIDTH.trailing_zeros() {
+            panic!("Attempted to one-hot decode a {}-bit signal, but this would result in a bit width of 2^{}, which is greater than the maximum signal bit width of {} bit(s).", s.bit_width(), s.bit_width(), MAX_SIGNAL_BIT_WIDTH);
         }
-        lhs.context.signal_arena.alloc(InternalSignal {
-            context: lhs.context,
-            module: lhs.module,
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
 
-            data: SignalData::ComparisonBinOp {
-                lhs,
-                rhs,
-                op: ComparisonBinOp::LessThanSigned,
+            data: SignalData::OneHot {
+                source: s,
+                bit_width: 1 << s.bit_width(),
             },
         })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of a signed `<=` comparison between `self` and `rhs`.
+    /// Creates a `Signal` that represents the index of this `Signal`'s least significant high bit, or `0` if none of its bits are high.
+    ///
+    /// The result is just wide enough to represent any index into this `Signal`, so a 32-bit `Signal` produces a 5-bit index. A 1-bit `Signal` produces a 1-bit index that's always `0`. Combine with [`or_reduce`](Self::or_reduce) to tell whether any bit is high.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(0x20u32, 8).priority_encode(); // Equivalent to 3-bit lit with value 5
+    /// let b = m.lit(0x24u32, 8).priority_encode(); // Equivalent to 3-bit lit with value 2
+    /// let d = m.lit(0x00u32, 8).priority_encode(); // Equivalent to 3-bit lit with value 0
+    /// ```
+    #[track_caller]
+    fn priority_encode(&'a self) -> &'a dyn Signal<'a> {
+        let s = self.internal_signal();
+        s.context.signal_arena.alloc(InternalSignal {
+            context: s.context,
+            module: s.module,
+            location: Location::caller(),
+
+            data: SignalData::PriorityEncode {
+                source: s,
+                bit_width: (32 - (s.bit_width() - 1).leading_zeros()).max(1),
+            },
+        })
+    }
+
+    /// Creates a [`SignedSignal`] that represents the same bits as `self`, interpreted as a signed, two's complement integer.
+    ///
+    /// The comparison methods and the `*`, `/`, `%` and `>>` operators of the returned [`SignedSignal`] use the signed variants of the corresponding operations (such as [`lt_signed`](SignalCompare::lt_signed) and [`shr_arithmetic`](SignalCompare::shr_arithmetic)) automatically. [`Output`](crate::Output)s driven by a [`SignedSignal`] are declared as `signed` in generated Verilog code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4).as_signed(); // -6
+    /// let lit_b = m.lit(0x3u32, 4); // 3
+    /// let lt = lit_a.lt(lit_b); // Equivalent to m.high()
+    /// let shifted = lit_a >> m.lit(1u32, 1); // Equivalent to m.lit(0xdu32, 4).as_signed()
+    /// ```
+    #[track_caller]
+    fn as_signed(&'a self) -> &'a SignedSignal<'a> {
+        let value = self.internal_signal();
+        value
+            .context
+            .signed_signal_arena
+            .alloc(SignedSignal { value })
+    }
+
+    /// Combines two `Signal`s, producing a new `Signal` that represents the signed product of the original two `Signal`s.
+    ///
+    /// The product's `bit_width` is equal to `self.bit_width() + rhs.bit_width()`.
     ///
     /// # Panics
     ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if `self.bit_width() + rhs.bit_width()` is greater than [`MAX_SIGNAL_BIT_WIDTH`].
     ///
     /// # Examples
     ///
@@ -597,46 +821,41 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let le_signed_1 = lit_a.le_signed(lit_a); // Equivalent to m.high()
-    /// let le_signed_2 = lit_b.le_signed(lit_b); // Equivalent to m.high()
-    /// let le_signed_3 = lit_a.le_signed(lit_b); // Equivalent to m.high()
-    /// let le_signed_4 = lit_b.le_signed(lit_a); // Equivalent to m.low()
+    /// let lhs = m.lit(4u32, 3); // -4
+    /// let rhs = m.lit(5u32, 4);
+    /// let sum = lhs.mul_signed(rhs); // Equivalent to m.lit(108u32, 7), -20
     /// ```
-    fn le_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
+    #[track_caller]
+    fn mul_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
         let lhs = self.internal_signal();
         let rhs = rhs.internal_signal();
         if !ptr::eq(lhs.module, rhs.module) {
             panic!("Attempted to combine signals from different modules.");
         }
-        if lhs.bit_width() != rhs.bit_width() {
-            panic!(
-                "Signals have different bit widths ({} and {}, respectively).",
-                lhs.bit_width(),
-                rhs.bit_width()
-            );
-        }
-        if lhs.bit_width() == 1 {
-            panic!("Cannot perform signed comparison of 1-bit signals.");
+        let bit_width = lhs.bit_width() + rhs.bit_width();
+        if bit_width > MAX_SIGNAL_BIT_WIDTH {
+            panic!("Attempted to multiply a {}-bit with a {}-bit signal, but this would result in a bit width of {}, which is greater than the maximum signal bit width of {} bit(s).", lhs.bit_width(), rhs.bit_width(), bit_width, MAX_SIGNAL_BIT_WIDTH);
         }
         lhs.context.signal_arena.alloc(InternalSignal {
             context: lhs.context,
             module: lhs.module,
+            location: Location::caller(),
 
-            data: SignalData::ComparisonBinOp {
+            data: SignalData::MulSigned {
                 lhs,
                 rhs,
-                op: ComparisonBinOp::LessThanEqualSigned,
+                bit_width,
             },
         })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of a signed `>` comparison between `self` and `rhs`.
+    /// Combines two `Signal`s, producing a new `Signal` that represents the signed quotient of the original two `Signal`s, interpreting both as signed, two's complement integers.
+    ///
+    /// The quotient is rounded towards zero and has the same `bit_width` as the original `Signal`s. If `rhs` is `0`, every bit of the quotient is set (representing `-1`). If `self` is the most negative value representable in its `bit_width` and `rhs` is `-1`, the quotient wraps around to `self`'s value.
     ///
     /// # Panics
     ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
     ///
     /// # Examples
     ///
@@ -647,14 +866,12 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let gt_signed_1 = lit_a.gt_signed(lit_a); // Equivalent to m.low()
-    /// let gt_signed_2 = lit_b.gt_signed(lit_b); // Equivalent to m.low()
-    /// let gt_signed_3 = lit_a.gt_signed(lit_b); // Equivalent to m.low()
-    /// let gt_signed_4 = lit_b.gt_signed(lit_a); // Equivalent to m.high()
+    /// let lhs = m.lit(0xf9u32, 8); // -7
+    /// let rhs = m.lit(2u32, 8);
+    /// let quotient = lhs.div_signed(rhs); // Equivalent to m.lit(0xfdu32, 8), -3
     /// ```
-    fn gt_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
+    #[track_caller]
+    fn div_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
         let lhs = self.internal_signal();
         let rhs = rhs.internal_signal();
         if !ptr::eq(lhs.module, rhs.module) {
@@ -667,26 +884,27 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
                 rhs.bit_width()
             );
         }
-        if lhs.bit_width() == 1 {
-            panic!("Cannot perform signed comparison of 1-bit signals.");
-        }
         lhs.context.signal_arena.alloc(InternalSignal {
             context: lhs.context,
             module: lhs.module,
+            location: Location::caller(),
 
-            data: SignalData::ComparisonBinOp {
+            data: SignalData::DivisionBinOp {
                 lhs,
                 rhs,
-                op: ComparisonBinOp::GreaterThanSigned,
+                op: DivisionBinOp::DivSigned,
+                bit_width: lhs.bit_width(),
             },
         })
     }
 
-    /// Creates a `Signal` that represents the single-bit result of a signed `>=` comparison between `self` and `rhs`.
+    /// Combines two `Signal`s, producing a new `Signal` that represents the signed remainder of the original two `Signal`s, interpreting both as signed, two's complement integers.
+    ///
+    /// The remainder is the remainder of [`div_signed`](Self::div_signed), whose sign matches `self`'s sign, and has the same `bit_width` as the original `Signal`s. If `rhs` is `0`, the remainder is equal to `self`.
     ///
     /// # Panics
     ///
-    /// Panics if `self` and `rhs` belong to different [`Module`]s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
     ///
     /// # Examples
     ///
@@ -697,14 +915,12 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lit_a = m.lit(0xau32, 4);
-    /// let lit_b = m.lit(0xbu32, 4);
-    /// let ge_signed_1 = lit_a.ge_signed(lit_a); // Equivalent to m.high()
-    /// let ge_signed_2 = lit_b.ge_signed(lit_b); // Equivalent to m.high()
-    /// let ge_signed_3 = lit_a.ge_signed(lit_b); // Equivalent to m.low()
-    /// let ge_signed_4 = lit_b.ge_signed(lit_a); // Equivalent to m.high()
+    /// let lhs = m.lit(0xf9u32, 8); // -7
+    /// let rhs = m.lit(2u32, 8);
+    /// let remainder = lhs.rem_signed(rhs); // Equivalent to m.lit(0xffu32, 8), -1
     /// ```
-    fn ge_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
+    #[track_caller]
+    fn rem_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
         let lhs = self.internal_signal();
         let rhs = rhs.internal_signal();
         if !ptr::eq(lhs.module, rhs.module) {
@@ -717,28 +933,27 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
                 rhs.bit_width()
             );
         }
-        if lhs.bit_width() == 1 {
-            panic!("Cannot perform signed comparison of 1-bit signals.");
-        }
         lhs.context.signal_arena.alloc(InternalSignal {
             context: lhs.context,
             module: lhs.module,
+            location: Location::caller(),
 
-            data: SignalData::ComparisonBinOp {
+            data: SignalData::DivisionBinOp {
                 lhs,
                 rhs,
-                op: ComparisonBinOp::GreaterThanEqualSigned,
+                op: DivisionBinOp::RemSigned,
+                bit_width: lhs.bit_width(),
             },
         })
     }
 
-    /// Combines two `Signal`s, producing a new `Signal` that represents `self` arithmetically shifted right by `rhs` bits.
+    /// Combines two `Signal`s, producing a new `Signal` that represents the sum of the original two `Signal`s along with a carry-out bit.
     ///
-    /// The result is truncated to `self`'s `bit_width`. If `rhs` specifies a value that's greater than or equal to `self`'s `bit_width`, the resulting value will be all `self`'s top bit repeated `self`'s `bit_width` times.
+    /// The result's `bit_width` is equal to `self.bit_width() + 1`, where the most significant bit is the carry-out bit and the remaining bits are the truncated sum.
     ///
     /// # Panics
     ///
-    /// Panics if `lhs` and `rhs` belong to different [`Module`]s.
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, if the bit widths of `lhs` and `rhs` aren't equal, or if `self.bit_width() + 1` is greater than [`MAX_SIGNAL_BIT_WIDTH`].
     ///
     /// # Examples
     ///
@@ -749,36 +964,40 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lhs = m.lit(0x80000000u32, 32);
-    /// let rhs = m.lit(1u32, 1);
-    /// let shifted = lhs.shr_arithmetic(rhs); // Equivalent to m.lit(0xc0000000u32, 32)
+    /// let lhs = m.lit(0xffffffffu32, 32);
+    /// let rhs = m.lit(0x00000002u32, 32);
+    /// let carry_sum = lhs.add_carry(rhs); // Equivalent to m.lit(0x100000001u64, 33)
+    /// let sum = carry_sum.bits(31, 0); // Equivalent to m.lit(1u32, 32)
+    /// let carry = carry_sum.bit(32); // Equivalent to m.high()
     /// ```
-    fn shr_arithmetic(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
+    #[track_caller]
+    fn add_carry(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
         let lhs = self.internal_signal();
         let rhs = rhs.internal_signal();
         if !ptr::eq(lhs.module, rhs.module) {
             panic!("Attempted to combine signals from different modules.");
         }
-        lhs.context.signal_arena.alloc(InternalSignal {
-            context: lhs.context,
-            module: lhs.module,
-
-            data: SignalData::ShiftBinOp {
-                lhs,
-                rhs,
-                op: ShiftBinOp::ShrArithmetic,
-                bit_width: lhs.bit_width(),
-            },
-        })
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        let bit_width = lhs.bit_width() + 1;
+        if bit_width > MAX_SIGNAL_BIT_WIDTH {
+            panic!("Attempted to add {}-bit signals with a carry-out bit, but this would result in a bit width of {}, which is greater than the maximum signal bit width of {} bit(s).", lhs.bit_width(), bit_width, MAX_SIGNAL_BIT_WIDTH);
+        }
+        lhs.zext(bit_width) + rhs.zext(bit_width)
     }
 
-    /// Combines two `Signal`s, producing a new `Signal` that represents the signed product of the original two `Signal`s.
+    /// Combines two `Signal`s, producing a new `Signal` that represents the difference of the original two `Signal`s along with a borrow-out bit.
     ///
-    /// The product's `bit_width` is equal to `self.bit_width() + rhs.bit_width()`.
+    /// The result's `bit_width` is equal to `self.bit_width() + 1`, where the most significant bit is the borrow-out bit (high when `rhs` is greater than `self`) and the remaining bits are the truncated difference.
     ///
     /// # Panics
     ///
-    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if `self.bit_width() + rhs.bit_width()` is greater than [`MAX_SIGNAL_BIT_WIDTH`].
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, if the bit widths of `lhs` and `rhs` aren't equal, or if `self.bit_width() + 1` is greater than [`MAX_SIGNAL_BIT_WIDTH`].
     ///
     /// # Examples
     ///
@@ -789,30 +1008,210 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     ///
     /// let m = c.module("m", "MyModule");
     ///
-    /// let lhs = m.lit(4u32, 3); // -4
-    /// let rhs = m.lit(5u32, 4);
-    /// let sum = lhs.mul_signed(rhs); // Equivalent to m.lit(108u32, 7), -20
+    /// let lhs = m.lit(1u32, 32);
+    /// let rhs = m.lit(2u32, 32);
+    /// let borrow_difference = lhs.sub_borrow(rhs); // Equivalent to m.lit(0x1ffffffffu64, 33)
+    /// let difference = borrow_difference.bits(31, 0); // Equivalent to m.lit(0xffffffffu32, 32)
+    /// let borrow = borrow_difference.bit(32); // Equivalent to m.high()
     /// ```
-    fn mul_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &dyn Signal<'a> {
+    #[track_caller]
+    fn sub_borrow(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
         let lhs = self.internal_signal();
         let rhs = rhs.internal_signal();
         if !ptr::eq(lhs.module, rhs.module) {
             panic!("Attempted to combine signals from different modules.");
         }
-        let bit_width = lhs.bit_width() + rhs.bit_width();
-        if bit_width > MAX_SIGNAL_BIT_WIDTH {
-            panic!("Attempted to multiply a {}-bit with a {}-bit signal, but this would result in a bit width of {}, which is greater than the maximum signal bit width of {} bit(s).", lhs.bit_width(), rhs.bit_width(), bit_width, MAX_SIGNAL_BIT_WIDTH);
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
         }
-        lhs.context.signal_arena.alloc(InternalSignal {
-            context: lhs.context,
-            module: lhs.module,
+        let bit_width = lhs.bit_width() + 1;
+        if bit_width > MAX_SIGNAL_BIT_WIDTH {
+            panic!("Attempted to subtract {}-bit signals with a borrow-out bit, but this would result in a bit width of {}, which is greater than the maximum signal bit width of {} bit(s).", lhs.bit_width(), bit_width, MAX_SIGNAL_BIT_WIDTH);
+        }
+        lhs.zext(bit_width) - rhs.zext(bit_width)
+    }
 
-            data: SignalData::MulSigned {
-                lhs,
-                rhs,
-                bit_width,
-            },
-        })
+    /// Combines two `Signal`s, producing a new `Signal` that represents the unsigned sum of the original two `Signal`s, saturated to the largest representable value instead of wrapping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(0xf0u32, 8).add_sat(m.lit(0x0fu32, 8)); // Equivalent to m.lit(0xffu32, 8)
+    /// let b = m.lit(0xf0u32, 8).add_sat(m.lit(0x20u32, 8)); // Equivalent to m.lit(0xffu32, 8)
+    /// ```
+    #[track_caller]
+    fn add_sat(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        let lhs: &'a dyn Signal<'a> = lhs;
+        let rhs: &'a dyn Signal<'a> = rhs;
+        let sum = lhs + rhs;
+        // The sum wrapped iff it's less than either operand, in which case all of its bits are forced high
+        let carry = sum.lt(lhs);
+        sum | carry.sext(lhs.bit_width())
+    }
+
+    /// Combines two `Signal`s, producing a new `Signal` that represents the signed sum of the original two `Signal`s, saturated to the smallest or largest representable value instead of wrapping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(0x70u32, 8).add_sat_signed(m.lit(0x20u32, 8)); // Equivalent to m.lit(0x7fu32, 8)
+    /// let b = m.lit(0x90u32, 8).add_sat_signed(m.lit(0xe0u32, 8)); // Equivalent to m.lit(0x80u32, 8)
+    /// let d = m.lit(0x70u32, 8).add_sat_signed(m.lit(0xe0u32, 8)); // Equivalent to m.lit(0x50u32, 8)
+    /// ```
+    #[track_caller]
+    fn add_sat_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        let module = lhs.module;
+        let lhs: &'a dyn Signal<'a> = lhs;
+        let rhs: &'a dyn Signal<'a> = rhs;
+        let bit_width = lhs.bit_width();
+        let sum = lhs + rhs;
+        let lhs_sign = lhs.bit(bit_width - 1);
+        let rhs_sign = rhs.bit(bit_width - 1);
+        // The sum overflowed iff both operands have the same sign and the sum's sign differs, in which case it saturates towards the operands' sign
+        let overflow = lhs_sign.eq(rhs_sign) & sum.bit(bit_width - 1).ne(lhs_sign);
+        let saturated = if bit_width > 1 {
+            lhs_sign.concat((!lhs_sign).sext(bit_width - 1))
+        } else {
+            lhs_sign
+        };
+        module.mux(overflow, saturated, sum)
+    }
+
+    /// Combines two `Signal`s, producing a new `Signal` that represents the unsigned difference of the original two `Signal`s, saturated to `0` instead of wrapping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(0x20u32, 8).sub_sat(m.lit(0x10u32, 8)); // Equivalent to m.lit(0x10u32, 8)
+    /// let b = m.lit(0x10u32, 8).sub_sat(m.lit(0x20u32, 8)); // Equivalent to m.lit(0u32, 8)
+    /// ```
+    #[track_caller]
+    fn sub_sat(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        let lhs: &'a dyn Signal<'a> = lhs;
+        let rhs: &'a dyn Signal<'a> = rhs;
+        // The difference wraps iff rhs is greater than lhs, in which case all of its bits are forced low
+        let borrow = lhs.lt(rhs);
+        (lhs - rhs) & (!borrow).sext(lhs.bit_width())
+    }
+
+    /// Combines two `Signal`s, producing a new `Signal` that represents the signed difference of the original two `Signal`s, saturated to the smallest or largest representable value instead of wrapping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(0x70u32, 8).sub_sat_signed(m.lit(0xe0u32, 8)); // Equivalent to m.lit(0x7fu32, 8)
+    /// let b = m.lit(0x90u32, 8).sub_sat_signed(m.lit(0x20u32, 8)); // Equivalent to m.lit(0x80u32, 8)
+    /// let d = m.lit(0x70u32, 8).sub_sat_signed(m.lit(0x20u32, 8)); // Equivalent to m.lit(0x50u32, 8)
+    /// ```
+    #[track_caller]
+    fn sub_sat_signed(&'a self, rhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        let module = lhs.module;
+        let lhs: &'a dyn Signal<'a> = lhs;
+        let rhs: &'a dyn Signal<'a> = rhs;
+        let bit_width = lhs.bit_width();
+        let difference = lhs - rhs;
+        let lhs_sign = lhs.bit(bit_width - 1);
+        let rhs_sign = rhs.bit(bit_width - 1);
+        // The difference overflowed iff the operands have different signs and the difference's sign differs from lhs's, in which case it saturates towards lhs's sign
+        let overflow = lhs_sign.ne(rhs_sign) & difference.bit(bit_width - 1).ne(lhs_sign);
+        let saturated = if bit_width > 1 {
+            lhs_sign.concat((!lhs_sign).sext(bit_width - 1))
+        } else {
+            lhs_sign
+        };
+        module.mux(overflow, saturated, difference)
     }
 
     /// Creates a 2:1 [multiplexer](https://en.wikipedia.org/wiki/Multiplexer) that represents `when_true`'s value when `self` is high, and `when_false`'s value when `self` is low.
@@ -838,6 +1237,7 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     /// m.output("my_output", cond.mux(a, b)); // Outputs a when cond is high, b otherwise
     /// ```
     // TODO: This is currently only used to support sugar; if it doesn't work out, remove this
+    #[track_caller]
     fn mux(
         &'a self,
         when_true: &'a dyn Signal<'a>,
@@ -848,96 +1248,1204 @@ pub trait Signal<'a>: GetInternalSignal<'a> {
     }
 }
 
-macro_rules! impl_extensions {
-    ($($t:ty),*) => ($(
-        impl<'a, S: Into<&'a dyn Signal<'a>>> Add<S> for &'a $t {
-            type Output = &'a dyn Signal<'a>;
-
-            /// Combines two `Signal`s, producing a new `Signal` that represents the sum of the original two `Signal`s.
-            ///
-            /// The sum is truncated to the `Signal`'s `bit_width`. If a carry bit is desired, the operands can be [`concat`]enated with a `0` bit before the operation.
-            ///
-            /// # Panics
-            ///
-            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
-            ///
-            /// # Examples
-            ///
-            /// ```
-            /// use kaze::*;
-            ///
-            /// let c = Context::new();
-            ///
-            /// let m = c.module("m", "MyModule");
-            ///
-            /// let lhs = m.lit(1u32, 32);
-            /// let rhs = m.lit(2u32, 32);
-            /// let sum = lhs + rhs; // Equivalent to m.lit(3u32, 32)
-            ///
-            /// let lhs = m.low().concat(m.lit(0xffffffffu32, 32)); // Concat 0 bits with operands for carry
-            /// let rhs = m.low().concat(m.lit(0x00000001u32, 32));
-            /// let carry_sum = lhs + rhs; // Equivalent to m.lit(0x100000000u64, 33)
-            /// let sum = carry_sum.bits(31, 0); // Equivalent to m.lit(0u32, 32)
-            /// let carry = carry_sum.bit(32); // Equivalent to m.lit(true, 1)
-            /// ```
-            ///
-            /// [`concat`]: Signal::concat
-            fn add(self, rhs: S) -> Self::Output {
-                let lhs = self.internal_signal();
-                let rhs = rhs.into().internal_signal();
-                if !ptr::eq(lhs.module, rhs.module) {
-                    panic!("Attempted to combine signals from different modules.");
-                }
-                if lhs.bit_width() != rhs.bit_width() {
-                    panic!(
-                        "Signals have different bit widths ({} and {}, respectively).",
-                        lhs.bit_width(),
-                        rhs.bit_width()
-                    );
-                }
-                lhs.context.signal_arena.alloc(InternalSignal {
-                    context: lhs.context,
-                    module: lhs.module,
-
-                    data: SignalData::AdditiveBinOp {
-                        lhs,
-                        rhs,
-                        op: AdditiveBinOp::Add,
-                        bit_width: lhs.bit_width(),
-                    },
-                })
-            }
+/// Comparison, arithmetic shift and rotation methods for [`Signal`]s, implemented for every [`Signal`].
+///
+/// These methods are kept separate from [`Signal`] because they're generic over their right-hand side (see [`IntoSignal`]), which allows comparing, shifting or rotating a [`Signal`] by another [`Signal`] or directly by an integer value. Generic methods can't be called through a `&dyn Signal`, so they can't be part of [`Signal`] itself; this trait must be imported alongside [`Signal`] (it's included in `use kaze::*`).
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let a = m.input("a", 8);
+/// let b = m.input("b", 8);
+/// m.output("a_eq_b", a.eq(b));
+/// m.output("a_lt_10", a.lt(10u32)); // Equivalent to a.lt(m.lit(10u32, 8))
+/// m.output("a_rotl_3", a.rotate_left(3)); // Equivalent to a.rotate_left(m.lit(3u32, 2))
+/// ```
+pub trait SignalCompare<'a>: Signal<'a> {
+    /// Creates a `Signal` that represents the single-bit result of a bitwise boolean equality comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let eq_1 = lit_a.eq(lit_a); // Equivalent to m.high()
+    /// let eq_2 = lit_b.eq(lit_b); // Equivalent to m.high()
+    /// let eq_3 = lit_a.eq(lit_b); // Equivalent to m.low()
+    /// let eq_4 = lit_b.eq(lit_a); // Equivalent to m.low()
+    /// ```
+    #[track_caller]
+    fn eq<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
         }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
 
-        impl<'a, S: Into<&'a dyn Signal<'a>>> BitAnd<S> for &'a $t {
-            type Output = &'a dyn Signal<'a>;
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::Equal,
+            },
+        })
+    }
 
-            /// Combines two `Signal`s, producing a new `Signal` whose bits represent the bitwise `&` of each of the bits of the original two `Signal`s.
-            ///
-            /// # Panics
-            ///
-            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
-            ///
-            /// # Examples
-            ///
-            /// ```
-            /// use kaze::*;
-            ///
-            /// let c = Context::new();
-            ///
-            /// let m = c.module("m", "MyModule");
-            ///
-            /// let lhs = m.low();
-            /// let rhs = m.high();
-            /// let single_bitand = lhs & rhs;
-            ///
+    /// Creates a `Signal` that represents the single-bit result of a bitwise boolean inequality comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let ne_1 = lit_a.ne(lit_a); // Equivalent to m.low()
+    /// let ne_2 = lit_b.ne(lit_b); // Equivalent to m.low()
+    /// let ne_3 = lit_a.ne(lit_b); // Equivalent to m.high()
+    /// let ne_4 = lit_b.ne(lit_a); // Equivalent to m.high()
+    /// ```
+    #[track_caller]
+    fn ne<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::NotEqual,
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of an unsigned `<` comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let lt_1 = lit_a.lt(lit_a); // Equivalent to m.low()
+    /// let lt_2 = lit_b.lt(lit_b); // Equivalent to m.low()
+    /// let lt_3 = lit_a.lt(lit_b); // Equivalent to m.high()
+    /// let lt_4 = lit_b.lt(lit_a); // Equivalent to m.low()
+    /// ```
+    #[track_caller]
+    fn lt<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::LessThan,
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of an unsigned `<=` comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let le_1 = lit_a.le(lit_a); // Equivalent to m.high()
+    /// let le_2 = lit_b.le(lit_b); // Equivalent to m.high()
+    /// let le_3 = lit_a.le(lit_b); // Equivalent to m.high()
+    /// let le_4 = lit_b.le(lit_a); // Equivalent to m.low()
+    /// ```
+    #[track_caller]
+    fn le<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::LessThanEqual,
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of an unsigned `>` comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let gt_1 = lit_a.gt(lit_a); // Equivalent to m.low()
+    /// let gt_2 = lit_b.gt(lit_b); // Equivalent to m.low()
+    /// let gt_3 = lit_a.gt(lit_b); // Equivalent to m.low()
+    /// let gt_4 = lit_b.gt(lit_a); // Equivalent to m.high()
+    /// ```
+    #[track_caller]
+    fn gt<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::GreaterThan,
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of an unsigned `>=` comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, or if the bit widths of `self` and `rhs` aren't equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let ge_1 = lit_a.ge(lit_a); // Equivalent to m.high()
+    /// let ge_2 = lit_b.ge(lit_b); // Equivalent to m.high()
+    /// let ge_3 = lit_a.ge(lit_b); // Equivalent to m.low()
+    /// let ge_4 = lit_b.ge(lit_a); // Equivalent to m.high()
+    /// ```
+    #[track_caller]
+    fn ge<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::GreaterThanEqual,
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of a signed `<` comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let lt_signed_1 = lit_a.lt_signed(lit_a); // Equivalent to m.low()
+    /// let lt_signed_2 = lit_b.lt_signed(lit_b); // Equivalent to m.low()
+    /// let lt_signed_3 = lit_a.lt_signed(lit_b); // Equivalent to m.high()
+    /// let lt_signed_4 = lit_b.lt_signed(lit_a); // Equivalent to m.low()
+    /// ```
+    #[track_caller]
+    fn lt_signed<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        if lhs.bit_width() == 1 {
+            panic!("Cannot perform signed comparison of 1-bit signals.");
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::LessThanSigned,
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of a signed `<=` comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let le_signed_1 = lit_a.le_signed(lit_a); // Equivalent to m.high()
+    /// let le_signed_2 = lit_b.le_signed(lit_b); // Equivalent to m.high()
+    /// let le_signed_3 = lit_a.le_signed(lit_b); // Equivalent to m.high()
+    /// let le_signed_4 = lit_b.le_signed(lit_a); // Equivalent to m.low()
+    /// ```
+    #[track_caller]
+    fn le_signed<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        if lhs.bit_width() == 1 {
+            panic!("Cannot perform signed comparison of 1-bit signals.");
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::LessThanEqualSigned,
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of a signed `>` comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let gt_signed_1 = lit_a.gt_signed(lit_a); // Equivalent to m.low()
+    /// let gt_signed_2 = lit_b.gt_signed(lit_b); // Equivalent to m.low()
+    /// let gt_signed_3 = lit_a.gt_signed(lit_b); // Equivalent to m.low()
+    /// let gt_signed_4 = lit_b.gt_signed(lit_a); // Equivalent to m.high()
+    /// ```
+    #[track_caller]
+    fn gt_signed<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        if lhs.bit_width() == 1 {
+            panic!("Cannot perform signed comparison of 1-bit signals.");
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::GreaterThanSigned,
+            },
+        })
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of a signed `>=` comparison between `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`]s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4);
+    /// let lit_b = m.lit(0xbu32, 4);
+    /// let ge_signed_1 = lit_a.ge_signed(lit_a); // Equivalent to m.high()
+    /// let ge_signed_2 = lit_b.ge_signed(lit_b); // Equivalent to m.high()
+    /// let ge_signed_3 = lit_a.ge_signed(lit_b); // Equivalent to m.low()
+    /// let ge_signed_4 = lit_b.ge_signed(lit_a); // Equivalent to m.high()
+    /// ```
+    #[track_caller]
+    fn ge_signed<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_signal(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        if lhs.bit_width() != rhs.bit_width() {
+            panic!(
+                "Signals have different bit widths ({} and {}, respectively).",
+                lhs.bit_width(),
+                rhs.bit_width()
+            );
+        }
+        if lhs.bit_width() == 1 {
+            panic!("Cannot perform signed comparison of 1-bit signals.");
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ComparisonBinOp {
+                lhs,
+                rhs,
+                op: ComparisonBinOp::GreaterThanEqualSigned,
+            },
+        })
+    }
+
+    /// Combines two `Signal`s, producing a new `Signal` that represents `self` arithmetically shifted right by `rhs` bits.
+    ///
+    /// The result is truncated to `self`'s `bit_width`. If `rhs` specifies a value that's greater than or equal to `self`'s `bit_width`, the resulting value will be all `self`'s top bit repeated `self`'s `bit_width` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lhs = m.lit(0x80000000u32, 32);
+    /// let rhs = m.lit(1u32, 1);
+    /// let shifted = lhs.shr_arithmetic(rhs); // Equivalent to m.lit(0xc0000000u32, 32)
+    /// let shifted = lhs.shr_arithmetic(4); // Equivalent to m.lit(0xf8000000u32, 32)
+    /// ```
+    #[track_caller]
+    fn shr_arithmetic<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_shift_amount(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::ShiftBinOp {
+                lhs,
+                rhs,
+                op: ShiftBinOp::ShrArithmetic,
+                bit_width: lhs.bit_width(),
+            },
+        })
+    }
+
+    /// Combines two `Signal`s, producing a new `Signal` that represents `self` rotated left by `rhs` bits, with the bits shifted out of one end wrapping around to the other.
+    ///
+    /// The result has the same `bit_width` as `self`, and `rhs` is taken modulo `self`'s `bit_width`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lhs = m.lit(0x80000001u32, 32);
+    /// let rhs = m.lit(1u32, 1);
+    /// let rotated = lhs.rotate_left(rhs); // Equivalent to m.lit(0x00000003u32, 32)
+    /// let rotated = lhs.rotate_left(4); // Equivalent to m.lit(0x00000018u32, 32)
+    /// ```
+    #[track_caller]
+    fn rotate_left<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_shift_amount(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::RotateBinOp {
+                lhs,
+                rhs,
+                op: RotateBinOp::Left,
+                bit_width: lhs.bit_width(),
+            },
+        })
+    }
+
+    /// Combines two `Signal`s, producing a new `Signal` that represents `self` rotated right by `rhs` bits, with the bits shifted out of one end wrapping around to the other.
+    ///
+    /// The result has the same `bit_width` as `self`, and `rhs` is taken modulo `self`'s `bit_width`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` belong to different [`Module`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lhs = m.lit(0x80000001u32, 32);
+    /// let rhs = m.lit(1u32, 1);
+    /// let rotated = lhs.rotate_right(rhs); // Equivalent to m.lit(0xc0000000u32, 32)
+    /// let rotated = lhs.rotate_right(4); // Equivalent to m.lit(0x18000000u32, 32)
+    /// ```
+    #[track_caller]
+    fn rotate_right<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        let lhs = self.internal_signal();
+        let rhs = rhs.into_shift_amount(lhs).internal_signal();
+        if !ptr::eq(lhs.module, rhs.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        lhs.context.signal_arena.alloc(InternalSignal {
+            context: lhs.context,
+            module: lhs.module,
+            location: Location::caller(),
+
+            data: SignalData::RotateBinOp {
+                lhs,
+                rhs,
+                op: RotateBinOp::Right,
+                bit_width: lhs.bit_width(),
+            },
+        })
+    }
+}
+
+impl<'a, T: Signal<'a> + ?Sized> SignalCompare<'a> for T {}
+
+macro_rules! impl_extensions {
+    ($($t:ty),*) => ($(
+        impl<'a, S: IntoSignal<'a>> Add<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` that represents the sum of the original two `Signal`s.
+            ///
+            /// The sum is truncated to the `Signal`'s `bit_width`. If a carry bit is desired, use [`add_carry`](Signal::add_carry), or [`concat`]enate the operands with a `0` bit before the operation. For a sum that saturates instead of wrapping, use [`add_sat`](Signal::add_sat) or [`add_sat_signed`](Signal::add_sat_signed).
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.lit(1u32, 32);
+            /// let rhs = m.lit(2u32, 32);
+            /// let sum = lhs + rhs; // Equivalent to m.lit(3u32, 32)
+            ///
+            /// let lhs = m.low().concat(m.lit(0xffffffffu32, 32)); // Concat 0 bits with operands for carry
+            /// let rhs = m.low().concat(m.lit(0x00000001u32, 32));
+            /// let carry_sum = lhs + rhs; // Equivalent to m.lit(0x100000000u64, 33)
+            /// let sum = carry_sum.bits(31, 0); // Equivalent to m.lit(0u32, 32)
+            /// let carry = carry_sum.bit(32); // Equivalent to m.lit(true, 1)
+            /// ```
+            ///
+            /// [`concat`]: Signal::concat
+            #[track_caller]
+            fn add(self, rhs: S) -> Self::Output {
+                let lhs = self.internal_signal();
+                let rhs = rhs.into_signal(lhs).internal_signal();
+                if !ptr::eq(lhs.module, rhs.module) {
+                    panic!("Attempted to combine signals from different modules.");
+                }
+                if lhs.bit_width() != rhs.bit_width() {
+                    panic!(
+                        "Signals have different bit widths ({} and {}, respectively).",
+                        lhs.bit_width(),
+                        rhs.bit_width()
+                    );
+                }
+                lhs.context.signal_arena.alloc(InternalSignal {
+                    context: lhs.context,
+                    module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::AdditiveBinOp {
+                        lhs,
+                        rhs,
+                        op: AdditiveBinOp::Add,
+                        bit_width: lhs.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: IntoSignal<'a>> BitAnd<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` whose bits represent the bitwise `&` of each of the bits of the original two `Signal`s.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.low();
+            /// let rhs = m.high();
+            /// let single_bitand = lhs & rhs;
+            ///
+            /// let lhs = m.input("in1", 3);
+            /// let rhs = m.input("in2", 3);
+            /// let multi_bitand = lhs & rhs;
+            /// ```
+            #[track_caller]
+            fn bitand(self, rhs: S) -> Self::Output {
+                let lhs = self.internal_signal();
+                let rhs = rhs.into_signal(lhs).internal_signal();
+                if !ptr::eq(lhs.module, rhs.module) {
+                    panic!("Attempted to combine signals from different modules.");
+                }
+                if lhs.bit_width() != rhs.bit_width() {
+                    panic!(
+                        "Signals have different bit widths ({} and {}, respectively).",
+                        lhs.bit_width(),
+                        rhs.bit_width()
+                    );
+                }
+                lhs.context.signal_arena.alloc(InternalSignal {
+                    context: lhs.context,
+                    module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::SimpleBinOp {
+                        lhs,
+                        rhs,
+                        op: SimpleBinOp::BitAnd,
+                        bit_width: lhs.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: IntoSignal<'a>> BitOr<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` whose bits represent the bitwise `|` of each of the bits of the original two `Signal`s.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.low();
+            /// let rhs = m.high();
+            /// let single_bitor = lhs | rhs;
+            ///
             /// let lhs = m.input("in1", 3);
             /// let rhs = m.input("in2", 3);
-            /// let multi_bitand = lhs & rhs;
+            /// let multi_bitor = lhs | rhs;
+            /// ```
+            #[track_caller]
+            fn bitor(self, rhs: S) -> Self::Output {
+                let lhs = self.internal_signal();
+                let rhs = rhs.into_signal(lhs).internal_signal();
+                if !ptr::eq(lhs.module, rhs.module) {
+                    panic!("Attempted to combine signals from different modules.");
+                }
+                if lhs.bit_width() != rhs.bit_width() {
+                    panic!(
+                        "Signals have different bit widths ({} and {}, respectively).",
+                        lhs.bit_width(),
+                        rhs.bit_width()
+                    );
+                }
+                lhs.context.signal_arena.alloc(InternalSignal {
+                    context: lhs.context,
+                    module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::SimpleBinOp {
+                        lhs,
+                        rhs,
+                        op: SimpleBinOp::BitOr,
+                        bit_width: lhs.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: IntoSignal<'a>> BitXor<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` whose bits represent the bitwise `^` of each of the bits of the original two `Signal`s.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.low();
+            /// let rhs = m.high();
+            /// let single_bitxor = lhs ^ rhs;
+            ///
+            /// let lhs = m.input("in1", 3);
+            /// let rhs = m.input("in2", 3);
+            /// let multi_bitxor = lhs ^ rhs;
+            /// ```
+            #[track_caller]
+            fn bitxor(self, rhs: S) -> Self::Output {
+                let lhs = self.internal_signal();
+                let rhs = rhs.into_signal(lhs).internal_signal();
+                if !ptr::eq(lhs.module, rhs.module) {
+                    panic!("Attempted to combine signals from different modules.");
+                }
+                if lhs.bit_width() != rhs.bit_width() {
+                    panic!(
+                        "Signals have different bit widths ({} and {}, respectively).",
+                        lhs.bit_width(),
+                        rhs.bit_width()
+                    );
+                }
+                lhs.context.signal_arena.alloc(InternalSignal {
+                    context: lhs.context,
+                    module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::SimpleBinOp {
+                        lhs,
+                        rhs,
+                        op: SimpleBinOp::BitXor,
+                        bit_width: lhs.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: IntoSignal<'a>> Div<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` that represents the unsigned quotient of the original two `Signal`s.
+            ///
+            /// The quotient is rounded towards zero and has the same `bit_width` as the original `Signal`s. If `rhs` is `0`, every bit of the quotient is set.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.lit(7u32, 8);
+            /// let rhs = m.lit(2u32, 8);
+            /// let quotient = lhs / rhs; // Equivalent to m.lit(3u32, 8)
+            ///
+            /// let quotient_by_zero = lhs / m.lit(0u32, 8); // Equivalent to m.lit(0xffu32, 8)
+            /// ```
+            #[track_caller]
+            fn div(self, rhs: S) -> Self::Output {
+                let lhs = self.internal_signal();
+                let rhs = rhs.into_signal(lhs).internal_signal();
+                if !ptr::eq(lhs.module, rhs.module) {
+                    panic!("Attempted to combine signals from different modules.");
+                }
+                if lhs.bit_width() != rhs.bit_width() {
+                    panic!(
+                        "Signals have different bit widths ({} and {}, respectively).",
+                        lhs.bit_width(),
+                        rhs.bit_width()
+                    );
+                }
+                lhs.context.signal_arena.alloc(InternalSignal {
+                    context: lhs.context,
+                    module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::DivisionBinOp {
+                        lhs,
+                        rhs,
+                        op: DivisionBinOp::Div,
+                        bit_width: lhs.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: IntoSignal<'a>> Mul<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` that represents the unsigned product of the original two `Signal`s.
+            ///
+            /// The product's `bit_width` is equal to `self.bit_width() + rhs.bit_width()`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if `self.bit_width() + rhs.bit_width()` is greater than [`MAX_SIGNAL_BIT_WIDTH`].
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.lit(4u32, 3);
+            /// let rhs = m.lit(5u32, 4);
+            /// let sum = lhs * rhs; // Equivalent to m.lit(20u32, 7)
+            /// ```
+            #[track_caller]
+            fn mul(self, rhs: S) -> Self::Output {
+                let lhs = self.internal_signal();
+                let rhs = rhs.into_signal(lhs).internal_signal();
+                if !ptr::eq(lhs.module, rhs.module) {
+                    panic!("Attempted to combine signals from different modules.");
+                }
+                let bit_width = lhs.bit_width() + rhs.bit_width();
+                if bit_width > MAX_SIGNAL_BIT_WIDTH {
+                    panic!("Attempted to multiply a {}-bit with a {}-bit signal, but this would result in a bit width of {}, which is greater than the maximum signal bit width of {} bit(s).", self.bit_width(), rhs.bit_width(), bit_width, MAX_SIGNAL_BIT_WIDTH);
+                }
+                lhs.context.signal_arena.alloc(InternalSignal {
+                    context: lhs.context,
+                    module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::Mul {
+                        lhs,
+                        rhs,
+                        bit_width,
+                    },
+                })
+            }
+        }
+
+        impl<'a> Not for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Produces a new `Signal` whose bits represent the bitwise `!` of each of the bits of the original `Signal`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let input1 = m.input("input1", 1);
+            /// let single_not = !input1;
+            ///
+            /// let input2 = m.input("input2", 6);
+            /// let multi_not = !input2;
+            /// ```
+            #[track_caller]
+            fn not(self) -> Self::Output {
+                let s = self.internal_signal();
+                s.context.signal_arena.alloc(InternalSignal {
+                    context: s.context,
+                    module: s.module,
+                    location: Location::caller(),
+
+                    data: SignalData::UnOp {
+                        source: s,
+                        op: UnOp::Not,
+                        bit_width: self.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: IntoSignal<'a>> Rem<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` that represents the unsigned remainder of the original two `Signal`s.
+            ///
+            /// The remainder has the same `bit_width` as the original `Signal`s. If `rhs` is `0`, the remainder is equal to `lhs`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.lit(7u32, 8);
+            /// let rhs = m.lit(2u32, 8);
+            /// let remainder = lhs % rhs; // Equivalent to m.lit(1u32, 8)
+            ///
+            /// let remainder_by_zero = lhs % m.lit(0u32, 8); // Equivalent to m.lit(7u32, 8)
+            /// ```
+            #[track_caller]
+            fn rem(self, rhs: S) -> Self::Output {
+                let lhs = self.internal_signal();
+                let rhs = rhs.into_signal(lhs).internal_signal();
+                if !ptr::eq(lhs.module, rhs.module) {
+                    panic!("Attempted to combine signals from different modules.");
+                }
+                if lhs.bit_width() != rhs.bit_width() {
+                    panic!(
+                        "Signals have different bit widths ({} and {}, respectively).",
+                        lhs.bit_width(),
+                        rhs.bit_width()
+                    );
+                }
+                lhs.context.signal_arena.alloc(InternalSignal {
+                    context: lhs.context,
+                    module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::DivisionBinOp {
+                        lhs,
+                        rhs,
+                        op: DivisionBinOp::Rem,
+                        bit_width: lhs.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: IntoSignal<'a>> Shl<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` that represents `self` logically shifted left by `rhs` bits.
+            ///
+            /// The result is truncated to `self`'s `bit_width`. If `rhs` specifies a value that's greater than or equal to `self`'s `bit_width`, the resulting value will be zero.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s.
+            ///
+            /// # Examples
+            ///
             /// ```
-            fn bitand(self, rhs: S) -> Self::Output {
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.lit(3u32, 32);
+            /// let rhs = m.lit(2u32, 2);
+            /// let shifted = lhs << rhs; // Equivalent to m.lit(12u32, 32)
+            /// ```
+            #[track_caller]
+            fn shl(self, rhs: S) -> Self::Output {
+                let lhs = self.internal_signal();
+                let rhs = rhs.into_shift_amount(lhs).internal_signal();
+                if !ptr::eq(lhs.module, rhs.module) {
+                    panic!("Attempted to combine signals from different modules.");
+                }
+                lhs.context.signal_arena.alloc(InternalSignal {
+                    context: lhs.context,
+                    module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::ShiftBinOp {
+                        lhs,
+                        rhs,
+                        op: ShiftBinOp::Shl,
+                        bit_width: lhs.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: IntoSignal<'a>> Shr<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` that represents `self` logically shifted right by `rhs` bits.
+            ///
+            /// The result is truncated to `self`'s `bit_width`. If `rhs` specifies a value that's greater than or equal to `self`'s `bit_width`, the resulting value will be zero.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.lit(12u32, 32);
+            /// let rhs = m.lit(2u32, 2);
+            /// let shifted = lhs >> rhs; // Equivalent to m.lit(3u32, 32)
+            /// ```
+            #[track_caller]
+            fn shr(self, rhs: S) -> Self::Output {
+                let lhs = self.internal_signal();
+                let rhs = rhs.into_shift_amount(lhs).internal_signal();
+                if !ptr::eq(lhs.module, rhs.module) {
+                    panic!("Attempted to combine signals from different modules.");
+                }
+                lhs.context.signal_arena.alloc(InternalSignal {
+                    context: lhs.context,
+                    module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::ShiftBinOp {
+                        lhs,
+                        rhs,
+                        op: ShiftBinOp::Shr,
+                        bit_width: lhs.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: IntoSignal<'a>> Sub<S> for &'a $t {
+            type Output = &'a dyn Signal<'a>;
+
+            /// Combines two `Signal`s, producing a new `Signal` that represents the difference of the original two `Signal`s.
+            ///
+            /// The difference is truncated to the `Signal`'s `bit_width`. If a borrow bit is desired, use [`sub_borrow`](Signal::sub_borrow). For a difference that saturates instead of wrapping, use [`sub_sat`](Signal::sub_sat) or [`sub_sat_signed`](Signal::sub_sat_signed).
+            ///
+            /// # Panics
+            ///
+            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use kaze::*;
+            ///
+            /// let c = Context::new();
+            ///
+            /// let m = c.module("m", "MyModule");
+            ///
+            /// let lhs = m.lit(3u32, 32);
+            /// let rhs = m.lit(2u32, 32);
+            /// let difference = lhs - rhs; // Equivalent to m.lit(1u32, 32)
+            /// ```
+            #[track_caller]
+            fn sub(self, rhs: S) -> Self::Output {
                 let lhs = self.internal_signal();
-                let rhs = rhs.into().internal_signal();
+                let rhs = rhs.into_signal(lhs).internal_signal();
                 if !ptr::eq(lhs.module, rhs.module) {
                     panic!("Attempted to combine signals from different modules.");
                 }
@@ -951,524 +2459,764 @@ macro_rules! impl_extensions {
                 lhs.context.signal_arena.alloc(InternalSignal {
                     context: lhs.context,
                     module: lhs.module,
+                    location: Location::caller(),
+
+                    data: SignalData::AdditiveBinOp {
+                        lhs,
+                        rhs,
+                        op: AdditiveBinOp::Sub,
+                        bit_width: self.bit_width(),
+                    },
+                })
+            }
+        }
+
+        impl<'a, S: Into<String>> RegNext<'a, S> for &'a $t {
+            #[track_caller]
+            fn reg_next(self, name: S) -> &'a dyn Signal<'a> {
+                let s = self.internal_signal();
+                let reg = s.module.reg(name, s.bit_width());
+                reg.drive_next(s);
+                reg
+            }
+        }
+
+        impl<'a, S: Into<String>, C: Into<Constant>> RegNextWithDefault<'a, S, C> for &'a $t {
+            #[track_caller]
+            fn reg_next_with_default(
+                self,
+                name: S,
+                default_value: C,
+            ) -> &'a dyn Signal<'a> {
+                let s = self.internal_signal();
+                let reg = s.module.reg(name, s.bit_width());
+                reg.default_value(default_value);
+                reg.drive_next(s);
+                reg
+            }
+        }
+    )*);
+}
+
+// TODO: Move extension stuff?
+use super::module::{Input, Output};
+use super::register::Register;
+impl_extensions! { dyn Signal<'a>, Input<'a>, Output<'a>, Register<'a> }
+
+impl<'a, T: GetInternalSignal<'a>> Signal<'a> for T {}
+
+impl<'a, T: GetInternalSignal<'a>> From<&'a T> for &'a dyn Signal<'a> {
+    fn from(s: &'a T) -> &'a dyn Signal<'a> {
+        s.internal_signal()
+    }
+}
+
+/// A value that can be used as the right-hand side of a [`Signal`]'s operators (such as `+`, `&` and `<<`) and comparison methods (such as [`eq`](SignalCompare::eq) and [`lt`](SignalCompare::lt)).
+///
+/// This is implemented for every [`Signal`] as well as for Rust's unsigned integer types and `i32` (the type of unsuffixed integer literals). Integer values are converted to literals in the left-hand side's [`Module`](crate::Module) with the left-hand side's bit width, as if by [`Module::lit`](crate::Module::lit); negative `i32` values are represented in two's complement.
+/// Shift and rotation amounts (the right-hand side of `<<`, `>>`, [`shr_arithmetic`](SignalCompare::shr_arithmetic), [`rotate_left`](SignalCompare::rotate_left) and [`rotate_right`](SignalCompare::rotate_right)) are the exception: integer values are given the smallest bit width that can represent them instead (see [`into_shift_amount`](Self::into_shift_amount)).
+///
+/// # Panics
+///
+/// Converting an integer value panics if the value doesn't fit in the left-hand side's bit width, or if it's a negative shift or rotation amount.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let a = m.input("a", 8);
+/// let is_three = a.eq(3u32); // Equivalent to a.eq(m.lit(3u32, 8))
+/// let incremented = a + 1; // Equivalent to a + m.lit(1u32, 8)
+/// let is_negative = a.lt_signed(0); // Equivalent to a.lt_signed(m.lit(0u32, 8))
+/// let is_minus_one = a.eq(-1); // Equivalent to a.eq(m.lit(0xffu32, 8))
+/// let shifted = a << 2; // Equivalent to a << m.lit(2u32, 2)
+/// let rotated = a.rotate_left(9); // Equivalent to a.rotate_left(m.lit(9u32, 4))
+/// ```
+pub trait IntoSignal<'a> {
+    /// Converts `self` into a [`Signal`], using `lhs` (the other operand) to determine the [`Module`](crate::Module) and bit width of integer values.
+    #[track_caller]
+    fn into_signal(self, lhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a>;
+
+    /// Converts `self` into a [`Signal`] that represents a shift or rotation amount for `lhs` (the value being shifted or rotated), using `lhs` to determine the [`Module`](crate::Module) of integer values.
+    ///
+    /// Unlike [`into_signal`](Self::into_signal), integer values are given the smallest bit width that can represent them (but at least 1 bit) rather than `lhs`'s bit width, since shift and rotation amounts are independent of the width of the value being shifted or rotated.
+    #[track_caller]
+    fn into_shift_amount(self, lhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a>;
+}
+
+impl<'a, T: Into<&'a dyn Signal<'a>>> IntoSignal<'a> for T {
+    fn into_signal(self, _lhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        self.into()
+    }
+
+    fn into_shift_amount(self, _lhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        self.into()
+    }
+}
+
+macro_rules! impl_into_signal_for_unsigned {
+    ($($t:ty),*) => ($(
+        impl<'a> IntoSignal<'a> for $t {
+            fn into_signal(self, lhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+                let lhs = lhs.internal_signal();
+                lhs.module.lit(self, lhs.bit_width())
+            }
+
+            fn into_shift_amount(self, lhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+                let lhs = lhs.internal_signal();
+                lhs.module.lit(self, (<$t>::BITS - self.leading_zeros()).max(1))
+            }
+        }
+    )*);
+}
+
+impl_into_signal_for_unsigned! { u8, u16, u32, u64, u128 }
+
+impl<'a> IntoSignal<'a> for i32 {
+    fn into_signal(self, lhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        let lhs = lhs.internal_signal();
+        let bit_width = lhs.bit_width();
+        if self >= 0 {
+            return lhs.module.lit(self as u32, bit_width);
+        }
+        let required_bits = 33 - self.leading_ones();
+        if required_bits > bit_width {
+            panic!("Cannot fit the specified value '{}' into the specified bit width '{}'. The value '{}' requires a bit width of at least {} bit(s).", self, bit_width, self, required_bits);
+        }
+        let mut words = vec![!0; bit_width.div_ceil(64) as usize];
+        words[0] = self as i64 as u64;
+        if bit_width % 64 != 0 {
+            *words.last_mut().unwrap() &= (1 << (bit_width % 64)) - 1;
+        }
+        lhs.module.lit(Constant::Wide(words), bit_width)
+    }
+
+    fn into_shift_amount(self, lhs: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        if self < 0 {
+            panic!("Cannot shift or rotate by a negative amount ({}).", self);
+        }
+        (self as u32).into_shift_amount(lhs)
+    }
+}
+
+pub trait RegNext<'a, S: Into<String>> {
+    /// Creates a [`Signal`] that represents the same value as this [`Signal`], but delayed by one cycle.
+    ///
+    /// This is achieved by creating a new [`Register`] called `name`, and specifying this [`Signal`] as the next value for the [`Register`]. Note that no default value is provided for this [`Register`], so the returned [`Signal`]'s value is undefined until the first clock edge, and its value is not affected by its [`Module`]'s implicit reset. If a default value is desired, use [`reg_next_with_default`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is already used by another item in this [`Signal`]'s [`Module`], is reserved for names in generated code (see [`Error::ReservedName`](crate::Error::ReservedName)), or is a Rust or Verilog keyword.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(true, 1);
+    /// let a_delayed = a.reg_next("a_delayed");
+    /// ```
+    ///
+    /// [`reg_next_with_default`]: Self::reg_next_with_default
+    #[track_caller]
+    fn reg_next(self, name: S) -> &'a dyn Signal<'a>;
+}
+
+pub trait RegNextWithDefault<'a, S: Into<String>, C: Into<Constant>> {
+    /// Creates a [`Signal`] that represents the same value as this [`Signal`], but delayed by one cycle, except when this [`Signal`]'s [`Module`]'s implicit reset is asserted, at which point it will represent `default_value`.
+    ///
+    /// This is achieved by creating a new [`Register`] called `name`, specifying `default_value` as its default value, and specifying this [`Signal`] as the next value for the [`Register`]. If a default value is not desired, use [`reg_next`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is already used by another item in this [`Signal`]'s [`Module`], is reserved for names in generated code (see [`Error::ReservedName`](crate::Error::ReservedName)), or is a Rust or Verilog keyword.
+    /// Also panics if the specified `value` doesn't fit into this [`Signal`]'s bit width.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.lit(true, 1);
+    /// let a_delayed = a.reg_next_with_default("a_delayed", false);
+    /// ```
+    ///
+    /// [`reg_next`]: Self::reg_next
+    #[track_caller]
+    fn reg_next_with_default(self, name: S, default_value: C) -> &'a dyn Signal<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::*;
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to take bit index 3 from a signal with a width of 3 bits. Bit indices must be in the range [0, 2] for a signal with a width of 3 bits."
+    )]
+    fn bit_index_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 3);
+
+        let _ = i.bit(0); // OK
+        let _ = i.bit(1); // OK
+        let _ = i.bit(2); // OK
+
+        let _ = i.bit(3); // Panic, `index` too high
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot specify a range of bits where the lower bound is greater than or equal to the number of bits in the source signal. The bounds must be in the range [0, 2] for a signal with a width of 3 bits, but a lower bound of 3 was given."
+    )]
+    fn bits_range_low_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 3);
+
+        // Panic
+        let _ = i.bits(4, 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot specify a range of bits where the upper bound is greater than or equal to the number of bits in the source signal. The bounds must be in the range [0, 2] for a signal with a width of 3 bits, but an upper bound of 3 was given."
+    )]
+    fn bits_range_high_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 3);
+
+        // Panic
+        let _ = i.bits(3, 2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot specify a range of bits where the lower bound is greater than the upper bound."
+    )]
+    fn bits_range_low_gt_high_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 3);
+
+        // Panic
+        let _ = i.bits(0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn bits_dyn_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 8);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.input("b", 3);
+
+        // Panic
+        let _ = i1.bits_dyn(i2, 4);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to take 0 bit(s) from a signal with a width of 8 bits. The number of bits must be in the range [1, 8] for a signal with a width of 8 bits."
+    )]
+    fn bits_dyn_width_zero_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 8);
+        let i2 = m.input("b", 3);
+
+        // Panic
+        let _ = i1.bits_dyn(i2, 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to take 9 bit(s) from a signal with a width of 8 bits. The number of bits must be in the range [1, 8] for a signal with a width of 8 bits."
+    )]
+    fn bits_dyn_width_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 8);
+        let i2 = m.input("b", 3);
+
+        // Panic
+        let _ = i1.bits_dyn(i2, 9);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to repeat a 1-bit signal 0 times, but this would result in a bit width of 0, which is less than the minimal signal bit width of 1 bit(s)."
+    )]
+    fn repeat_count_zero_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 1);
+
+        // Panic
+        let _ = i.repeat(0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to repeat a 1-bit signal 65537 times, but this would result in a bit width of 65537, which is greater than the maximum signal bit width of 65536 bit(s)."
+    )]
+    fn repeat_count_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 1);
+
+        // Panic
+        let _ = i.repeat(65537);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn concat_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
+
+        // Panic
+        let _ = i1.concat(i2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to concatenate signals with 65536 bit(s) and 1 bit(s) respectively, but this would result in a bit width of 65537, which is greater than the maximum signal bit width of 65536 bit(s)."
+    )]
+    fn concat_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i1 = m.input("i1", 65536);
+        let i2 = m.input("i2", 1);
+
+        // Panic
+        let _ = i1.concat(i2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to zero-extend a 8-bit signal to 7 bit(s), which is less than the signal's bit width."
+    )]
+    fn zext_narrower_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 8);
+
+        // Panic
+        let _ = i.zext(7);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to zero-extend a 8-bit signal to 65537 bit(s), which is greater than the maximum signal bit width of 65536 bit(s)."
+    )]
+    fn zext_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 8);
+
+        // Panic
+        let _ = i.zext(65537);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to sign-extend a 8-bit signal to 7 bit(s), which is less than the signal's bit width."
+    )]
+    fn sext_narrower_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 8);
+
+        // Panic
+        let _ = i.sext(7);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to sign-extend a 8-bit signal to 65537 bit(s), which is greater than the maximum signal bit width of 65536 bit(s)."
+    )]
+    fn sext_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 8);
+
+        // Panic
+        let _ = i.sext(65537);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to resize a 8-bit signal to 0 bit(s), which is less than the minimal signal bit width of 1 bit(s)."
+    )]
+    fn resize_zero_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 8);
+
+        // Panic
+        let _ = i.resize(0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to resize a 8-bit signal to 65537 bit(s), which is greater than the maximum signal bit width of 65536 bit(s)."
+    )]
+    fn resize_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 8);
+
+        // Panic
+        let _ = i.resize(65537);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to one-hot decode a 17-bit signal, but this would result in a bit width of 2^17, which is greater than the maximum signal bit width of 65536 bit(s)."
+    )]
+    fn one_hot_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 17);
+
+        // Panic
+        let _ = i.one_hot();
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn eq_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
+
+        // Panic
+        let _ = i1.eq(i2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn eq_incompatible_bit_widths_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
+
+        // Panic
+        let _ = i1.eq(i2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn ne_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
 
-                    data: SignalData::SimpleBinOp {
-                        lhs,
-                        rhs,
-                        op: SimpleBinOp::BitAnd,
-                        bit_width: lhs.bit_width(),
-                    },
-                })
-            }
-        }
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
-        impl<'a, S: Into<&'a dyn Signal<'a>>> BitOr<S> for &'a $t {
-            type Output = &'a dyn Signal<'a>;
+        // Panic
+        let _ = i1.ne(i2);
+    }
 
-            /// Combines two `Signal`s, producing a new `Signal` whose bits represent the bitwise `|` of each of the bits of the original two `Signal`s.
-            ///
-            /// # Panics
-            ///
-            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
-            ///
-            /// # Examples
-            ///
-            /// ```
-            /// use kaze::*;
-            ///
-            /// let c = Context::new();
-            ///
-            /// let m = c.module("m", "MyModule");
-            ///
-            /// let lhs = m.low();
-            /// let rhs = m.high();
-            /// let single_bitor = lhs | rhs;
-            ///
-            /// let lhs = m.input("in1", 3);
-            /// let rhs = m.input("in2", 3);
-            /// let multi_bitor = lhs | rhs;
-            /// ```
-            fn bitor(self, rhs: S) -> Self::Output {
-                let lhs = self.internal_signal();
-                let rhs = rhs.into().internal_signal();
-                if !ptr::eq(lhs.module, rhs.module) {
-                    panic!("Attempted to combine signals from different modules.");
-                }
-                if lhs.bit_width() != rhs.bit_width() {
-                    panic!(
-                        "Signals have different bit widths ({} and {}, respectively).",
-                        lhs.bit_width(),
-                        rhs.bit_width()
-                    );
-                }
-                lhs.context.signal_arena.alloc(InternalSignal {
-                    context: lhs.context,
-                    module: lhs.module,
+    #[test]
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn ne_incompatible_bit_widths_error() {
+        let c = Context::new();
 
-                    data: SignalData::SimpleBinOp {
-                        lhs,
-                        rhs,
-                        op: SimpleBinOp::BitOr,
-                        bit_width: lhs.bit_width(),
-                    },
-                })
-            }
-        }
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
-        impl<'a, S: Into<&'a dyn Signal<'a>>> BitXor<S> for &'a $t {
-            type Output = &'a dyn Signal<'a>;
+        // Panic
+        let _ = i1.ne(i2);
+    }
 
-            /// Combines two `Signal`s, producing a new `Signal` whose bits represent the bitwise `^` of each of the bits of the original two `Signal`s.
-            ///
-            /// # Panics
-            ///
-            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
-            ///
-            /// # Examples
-            ///
-            /// ```
-            /// use kaze::*;
-            ///
-            /// let c = Context::new();
-            ///
-            /// let m = c.module("m", "MyModule");
-            ///
-            /// let lhs = m.low();
-            /// let rhs = m.high();
-            /// let single_bitxor = lhs ^ rhs;
-            ///
-            /// let lhs = m.input("in1", 3);
-            /// let rhs = m.input("in2", 3);
-            /// let multi_bitxor = lhs ^ rhs;
-            /// ```
-            fn bitxor(self, rhs: S) -> Self::Output {
-                let lhs = self.internal_signal();
-                let rhs = rhs.into().internal_signal();
-                if !ptr::eq(lhs.module, rhs.module) {
-                    panic!("Attempted to combine signals from different modules.");
-                }
-                if lhs.bit_width() != rhs.bit_width() {
-                    panic!(
-                        "Signals have different bit widths ({} and {}, respectively).",
-                        lhs.bit_width(),
-                        rhs.bit_width()
-                    );
-                }
-                lhs.context.signal_arena.alloc(InternalSignal {
-                    context: lhs.context,
-                    module: lhs.module,
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn lt_separate_module_error() {
+        let c = Context::new();
 
-                    data: SignalData::SimpleBinOp {
-                        lhs,
-                        rhs,
-                        op: SimpleBinOp::BitXor,
-                        bit_width: lhs.bit_width(),
-                    },
-                })
-            }
-        }
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
 
-        impl<'a, S: Into<&'a dyn Signal<'a>>> Mul<S> for &'a $t {
-            type Output = &'a dyn Signal<'a>;
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
-            /// Combines two `Signal`s, producing a new `Signal` that represents the unsigned product of the original two `Signal`s.
-            ///
-            /// The product's `bit_width` is equal to `self.bit_width() + rhs.bit_width()`.
-            ///
-            /// # Panics
-            ///
-            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if `self.bit_width() + rhs.bit_width()` is greater than [`MAX_SIGNAL_BIT_WIDTH`].
-            ///
-            /// # Examples
-            ///
-            /// ```
-            /// use kaze::*;
-            ///
-            /// let c = Context::new();
-            ///
-            /// let m = c.module("m", "MyModule");
-            ///
-            /// let lhs = m.lit(4u32, 3);
-            /// let rhs = m.lit(5u32, 4);
-            /// let sum = lhs * rhs; // Equivalent to m.lit(20u32, 7)
-            /// ```
-            fn mul(self, rhs: S) -> Self::Output {
-                let lhs = self.internal_signal();
-                let rhs = rhs.into().internal_signal();
-                if !ptr::eq(lhs.module, rhs.module) {
-                    panic!("Attempted to combine signals from different modules.");
-                }
-                let bit_width = lhs.bit_width() + rhs.bit_width();
-                if bit_width > MAX_SIGNAL_BIT_WIDTH {
-                    panic!("Attempted to multiply a {}-bit with a {}-bit signal, but this would result in a bit width of {}, which is greater than the maximum signal bit width of {} bit(s).", self.bit_width(), rhs.bit_width(), bit_width, MAX_SIGNAL_BIT_WIDTH);
-                }
-                lhs.context.signal_arena.alloc(InternalSignal {
-                    context: lhs.context,
-                    module: lhs.module,
+        // Panic
+        let _ = i1.lt(i2);
+    }
 
-                    data: SignalData::Mul {
-                        lhs,
-                        rhs,
-                        bit_width,
-                    },
-                })
-            }
-        }
+    #[test]
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn lt_incompatible_bit_widths_error() {
+        let c = Context::new();
 
-        impl<'a> Not for &'a $t {
-            type Output = &'a dyn Signal<'a>;
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
-            /// Produces a new `Signal` whose bits represent the bitwise `!` of each of the bits of the original `Signal`.
-            ///
-            /// # Examples
-            ///
-            /// ```
-            /// use kaze::*;
-            ///
-            /// let c = Context::new();
-            ///
-            /// let m = c.module("m", "MyModule");
-            ///
-            /// let input1 = m.input("input1", 1);
-            /// let single_not = !input1;
-            ///
-            /// let input2 = m.input("input2", 6);
-            /// let multi_not = !input2;
-            /// ```
-            fn not(self) -> Self::Output {
-                let s = self.internal_signal();
-                s.context.signal_arena.alloc(InternalSignal {
-                    context: s.context,
-                    module: s.module,
+        // Panic
+        let _ = i1.lt(i2);
+    }
 
-                    data: SignalData::UnOp {
-                        source: s,
-                        op: UnOp::Not,
-                        bit_width: self.bit_width(),
-                    },
-                })
-            }
-        }
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn le_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
+
+        // Panic
+        let _ = i1.le(i2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn le_incompatible_bit_widths_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
+
+        // Panic
+        let _ = i1.le(i2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn gt_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
+
+        // Panic
+        let _ = i1.gt(i2);
+    }
 
-        impl<'a, S: Into<&'a dyn Signal<'a>>> Shl<S> for &'a $t {
-            type Output = &'a dyn Signal<'a>;
+    #[test]
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn gt_incompatible_bit_widths_error() {
+        let c = Context::new();
 
-            /// Combines two `Signal`s, producing a new `Signal` that represents `self` logically shifted left by `rhs` bits.
-            ///
-            /// The result is truncated to `self`'s `bit_width`. If `rhs` specifies a value that's greater than or equal to `self`'s `bit_width`, the resulting value will be zero.
-            ///
-            /// # Panics
-            ///
-            /// Panics if `lhs` and `rhs` belong to different [`Module`]s.
-            ///
-            /// # Examples
-            ///
-            /// ```
-            /// use kaze::*;
-            ///
-            /// let c = Context::new();
-            ///
-            /// let m = c.module("m", "MyModule");
-            ///
-            /// let lhs = m.lit(3u32, 32);
-            /// let rhs = m.lit(2u32, 2);
-            /// let shifted = lhs << rhs; // Equivalent to m.lit(12u32, 32)
-            /// ```
-            fn shl(self, rhs: S) -> Self::Output {
-                let lhs = self.internal_signal();
-                let rhs = rhs.into().internal_signal();
-                if !ptr::eq(lhs.module, rhs.module) {
-                    panic!("Attempted to combine signals from different modules.");
-                }
-                lhs.context.signal_arena.alloc(InternalSignal {
-                    context: lhs.context,
-                    module: lhs.module,
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
-                    data: SignalData::ShiftBinOp {
-                        lhs,
-                        rhs,
-                        op: ShiftBinOp::Shl,
-                        bit_width: lhs.bit_width(),
-                    },
-                })
-            }
-        }
+        // Panic
+        let _ = i1.gt(i2);
+    }
 
-        impl<'a, S: Into<&'a dyn Signal<'a>>> Shr<S> for &'a $t {
-            type Output = &'a dyn Signal<'a>;
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn ge_separate_module_error() {
+        let c = Context::new();
 
-            /// Combines two `Signal`s, producing a new `Signal` that represents `self` logically shifted right by `rhs` bits.
-            ///
-            /// The result is truncated to `self`'s `bit_width`. If `rhs` specifies a value that's greater than or equal to `self`'s `bit_width`, the resulting value will be zero.
-            ///
-            /// # Panics
-            ///
-            /// Panics if `lhs` and `rhs` belong to different [`Module`]s.
-            ///
-            /// # Examples
-            ///
-            /// ```
-            /// use kaze::*;
-            ///
-            /// let c = Context::new();
-            ///
-            /// let m = c.module("m", "MyModule");
-            ///
-            /// let lhs = m.lit(12u32, 32);
-            /// let rhs = m.lit(2u32, 2);
-            /// let shifted = lhs >> rhs; // Equivalent to m.lit(3u32, 32)
-            /// ```
-            fn shr(self, rhs: S) -> Self::Output {
-                let lhs = self.internal_signal();
-                let rhs = rhs.into().internal_signal();
-                if !ptr::eq(lhs.module, rhs.module) {
-                    panic!("Attempted to combine signals from different modules.");
-                }
-                lhs.context.signal_arena.alloc(InternalSignal {
-                    context: lhs.context,
-                    module: lhs.module,
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
 
-                    data: SignalData::ShiftBinOp {
-                        lhs,
-                        rhs,
-                        op: ShiftBinOp::Shr,
-                        bit_width: lhs.bit_width(),
-                    },
-                })
-            }
-        }
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
-        impl<'a, S: Into<&'a dyn Signal<'a>>> Sub<S> for &'a $t {
-            type Output = &'a dyn Signal<'a>;
+        // Panic
+        let _ = i1.ge(i2);
+    }
 
-            /// Combines two `Signal`s, producing a new `Signal` that represents the difference of the original two `Signal`s.
-            ///
-            /// The difference is truncated to the `Signal`'s `bit_width`.
-            ///
-            /// # Panics
-            ///
-            /// Panics if `lhs` and `rhs` belong to different [`Module`]s, or if the bit widths of `lhs` and `rhs` aren't equal.
-            ///
-            /// # Examples
-            ///
-            /// ```
-            /// use kaze::*;
-            ///
-            /// let c = Context::new();
-            ///
-            /// let m = c.module("m", "MyModule");
-            ///
-            /// let lhs = m.lit(3u32, 32);
-            /// let rhs = m.lit(2u32, 32);
-            /// let difference = lhs - rhs; // Equivalent to m.lit(1u32, 32)
-            /// ```
-            fn sub(self, rhs: S) -> Self::Output {
-                let lhs = self.internal_signal();
-                let rhs = rhs.into().internal_signal();
-                if !ptr::eq(lhs.module, rhs.module) {
-                    panic!("Attempted to combine signals from different modules.");
-                }
-                if lhs.bit_width() != rhs.bit_width() {
-                    panic!(
-                        "Signals have different bit widths ({} and {}, respectively).",
-                        lhs.bit_width(),
-                        rhs.bit_width()
-                    );
-                }
-                lhs.context.signal_arena.alloc(InternalSignal {
-                    context: lhs.context,
-                    module: lhs.module,
+    #[test]
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn ge_incompatible_bit_widths_error() {
+        let c = Context::new();
 
-                    data: SignalData::AdditiveBinOp {
-                        lhs,
-                        rhs,
-                        op: AdditiveBinOp::Sub,
-                        bit_width: self.bit_width(),
-                    },
-                })
-            }
-        }
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
-        impl<'a, S: Into<String>> RegNext<'a, S> for &'a $t {
-            fn reg_next(self, name: S) -> &'a dyn Signal<'a> {
-                let s = self.internal_signal();
-                let reg = s.module.reg(name, s.bit_width());
-                reg.drive_next(s);
-                reg
-            }
-        }
+        // Panic
+        let _ = i1.ge(i2);
+    }
 
-        impl<'a, S: Into<String>, C: Into<Constant>> RegNextWithDefault<'a, S, C> for &'a $t {
-            fn reg_next_with_default(
-                self,
-                name: S,
-                default_value: C,
-            ) -> &'a dyn Signal<'a> {
-                let s = self.internal_signal();
-                let reg = s.module.reg(name, s.bit_width());
-                reg.default_value(default_value);
-                reg.drive_next(s);
-                reg
-            }
-        }
-    )*);
-}
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn lt_signed_separate_module_error() {
+        let c = Context::new();
 
-// TODO: Move extension stuff?
-use super::module::{Input, Output};
-use super::register::Register;
-impl_extensions! { dyn Signal<'a>, Input<'a>, Output<'a>, Register<'a> }
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
 
-impl<'a, T: GetInternalSignal<'a>> Signal<'a> for T {}
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
-impl<'a, T: GetInternalSignal<'a>> From<&'a T> for &'a dyn Signal<'a> {
-    fn from(s: &'a T) -> &'a dyn Signal<'a> {
-        s.internal_signal()
+        // Panic
+        let _ = i1.lt_signed(i2);
     }
-}
 
-pub trait RegNext<'a, S: Into<String>> {
-    /// Creates a [`Signal`] that represents the same value as this [`Signal`], but delayed by one cycle.
-    ///
-    /// This is achieved by creating a new [`Register`] called `name`, and specifying this [`Signal`] as the next value for the [`Register`]. Note that no default value is provided for this [`Register`], so the returned [`Signal`]'s value is undefined until the first clock edge, and its value is not affected by its [`Module`]'s implicit reset. If a default value is desired, use [`reg_next_with_default`] instead.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use kaze::*;
-    ///
-    /// let c = Context::new();
-    ///
-    /// let m = c.module("m", "MyModule");
-    ///
-    /// let a = m.lit(true, 1);
-    /// let a_delayed = a.reg_next("a_delayed");
-    /// ```
-    ///
-    /// [`reg_next_with_default`]: Self::reg_next_with_default
-    fn reg_next(self, name: S) -> &'a dyn Signal<'a>;
-}
+    #[test]
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn lt_signed_incompatible_bit_widths_error() {
+        let c = Context::new();
 
-pub trait RegNextWithDefault<'a, S: Into<String>, C: Into<Constant>> {
-    /// Creates a [`Signal`] that represents the same value as this [`Signal`], but delayed by one cycle, except when this [`Signal`]'s [`Module`]'s implicit reset is asserted, at which point it will represent `default_value`.
-    ///
-    /// This is achieved by creating a new [`Register`] called `name`, specifying `default_value` as its default value, and specifying this [`Signal`] as the next value for the [`Register`]. If a default value is not desired, use [`reg_next`] instead.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the specified `value` doesn't fit into this [`Signal`]'s bit width.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use kaze::*;
-    ///
-    /// let c = Context::new();
-    ///
-    /// let m = c.module("m", "MyModule");
-    ///
-    /// let a = m.lit(true, 1);
-    /// let a_delayed = a.reg_next_with_default("a_delayed", false);
-    /// ```
-    ///
-    /// [`reg_next`]: Self::reg_next
-    fn reg_next_with_default(self, name: S, default_value: C) -> &'a dyn Signal<'a>;
-}
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
-#[cfg(test)]
-mod tests {
-    use crate::graph::*;
+        // Panic
+        let _ = i1.lt_signed(i2);
+    }
 
     #[test]
-    #[should_panic(
-        expected = "Attempted to take bit index 3 from a signal with a width of 3 bits. Bit indices must be in the range [0, 2] for a signal with a width of 3 bits."
-    )]
-    fn bit_index_oob_error() {
+    #[should_panic(expected = "Cannot perform signed comparison of 1-bit signals.")]
+    fn lt_signed_bit_width_1_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i = m.input("i", 3);
+        let i1 = m.input("a", 1);
+        let i2 = m.input("b", 1);
 
-        let _ = i.bit(0); // OK
-        let _ = i.bit(1); // OK
-        let _ = i.bit(2); // OK
+        // Panic
+        let _ = i1.lt_signed(i2);
+    }
 
-        let _ = i.bit(3); // Panic, `index` too high
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn le_signed_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
+
+        // Panic
+        let _ = i1.le_signed(i2);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Cannot specify a range of bits where the lower bound is greater than or equal to the number of bits in the source signal. The bounds must be in the range [0, 2] for a signal with a width of 3 bits, but a lower bound of 3 was given."
-    )]
-    fn bits_range_low_oob_error() {
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn le_signed_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i = m.input("i", 3);
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i.bits(4, 3);
+        let _ = i1.le_signed(i2);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Cannot specify a range of bits where the upper bound is greater than or equal to the number of bits in the source signal. The bounds must be in the range [0, 2] for a signal with a width of 3 bits, but an upper bound of 3 was given."
-    )]
-    fn bits_range_high_oob_error() {
+    #[should_panic(expected = "Cannot perform signed comparison of 1-bit signals.")]
+    fn le_signed_bit_width_1_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i = m.input("i", 3);
+        let i1 = m.input("a", 1);
+        let i2 = m.input("b", 1);
 
         // Panic
-        let _ = i.bits(3, 2);
+        let _ = i1.le_signed(i2);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Cannot specify a range of bits where the lower bound is greater than the upper bound."
-    )]
-    fn bits_range_low_gt_high_error() {
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn gt_signed_separate_module_error() {
         let c = Context::new();
 
-        let m = c.module("a", "A");
-        let i = m.input("i", 3);
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
         // Panic
-        let _ = i.bits(0, 1);
+        let _ = i1.gt_signed(i2);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Attempted to repeat a 1-bit signal 0 times, but this would result in a bit width of 0, which is less than the minimal signal bit width of 1 bit(s)."
-    )]
-    fn repeat_count_zero_error() {
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn gt_signed_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i = m.input("i", 1);
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i.repeat(0);
+        let _ = i1.gt_signed(i2);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Attempted to repeat a 1-bit signal 129 times, but this would result in a bit width of 129, which is greater than the maximum signal bit width of 128 bit(s)."
-    )]
-    fn repeat_count_oob_error() {
+    #[should_panic(expected = "Cannot perform signed comparison of 1-bit signals.")]
+    fn gt_signed_bit_width_1_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i = m.input("i", 1);
+        let i1 = m.input("a", 1);
+        let i2 = m.input("b", 1);
 
         // Panic
-        let _ = i.repeat(129);
+        let _ = i1.gt_signed(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn concat_separate_module_error() {
+    fn ge_signed_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1478,27 +3226,38 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.concat(i2);
+        let _ = i1.ge_signed(i2);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Attempted to concatenate signals with 128 bit(s) and 1 bit(s) respectively, but this would result in a bit width of 129, which is greater than the maximum signal bit width of 128 bit(s)."
-    )]
-    fn concat_oob_error() {
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn ge_signed_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i1 = m.input("i1", 128);
-        let i2 = m.input("i2", 1);
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.concat(i2);
+        let _ = i1.ge_signed(i2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot perform signed comparison of 1-bit signals.")]
+    fn ge_signed_bit_width_1_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 1);
+        let i2 = m.input("b", 1);
+
+        // Panic
+        let _ = i1.ge_signed(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn eq_separate_module_error() {
+    fn shr_arithmetic_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1508,25 +3267,27 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.eq(i2);
+        let _ = i1.shr_arithmetic(i2);
     }
 
     #[test]
-    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn eq_incompatible_bit_widths_error() {
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn rotate_left_separate_module_error() {
         let c = Context::new();
 
-        let m = c.module("a", "A");
-        let i1 = m.input("a", 3);
-        let i2 = m.input("b", 5);
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
         // Panic
-        let _ = i1.eq(i2);
+        let _ = i1.rotate_left(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn ne_separate_module_error() {
+    fn rotate_right_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1536,25 +3297,120 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.ne(i2);
+        let _ = i1.rotate_right(i2);
     }
 
     #[test]
-    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn ne_incompatible_bit_widths_error() {
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn mul_signed_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
+
+        // Panic
+        let _ = i1.mul_signed(i2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to multiply a 65536-bit with a 1-bit signal, but this would result in a bit width of 65537, which is greater than the maximum signal bit width of 65536 bit(s)."
+    )]
+    fn mul_signed_oob_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i1 = m.input("a", 3);
-        let i2 = m.input("b", 5);
+        let i1 = m.input("a", 65536);
+        let i2 = m.input("b", 1);
 
         // Panic
-        let _ = i1.ne(i2);
+        let _ = i1.mul_signed(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn lt_separate_module_error() {
+    fn mux_cond_separate_module_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(false, 1);
+
+        let b = c.module("b", "B");
+        let l2 = b.lit(32u8, 8);
+        let l3 = b.lit(32u8, 8);
+
+        // Panic
+        let _ = l1.mux(l2, l3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn mux_when_true_separate_module_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(32u8, 8);
+
+        let b = c.module("b", "B");
+        let l2 = b.lit(true, 1);
+        let l3 = b.lit(32u8, 8);
+
+        // Panic
+        let _ = l2.mux(l1, l3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn mux_when_false_separate_module_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(32u8, 8);
+
+        let b = c.module("b", "B");
+        let l2 = b.lit(true, 1);
+        let l3 = b.lit(32u8, 8);
+
+        // Panic
+        let _ = l2.mux(l3, l1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Multiplexer conditionals can only be 1 bit wide.")]
+    fn mux_cond_bit_width_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(2u8, 2);
+        let l2 = a.lit(32u8, 8);
+        let l3 = a.lit(32u8, 8);
+
+        // Panic
+        let _ = l1.mux(l2, l3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot multiplex signals with different bit widths (3 and 5, respectively)."
+    )]
+    fn mux_true_false_bit_width_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let l1 = a.lit(false, 1);
+        let l2 = a.lit(3u8, 3);
+        let l3 = a.lit(3u8, 5);
+
+        // Panic
+        let _ = a.mux(l1, l2, l3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn add_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1564,12 +3420,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.lt(i2);
+        let _ = i1 + i2;
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn lt_incompatible_bit_widths_error() {
+    fn add_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -1577,12 +3433,12 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.lt(i2);
+        let _ = i1 + i2;
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn le_separate_module_error() {
+    fn bitand_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1592,12 +3448,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.le(i2);
+        let _ = i1 & i2;
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn le_incompatible_bit_widths_error() {
+    fn bitand_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -1605,12 +3461,12 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.le(i2);
+        let _ = i1 & i2;
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn gt_separate_module_error() {
+    fn bitor_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1620,12 +3476,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.gt(i2);
+        let _ = i1 | i2;
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn gt_incompatible_bit_widths_error() {
+    fn bitor_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -1633,12 +3489,12 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.gt(i2);
+        let _ = i1 | i2;
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn ge_separate_module_error() {
+    fn bitxor_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1648,12 +3504,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.ge(i2);
+        let _ = i1 ^ i2;
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn ge_incompatible_bit_widths_error() {
+    fn bitxor_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -1661,12 +3517,12 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.ge(i2);
+        let _ = i1 ^ i2;
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn lt_signed_separate_module_error() {
+    fn mul_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1676,38 +3532,42 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.lt_signed(i2);
+        let _ = i1 * i2;
     }
 
     #[test]
-    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn lt_signed_incompatible_bit_widths_error() {
+    #[should_panic(
+        expected = "Attempted to multiply a 65536-bit with a 1-bit signal, but this would result in a bit width of 65537, which is greater than the maximum signal bit width of 65536 bit(s)."
+    )]
+    fn mul_oob_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i1 = m.input("a", 3);
-        let i2 = m.input("b", 5);
+        let i1 = m.input("a", 65536);
+        let i2 = m.input("b", 1);
 
         // Panic
-        let _ = i1.lt_signed(i2);
+        let _ = i1 * i2;
     }
 
     #[test]
-    #[should_panic(expected = "Cannot perform signed comparison of 1-bit signals.")]
-    fn lt_signed_bit_width_1_error() {
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn shl_separate_module_error() {
         let c = Context::new();
 
-        let m = c.module("a", "A");
-        let i1 = m.input("a", 1);
-        let i2 = m.input("b", 1);
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
         // Panic
-        let _ = i1.lt_signed(i2);
+        let _ = i1 << i2;
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn le_signed_separate_module_error() {
+    fn shr_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1717,38 +3577,40 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.le_signed(i2);
+        let _ = i1 >> i2;
     }
 
     #[test]
-    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn le_signed_incompatible_bit_widths_error() {
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn sub_separate_module_error() {
         let c = Context::new();
 
-        let m = c.module("a", "A");
-        let i1 = m.input("a", 3);
-        let i2 = m.input("b", 5);
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
         // Panic
-        let _ = i1.le_signed(i2);
+        let _ = i1 - i2;
     }
 
     #[test]
-    #[should_panic(expected = "Cannot perform signed comparison of 1-bit signals.")]
-    fn le_signed_bit_width_1_error() {
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn sub_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i1 = m.input("a", 1);
-        let i2 = m.input("b", 1);
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.le_signed(i2);
+        let _ = i1 - i2;
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn gt_signed_separate_module_error() {
+    fn div_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1758,12 +3620,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.gt_signed(i2);
+        let _ = i1 / i2;
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn gt_signed_incompatible_bit_widths_error() {
+    fn div_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -1771,25 +3633,12 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.gt_signed(i2);
-    }
-
-    #[test]
-    #[should_panic(expected = "Cannot perform signed comparison of 1-bit signals.")]
-    fn gt_signed_bit_width_1_error() {
-        let c = Context::new();
-
-        let m = c.module("a", "A");
-        let i1 = m.input("a", 1);
-        let i2 = m.input("b", 1);
-
-        // Panic
-        let _ = i1.gt_signed(i2);
+        let _ = i1 / i2;
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn ge_signed_separate_module_error() {
+    fn rem_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1799,12 +3648,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.ge_signed(i2);
+        let _ = i1 % i2;
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn ge_signed_incompatible_bit_widths_error() {
+    fn rem_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -1812,40 +3661,40 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.ge_signed(i2);
+        let _ = i1 % i2;
     }
 
     #[test]
-    #[should_panic(expected = "Cannot perform signed comparison of 1-bit signals.")]
-    fn ge_signed_bit_width_1_error() {
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn div_signed_separate_module_error() {
         let c = Context::new();
 
-        let m = c.module("a", "A");
-        let i1 = m.input("a", 1);
-        let i2 = m.input("b", 1);
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
+
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
         // Panic
-        let _ = i1.ge_signed(i2);
+        let _ = i1.div_signed(i2);
     }
 
     #[test]
-    #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn shr_arithmetic_separate_module_error() {
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn div_signed_incompatible_bit_widths_error() {
         let c = Context::new();
 
-        let m1 = c.module("a", "A");
-        let i1 = m1.input("a", 1);
-
-        let m2 = c.module("b", "B");
-        let i2 = m2.high();
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.shr_arithmetic(i2);
+        let _ = i1.div_signed(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn mul_signed_separate_module_error() {
+    fn rem_signed_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1855,105 +3704,111 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1.mul_signed(i2);
+        let _ = i1.rem_signed(i2);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Attempted to multiply a 128-bit with a 1-bit signal, but this would result in a bit width of 129, which is greater than the maximum signal bit width of 128 bit(s)."
-    )]
-    fn mul_signed_oob_error() {
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn rem_signed_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i1 = m.input("a", 128);
-        let i2 = m.input("b", 1);
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1.mul_signed(i2);
+        let _ = i1.rem_signed(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn mux_cond_separate_module_error() {
+    fn add_carry_separate_module_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(false, 1);
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
 
-        let b = c.module("b", "B");
-        let l2 = b.lit(32u8, 8);
-        let l3 = b.lit(32u8, 8);
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
         // Panic
-        let _ = l1.mux(l2, l3);
+        let _ = i1.add_carry(i2);
     }
 
     #[test]
-    #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn mux_when_true_separate_module_error() {
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn add_carry_incompatible_bit_widths_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(32u8, 8);
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
-        let b = c.module("b", "B");
-        let l2 = b.lit(true, 1);
-        let l3 = b.lit(32u8, 8);
+        // Panic
+        let _ = i1.add_carry(i2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to add 65536-bit signals with a carry-out bit, but this would result in a bit width of 65537, which is greater than the maximum signal bit width of 65536 bit(s)."
+    )]
+    fn add_carry_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 65536);
+        let i2 = m.input("b", 65536);
 
         // Panic
-        let _ = l2.mux(l1, l3);
+        let _ = i1.add_carry(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn mux_when_false_separate_module_error() {
+    fn sub_borrow_separate_module_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(32u8, 8);
+        let m1 = c.module("a", "A");
+        let i1 = m1.input("a", 1);
 
-        let b = c.module("b", "B");
-        let l2 = b.lit(true, 1);
-        let l3 = b.lit(32u8, 8);
+        let m2 = c.module("b", "B");
+        let i2 = m2.high();
 
         // Panic
-        let _ = l2.mux(l3, l1);
+        let _ = i1.sub_borrow(i2);
     }
 
     #[test]
-    #[should_panic(expected = "Multiplexer conditionals can only be 1 bit wide.")]
-    fn mux_cond_bit_width_error() {
+    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
+    fn sub_borrow_incompatible_bit_widths_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(2u8, 2);
-        let l2 = a.lit(32u8, 8);
-        let l3 = a.lit(32u8, 8);
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 3);
+        let i2 = m.input("b", 5);
 
         // Panic
-        let _ = l1.mux(l2, l3);
+        let _ = i1.sub_borrow(i2);
     }
 
     #[test]
     #[should_panic(
-        expected = "Cannot multiplex signals with different bit widths (3 and 5, respectively)."
+        expected = "Attempted to subtract 65536-bit signals with a borrow-out bit, but this would result in a bit width of 65537, which is greater than the maximum signal bit width of 65536 bit(s)."
     )]
-    fn mux_true_false_bit_width_error() {
+    fn sub_borrow_oob_error() {
         let c = Context::new();
 
-        let a = c.module("a", "A");
-        let l1 = a.lit(false, 1);
-        let l2 = a.lit(3u8, 3);
-        let l3 = a.lit(3u8, 5);
+        let m = c.module("a", "A");
+        let i1 = m.input("a", 65536);
+        let i2 = m.input("b", 65536);
 
         // Panic
-        let _ = a.mux(l1, l2, l3);
+        let _ = i1.sub_borrow(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn add_separate_module_error() {
+    fn add_sat_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1963,12 +3818,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1 + i2;
+        let _ = i1.add_sat(i2);
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn add_incompatible_bit_widths_error() {
+    fn add_sat_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -1976,12 +3831,12 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1 + i2;
+        let _ = i1.add_sat(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn bitand_separate_module_error() {
+    fn add_sat_signed_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -1991,12 +3846,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1 & i2;
+        let _ = i1.add_sat_signed(i2);
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn bitand_incompatible_bit_widths_error() {
+    fn add_sat_signed_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -2004,12 +3859,12 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1 & i2;
+        let _ = i1.add_sat_signed(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn bitor_separate_module_error() {
+    fn sub_sat_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -2019,12 +3874,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1 | i2;
+        let _ = i1.sub_sat(i2);
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn bitor_incompatible_bit_widths_error() {
+    fn sub_sat_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -2032,12 +3887,12 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1 | i2;
+        let _ = i1.sub_sat(i2);
     }
 
     #[test]
     #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn bitxor_separate_module_error() {
+    fn sub_sat_signed_separate_module_error() {
         let c = Context::new();
 
         let m1 = c.module("a", "A");
@@ -2047,12 +3902,12 @@ mod tests {
         let i2 = m2.high();
 
         // Panic
-        let _ = i1 ^ i2;
+        let _ = i1.sub_sat_signed(i2);
     }
 
     #[test]
     #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn bitxor_incompatible_bit_widths_error() {
+    fn sub_sat_signed_incompatible_bit_widths_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
@@ -2060,94 +3915,90 @@ mod tests {
         let i2 = m.input("b", 5);
 
         // Panic
-        let _ = i1 ^ i2;
+        let _ = i1.sub_sat_signed(i2);
     }
 
     #[test]
-    #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn mul_separate_module_error() {
+    #[should_panic(
+        expected = "Cannot fit the specified value '256' into the specified bit width '8'. The value '256' requires a bit width of at least 9 bit(s)."
+    )]
+    fn add_lit_oob_error() {
         let c = Context::new();
 
-        let m1 = c.module("a", "A");
-        let i1 = m1.input("a", 1);
-
-        let m2 = c.module("b", "B");
-        let i2 = m2.high();
+        let m = c.module("a", "A");
+        let i = m.input("a", 8);
 
         // Panic
-        let _ = i1 * i2;
+        let _ = i + 256u32;
     }
 
     #[test]
     #[should_panic(
-        expected = "Attempted to multiply a 128-bit with a 1-bit signal, but this would result in a bit width of 129, which is greater than the maximum signal bit width of 128 bit(s)."
+        expected = "Cannot fit the specified value '4' into the specified bit width '2'. The value '4' requires a bit width of at least 3 bit(s)."
     )]
-    fn mul_oob_error() {
+    fn eq_lit_oob_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i1 = m.input("a", 128);
-        let i2 = m.input("b", 1);
+        let i = m.input("a", 2);
 
         // Panic
-        let _ = i1 * i2;
+        let _ = i.eq(4);
     }
 
     #[test]
-    #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn shl_separate_module_error() {
+    #[should_panic(
+        expected = "Cannot fit the specified value '-129' into the specified bit width '8'. The value '-129' requires a bit width of at least 9 bit(s)."
+    )]
+    fn lt_signed_negative_lit_oob_error() {
         let c = Context::new();
 
-        let m1 = c.module("a", "A");
-        let i1 = m1.input("a", 1);
-
-        let m2 = c.module("b", "B");
-        let i2 = m2.high();
+        let m = c.module("a", "A");
+        let i = m.input("a", 8);
 
         // Panic
-        let _ = i1 << i2;
+        let _ = i.lt_signed(-129);
     }
 
-    #[test]
-    #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn shr_separate_module_error() {
-        let c = Context::new();
-
-        let m1 = c.module("a", "A");
-        let i1 = m1.input("a", 1);
-
-        let m2 = c.module("b", "B");
-        let i2 = m2.high();
-
-        // Panic
-        let _ = i1 >> i2;
+    fn shift_amount_bit_width<'a>(s: &'a dyn Signal<'a>) -> u32 {
+        match s.node() {
+            SignalNode::ShiftBinOp { rhs, .. } | SignalNode::RotateBinOp { rhs, .. } => {
+                rhs.bit_width()
+            }
+            _ => panic!("Expected a shift or rotation."),
+        }
     }
 
     #[test]
-    #[should_panic(expected = "Attempted to combine signals from different modules.")]
-    fn sub_separate_module_error() {
+    fn integer_shift_amounts() {
         let c = Context::new();
 
-        let m1 = c.module("a", "A");
-        let i1 = m1.input("a", 1);
-
-        let m2 = c.module("b", "B");
-        let i2 = m2.high();
-
-        // Panic
-        let _ = i1 - i2;
+        let m = c.module("a", "A");
+        let one_bit = m.input("a", 1);
+        let nibble = m.input("b", 4);
+        let wide = m.input("c", 200);
+
+        let shl = one_bit << 2;
+        assert_eq!(shl.bit_width(), 1);
+        assert_eq!(shift_amount_bit_width(shl), 2);
+        assert_eq!(shift_amount_bit_width(nibble >> 0u8), 1);
+        assert_eq!(shift_amount_bit_width(nibble.rotate_left(5)), 3);
+        assert_eq!(shift_amount_bit_width(nibble.rotate_right(16u64)), 5);
+        assert_eq!(shift_amount_bit_width(one_bit.shr_arithmetic(1)), 1);
+        assert_eq!(shift_amount_bit_width(wide << 199u128), 8);
+        assert_eq!(shift_amount_bit_width(nibble.as_signed() >> 3), 2);
+        assert_eq!(shift_amount_bit_width(nibble.as_signed() << 9u32), 4);
     }
 
     #[test]
-    #[should_panic(expected = "Signals have different bit widths (3 and 5, respectively).")]
-    fn sub_incompatible_bit_widths_error() {
+    #[should_panic(expected = "Cannot shift or rotate by a negative amount (-1).")]
+    fn negative_shift_amount_error() {
         let c = Context::new();
 
         let m = c.module("a", "A");
-        let i1 = m.input("a", 3);
-        let i2 = m.input("b", 5);
+        let i = m.input("a", 8);
 
         // Panic
-        let _ = i1 - i2;
+        let _ = i.rotate_left(-1);
     }
 }
@@ -0,0 +1,334 @@
+use super::internal_signal::*;
+use super::signal::*;
+
+use std::panic::Location;
+use std::ptr;
+
+/// A fixed-length vector of [`Signal`]s with the same bit width, which can be indexed with a [`Signal`].
+///
+/// A `SignalVec` is useful for describing things like register file read data or per-lane values, where one of several same-width values is selected at runtime.
+/// Rather than building a tree of [`mux`](Signal::mux)es, [`get`](Self::get) creates a single indexing node, which generated simulators evaluate with an array lookup.
+///
+/// All of a `SignalVec`'s elements belong to the same [`Module`](crate::Module).
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let lanes = SignalVec::new(&[
+///     m.input("lane0", 8),
+///     m.input("lane1", 8),
+///     m.input("lane2", 8),
+///     m.input("lane3", 8),
+/// ]);
+/// let sel = m.input("sel", 2);
+/// m.output("selected", lanes.get(sel));
+///
+/// let sum = lanes.fold(m.lit(0u32, 8), |acc, lane| acc + lane);
+/// m.output("sum", sum);
+/// ```
+#[derive(Clone)]
+#[must_use]
+pub struct SignalVec<'a> {
+    elements: Vec<&'a dyn Signal<'a>>,
+}
+
+impl<'a> SignalVec<'a> {
+    /// Creates a new `SignalVec` containing `elements`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `elements` is empty, if `elements` belong to different [`Module`](crate::Module)s, or if the bit widths of `elements` aren't all equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let v = SignalVec::new(&[m.lit(1u32, 4), m.lit(2u32, 4), m.lit(3u32, 4)]);
+    /// assert_eq!(v.len(), 3);
+    /// assert_eq!(v.bit_width(), 4);
+    /// ```
+    #[track_caller]
+    pub fn new(elements: &[&'a dyn Signal<'a>]) -> SignalVec<'a> {
+        let first = match elements.first() {
+            Some(first) => first.internal_signal(),
+            None => panic!("Cannot create a signal vector without any elements."),
+        };
+        for element in elements[1..].iter() {
+            let element = element.internal_signal();
+            if !ptr::eq(first.module, element.module) {
+                panic!("Attempted to combine signals from different modules.");
+            }
+            if element.bit_width() != first.bit_width() {
+                panic!(
+                    "Signal vector elements have different bit widths ({} and {}, respectively).",
+                    first.bit_width(),
+                    element.bit_width()
+                );
+            }
+        }
+        SignalVec {
+            elements: elements.to_vec(),
+        }
+    }
+
+    /// Returns the number of elements in this `SignalVec`.
+    ///
+    /// Since a `SignalVec` always has at least one element, this is never 0.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns the bit width of this `SignalVec`'s elements.
+    pub fn bit_width(&self) -> u32 {
+        self.elements[0].bit_width()
+    }
+
+    /// Returns this `SignalVec`'s elements.
+    pub fn elements(&self) -> &[&'a dyn Signal<'a>] {
+        &self.elements
+    }
+
+    /// Creates a [`Signal`] that represents the element of this `SignalVec` selected by `index`.
+    ///
+    /// Values of `index` past the last element select the last element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` belongs to a different [`Module`](crate::Module) than this `SignalVec`'s elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let v = SignalVec::new(&[m.lit(1u32, 4), m.lit(2u32, 4), m.lit(3u32, 4)]);
+    /// let index = m.input("index", 2);
+    /// m.output("o", v.get(index)); // Index 3 selects the last element
+    /// ```
+    #[track_caller]
+    pub fn get(&self, index: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        let index = index.internal_signal();
+        let first = self.elements[0].internal_signal();
+        if !ptr::eq(first.module, index.module) {
+            panic!("Attempted to combine signals from different modules.");
+        }
+        // Elements past the largest value of `index` can never be selected
+        let index_bit_width = index.bit_width();
+        let element_count = if index_bit_width < usize::BITS {
+            self.elements.len().min(1 << index_bit_width)
+        } else {
+            self.elements.len()
+        };
+        if element_count == 1 {
+            return self.elements[0];
+        }
+        first.context.signal_arena.alloc(InternalSignal {
+            context: first.context,
+            module: first.module,
+            location: Location::caller(),
+
+            data: SignalData::Index {
+                elements: self.elements[..element_count]
+                    .iter()
+                    .map(|element| element.internal_signal())
+                    .collect(),
+                index,
+                bit_width: first.bit_width(),
+            },
+        })
+    }
+
+    /// Creates a new `SignalVec` in which the element selected by `index` is replaced with `value`, and all other elements are unchanged.
+    ///
+    /// Each element of the result is a [`mux`](Signal::mux) that compares `index` with that element's index, so at most one element is replaced. Values of `index` past the last element don't replace any element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` or `value` belong to a different [`Module`](crate::Module) than this `SignalVec`'s elements, or if `value`'s bit width doesn't match this `SignalVec`'s [`bit_width`](Self::bit_width).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let regs = (0..4)
+    ///     .map(|i| m.reg(format!("r{}", i), 32) as &dyn Signal)
+    ///     .collect::<Vec<_>>();
+    /// let v = SignalVec::new(&regs);
+    /// let write_index = m.input("write_index", 2);
+    /// let write_value = m.input("write_value", 32);
+    /// let next = v.set(write_index, write_value);
+    /// ```
+    #[track_caller]
+    pub fn set(&self, index: &'a dyn Signal<'a>, value: &'a dyn Signal<'a>) -> SignalVec<'a> {
+        let index_bit_width = index.bit_width();
+        if value.bit_width() != self.bit_width() {
+            panic!("Attempted to set a signal vector element with a signal that has a different bit width than the vector's elements ({} and {}, respectively).", value.bit_width(), self.bit_width());
+        }
+        SignalVec::new(
+            &self
+                .elements
+                .iter()
+                .enumerate()
+                .map(|(i, &element)| {
+                    if index_bit_width < u64::BITS && i as u64 >> index_bit_width != 0 {
+                        element
+                    } else {
+                        index.eq(i as u64).mux(value, element)
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Creates a new `SignalVec` by applying `f` to each of this `SignalVec`'s elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the results of `f` belong to different [`Module`](crate::Module)s or have different bit widths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let v = SignalVec::new(&[m.input("a", 8), m.input("b", 8)]);
+    /// let inverted = v.map(|element| !element);
+    /// let low_nibbles = v.map(|element| element.bits(3, 0));
+    /// assert_eq!(low_nibbles.bit_width(), 4);
+    /// ```
+    #[track_caller]
+    pub fn map(&self, f: impl FnMut(&'a dyn Signal<'a>) -> &'a dyn Signal<'a>) -> SignalVec<'a> {
+        SignalVec::new(&self.elements.iter().copied().map(f).collect::<Vec<_>>())
+    }
+
+    /// Combines this `SignalVec`'s elements into a single value by applying `f` to an accumulator and each element in turn, starting with `init`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let v = SignalVec::new(&[m.input("a", 8), m.input("b", 8), m.input("c", 8)]);
+    /// let any_zero = v.fold(m.low(), |acc, element| acc | element.eq(0u32));
+    /// ```
+    pub fn fold<T>(&self, init: T, f: impl FnMut(T, &'a dyn Signal<'a>) -> T) -> T {
+        self.elements.iter().copied().fold(init, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn get_unreachable_elements() {
+        let c = Context::new();
+
+        let m = c.module("m", "M");
+        let v = SignalVec::new(&[m.input("a", 8), m.input("b", 8), m.input("c", 8)]);
+
+        match v.get(m.input("i", 1)).node() {
+            SignalNode::Index { elements, .. } => assert_eq!(elements.len(), 2),
+            _ => unreachable!(),
+        }
+
+        let d = SignalVec::new(&[m.input("d", 8)]);
+        assert!(matches!(
+            d.get(m.input("j", 4)).node(),
+            SignalNode::Input { .. }
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot create a signal vector without any elements.")]
+    fn new_empty_error() {
+        // Panic
+        let _ = SignalVec::new(&[]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Signal vector elements have different bit widths (8 and 4, respectively)."
+    )]
+    fn new_incompatible_bit_widths_error() {
+        let c = Context::new();
+
+        let m = c.module("m", "M");
+
+        // Panic
+        let _ = SignalVec::new(&[m.input("a", 8), m.input("b", 4)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn new_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("m1", "M1");
+        let a = m1.input("a", 8);
+
+        let m2 = c.module("m2", "M2");
+        let b = m2.input("b", 8);
+
+        // Panic
+        let _ = SignalVec::new(&[a, b]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn get_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("m1", "M1");
+        let v = SignalVec::new(&[m1.input("a", 8), m1.input("b", 8)]);
+
+        let m2 = c.module("m2", "M2");
+        let i = m2.input("i", 1);
+
+        // Panic
+        let _ = v.get(i);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to set a signal vector element with a signal that has a different bit width than the vector's elements (4 and 8, respectively)."
+    )]
+    fn set_incompatible_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("m", "M");
+        let v = SignalVec::new(&[m.input("a", 8), m.input("b", 8)]);
+
+        // Panic
+        let _ = v.set(m.input("i", 1), m.input("c", 4));
+    }
+}
@@ -0,0 +1,271 @@
+use super::internal_signal::*;
+use super::signal::*;
+
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Not, Rem, Shl, Shr, Sub};
+
+/// A signed view of a [`Signal`], created by the [`Signal::as_signed`] method.
+///
+/// A `SignedSignal` represents exactly the same bits as the `Signal` it was created from, but interprets them as a signed, two's complement integer.
+/// Its comparison methods ([`lt`], [`le`], [`gt`], [`ge`]) and its `*`, `/`, `%` and `>>` operators pick the signed variants of the corresponding operations ([`SignalCompare::lt_signed`], [`Signal::mul_signed`], [`SignalCompare::shr_arithmetic`], etc.) automatically, so a signed value can't accidentally be compared or shifted as an unsigned one.
+/// Operators whose results are signed ([`Add`], [`Sub`], [`Mul`], [`Div`], [`Rem`], [`Shl`], [`Shr`], [`BitAnd`], [`BitOr`], [`BitXor`] and [`Not`]) produce `SignedSignal`s as well, and only the left-hand side of a binary operator determines whether the signed variant of the operation is used.
+///
+/// A `SignedSignal` can be used anywhere a [`Signal`] is expected. Any other [`Signal`] method (such as [`bits`](Signal::bits) or [`concat`](Signal::concat)) treats it as a plain, unsigned `Signal`, and [`as_unsigned`] can be used to explicitly drop the signed view.
+/// [`Output`](crate::Output)s driven by a `SignedSignal` are declared as `signed` in generated Verilog code and are reported as signed to [`Trace`](crate::runtime::tracing::Trace) implementations.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let a = m.input("a", 8).as_signed();
+/// let b = m.input("b", 8).as_signed();
+///
+/// m.output("lt", a.lt(b)); // Equivalent to a.lt_signed(b)
+/// m.output("prod", a * b); // Equivalent to a.mul_signed(b), and declared as a signed output
+/// m.output("shr", a >> m.lit(1u32, 3)); // Equivalent to a.shr_arithmetic(m.lit(1u32, 3))
+/// ```
+///
+/// [`as_unsigned`]: Self::as_unsigned
+/// [`ge`]: Self::ge
+/// [`gt`]: Self::gt
+/// [`le`]: Self::le
+/// [`lt`]: Self::lt
+#[must_use]
+pub struct SignedSignal<'a> {
+    pub(crate) value: &'a InternalSignal<'a>,
+}
+
+impl<'a> SignedSignal<'a> {
+    /// Returns a plain, unsigned `Signal` representing the same bits as this `SignedSignal`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let a = m.input("a", 8).as_signed();
+    /// let b = m.input("b", 8);
+    /// let lt = a.as_unsigned().lt(b); // Unsigned comparison
+    /// ```
+    pub fn as_unsigned(&'a self) -> &'a dyn Signal<'a> {
+        self.value
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of a signed `<` comparison between `self` and `rhs`.
+    ///
+    /// This is equivalent to [`SignalCompare::lt_signed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`](crate::Module)s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4).as_signed(); // -6
+    /// let lit_b = m.lit(0x3u32, 4).as_signed(); // 3
+    /// let lt = lit_a.lt(lit_b); // Equivalent to m.high()
+    /// ```
+    #[track_caller]
+    pub fn lt<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        self.value.lt_signed(rhs)
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of a signed `<=` comparison between `self` and `rhs`.
+    ///
+    /// This is equivalent to [`SignalCompare::le_signed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`](crate::Module)s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4).as_signed(); // -6
+    /// let lit_b = m.lit(0x3u32, 4).as_signed(); // 3
+    /// let le = lit_b.le(lit_a); // Equivalent to m.low()
+    /// ```
+    #[track_caller]
+    pub fn le<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        self.value.le_signed(rhs)
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of a signed `>` comparison between `self` and `rhs`.
+    ///
+    /// This is equivalent to [`SignalCompare::gt_signed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`](crate::Module)s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4).as_signed(); // -6
+    /// let lit_b = m.lit(0x3u32, 4).as_signed(); // 3
+    /// let gt = lit_b.gt(lit_a); // Equivalent to m.high()
+    /// ```
+    #[track_caller]
+    pub fn gt<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        self.value.gt_signed(rhs)
+    }
+
+    /// Creates a `Signal` that represents the single-bit result of a signed `>=` comparison between `self` and `rhs`.
+    ///
+    /// This is equivalent to [`SignalCompare::ge_signed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` belong to different [`Module`](crate::Module)s, if the bit widths of `self` and `rhs` aren't equal, or if the bit widths of `self` and `rhs` are 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kaze::*;
+    ///
+    /// let c = Context::new();
+    ///
+    /// let m = c.module("m", "MyModule");
+    ///
+    /// let lit_a = m.lit(0xau32, 4).as_signed(); // -6
+    /// let lit_b = m.lit(0x3u32, 4).as_signed(); // 3
+    /// let ge = lit_a.ge(lit_b); // Equivalent to m.low()
+    /// ```
+    #[track_caller]
+    pub fn ge<R: IntoSignal<'a>>(&'a self, rhs: R) -> &'a dyn Signal<'a> {
+        self.value.ge_signed(rhs)
+    }
+}
+
+impl<'a> GetInternalSignal<'a> for SignedSignal<'a> {
+    fn internal_signal(&'a self) -> &'a InternalSignal<'a> {
+        self.value
+    }
+
+    fn is_signed(&self) -> bool {
+        true
+    }
+}
+
+macro_rules! impl_signed_bin_ops {
+    ($($trait:ident, $fn:ident, $into:ident, $desc:literal, |$lhs:ident, $rhs:ident| $e:expr;)*) => {
+        $(
+            impl<'a, S: IntoSignal<'a>> $trait<S> for &'a SignedSignal<'a> {
+                type Output = &'a SignedSignal<'a>;
+
+                #[doc = $desc]
+                ///
+                /// The result is itself a [`SignedSignal`].
+                #[track_caller]
+                fn $fn(self, rhs: S) -> Self::Output {
+                    let $lhs: &'a dyn Signal<'a> = self.value;
+                    let $rhs = rhs.$into($lhs);
+                    $e.as_signed()
+                }
+            }
+        )*
+    };
+}
+
+impl_signed_bin_ops! {
+    Add, add, into_signal, "Equivalent to `self.as_unsigned() + rhs`.", |lhs, rhs| (lhs + rhs);
+    BitAnd, bitand, into_signal, "Equivalent to `self.as_unsigned() & rhs`.", |lhs, rhs| (lhs & rhs);
+    BitOr, bitor, into_signal, "Equivalent to `self.as_unsigned() | rhs`.", |lhs, rhs| (lhs | rhs);
+    BitXor, bitxor, into_signal, "Equivalent to `self.as_unsigned() ^ rhs`.", |lhs, rhs| (lhs ^ rhs);
+    Div, div, into_signal, "Equivalent to [`Signal::div_signed`].", |lhs, rhs| lhs.div_signed(rhs);
+    Mul, mul, into_signal, "Equivalent to [`Signal::mul_signed`].", |lhs, rhs| lhs.mul_signed(rhs);
+    Rem, rem, into_signal, "Equivalent to [`Signal::rem_signed`].", |lhs, rhs| lhs.rem_signed(rhs);
+    Shl, shl, into_shift_amount, "Equivalent to `self.as_unsigned() << rhs`.", |lhs, rhs| (lhs << rhs);
+    Shr, shr, into_shift_amount, "Equivalent to [`SignalCompare::shr_arithmetic`].", |lhs, rhs| lhs.shr_arithmetic(rhs);
+    Sub, sub, into_signal, "Equivalent to `self.as_unsigned() - rhs`.", |lhs, rhs| (lhs - rhs);
+}
+
+impl<'a> Not for &'a SignedSignal<'a> {
+    type Output = &'a SignedSignal<'a>;
+
+    /// Equivalent to `!self.as_unsigned()`.
+    ///
+    /// The result is itself a [`SignedSignal`].
+    #[track_caller]
+    fn not(self) -> Self::Output {
+        let s: &'a dyn Signal<'a> = self.value;
+        (!s).as_signed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn signed_ops() {
+        let c = Context::new();
+
+        let m = c.module("m", "M");
+        let a = m.input("a", 8).as_signed();
+        let b = m.input("b", 8);
+
+        assert_eq!((a * b).bit_width(), 16);
+        assert_eq!((a + b).bit_width(), 8);
+        assert_eq!(a.lt(b).bit_width(), 1);
+        assert!(m.output("o1", a + b).is_signed());
+        assert!(m.output("o2", !a).is_signed());
+        assert!(!m.output("o3", a.as_unsigned()).is_signed());
+        assert!(!m.output("o4", a.bits(3, 0)).is_signed());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot perform signed comparison of 1-bit signals.")]
+    fn lt_bit_width_1_error() {
+        let c = Context::new();
+
+        let m = c.module("m", "M");
+        let a = m.input("a", 1).as_signed();
+        let b = m.input("b", 1);
+
+        // Panic
+        let _ = a.lt(b);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn mul_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("m1", "M1");
+        let a = m1.input("a", 8).as_signed();
+
+        let m2 = c.module("m2", "M2");
+        let b = m2.input("b", 8);
+
+        // Panic
+        let _ = a * b;
+    }
+}
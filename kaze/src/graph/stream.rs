@@ -0,0 +1,729 @@
+use super::bundle::*;
+use super::internal_signal::*;
+use super::module::*;
+use super::signal::*;
+
+use crate::error::ItemKind;
+
+use std::cell::RefCell;
+use std::iter;
+use std::ptr;
+
+/// The producing end of a valid/ready stream carrying a [`Bundle`] payload, created by the [`Module::stream_producer`] method.
+///
+/// A producer has a `valid` [`Output`] and an [`Output`] for each field of its payload, which are specified with [`drive`](Self::drive), along with a `ready` [`Input`].
+/// A transfer occurs on each cycle where both `valid` and `ready` are high.
+///
+/// A producer is connected to a [`StreamConsumer`] with the same payload type in a sibling [`Module`] with [`connect`], or forwarded out of its [`Module`] through a producer in the parent [`Module`] with [`forward_producer`].
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// bundle! {
+///     pub struct Packet {
+///         pub data: 8,
+///     }
+/// }
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let out = m.stream_producer::<Packet>("out"); // Creates an "out_ready" input
+/// let data = m.reg("data", 8);
+/// data.default_value(0u32);
+/// data.drive_next(out.ready().mux(data + 1u32, data));
+/// out.drive(m.high(), &Packet { data }); // Creates "out_valid" and "out_data" outputs
+/// ```
+#[must_use]
+pub struct StreamProducer<'a, B> {
+    pub(crate) name: String,
+    pub(crate) ready: &'a Input<'a>,
+    pub(crate) outputs: RefCell<Option<(&'a Output<'a>, B)>>,
+}
+
+impl<'a, B: Bundle<'a>> StreamProducer<'a, B> {
+    /// Returns the `ready` [`Input`] of this producer, for use inside its [`Module`].
+    pub fn ready(&self) -> &'a Input<'a> {
+        self.ready
+    }
+
+    /// Creates this producer's `valid` [`Output`] and payload [`Output`]s, driven by `valid` and the fields of `payload`, respectively.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this producer has already been driven, if `valid`'s bit width is not 1, or under the same conditions as [`Module::output`] and [`Module::bundle_output`] for the outputs.
+    /// All outputs are checked before any of them is created, so a panic leaves the [`Module`] without any of them.
+    #[track_caller]
+    pub fn drive(&self, valid: &'a dyn Signal<'a>, payload: &B) {
+        self.check_drive(valid, payload);
+        let module = self.ready.module;
+        let valid = module.output(format!("{}_valid", self.name), valid);
+        let payload = module.bundle_output(&self.name, payload);
+        *self.outputs.borrow_mut() = Some((valid, payload));
+    }
+
+    #[track_caller]
+    fn check_drive(&self, valid: &'a dyn Signal<'a>, payload: &B) {
+        if self.outputs.borrow().is_some() {
+            panic!(
+                "Attempted to drive stream \"{}\" in module \"{}\", but this stream is already driven.",
+                self.name,
+                self.ready.module.name()
+            );
+        }
+        if valid.bit_width() != 1 {
+            panic!("Attempted to drive the valid signal of stream \"{}\" with a {}-bit signal, but valid signals are required to be 1 bit wide.", self.name, valid.bit_width());
+        }
+        check_field_bit_widths(payload);
+        let module = self.ready.module;
+        for signal in iter::once(valid).chain(payload.signals()) {
+            if !ptr::eq(module, signal.internal_signal().module) {
+                panic!("Cannot output a signal from another module.");
+            }
+        }
+        let names = iter::once(format!("{}_valid", self.name))
+            .chain(
+                B::fields()
+                    .into_iter()
+                    .map(|(name, _)| format!("{}_{}", self.name, name)),
+            )
+            .collect::<Vec<_>>();
+        module.check_names(&names, ItemKind::Output);
+    }
+}
+
+/// The consuming end of a valid/ready stream carrying a [`Bundle`] payload, created by the [`Module::stream_consumer`] method.
+///
+/// A consumer has the same ports as a [`StreamProducer`] with their directions flipped: a `valid` [`Input`] and an [`Input`] for each field of its payload, along with a `ready` [`Output`], which is specified with [`drive_ready`](Self::drive_ready).
+/// A transfer occurs on each cycle where both `valid` and `ready` are high.
+///
+/// A consumer is connected to a [`StreamProducer`] in a sibling [`Module`] with [`connect`], or forwarded into a child [`Module`] through a consumer in that [`Module`] with [`forward_consumer`].
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// bundle! {
+///     pub struct Packet {
+///         pub data: 8,
+///     }
+/// }
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let input = m.stream_consumer::<Packet>("in"); // Creates "in_valid" and "in_data" inputs
+/// let sum = m.reg("sum", 8);
+/// sum.default_value(0u32);
+/// sum.drive_next(input.valid().mux(sum + input.payload().data, sum));
+/// input.drive_ready(m.high()); // Creates an "in_ready" output
+/// m.output("total", sum);
+/// ```
+#[must_use]
+pub struct StreamConsumer<'a, B> {
+    pub(crate) name: String,
+    pub(crate) valid: &'a Input<'a>,
+    pub(crate) payload: BundleInput<'a, B>,
+    pub(crate) ready: RefCell<Option<&'a Output<'a>>>,
+}
+
+impl<'a, B: Bundle<'a>> StreamConsumer<'a, B> {
+    /// Returns the `valid` [`Input`] of this consumer, for use inside its [`Module`].
+    pub fn valid(&self) -> &'a Input<'a> {
+        self.valid
+    }
+
+    /// Returns the payload [`Input`]s of this consumer as a `B`, for use inside its [`Module`].
+    pub fn payload(&self) -> &B {
+        self.payload.value()
+    }
+
+    /// Creates this consumer's `ready` [`Output`], driven by `ready`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this consumer's `ready` [`Output`] has already been created, if `ready`'s bit width is not 1, or under the same conditions as [`Module::output`].
+    #[track_caller]
+    pub fn drive_ready(&self, ready: &'a dyn Signal<'a>) {
+        self.check_drive_ready(ready);
+        let ready = self
+            .valid
+            .module
+            .output(format!("{}_ready", self.name), ready);
+        *self.ready.borrow_mut() = Some(ready);
+    }
+
+    #[track_caller]
+    fn check_drive_ready(&self, ready: &'a dyn Signal<'a>) {
+        if self.ready.borrow().is_some() {
+            panic!(
+                "Attempted to drive the ready signal of stream \"{}\" in module \"{}\", but this signal is already driven.",
+                self.name,
+                self.valid.module.name()
+            );
+        }
+        if ready.bit_width() != 1 {
+            panic!("Attempted to drive the ready signal of stream \"{}\" with a {}-bit signal, but ready signals are required to be 1 bit wide.", self.name, ready.bit_width());
+        }
+        let module = self.valid.module;
+        if !ptr::eq(module, ready.internal_signal().module) {
+            panic!("Cannot output a signal from another module.");
+        }
+        module.check_names(&[format!("{}_ready", self.name)], ItemKind::Output);
+    }
+}
+
+/// Connects `producer` to `consumer`, driving the `valid` and payload [`Input`]s of `consumer` with the corresponding [`Output`]s of `producer`, and the `ready` [`Input`] of `producer` with the `ready` [`Output`] of `consumer`.
+///
+/// `producer` and `consumer` must belong to sibling [`Module`]s, and `connect` must be called after both ends are driven (with [`StreamProducer::drive`] and [`StreamConsumer::drive_ready`], respectively).
+///
+/// # Panics
+///
+/// Panics if either end hasn't been driven yet, or under the same conditions as [`Input::drive`] for any of the connected inputs. All inputs are checked before any of them is driven, so a panic leaves the stream unconnected.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// bundle! {
+///     pub struct Packet {
+///         pub data: 8,
+///     }
+/// }
+///
+/// let c = Context::new();
+///
+/// let top = c.module("top", "Top");
+///
+/// let producer = top.module("producer", "Producer");
+/// let out = producer.stream_producer::<Packet>("out");
+/// out.drive(producer.input("valid", 1), &Packet { data: producer.input("data", 8) });
+///
+/// let consumer = top.module("consumer", "Consumer");
+/// let input = consumer.stream_consumer::<Packet>("in");
+/// input.drive_ready(consumer.high());
+///
+/// connect(&out, &input);
+/// ```
+#[track_caller]
+pub fn connect<'a, B: Bundle<'a>>(
+    producer: &StreamProducer<'a, B>,
+    consumer: &StreamConsumer<'a, B>,
+) {
+    let outputs = producer.outputs.borrow();
+    let (valid, payload) = match *outputs {
+        Some((valid, ref payload)) => (valid, payload),
+        None => panic!(
+            "Attempted to connect stream \"{}\" in module \"{}\" before it was driven.",
+            producer.name,
+            producer.ready.module.name()
+        ),
+    };
+    let ready = match *consumer.ready.borrow() {
+        Some(ready) => ready,
+        None => panic!(
+            "Attempted to connect stream \"{}\" in module \"{}\" before its ready signal was driven.",
+            consumer.name,
+            consumer.valid.module.name()
+        ),
+    };
+    consumer.valid.check_drive(valid.internal_signal());
+    consumer.payload.check_drive(payload);
+    producer.ready.check_drive(ready.internal_signal());
+
+    consumer.valid.drive(valid);
+    consumer.payload.drive(payload);
+    producer.ready.drive(ready);
+}
+
+/// Forwards `parent`, a consumer in the parent [`Module`] of `child`'s [`Module`], into `child`, driving the `valid` and payload [`Input`]s of `child` with the corresponding [`Input`]s of `parent`, and creating the `ready` [`Output`] of `parent` (as if by [`StreamConsumer::drive_ready`]) driven by the `ready` [`Output`] of `child`.
+///
+/// This allows a [`Module`] to pass a stream it consumes on to one of its child [`Module`]s. `forward_consumer` must be called after `child`'s `ready` [`Output`] is driven (with [`StreamConsumer::drive_ready`]), and `parent`'s `ready` [`Output`] must not be driven.
+///
+/// # Panics
+///
+/// Panics if `child` doesn't belong to a child [`Module`] of `parent`'s [`Module`], if `child`'s `ready` [`Output`] hasn't been driven yet, under the same conditions as [`StreamConsumer::drive_ready`] for `parent`, or under the same conditions as [`Input::drive`] for any of `child`'s inputs.
+/// Everything is checked before any port is driven, so a panic leaves the stream unconnected.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// bundle! {
+///     pub struct Packet {
+///         pub data: 8,
+///     }
+/// }
+///
+/// let c = Context::new();
+///
+/// let top = c.module("top", "Top");
+/// let input = top.stream_consumer::<Packet>("in");
+///
+/// let inner = top.module("inner", "Inner");
+/// let inner_input = inner.stream_consumer::<Packet>("in");
+/// inner_input.drive_ready(inner.high());
+/// inner.output("data", inner_input.payload().data);
+///
+/// forward_consumer(&input, &inner_input); // Creates an "in_ready" output on top
+/// ```
+#[track_caller]
+pub fn forward_consumer<'a, B: Bundle<'a>>(
+    parent: &StreamConsumer<'a, B>,
+    child: &StreamConsumer<'a, B>,
+) {
+    check_forward(
+        &parent.name,
+        parent.valid.module,
+        &child.name,
+        child.valid.module,
+    );
+    let ready = match *child.ready.borrow() {
+        Some(ready) => ready,
+        None => panic!(
+            "Attempted to forward stream \"{}\" in module \"{}\" before its ready signal was driven.",
+            child.name,
+            child.valid.module.name()
+        ),
+    };
+    parent.check_drive_ready(ready);
+    child.valid.check_drive(parent.valid.internal_signal());
+    child.payload.check_drive(parent.payload());
+
+    parent.drive_ready(ready);
+    child.valid.drive(parent.valid);
+    child.payload.drive(parent.payload());
+}
+
+/// Forwards `child`, a producer in a child [`Module`] of `parent`'s [`Module`], out through `parent`, creating the `valid` and payload [`Output`]s of `parent` (as if by [`StreamProducer::drive`]) driven by the corresponding [`Output`]s of `child`, and driving the `ready` [`Input`] of `child` with the `ready` [`Input`] of `parent`.
+///
+/// This allows a [`Module`] to expose a stream produced by one of its child [`Module`]s. `forward_producer` must be called after `child` is driven (with [`StreamProducer::drive`]), and `parent` must not be driven.
+///
+/// # Panics
+///
+/// Panics if `child` doesn't belong to a child [`Module`] of `parent`'s [`Module`], if `child` hasn't been driven yet, under the same conditions as [`StreamProducer::drive`] for `parent`, or under the same conditions as [`Input::drive`] for `child`'s `ready` [`Input`].
+/// Everything is checked before any port is driven, so a panic leaves the stream unconnected.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// bundle! {
+///     pub struct Packet {
+///         pub data: 8,
+///     }
+/// }
+///
+/// let c = Context::new();
+///
+/// let top = c.module("top", "Top");
+/// let out = top.stream_producer::<Packet>("out");
+///
+/// let inner = top.module("inner", "Inner");
+/// let inner_out = inner.stream_producer::<Packet>("out");
+/// inner_out.drive(inner.high(), &Packet { data: inner.input("data", 8) });
+///
+/// forward_producer(&inner_out, &out); // Creates "out_valid" and "out_data" outputs on top
+/// ```
+#[track_caller]
+pub fn forward_producer<'a, B: Bundle<'a>>(
+    child: &StreamProducer<'a, B>,
+    parent: &StreamProducer<'a, B>,
+) {
+    check_forward(
+        &parent.name,
+        parent.ready.module,
+        &child.name,
+        child.ready.module,
+    );
+    let outputs = child.outputs.borrow();
+    let (valid, payload) = match *outputs {
+        Some((valid, ref payload)) => (valid, payload),
+        None => panic!(
+            "Attempted to forward stream \"{}\" in module \"{}\" before it was driven.",
+            child.name,
+            child.ready.module.name()
+        ),
+    };
+    parent.check_drive(valid, payload);
+    child.ready.check_drive(parent.ready.internal_signal());
+
+    parent.drive(valid, payload);
+    child.ready.drive(parent.ready);
+}
+
+#[track_caller]
+fn check_forward<'a>(
+    parent_name: &str,
+    parent_module: &'a Module<'a>,
+    child_name: &str,
+    child_module: &'a Module<'a>,
+) {
+    if !child_module
+        .parent
+        .is_some_and(|parent| ptr::eq(parent, parent_module))
+    {
+        panic!("Attempted to forward stream \"{}\" in module \"{}\" through stream \"{}\" in module \"{}\", but \"{}\" is not a child of \"{}\".", child_name, child_module.name(), parent_name, parent_module.name(), child_module.name(), parent_module.name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    bundle! {
+        struct TestPacket {
+            data: 8,
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to drive stream \"out\" in module \"A\", but this stream is already driven."
+    )]
+    fn producer_already_driven_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let out = m.stream_producer::<TestPacket>("out");
+        let data = m.input("data", 8);
+
+        out.drive(m.high(), &TestPacket { data });
+
+        // Panic
+        out.drive(m.high(), &TestPacket { data });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot create an output called \"out_data\" in module \"A\" because an output with the same name already exists in this module."
+    )]
+    fn producer_payload_name_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let out = m.stream_producer::<TestPacket>("out");
+        let data = m.input("data", 8);
+        m.output("out_data", data);
+
+        // Panic
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            out.drive(m.high(), &TestPacket { data });
+        }));
+        // No ports may be left behind by the failed drive
+        assert!(m.get_output("out_valid").is_none());
+        assert!(out.outputs.borrow().is_none());
+        std::panic::resume_unwind(result.unwrap_err());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to drive the valid signal of stream \"out\" with a 2-bit signal, but valid signals are required to be 1 bit wide."
+    )]
+    fn producer_valid_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let out = m.stream_producer::<TestPacket>("out");
+        let data = m.input("data", 8);
+
+        // Panic
+        out.drive(m.lit(0u32, 2), &TestPacket { data });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to drive the ready signal of stream \"in\" in module \"A\", but this signal is already driven."
+    )]
+    fn consumer_ready_already_driven_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let input = m.stream_consumer::<TestPacket>("in");
+
+        input.drive_ready(m.high());
+
+        // Panic
+        input.drive_ready(m.high());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to drive the ready signal of stream \"in\" with a 8-bit signal, but ready signals are required to be 1 bit wide."
+    )]
+    fn consumer_ready_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let input = m.stream_consumer::<TestPacket>("in");
+
+        // Panic
+        input.drive_ready(input.payload().data);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to connect stream \"out\" in module \"Producer\" before it was driven."
+    )]
+    fn connect_producer_not_driven_error() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let producer = top.module("producer", "Producer");
+        let out = producer.stream_producer::<TestPacket>("out");
+        let consumer = top.module("consumer", "Consumer");
+        let input = consumer.stream_consumer::<TestPacket>("in");
+        input.drive_ready(consumer.high());
+
+        // Panic
+        connect(&out, &input);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to connect stream \"in\" in module \"Consumer\" before its ready signal was driven."
+    )]
+    fn connect_consumer_not_driven_error() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let producer = top.module("producer", "Producer");
+        let out = producer.stream_producer::<TestPacket>("out");
+        out.drive(
+            producer.high(),
+            &TestPacket {
+                data: producer.input("data", 8),
+            },
+        );
+        let consumer = top.module("consumer", "Consumer");
+        let input = consumer.stream_consumer::<TestPacket>("in");
+
+        // Panic
+        connect(&out, &input);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to drive an instance input with a signal from a different module than that instance's parent module."
+    )]
+    fn connect_separate_parent_error() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let producer = top.module("producer", "Producer");
+        let out = producer.stream_producer::<TestPacket>("out");
+        out.drive(
+            producer.high(),
+            &TestPacket {
+                data: producer.input("data", 8),
+            },
+        );
+        let inner = top.module("inner", "Inner");
+        let consumer = inner.module("consumer", "Consumer");
+        let input = consumer.stream_consumer::<TestPacket>("in");
+        input.drive_ready(consumer.high());
+
+        // Panic
+        connect(&out, &input);
+    }
+
+    #[test]
+    fn connect_checks_all_inputs_before_driving() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let producer = top.module("producer", "Producer");
+        let out = producer.stream_producer::<TestPacket>("out");
+        out.drive(
+            producer.high(),
+            &TestPacket {
+                data: producer.input("data", 8),
+            },
+        );
+        let consumer = top.module("consumer", "Consumer");
+        let input = consumer.stream_consumer::<TestPacket>("in");
+        input.drive_ready(consumer.high());
+        out.ready().drive(top.high());
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            connect(&out, &input);
+        }));
+        assert!(result.is_err());
+        assert!(input.valid().driver().is_none());
+        assert!(input.payload.inputs()[0].driver().is_none());
+    }
+
+    #[test]
+    fn forward() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let input = top.stream_consumer::<TestPacket>("in");
+        let out = top.stream_producer::<TestPacket>("out");
+
+        let inner = top.module("inner", "Inner");
+        let inner_input = inner.stream_consumer::<TestPacket>("in");
+        let inner_out = inner.stream_producer::<TestPacket>("out");
+        inner_input.drive_ready(inner_out.ready());
+        inner_out.drive(inner_input.valid(), inner_input.payload());
+
+        forward_consumer(&input, &inner_input);
+        forward_producer(&inner_out, &out);
+
+        assert!(inner_input.valid().driver().is_some());
+        assert!(inner_input.payload.inputs()[0].driver().is_some());
+        assert!(inner_out.ready().driver().is_some());
+        assert!(input.ready.borrow().is_some());
+        assert!(out.outputs.borrow().is_some());
+        assert_eq!(top.outputs().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to forward stream \"in\" in module \"Consumer\" through stream \"in\" in module \"Top\", but \"Consumer\" is not a child of \"Top\"."
+    )]
+    fn forward_consumer_not_child_error() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let input = top.stream_consumer::<TestPacket>("in");
+        let inner = top.module("inner", "Inner");
+        let consumer = inner.module("consumer", "Consumer");
+        let consumer_input = consumer.stream_consumer::<TestPacket>("in");
+        consumer_input.drive_ready(consumer.high());
+
+        // Panic
+        forward_consumer(&input, &consumer_input);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to forward stream \"in\" in module \"Inner\" before its ready signal was driven."
+    )]
+    fn forward_consumer_not_driven_error() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let input = top.stream_consumer::<TestPacket>("in");
+        let inner = top.module("inner", "Inner");
+        let inner_input = inner.stream_consumer::<TestPacket>("in");
+
+        // Panic
+        forward_consumer(&input, &inner_input);
+    }
+
+    #[test]
+    fn forward_consumer_checks_all_ports_before_driving() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let input = top.stream_consumer::<TestPacket>("in");
+        let inner = top.module("inner", "Inner");
+        let inner_input = inner.stream_consumer::<TestPacket>("in");
+        inner_input.drive_ready(inner.high());
+        inner_input.payload.inputs()[0].drive(top.lit(0u32, 8));
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            forward_consumer(&input, &inner_input);
+        }));
+        assert!(result.is_err());
+        assert!(inner_input.valid().driver().is_none());
+        assert!(input.ready.borrow().is_none());
+        assert!(top.outputs().is_empty());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to forward stream \"out\" in module \"Inner\" through stream \"out\" in module \"Other\", but \"Inner\" is not a child of \"Other\"."
+    )]
+    fn forward_producer_not_child_error() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let other = top.module("other", "Other");
+        let out = other.stream_producer::<TestPacket>("out");
+        let inner = top.module("inner", "Inner");
+        let inner_out = inner.stream_producer::<TestPacket>("out");
+        inner_out.drive(
+            inner.high(),
+            &TestPacket {
+                data: inner.input("data", 8),
+            },
+        );
+
+        // Panic
+        forward_producer(&inner_out, &out);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to forward stream \"out\" in module \"Inner\" before it was driven."
+    )]
+    fn forward_producer_not_driven_error() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let out = top.stream_producer::<TestPacket>("out");
+        let inner = top.module("inner", "Inner");
+        let inner_out = inner.stream_producer::<TestPacket>("out");
+
+        // Panic
+        forward_producer(&inner_out, &out);
+    }
+
+    #[test]
+    fn forward_producer_checks_all_ports_before_driving() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let out = top.stream_producer::<TestPacket>("out");
+        let inner = top.module("inner", "Inner");
+        let inner_out = inner.stream_producer::<TestPacket>("out");
+        inner_out.drive(
+            inner.high(),
+            &TestPacket {
+                data: inner.input("data", 8),
+            },
+        );
+        inner_out.ready().drive(top.high());
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            forward_producer(&inner_out, &out);
+        }));
+        assert!(result.is_err());
+        assert!(out.outputs.borrow().is_none());
+        assert!(top.outputs().is_empty());
+    }
+
+    #[test]
+    fn forward_producer_checks_port_names_before_driving() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let out = top.stream_producer::<TestPacket>("out");
+        top.output("out_data", top.lit(0u32, 8));
+        let inner = top.module("inner", "Inner");
+        let inner_out = inner.stream_producer::<TestPacket>("out");
+        inner_out.drive(
+            inner.high(),
+            &TestPacket {
+                data: inner.input("data", 8),
+            },
+        );
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            forward_producer(&inner_out, &out);
+        }));
+        assert!(result.is_err());
+        assert!(top.get_output("out_valid").is_none());
+        assert!(inner_out.ready().driver().is_none());
+    }
+}
@@ -1,5 +1,10 @@
+use super::constant::*;
+use super::context::*;
 use super::signal::*;
 
+use std::panic::Location;
+use std::ptr;
+
 /// **UNSTABLE:** Provides a convenient way to write conditional combinational logic.
 ///
 /// # Panics
@@ -24,6 +29,7 @@ use super::signal::*;
 /// m.output("o", o);
 /// ```
 // TODO: Can we constrain T more than this to make sure it's only a supported type?
+#[track_caller]
 pub fn if_<'a, T>(cond: &'a dyn Signal<'a>, when_true: T) -> If<'a, T> {
     If::new(cond, when_true)
 }
@@ -35,10 +41,12 @@ pub struct If<'a, T> {
 }
 
 impl<'a, T> If<'a, T> {
+    #[track_caller]
     fn new(cond: &'a dyn Signal<'a>, when_true: T) -> If<'a, T> {
         If { cond, when_true }
     }
 
+    #[track_caller]
     pub fn else_if(self, cond: &'a dyn Signal<'a>, when_true: T) -> ElseIf<'a, T> {
         ElseIf {
             parent: ElseIfParent::If(self),
@@ -49,6 +57,7 @@ impl<'a, T> If<'a, T> {
 }
 
 impl<'a, T: Into<&'a dyn Signal<'a>>> If<'a, T> {
+    #[track_caller]
     pub fn else_<F: Into<&'a dyn Signal<'a>>>(self, when_false: F) -> &'a dyn Signal<'a> {
         self.cond.mux(self.when_true.into(), when_false.into())
     }
@@ -59,6 +68,7 @@ macro_rules! replace_tt { ($t:tt, $($i:tt)*) => { $($i)* } }
 macro_rules! generate_if {
     (($($number: tt, $t: tt, $f: tt),*)) => {
         impl<'a, $($t: Into<&'a dyn Signal<'a>>),*,> If<'a, ($($t),*,)> {
+            #[track_caller]
             pub fn else_<$($f: Into<&'a dyn Signal<'a>>),*,>(self, when_false: ($($f),*,)) -> ($(&'a replace_tt!($number, dyn Signal<'a>)),*,) {
                 (
                     $(self.cond.mux(self.when_true.$number.into(), when_false.$number.into())),*,
@@ -108,6 +118,7 @@ pub struct ElseIf<'a, T> {
 }
 
 impl<'a, T> ElseIf<'a, T> {
+    #[track_caller]
     pub fn else_if(self, cond: &'a dyn Signal<'a>, when_true: T) -> ElseIf<'a, T> {
         ElseIf {
             parent: ElseIfParent::ElseIf(Box::new(self)),
@@ -118,6 +129,7 @@ impl<'a, T> ElseIf<'a, T> {
 }
 
 impl<'a, T: Into<&'a dyn Signal<'a>>> ElseIf<'a, T> {
+    #[track_caller]
     pub fn else_<F: Into<&'a dyn Signal<'a>>>(self, when_false: F) -> &'a dyn Signal<'a> {
         let ret = self.cond.mux(self.when_true.into(), when_false.into());
         match self.parent {
@@ -130,6 +142,7 @@ impl<'a, T: Into<&'a dyn Signal<'a>>> ElseIf<'a, T> {
 macro_rules! generate_else_if {
     (($($number: tt, $t: tt, $f: tt),*)) => {
         impl<'a, $($t: Into<&'a dyn Signal<'a>>),*,> ElseIf<'a, ($($t),*,)> {
+            #[track_caller]
             pub fn else_<$($f: Into<&'a dyn Signal<'a>>),*,>(self, when_false: ($($f),*,)) -> ($(&'a replace_tt!($number, dyn Signal<'a>)),*,) {
                 let ret = (
                     $(self.cond.mux(self.when_true.$number.into(), when_false.$number.into())),*,
@@ -169,3 +182,466 @@ generate_else_if!((
     0, T0, F0, 1, T1, F1, 2, T2, F2, 3, T3, F3, 4, T4, F4, 5, T5, F5, 6, T6, F6, 7, T7, F7, 8, T8,
     F8, 9, T9, F9, 10, T10, F10, 11, T11, F11
 ));
+
+/// **UNSTABLE:** Provides a convenient way to select between many values based on the value of a selector, similar to a `case` statement in Verilog.
+///
+/// Each `case` specifies the value selected when `sel` is equal to a given constant. The switch is completed with either `default`, which specifies the value selected when no case matches, or `end`, which selects the last case's value instead. Like [`if_`], a switch can also select tuples of values, in which case every case (and the default) must provide a tuple of the same size.
+///
+/// Switches whose cases cover most of `sel`'s values are built as a tree of multiplexers indexed by `sel`'s bits, which is much shallower than the chain of multiplexers an equivalent [`if_`] would produce. Other switches compare `sel` against each case in turn.
+///
+/// # Panics
+///
+/// Panics if a case's value doesn't fit in `sel`'s bit width, if more than one case is specified for the same value, or if `end` is used on a switch without any cases.
+///
+/// Since this construct wraps the selected values with [`Signal::mux`], any panic conditions from that method apply to the generated code as well.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let p = Context::new();
+///
+/// let m = p.module("m", "MyModule");
+/// let op = m.input("op", 2);
+/// let a = m.input("a", 8);
+/// let b = m.input("b", 8);
+/// let o = switch_(op)
+///     .case(0u32, a + b)
+///     .case(1u32, a - b)
+///     .case(2u32, a & b)
+///     .default(a | b);
+/// m.output("o", o);
+///
+/// let (x, y) = switch_(op)
+///     .case(0u32, (a, b))
+///     .case(1u32, (b, a))
+///     .end(); // Values 2 and 3 select the last case, which lint reports
+/// m.output("x", x);
+/// m.output("y", y);
+/// ```
+#[track_caller]
+pub fn switch_<'a, O: SwitchOutput<'a>>(sel: &'a dyn Signal<'a>) -> Switch<'a, O> {
+    Switch::new(sel)
+}
+
+#[doc(hidden)]
+#[must_use]
+pub struct Switch<'a, O> {
+    sel: &'a dyn Signal<'a>,
+    location: &'static Location<'static>,
+    cases: Vec<(Vec<u64>, Vec<&'a dyn Signal<'a>>)>,
+    output: std::marker::PhantomData<O>,
+}
+
+impl<'a, O: SwitchOutput<'a>> Switch<'a, O> {
+    #[track_caller]
+    fn new(sel: &'a dyn Signal<'a>) -> Switch<'a, O> {
+        Switch {
+            sel,
+            location: Location::caller(),
+            cases: Vec::new(),
+            output: std::marker::PhantomData,
+        }
+    }
+
+    /// Adds a case that selects `when_equal` when the selector is equal to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` doesn't fit in the selector's bit width, or if a case for `value` was already added.
+    #[track_caller]
+    pub fn case<A: SwitchArm<'a, O>>(mut self, value: impl Into<Constant>, when_equal: A) -> Self {
+        let value = value.into();
+        let bit_width = self.sel.bit_width();
+        let required_bits = value.required_bits();
+        if required_bits > bit_width {
+            let value_string = value.value_string();
+            panic!("Cannot fit the specified case value '{}' into the switch selector's bit width '{}'. The value '{}' requires a bit width of at least {} bit(s).", value_string, bit_width, value_string, required_bits);
+        }
+        let words = value.words(bit_width.div_ceil(64) as usize);
+        if self
+            .cases
+            .iter()
+            .any(|(case_words, _)| *case_words == words)
+        {
+            panic!(
+                "Attempted to add a case for the value '{}' to a switch which already has a case for this value.",
+                value.value_string()
+            );
+        }
+        self.cases.push((words, when_equal.into_signals()));
+        self
+    }
+
+    /// Completes this switch, selecting `default` when the selector doesn't match any case.
+    #[track_caller]
+    pub fn default<A: SwitchArm<'a, O>>(self, default: A) -> O {
+        let default = default.into_signals();
+        O::from_signals(self.select(default))
+    }
+
+    /// Completes this switch without a default, selecting the last case's value when the selector doesn't match any case.
+    ///
+    /// If the cases don't cover every value of the selector, this is reported by [`lint`](crate::lint) as a [`Warning::IncompleteSwitch`](crate::Warning::IncompleteSwitch).
+    ///
+    /// # Panics
+    ///
+    /// Panics if no cases were added to this switch.
+    #[track_caller]
+    pub fn end(mut self) -> O {
+        let (_, fallback) = match self.cases.pop() {
+            Some(case) => case,
+            None => panic!("Attempted to end a switch without any cases."),
+        };
+        let case_count = self.cases.len() + 1;
+        let bit_width = self.sel.bit_width();
+        if bit_width >= usize::BITS || case_count != 1 << bit_width {
+            let sel = self.sel.internal_signal();
+            sel.module
+                .incomplete_switches
+                .borrow_mut()
+                .push(IncompleteSwitch {
+                    case_count,
+                    selector_bit_width: bit_width,
+                    location: self.location,
+                });
+        }
+        O::from_signals(self.select(fallback))
+    }
+
+    #[track_caller]
+    fn select(self, fallback: Vec<&'a dyn Signal<'a>>) -> Vec<&'a dyn Signal<'a>> {
+        let sel = self.sel;
+        let bit_width = sel.bit_width();
+        if bit_width <= MAX_SWITCH_TREE_SELECTOR_BIT_WIDTH && self.cases.len() * 2 >= 1 << bit_width
+        {
+            (0..fallback.len())
+                .map(|i| {
+                    let mut values = vec![fallback[i]; 1 << bit_width];
+                    for (value, when_equal) in self.cases.iter() {
+                        values[value[0] as usize] = when_equal[i];
+                    }
+                    for index in 0..bit_width {
+                        let cond = sel.bit(index);
+                        values = values
+                            .chunks(2)
+                            .map(|pair| {
+                                if ptr::eq(pair[0].internal_signal(), pair[1].internal_signal()) {
+                                    pair[0]
+                                } else {
+                                    cond.mux(pair[1], pair[0])
+                                }
+                            })
+                            .collect();
+                    }
+                    values[0]
+                })
+                .collect()
+        } else {
+            let module = sel.internal_signal().module;
+            let mut ret = fallback;
+            for (value, when_equal) in self.cases.into_iter().rev() {
+                let cond = sel.eq(module.lit(Constant::Wide(value), bit_width));
+                ret = when_equal
+                    .into_iter()
+                    .zip(ret)
+                    .map(|(when_equal, when_not_equal)| cond.mux(when_equal, when_not_equal))
+                    .collect();
+            }
+            ret
+        }
+    }
+}
+
+// Selectors up to this wide are candidates for a multiplexer tree, which has 2^bit_width leaves
+const MAX_SWITCH_TREE_SELECTOR_BIT_WIDTH: u32 = 10;
+
+#[derive(Clone)]
+pub(crate) struct IncompleteSwitch {
+    pub case_count: usize,
+    pub selector_bit_width: u32,
+    pub location: &'static Location<'static>,
+}
+
+#[doc(hidden)]
+pub trait SwitchOutput<'a> {
+    fn from_signals(signals: Vec<&'a dyn Signal<'a>>) -> Self;
+}
+
+#[doc(hidden)]
+pub trait SwitchArm<'a, O> {
+    fn into_signals(self) -> Vec<&'a dyn Signal<'a>>;
+}
+
+impl<'a> SwitchOutput<'a> for &'a dyn Signal<'a> {
+    fn from_signals(signals: Vec<&'a dyn Signal<'a>>) -> Self {
+        signals[0]
+    }
+}
+
+impl<'a, T: Into<&'a dyn Signal<'a>>> SwitchArm<'a, &'a dyn Signal<'a>> for T {
+    fn into_signals(self) -> Vec<&'a dyn Signal<'a>> {
+        vec![self.into()]
+    }
+}
+
+macro_rules! generate_switch {
+    (($($number: tt, $t: tt),*)) => {
+        impl<'a> SwitchOutput<'a> for ($(&'a replace_tt!($number, dyn Signal<'a>)),*,) {
+            fn from_signals(signals: Vec<&'a dyn Signal<'a>>) -> Self {
+                ($(signals[$number]),*,)
+            }
+        }
+
+        impl<'a, $($t: Into<&'a dyn Signal<'a>>),*,> SwitchArm<'a, ($(&'a replace_tt!($number, dyn Signal<'a>)),*,)> for ($($t),*,) {
+            fn into_signals(self) -> Vec<&'a dyn Signal<'a>> {
+                vec![$(self.$number.into()),*]
+            }
+        }
+    };
+}
+
+generate_switch!((0, T0));
+generate_switch!((0, T0, 1, T1));
+generate_switch!((0, T0, 1, T1, 2, T2));
+generate_switch!((0, T0, 1, T1, 2, T2, 3, T3));
+generate_switch!((0, T0, 1, T1, 2, T2, 3, T3, 4, T4));
+generate_switch!((0, T0, 1, T1, 2, T2, 3, T3, 4, T4, 5, T5));
+generate_switch!((0, T0, 1, T1, 2, T2, 3, T3, 4, T4, 5, T5, 6, T6));
+generate_switch!((0, T0, 1, T1, 2, T2, 3, T3, 4, T4, 5, T5, 6, T6, 7, T7));
+generate_switch!((0, T0, 1, T1, 2, T2, 3, T3, 4, T4, 5, T5, 6, T6, 7, T7, 8, T8));
+generate_switch!((0, T0, 1, T1, 2, T2, 3, T3, 4, T4, 5, T5, 6, T6, 7, T7, 8, T8, 9, T9));
+generate_switch!((0, T0, 1, T1, 2, T2, 3, T3, 4, T4, 5, T5, 6, T6, 7, T7, 8, T8, 9, T9, 10, T10));
+generate_switch!((
+    0, T0, 1, T1, 2, T2, 3, T3, 4, T4, 5, T5, 6, T6, 7, T7, 8, T8, 9, T9, 10, T10, 11, T11
+));
+
+/// **UNSTABLE:** Provides a convenient way to write conditional assignments to [`Register`](crate::Register)s and [`Mem`](crate::Mem)s.
+///
+/// Calls `f` immediately, and any [`Register::assign`](crate::Register::assign) or [`Mem::write`](crate::Mem::write) calls made within `f` only take effect while `cond` is high.
+/// `when` blocks can be nested, in which case the inner block's assignments only take effect while the conditions of all enclosing blocks are high as well.
+/// Further branches can be added with [`else_when`](When::else_when) and [`otherwise`](When::otherwise), and assignments in a later block (including those following the whole `when` construct) take priority over assignments in earlier blocks.
+///
+/// A `Register` that isn't assigned while none of the conditions hold keeps its current value.
+///
+/// # Panics
+///
+/// Panics if `cond`'s bit width is not 1, or if `cond` belongs to a different [`Module`](crate::Module) than the conditions of any enclosing `when` blocks.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let start = m.input("start", 1);
+/// let done = m.input("done", 1);
+///
+/// let busy = m.reg("busy", 1);
+/// busy.default_value(false);
+/// let count = m.reg("count", 8);
+/// count.default_value(0u32);
+///
+/// when(start, || {
+///     busy.assign(m.high());
+///     count.assign(m.lit(0u32, 8));
+/// })
+/// .else_when(busy, || {
+///     count.assign(count + 1);
+///     when(done, || {
+///         busy.assign(m.low());
+///     });
+/// });
+///
+/// m.output("busy_out", busy);
+/// m.output("count_out", count);
+/// ```
+#[track_caller]
+pub fn when<'a>(cond: &'a dyn Signal<'a>, f: impl FnOnce()) -> When<'a> {
+    check_when_cond(cond);
+    let context = cond.internal_signal().context;
+    let parent = context
+        .when_guards
+        .borrow()
+        .last()
+        .map(|&guard| guard as &'a dyn Signal<'a>);
+    let guard = match parent {
+        Some(parent) => parent & cond,
+        None => cond,
+    };
+    run_guarded(guard, f);
+    When {
+        parent,
+        taken: cond,
+    }
+}
+
+#[doc(hidden)]
+pub struct When<'a> {
+    parent: Option<&'a dyn Signal<'a>>,
+    taken: &'a dyn Signal<'a>,
+}
+
+impl<'a> When<'a> {
+    /// Adds a block whose assignments only take effect while `cond` is high and the conditions of all previous blocks in this `when` construct are low.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cond`'s bit width is not 1, or if `cond` belongs to a different [`Module`](crate::Module) than the previous conditions.
+    #[track_caller]
+    pub fn else_when(self, cond: &'a dyn Signal<'a>, f: impl FnOnce()) -> When<'a> {
+        check_when_cond(cond);
+        run_guarded(self.guard(!self.taken & cond), f);
+        When {
+            parent: self.parent,
+            taken: self.taken | cond,
+        }
+    }
+
+    /// Adds a final block whose assignments only take effect while the conditions of all previous blocks in this `when` construct are low.
+    #[track_caller]
+    pub fn otherwise(self, f: impl FnOnce()) {
+        run_guarded(self.guard(!self.taken), f);
+    }
+
+    #[track_caller]
+    fn guard(&self, cond: &'a dyn Signal<'a>) -> &'a dyn Signal<'a> {
+        match self.parent {
+            Some(parent) => parent & cond,
+            None => cond,
+        }
+    }
+}
+
+#[track_caller]
+fn check_when_cond<'a>(cond: &'a dyn Signal<'a>) {
+    if cond.bit_width() != 1 {
+        panic!("When block conditions can only be 1 bit wide.");
+    }
+}
+
+fn run_guarded<'a>(guard: &'a dyn Signal<'a>, f: impl FnOnce()) {
+    let guard = guard.internal_signal();
+    guard.context.when_guards.borrow_mut().push(guard);
+    let _pop_guard = PopWhenGuard {
+        context: guard.context,
+    };
+    f();
+}
+
+// Pops the innermost when guard when dropped, so that it's removed even if the block panics
+struct PopWhenGuard<'a> {
+    context: &'a Context<'a>,
+}
+
+impl<'a> Drop for PopWhenGuard<'a> {
+    fn drop(&mut self) {
+        self.context.when_guards.borrow_mut().pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot fit the specified case value '4' into the switch selector's bit width '2'. The value '4' requires a bit width of at least 3 bit(s)."
+    )]
+    fn switch_case_oob_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let s = m.input("s", 2);
+        let i = m.input("i", 1);
+
+        // Panic
+        let _ = switch_(s).case(4u32, i).default(i);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempted to add a case for the value '1' to a switch which already has a case for this value."
+    )]
+    fn switch_duplicate_case_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let s = m.input("s", 2);
+        let i = m.input("i", 1);
+
+        // Panic
+        let _ = switch_(s).case(1u32, i).case(1u8, !i).default(i);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to end a switch without any cases.")]
+    fn switch_end_without_cases_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let s = m.input("s", 2);
+
+        // Panic
+        let _: &dyn Signal = switch_(s).end();
+    }
+
+    #[test]
+    #[should_panic(expected = "When block conditions can only be 1 bit wide.")]
+    fn when_cond_bit_width_error() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let cond = m.input("cond", 2);
+
+        // Panic
+        when(cond, || {});
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to combine signals from different modules.")]
+    fn when_nested_separate_module_error() {
+        let c = Context::new();
+
+        let m1 = c.module("a", "A");
+        let cond1 = m1.input("cond", 1);
+
+        let m2 = c.module("b", "B");
+        let cond2 = m2.input("cond", 1);
+
+        when(cond1, || {
+            // Panic
+            when(cond2, || {});
+        });
+    }
+
+    #[test]
+    fn when_guard_popped_on_panic() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let cond = m.input("cond", 1);
+        let r = m.reg("r", 1);
+        r.default_value(false);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            when(cond, || {
+                when(cond, || panic!("Panic inside when block"));
+            });
+        }));
+        assert!(result.is_err());
+        assert!(c.when_guards.borrow().is_empty());
+
+        // Assignments after the panic must not be guarded by the abandoned blocks
+        let high = m.high();
+        r.assign(high);
+        assert!(std::ptr::eq(
+            r.data.next.borrow().unwrap(),
+            high.internal_signal()
+        ));
+    }
+}
@@ -31,19 +31,27 @@
 //! sim::generate(inverter, sim::GenerationOptions::default(), std::io::stdout())?;
 //!
 //! // Generate Verilog code
-//! //verilog::generate(inverter, std::io::stdout())?;
+//! verilog::generate(inverter, std::io::stdout())?;
 //! # Ok(())
 //! # }
 //! ```
 
 // Must be kept up-to-date with version in Cargo.toml
 #![doc(html_root_url = "https://docs.rs/kaze/0.1.19")]
+// Graph nodes are hashed and compared by address, so interior mutability in keys is harmless
+#![allow(clippy::mutable_key_type)]
+// Errors carry owned names for reporting and are only produced on the (cold) failure path
+#![allow(clippy::result_large_err)]
 
 mod code_writer;
+mod error;
 mod graph;
+mod lint;
 pub mod runtime;
 pub mod sim;
 mod validation;
-//pub mod verilog;
+pub mod verilog;
 
+pub use error::*;
 pub use graph::*;
+pub use lint::*;
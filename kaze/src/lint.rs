@@ -0,0 +1,620 @@
+use crate::graph;
+use crate::graph::internal_signal;
+use crate::validation::combinational_sources;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::panic::Location;
+
+/// A warning describing hardware in a [`Module`](crate::Module) hierarchy that's legal but likely unintended, returned by [`lint`].
+///
+/// Like [`Error`](crate::Error)s, each variant carries the path of the [`Module`](crate::Module) instance the warning refers to (eg. `"top.cpu.alu"`), the names involved, and the source location the offending item was declared at, which is included in the warning's message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// An input's value is never read, either by logic in its module or by any of its module's instances.
+    UnusedInput {
+        module_path: String,
+        module_name: String,
+        input_name: String,
+        location: &'static Location<'static>,
+    },
+    /// A register's value never affects any output of the root module of the hierarchy.
+    UnobservableRegister {
+        root_module_name: String,
+        module_path: String,
+        module_name: String,
+        register_name: String,
+        location: &'static Location<'static>,
+    },
+    /// An output's value only depends on literals.
+    ConstantOutput {
+        module_path: String,
+        module_name: String,
+        output_name: String,
+        location: &'static Location<'static>,
+    },
+    /// A memory's write port is enabled by a literal low signal, so the memory is never written.
+    MemWritePortNeverEnabled {
+        module_path: String,
+        module_name: String,
+        mem_name: String,
+        location: &'static Location<'static>,
+    },
+    /// A multiplexer's condition is a literal, so it always selects the same input.
+    ConstantMuxCondition {
+        module_path: String,
+        module_name: String,
+        cond: bool,
+        location: &'static Location<'static>,
+    },
+    /// A switch completed without a default doesn't have a case for every value of its selector, so the remaining values select its last case.
+    IncompleteSwitch {
+        module_path: String,
+        module_name: String,
+        case_count: usize,
+        selector_bit_width: u32,
+        location: &'static Location<'static>,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::UnusedInput {
+                module_name,
+                input_name,
+                location,
+                ..
+            } => write!(f, "Module \"{}\" contains an input called \"{}\" which is never read. The input was declared at {}:{}.", module_name, input_name, location.file(), location.line()),
+            Warning::UnobservableRegister {
+                root_module_name,
+                module_name,
+                register_name,
+                location,
+                ..
+            } => write!(f, "Module \"{}\" contains a register called \"{}\" whose value never reaches an output of module \"{}\". The register was declared at {}:{}.", module_name, register_name, root_module_name, location.file(), location.line()),
+            Warning::ConstantOutput {
+                module_name,
+                output_name,
+                location,
+                ..
+            } => write!(f, "Module \"{}\" contains an output called \"{}\" whose value is constant. The output's source signal was created at {}:{}.", module_name, output_name, location.file(), location.line()),
+            Warning::MemWritePortNeverEnabled {
+                module_name,
+                mem_name,
+                location,
+                ..
+            } => write!(f, "Module \"{}\" contains a memory called \"{}\" whose write port is never enabled. The memory was declared at {}:{}.", module_name, mem_name, location.file(), location.line()),
+            Warning::ConstantMuxCondition {
+                module_name,
+                cond,
+                location,
+                ..
+            } => write!(f, "Module \"{}\" contains a multiplexer whose condition is a literal, so it always selects its {} input. The multiplexer was created at {}:{}.", module_name, if *cond { "true" } else { "false" }, location.file(), location.line()),
+            Warning::IncompleteSwitch {
+                module_name,
+                case_count,
+                selector_bit_width,
+                location,
+                ..
+            } => write!(f, "Module \"{}\" contains a switch without a default whose {} case(s) don't cover every value of its {}-bit selector. The switch was created at {}:{}.", module_name, case_count, selector_bit_width, location.file(), location.line()),
+        }
+    }
+}
+
+/// Checks the [`Module`](crate::Module) hierarchy rooted at `m` for hardware that's legal but likely unintended, and returns a [`Warning`] for each occurrence.
+///
+/// The following are reported:
+/// - [`Input`](crate::Input)s that are never read
+/// - [`Register`](crate::Register)s whose values never reach an output of `m`
+/// - [`Output`](crate::Output)s whose values only depend on literals
+/// - [`Mem`](crate::Mem)s whose write port enable is a literal low signal
+/// - Multiplexers whose condition is a literal
+/// - Switches (see [`switch_`](crate::switch_)) completed without a default whose cases don't cover every value of their selector
+///
+/// Unlike the generators, this function doesn't panic if the hierarchy is invalid (see [`Module::validate`](crate::Module::validate)); undriven items are simply ignored.
+/// Warnings are returned in hierarchy order, starting with those for `m` itself.
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let m = c.module("m", "MyModule");
+///
+/// let a = m.input("a", 8);
+/// let b = m.input("b", 8);
+/// let _ = m.input("c", 8);
+/// m.output("o", m.mux(m.high(), a, b));
+///
+/// let warnings = lint(m);
+/// assert_eq!(warnings.len(), 2);
+/// assert!(matches!(&warnings[0], Warning::UnusedInput { input_name, .. } if input_name == "c"));
+/// assert!(matches!(warnings[1], Warning::ConstantMuxCondition { cond: true, .. }));
+/// ```
+pub fn lint<'a>(m: &'a graph::Module<'a>) -> Vec<Warning> {
+    // Every signal read anywhere in the hierarchy, in the order they were first visited
+    let mut used_signals = Vec::new();
+    let mut used = HashSet::new();
+    visit_used_signals(m, &mut used, &mut used_signals);
+
+    // Every signal that can affect an output of the root module
+    let mut observable = HashSet::new();
+    let roots = m
+        .outputs
+        .borrow()
+        .values()
+        .map(|output| output.data.source)
+        .collect::<Vec<_>>();
+    visit_signals(roots, &mut observable, &mut Vec::new());
+
+    // Grouped by owning module once, so each module only looks at its own signals
+    let mut used_signals_by_module: HashMap<_, Vec<_>> = HashMap::new();
+    for signal in used_signals {
+        used_signals_by_module
+            .entry(signal.module)
+            .or_default()
+            .push(signal);
+    }
+
+    let mut warnings = Vec::new();
+    lint_module(
+        m,
+        m,
+        &used,
+        &used_signals_by_module,
+        &observable,
+        &mut warnings,
+    );
+    warnings
+}
+
+fn visit_used_signals<'a>(
+    m: &'a graph::Module<'a>,
+    used: &mut HashSet<&'a internal_signal::InternalSignal<'a>>,
+    used_signals: &mut Vec<&'a internal_signal::InternalSignal<'a>>,
+) {
+    let mut roots = Vec::new();
+    for output in m.outputs.borrow().values() {
+        roots.push(output.data.source);
+    }
+    roots.extend(m.registers.borrow().iter().copied());
+    for reset in m.resets.borrow().iter() {
+        roots.push(reset.source);
+    }
+    for mem in m.mems.borrow().iter() {
+        for &(address, enable) in mem.read_ports.borrow().iter() {
+            roots.push(address);
+            roots.push(enable);
+        }
+        if let Some((address, value, enable)) = *mem.write_port.borrow() {
+            roots.push(address);
+            roots.push(value);
+            roots.push(enable);
+        }
+    }
+    for module in m.modules.borrow().iter() {
+        for input in module.inputs.borrow().values() {
+            roots.extend(*input.data.driven_value.borrow());
+        }
+    }
+    visit_signals(roots, used, used_signals);
+
+    for module in m.modules.borrow().iter() {
+        visit_used_signals(module, used, used_signals);
+    }
+}
+
+fn visit_signals<'a>(
+    mut stack: Vec<&'a internal_signal::InternalSignal<'a>>,
+    visited: &mut HashSet<&'a internal_signal::InternalSignal<'a>>,
+    visited_signals: &mut Vec<&'a internal_signal::InternalSignal<'a>>,
+) {
+    stack.reverse();
+    while let Some(signal) = stack.pop() {
+        if !visited.insert(signal) {
+            continue;
+        }
+        visited_signals.push(signal);
+
+        // Unlike combinational dependencies, these cross register and memory boundaries
+        let mut sources = combinational_sources(signal);
+        match signal.data {
+            internal_signal::SignalData::Reg { data } => {
+                sources.extend(*data.next.borrow());
+                if let Some(reset) = *data.reset.borrow() {
+                    sources.push(reset.source);
+                }
+            }
+            internal_signal::SignalData::MemReadPortOutput {
+                mem,
+                address,
+                enable,
+            } => {
+                sources.push(address);
+                sources.push(enable);
+                if let Some((address, value, enable)) = *mem.write_port.borrow() {
+                    sources.push(address);
+                    sources.push(value);
+                    sources.push(enable);
+                }
+            }
+            _ => (),
+        }
+        stack.extend(sources.into_iter().rev());
+    }
+}
+
+fn lint_module<'a>(
+    m: &'a graph::Module<'a>,
+    root: &'a graph::Module<'a>,
+    used: &HashSet<&'a internal_signal::InternalSignal<'a>>,
+    used_signals_by_module: &HashMap<
+        &'a graph::Module<'a>,
+        Vec<&'a internal_signal::InternalSignal<'a>>,
+    >,
+    observable: &HashSet<&'a internal_signal::InternalSignal<'a>>,
+    warnings: &mut Vec<Warning>,
+) {
+    for (name, input) in m.inputs.borrow().iter() {
+        if !used.contains(&input.value) {
+            warnings.push(Warning::UnusedInput {
+                module_path: m.path(),
+                module_name: m.name.clone(),
+                input_name: name.clone(),
+                location: input.data.location,
+            });
+        }
+    }
+
+    for register in m.registers.borrow().iter() {
+        if !observable.contains(register) {
+            if let internal_signal::SignalData::Reg { data } = register.data {
+                warnings.push(Warning::UnobservableRegister {
+                    root_module_name: root.name.clone(),
+                    module_path: m.path(),
+                    module_name: m.name.clone(),
+                    register_name: data.name.clone(),
+                    location: data.location,
+                });
+            }
+        }
+    }
+
+    for (name, output) in m.outputs.borrow().iter() {
+        if is_constant(output.data.source) {
+            warnings.push(Warning::ConstantOutput {
+                module_path: m.path(),
+                module_name: m.name.clone(),
+                output_name: name.clone(),
+                location: output.data.source.location,
+            });
+        }
+    }
+
+    for mem in m.mems.borrow().iter() {
+        if let Some((_, _, enable)) = *mem.write_port.borrow() {
+            if literal_value(enable) == Some(false) {
+                warnings.push(Warning::MemWritePortNeverEnabled {
+                    module_path: m.path(),
+                    module_name: m.name.clone(),
+                    mem_name: mem.name.clone(),
+                    location: mem.location,
+                });
+            }
+        }
+    }
+
+    for signal in used_signals_by_module.get(&m).into_iter().flatten() {
+        if let internal_signal::SignalData::Mux { cond, .. } = signal.data {
+            if let Some(cond) = literal_value(cond) {
+                warnings.push(Warning::ConstantMuxCondition {
+                    module_path: m.path(),
+                    module_name: m.name.clone(),
+                    cond,
+                    location: signal.location,
+                });
+            }
+        }
+    }
+
+    for switch in m.incomplete_switches.borrow().iter() {
+        warnings.push(Warning::IncompleteSwitch {
+            module_path: m.path(),
+            module_name: m.name.clone(),
+            case_count: switch.case_count,
+            selector_bit_width: switch.selector_bit_width,
+            location: switch.location,
+        });
+    }
+
+    for module in m.modules.borrow().iter() {
+        lint_module(
+            module,
+            root,
+            used,
+            used_signals_by_module,
+            observable,
+            warnings,
+        );
+    }
+}
+
+fn is_constant<'a>(signal: &'a internal_signal::InternalSignal<'a>) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![signal];
+    while let Some(signal) = stack.pop() {
+        if !visited.insert(signal) {
+            continue;
+        }
+        match signal.data {
+            internal_signal::SignalData::Input { .. }
+            | internal_signal::SignalData::Reg { .. }
+            | internal_signal::SignalData::MemReadPortOutput { .. } => return false,
+            _ => stack.extend(combinational_sources(signal)),
+        }
+    }
+    true
+}
+
+// Only 1-bit literals are considered, as these are the only ones used as conditions/enables
+fn literal_value(signal: &internal_signal::InternalSignal) -> Option<bool> {
+    match signal.data {
+        internal_signal::SignalData::Lit {
+            ref value,
+            bit_width: 1,
+        } => Some(value.low_u128() != 0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn clean_module() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 4);
+        let r = m.reg("r", 4);
+        r.drive_next(i);
+        m.output("o", r);
+
+        assert_eq!(lint(m), Vec::new());
+    }
+
+    #[test]
+    fn unused_input() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i1 = m.input("i1", 1);
+        let _ = m.input("i2", 1);
+        let line = line!() - 1;
+        m.output("o", i1);
+
+        let warnings = lint(m);
+        assert_eq!(warnings.len(), 1);
+        match &warnings[0] {
+            Warning::UnusedInput {
+                module_path,
+                input_name,
+                location,
+                ..
+            } => {
+                assert_eq!(module_path, "a");
+                assert_eq!(input_name, "i2");
+                assert_eq!(location.line(), line);
+            }
+            _ => unreachable!(),
+        }
+        assert!(warnings[0]
+            .to_string()
+            .starts_with("Module \"A\" contains an input called \"i2\" which is never read."));
+    }
+
+    #[test]
+    fn input_read_by_instance_or_register() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i1 = m.input("i1", 1);
+        let i2 = m.input("i2", 1);
+        let inner = m.module("inner", "Inner");
+        let inner_i = inner.input("i", 1);
+        inner.output("o", inner_i);
+        inner_i.drive(i1);
+        let r = m.reg("r", 1);
+        r.drive_next(i2);
+
+        // The instance output and register are unobservable, but both inputs are read
+        let warnings = lint(m);
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            matches!(&warnings[0], Warning::UnobservableRegister { register_name, .. } if register_name == "r")
+        );
+    }
+
+    #[test]
+    fn unused_instance_input() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let inner = m.module("inner", "Inner");
+        let inner_i = inner.input("i", 1);
+        inner_i.drive(m.input("i", 1));
+        m.output("o", m.low() ^ m.input("j", 1));
+
+        // The instance's input is read by its parent, but not by the instance itself
+        let warnings = lint(m);
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            matches!(&warnings[0], Warning::UnusedInput { module_path, input_name, .. } if module_path == "a.inner" && input_name == "i")
+        );
+    }
+
+    #[test]
+    fn unobservable_register() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let r1 = m.reg("r1", 8);
+        let r2 = m.reg("r2", 8);
+        let r3 = m.reg("r3", 8);
+        r1.drive_next(r2);
+        r2.drive_next(r1);
+        r3.drive_next(r3);
+        m.output("o", r1);
+
+        let warnings = lint(m);
+        assert_eq!(warnings.len(), 1);
+        match &warnings[0] {
+            Warning::UnobservableRegister {
+                root_module_name,
+                register_name,
+                ..
+            } => {
+                assert_eq!(root_module_name, "A");
+                assert_eq!(register_name, "r3");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn register_observable_through_mem() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let r = m.reg("r", 8);
+        r.drive_next(r);
+        let mem = m.mem("mem", 1, 8);
+        mem.write_port(m.low(), r, m.input("we", 1));
+        m.output("o", mem.read_port(m.low(), m.high()));
+
+        assert_eq!(lint(m), Vec::new());
+    }
+
+    #[test]
+    fn constant_output() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let i = m.input("i", 8);
+        m.output("o1", i + m.lit(1u32, 8));
+        m.output("o2", m.lit(1u32, 8) + m.lit(2u32, 8));
+
+        let inner = m.module("inner", "Inner");
+        let inner_o = inner.output("o", inner.high());
+        m.output("o3", inner_o & m.high());
+
+        let warnings = lint(m);
+        assert_eq!(warnings.len(), 3);
+        assert!(
+            matches!(&warnings[0], Warning::ConstantOutput { module_path, output_name, .. } if module_path == "a" && output_name == "o2")
+        );
+        assert!(
+            matches!(&warnings[1], Warning::ConstantOutput { module_path, output_name, .. } if module_path == "a" && output_name == "o3")
+        );
+        assert!(
+            matches!(&warnings[2], Warning::ConstantOutput { module_path, output_name, .. } if module_path == "a.inner" && output_name == "o")
+        );
+    }
+
+    #[test]
+    fn mem_write_port_never_enabled() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let mem = m.mem("mem", 1, 8);
+        mem.initial_contents(&[0u32, 1u32]);
+        mem.write_port(m.input("a", 1), m.input("v", 8), m.low());
+        m.output("o", mem.read_port(m.input("ra", 1), m.high()));
+
+        let warnings = lint(m);
+        assert_eq!(warnings.len(), 1);
+        match &warnings[0] {
+            Warning::MemWritePortNeverEnabled { mem_name, .. } => assert_eq!(mem_name, "mem"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn constant_mux_condition() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let a = m.input("a", 8);
+        let b = m.input("b", 8);
+        let s = m.input("s", 1);
+        let mux = m.mux(m.low(), a, b);
+        let line = line!() - 1;
+        m.output("o", m.mux(s, mux, a));
+
+        let warnings = lint(m);
+        assert_eq!(warnings.len(), 1);
+        match &warnings[0] {
+            Warning::ConstantMuxCondition { cond, location, .. } => {
+                assert!(!cond);
+                assert_eq!(location.line(), line);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn incomplete_switch() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let a = m.input("a", 8);
+        let s = m.input("s", 2);
+        let complete = switch_(s)
+            .case(0u32, a)
+            .case(1u32, !a)
+            .case(2u32, a)
+            .case(3u32, !a)
+            .end();
+        let with_default = switch_(s).case(0u32, a).default(!a);
+        let incomplete = switch_(s);
+        let line = line!() - 1;
+        let incomplete = incomplete.case(0u32, a).case(1u32, !a).end();
+        m.output("o", complete ^ with_default ^ incomplete);
+
+        let warnings = lint(m);
+        assert_eq!(warnings.len(), 1);
+        match &warnings[0] {
+            Warning::IncompleteSwitch {
+                case_count,
+                selector_bit_width,
+                location,
+                ..
+            } => {
+                assert_eq!(*case_count, 2);
+                assert_eq!(*selector_bit_width, 2);
+                assert_eq!(location.line(), line);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn invalid_hierarchy() {
+        let c = Context::new();
+
+        let m = c.module("a", "A");
+        let r = m.reg("r", 1);
+        let inner = m.module("inner", "Inner");
+        let inner_i = inner.input("i", 1);
+        m.output("o1", r);
+        m.output("o2", inner.output("o", inner_i));
+
+        // Undriven registers and instance inputs don't cause a panic
+        assert_eq!(lint(m), Vec::new());
+    }
+}
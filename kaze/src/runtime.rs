@@ -1,3 +1,6 @@
-//! Rust simulator runtime dependencies. These are only required for simulators with tracing enabled.
+//! Rust simulator runtime dependencies. These are only required for simulators with tracing enabled or with signals wider than 128 bits.
 
 pub mod tracing;
+mod wide_uint;
+
+pub use wide_uint::*;
@@ -6,6 +6,7 @@ use std::io;
 
 // TODO: Do we want to re-use graph::Constant for this? They're equivalent but currently distinct in their usage, so I'm not sure it's the right API design decision.
 #[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum TraceValue {
     /// Contains a boolean value
     Bool(bool),
@@ -15,14 +16,18 @@ pub enum TraceValue {
     U64(u64),
     /// Contains an unsigned, 128-bit value
     U128(u128),
+    /// Contains an unsigned value wider than 128 bits, stored as 64-bit words in little-endian order
+    Wide(Vec<u64>),
 }
 
 #[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum TraceValueType {
     Bool,
     U32,
     U64,
     U128,
+    Wide,
 }
 
 impl TraceValueType {
@@ -36,7 +41,7 @@ impl TraceValueType {
         } else if bit_width <= 128 {
             TraceValueType::U128
         } else {
-            unreachable!()
+            TraceValueType::Wide
         }
     }
 }
@@ -52,6 +57,17 @@ pub trait Trace {
         bit_width: u32,
         type_: TraceValueType,
     ) -> io::Result<Self::SignalId>;
+    /// Adds a signal whose value should be interpreted as a signed, two's complement integer, such as an [`Output`](crate::Output) driven by a [`SignedSignal`](crate::SignedSignal), an input created with [`Module::signed_input`](crate::Module::signed_input) or a register created with [`Module::signed_reg`](crate::Module::signed_reg).
+    ///
+    /// Values for signed signals are still reported as raw bits via [`update_signal`](Self::update_signal). The default implementation forwards to [`add_signal`](Self::add_signal), ignoring the signedness.
+    fn add_signed_signal(
+        &mut self,
+        name: &'static str,
+        bit_width: u32,
+        type_: TraceValueType,
+    ) -> io::Result<Self::SignalId> {
+        self.add_signal(name, bit_width, type_)
+    }
 
     fn update_time_stamp(&mut self, time_stamp: u64) -> io::Result<()>;
     fn update_signal(&mut self, signal_id: &Self::SignalId, value: TraceValue) -> io::Result<()>;
@@ -32,6 +32,8 @@ pub struct VcdTrace<W: io::Write> {
     module_hierarchy_depth: u32,
 
     signals: Vec<VcdTraceSignal>,
+    // Reused across value changes to avoid allocating for each of them
+    scalar_values: Vec<vcd::Value>,
 
     w: vcd::Writer<W>,
 }
@@ -46,6 +48,7 @@ impl<W: io::Write> VcdTrace<W> {
             module_hierarchy_depth: 0,
 
             signals: Vec::new(),
+            scalar_values: Vec::new(),
 
             w,
         })
@@ -93,6 +96,30 @@ impl<W: io::Write> Trace for VcdTrace<W> {
         Ok(ret)
     }
 
+    fn add_signed_signal(
+        &mut self,
+        name: &'static str,
+        bit_width: u32,
+        type_: TraceValueType,
+    ) -> io::Result<Self::SignalId> {
+        let ret = self.signals.len();
+
+        // VCD has no signed wires, but viewers display integer variables as signed values; a single bit has
+        //  no sign to display, so it's traced like an unsigned signal
+        let var_type = if bit_width == 1 {
+            vcd::VarType::Wire
+        } else {
+            vcd::VarType::Integer
+        };
+        self.signals.push(VcdTraceSignal {
+            bit_width,
+            type_,
+            id: self.w.add_var(var_type, bit_width, name, None)?,
+        });
+
+        Ok(ret)
+    }
+
     fn update_time_stamp(&mut self, time_stamp: u64) -> io::Result<()> {
         self.w.timestamp(time_stamp)
     }
@@ -106,22 +133,27 @@ impl<W: io::Write> Trace for VcdTrace<W> {
                 signal.id,
                 match value {
                     TraceValue::Bool(value) => value,
-                    TraceValue::U32(_) | TraceValue::U64(_) | TraceValue::U128(_) => unreachable!(),
+                    TraceValue::U32(_)
+                    | TraceValue::U64(_)
+                    | TraceValue::U128(_)
+                    | TraceValue::Wide(_) => unreachable!(),
                 },
             )?;
         } else {
-            let value = match value {
+            let bit = |i: usize| match value {
                 TraceValue::Bool(_) => unreachable!(),
-                TraceValue::U32(value) => value as _,
-                TraceValue::U64(value) => value as _,
-                TraceValue::U128(value) => value,
+                TraceValue::U32(value) => (value >> i) & 1 != 0,
+                TraceValue::U64(value) => (value >> i) & 1 != 0,
+                TraceValue::U128(value) => (value >> i) & 1 != 0,
+                TraceValue::Wide(ref words) => (words[i / 64] >> (i % 64)) & 1 != 0,
             };
-            let mut scalar_values = [vcd::Value::V0; 128];
-            for i in 0..signal.bit_width as usize {
-                scalar_values[i] = ((value >> (signal.bit_width as usize - 1 - i)) & 1 != 0).into();
-            }
-            self.w
-                .change_vector(signal.id, &scalar_values[0..signal.bit_width as usize])?;
+            self.scalar_values.clear();
+            self.scalar_values.extend(
+                (0..signal.bit_width as usize)
+                    .rev()
+                    .map(|i| vcd::Value::from(bit(i))),
+            );
+            self.w.change_vector(signal.id, &self.scalar_values)?;
         }
 
         Ok(())
@@ -133,3 +165,54 @@ struct VcdTraceSignal {
     type_: TraceValueType,
     id: vcd::IdCode,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_signal() -> io::Result<()> {
+        let mut buf = Vec::new();
+        {
+            let mut trace = VcdTrace::new(&mut buf, 1, TimeScaleUnit::Ns)?;
+            trace.push_module("top")?;
+            let id = trace.add_signal("wide", 130, TraceValueType::Wide)?;
+            trace.pop_module()?;
+            trace.update_time_stamp(0)?;
+            trace.update_signal(&id, TraceValue::Wide(vec![1, 0, 0b10]))?;
+        }
+        let vcd = String::from_utf8(buf).unwrap();
+
+        assert!(vcd.contains(&format!("b1{}1 ", "0".repeat(128))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn signed_signal() -> io::Result<()> {
+        let mut buf = Vec::new();
+        {
+            let mut trace = VcdTrace::new(&mut buf, 1, TimeScaleUnit::Ns)?;
+            trace.push_module("top")?;
+            let unsigned_id = trace.add_signal("unsigned_value", 8, TraceValueType::U32)?;
+            let signed_id = trace.add_signed_signal("signed_value", 8, TraceValueType::U32)?;
+            let signed_bit_id = trace.add_signed_signal("signed_bit", 1, TraceValueType::Bool)?;
+            trace.pop_module()?;
+            trace.update_time_stamp(0)?;
+            trace.update_signal(&unsigned_id, TraceValue::U32(0xff))?;
+            trace.update_signal(&signed_id, TraceValue::U32(0xfe))?;
+            trace.update_signal(&signed_bit_id, TraceValue::Bool(true))?;
+            trace.update_signal(&signed_id, TraceValue::U32(0x01))?;
+        }
+        let vcd = String::from_utf8(buf).unwrap();
+
+        assert!(vcd.contains("$var wire 8 ! unsigned_value $end"));
+        assert!(vcd.contains("$var integer 8 \" signed_value $end"));
+        assert!(vcd.contains("$var wire 1 # signed_bit $end"));
+        assert!(vcd.contains("b11111110 \""));
+        assert!(vcd.contains("1#"));
+        assert!(vcd.contains("b00000001 \""));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,614 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+/// A fixed-width unsigned integer made up of `WORDS` 64-bit words, used by generated simulators to store and operate on values of signals wider than 128 bits.
+///
+/// Words are stored in little-endian order, so `words()[0]` contains the least significant 64 bits of the value.
+///
+/// Like Rust's native unsigned integer types, arithmetic (including signed division and remainder) is available in `wrapping_*` form, and shifts are available in `checked_*` form as well as through the [`Shl`] and [`Shr`] operators (which panic if the shift amount is greater than or equal to the type's bit width).
+///
+/// # Examples
+///
+/// ```
+/// use kaze::runtime::WideUint;
+///
+/// let a = WideUint::<4>::from_words([0, 0, 0, 1 << 63]);
+/// let b = WideUint::<4>::from(1u32);
+///
+/// assert_eq!(a.wrapping_add(a), WideUint::ZERO);
+/// assert_eq!(b << 255, a);
+/// assert!(b < a);
+/// assert_eq!(a.wrapping_sub(b).words(), &[!0, !0, !0, !0 >> 1]);
+/// ```
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct WideUint<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> WideUint<WORDS> {
+    /// The number of bits in this type.
+    pub const BITS: u32 = (WORDS * 64) as u32;
+
+    /// The value `0`.
+    pub const ZERO: Self = WideUint { words: [0; WORDS] };
+
+    /// Creates a value from 64-bit words in little-endian order.
+    pub const fn from_words(words: [u64; WORDS]) -> Self {
+        WideUint { words }
+    }
+
+    /// Returns this value's 64-bit words in little-endian order.
+    pub fn words(&self) -> &[u64; WORDS] {
+        &self.words
+    }
+
+    /// Returns the bit at `index`, where bit `0` is the least significant bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than or equal to [`BITS`](Self::BITS).
+    pub fn bit(&self, index: u32) -> bool {
+        (self.words[(index / 64) as usize] >> (index % 64)) & 1 != 0
+    }
+
+    /// Returns the number of ones in the binary representation of this value.
+    pub fn count_ones(&self) -> u32 {
+        self.words.iter().map(|word| word.count_ones()).sum()
+    }
+
+    /// Returns the number of leading zeros in the binary representation of this value.
+    pub fn leading_zeros(&self) -> u32 {
+        let mut ret = 0;
+        for word in self.words.iter().rev() {
+            ret += word.leading_zeros();
+            if *word != 0 {
+                break;
+            }
+        }
+        ret
+    }
+
+    /// Returns the number of trailing zeros in the binary representation of this value.
+    pub fn trailing_zeros(&self) -> u32 {
+        let mut ret = 0;
+        for word in self.words.iter() {
+            ret += word.trailing_zeros();
+            if *word != 0 {
+                break;
+            }
+        }
+        ret
+    }
+
+    /// Returns the least significant 128 bits of this value.
+    pub fn low_u128(&self) -> u128 {
+        self.words
+            .iter()
+            .take(2)
+            .enumerate()
+            .fold(0, |acc, (i, &word)| acc | ((word as u128) << (i * 64)))
+    }
+
+    /// Converts this value to a `WideUint` with a different number of words, truncating or zero-extending as necessary.
+    pub fn resize<const TARGET_WORDS: usize>(self) -> WideUint<TARGET_WORDS> {
+        let mut ret = WideUint::<TARGET_WORDS>::ZERO;
+        for (target, source) in ret.words.iter_mut().zip(self.words.iter()) {
+            *target = *source;
+        }
+        ret
+    }
+
+    /// Wrapping (modular) addition.
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        let mut ret = Self::ZERO;
+        let mut carry = false;
+        for i in 0..WORDS {
+            let (sum, carry_a) = self.words[i].overflowing_add(rhs.words[i]);
+            let (sum, carry_b) = sum.overflowing_add(carry as u64);
+            ret.words[i] = sum;
+            carry = carry_a || carry_b;
+        }
+        ret
+    }
+
+    /// Wrapping (modular) subtraction.
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        let mut ret = Self::ZERO;
+        let mut borrow = false;
+        for i in 0..WORDS {
+            let (difference, borrow_a) = self.words[i].overflowing_sub(rhs.words[i]);
+            let (difference, borrow_b) = difference.overflowing_sub(borrow as u64);
+            ret.words[i] = difference;
+            borrow = borrow_a || borrow_b;
+        }
+        ret
+    }
+
+    /// Wrapping (modular) multiplication.
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        let mut ret = Self::ZERO;
+        for i in 0..WORDS {
+            let mut carry = 0u128;
+            for j in 0..WORDS - i {
+                let product = (self.words[i] as u128) * (rhs.words[j] as u128)
+                    + ret.words[i + j] as u128
+                    + carry;
+                ret.words[i + j] = product as u64;
+                carry = product >> 64;
+            }
+        }
+        ret
+    }
+
+    /// Wrapping (modular) division, rounding towards zero. Since unsigned division can't overflow, this is equivalent to ordinary division.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is `0`.
+    pub fn wrapping_div(self, rhs: Self) -> Self {
+        self.div_rem(rhs).0
+    }
+
+    /// Wrapping (modular) remainder. Since unsigned division can't overflow, this is equivalent to the ordinary remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is `0`.
+    pub fn wrapping_rem(self, rhs: Self) -> Self {
+        self.div_rem(rhs).1
+    }
+
+    /// Wrapping (modular) division, rounding towards zero, treating both values as signed, two's complement values of [`BITS`](Self::BITS) bits.
+    ///
+    /// The only case where wrapping occurs is when the most negative value is divided by `-1`, in which case the result is the most negative value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is `0`.
+    pub fn wrapping_div_signed(self, rhs: Self) -> Self {
+        let quotient = self.wrapping_abs().div_rem(rhs.wrapping_abs()).0;
+        if self.is_negative() != rhs.is_negative() {
+            quotient.wrapping_neg()
+        } else {
+            quotient
+        }
+    }
+
+    /// Wrapping (modular) remainder, treating both values as signed, two's complement values of [`BITS`](Self::BITS) bits.
+    ///
+    /// The sign of the result matches the sign of this value. When the most negative value is divided by `-1`, the result is `0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is `0`.
+    pub fn wrapping_rem_signed(self, rhs: Self) -> Self {
+        let remainder = self.wrapping_abs().div_rem(rhs.wrapping_abs()).1;
+        if self.is_negative() {
+            remainder.wrapping_neg()
+        } else {
+            remainder
+        }
+    }
+
+    fn div_rem(self, rhs: Self) -> (Self, Self) {
+        if rhs == Self::ZERO {
+            panic!("attempt to divide by zero");
+        }
+
+        // Restoring division, skipping this value's leading zeros
+        let high_bit = match (0..WORDS).rev().find(|&i| self.words[i] != 0) {
+            Some(i) => i as u32 * 64 + 63 - self.words[i].leading_zeros(),
+            None => return (Self::ZERO, Self::ZERO),
+        };
+        let mut quotient = Self::ZERO;
+        let mut remainder = Self::ZERO;
+        for i in (0..=high_bit).rev() {
+            // The remainder is always less than rhs, but shifting it may still overflow if rhs is large enough
+            let carry = remainder.is_negative();
+            remainder = remainder << 1;
+            remainder.words[0] |= self.bit(i) as u64;
+            if carry || remainder >= rhs {
+                remainder = remainder.wrapping_sub(rhs);
+                quotient.words[(i / 64) as usize] |= 1 << (i % 64);
+            }
+        }
+        (quotient, remainder)
+    }
+
+    fn is_negative(&self) -> bool {
+        self.bit(Self::BITS - 1)
+    }
+
+    fn wrapping_neg(self) -> Self {
+        Self::ZERO.wrapping_sub(self)
+    }
+
+    fn wrapping_abs(self) -> Self {
+        if self.is_negative() {
+            self.wrapping_neg()
+        } else {
+            self
+        }
+    }
+
+    /// Checked shift left. Returns `None` if `shift` is greater than or equal to [`BITS`](Self::BITS).
+    pub fn checked_shl(self, shift: u32) -> Option<Self> {
+        if shift >= Self::BITS {
+            return None;
+        }
+
+        let word_shift = (shift / 64) as usize;
+        let bit_shift = shift % 64;
+        let mut ret = Self::ZERO;
+        for i in word_shift..WORDS {
+            ret.words[i] = self.words[i - word_shift] << bit_shift;
+            if bit_shift != 0 && i > word_shift {
+                ret.words[i] |= self.words[i - word_shift - 1] >> (64 - bit_shift);
+            }
+        }
+        Some(ret)
+    }
+
+    /// Checked logical shift right. Returns `None` if `shift` is greater than or equal to [`BITS`](Self::BITS).
+    pub fn checked_shr(self, shift: u32) -> Option<Self> {
+        if shift >= Self::BITS {
+            return None;
+        }
+
+        let word_shift = (shift / 64) as usize;
+        let bit_shift = shift % 64;
+        let mut ret = Self::ZERO;
+        for i in 0..WORDS - word_shift {
+            ret.words[i] = self.words[i + word_shift] >> bit_shift;
+            if bit_shift != 0 && i + word_shift + 1 < WORDS {
+                ret.words[i] |= self.words[i + word_shift + 1] << (64 - bit_shift);
+            }
+        }
+        Some(ret)
+    }
+
+    /// Shifts the bits to the left by `shift` bits, wrapping the truncated bits around to the least significant end. `shift` is taken modulo [`BITS`](Self::BITS).
+    pub fn rotate_left(self, shift: u32) -> Self {
+        let shift = shift % Self::BITS;
+        if shift == 0 {
+            return self;
+        }
+        (self << shift) | (self >> (Self::BITS - shift))
+    }
+
+    /// Shifts the bits to the right by `shift` bits, wrapping the truncated bits around to the most significant end. `shift` is taken modulo [`BITS`](Self::BITS).
+    pub fn rotate_right(self, shift: u32) -> Self {
+        let shift = shift % Self::BITS;
+        if shift == 0 {
+            return self;
+        }
+        (self >> shift) | (self << (Self::BITS - shift))
+    }
+
+    /// Arithmetic shift right, treating this value as a signed, two's complement value of [`BITS`](Self::BITS) bits.
+    ///
+    /// If `shift` is greater than or equal to [`BITS`](Self::BITS), every bit of the result is equal to this value's most significant bit.
+    pub fn shr_arithmetic(self, shift: u32) -> Self {
+        if shift == 0 {
+            return self;
+        }
+
+        let fill = if self.bit(Self::BITS - 1) {
+            !Self::ZERO
+        } else {
+            Self::ZERO
+        };
+        match self.checked_shr(shift) {
+            Some(shifted) => shifted | (fill << (Self::BITS - shift)),
+            None => fill,
+        }
+    }
+
+    /// Sign-extends the least significant `bit_width` bits of this value to [`BITS`](Self::BITS) bits, replacing all bits above them with bit `bit_width - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit_width` is `0` or greater than [`BITS`](Self::BITS).
+    pub fn sign_extend(self, bit_width: u32) -> Self {
+        if bit_width == 0 || bit_width > Self::BITS {
+            panic!(
+                "Cannot sign-extend a {}-bit value to {} bit(s).",
+                bit_width,
+                Self::BITS
+            );
+        }
+        let shift = Self::BITS - bit_width;
+        (self << shift).shr_arithmetic(shift)
+    }
+}
+
+impl<const WORDS: usize> Default for WideUint<WORDS> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const WORDS: usize> Ord for WideUint<WORDS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.words.iter().rev().cmp(other.words.iter().rev())
+    }
+}
+
+impl<const WORDS: usize> PartialOrd for WideUint<WORDS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const WORDS: usize> BitAnd for WideUint<WORDS> {
+    type Output = Self;
+
+    fn bitand(mut self, rhs: Self) -> Self {
+        for (lhs, rhs) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *lhs &= rhs;
+        }
+        self
+    }
+}
+
+impl<const WORDS: usize> BitOr for WideUint<WORDS> {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self {
+        for (lhs, rhs) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *lhs |= rhs;
+        }
+        self
+    }
+}
+
+impl<const WORDS: usize> BitXor for WideUint<WORDS> {
+    type Output = Self;
+
+    fn bitxor(mut self, rhs: Self) -> Self {
+        for (lhs, rhs) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *lhs ^= rhs;
+        }
+        self
+    }
+}
+
+impl<const WORDS: usize> Not for WideUint<WORDS> {
+    type Output = Self;
+
+    fn not(mut self) -> Self {
+        for word in self.words.iter_mut() {
+            *word = !*word;
+        }
+        self
+    }
+}
+
+impl<const WORDS: usize> Shl<u32> for WideUint<WORDS> {
+    type Output = Self;
+
+    fn shl(self, shift: u32) -> Self {
+        self.checked_shl(shift)
+            .expect("attempt to shift left with overflow")
+    }
+}
+
+impl<const WORDS: usize> Shr<u32> for WideUint<WORDS> {
+    type Output = Self;
+
+    fn shr(self, shift: u32) -> Self {
+        self.checked_shr(shift)
+            .expect("attempt to shift right with overflow")
+    }
+}
+
+impl<const WORDS: usize> From<bool> for WideUint<WORDS> {
+    fn from(value: bool) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl<const WORDS: usize> From<u8> for WideUint<WORDS> {
+    fn from(value: u8) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl<const WORDS: usize> From<u16> for WideUint<WORDS> {
+    fn from(value: u16) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl<const WORDS: usize> From<u32> for WideUint<WORDS> {
+    fn from(value: u32) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl<const WORDS: usize> From<u64> for WideUint<WORDS> {
+    fn from(value: u64) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl<const WORDS: usize> From<u128> for WideUint<WORDS> {
+    fn from(value: u128) -> Self {
+        let mut ret = Self::ZERO;
+        for (i, word) in ret.words.iter_mut().take(2).enumerate() {
+            *word = (value >> (i * 64)) as u64;
+        }
+        ret
+    }
+}
+
+impl<const WORDS: usize> fmt::LowerHex for WideUint<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut words = self.words.iter().rev().skip_while(|&&word| word == 0);
+        match words.next() {
+            Some(word) => {
+                let mut s = format!("{:x}", word);
+                for word in words {
+                    s.push_str(&format!("{:016x}", word));
+                }
+                f.pad_integral(true, "0x", &s)
+            }
+            None => f.pad_integral(true, "0x", "0"),
+        }
+    }
+}
+
+impl<const WORDS: usize> fmt::Debug for WideUint<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#x}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_sub_carry_across_words() {
+        let a = WideUint::<3>::from(u128::MAX);
+        let b = WideUint::<3>::from(1u32);
+
+        assert_eq!(a.wrapping_add(b), WideUint::from_words([0, 0, 1]));
+        assert_eq!(a.wrapping_add(b).wrapping_sub(b), a);
+        assert_eq!(WideUint::<3>::ZERO.wrapping_sub(b), !WideUint::ZERO);
+    }
+
+    #[test]
+    fn mul() {
+        let a = WideUint::<4>::from(u128::MAX);
+
+        // (2^128 - 1)^2 = 2^256 - 2^129 + 1
+        assert_eq!(a.wrapping_mul(a), WideUint::from_words([1, 0, !0 - 1, !0]));
+        assert_eq!(
+            (!WideUint::<4>::ZERO).wrapping_mul(!WideUint::ZERO),
+            WideUint::from(1u32)
+        );
+    }
+
+    #[test]
+    fn div_rem() {
+        let a = WideUint::<3>::from_words([5, 0, 1]);
+        let b = WideUint::<3>::from(3u32);
+
+        // 2^128 + 5 = 3 * 0x5555...5557 + 0
+        assert_eq!(a.wrapping_div(b), WideUint::from(u128::MAX / 3 + 2));
+        assert_eq!(a.wrapping_rem(b), WideUint::ZERO);
+        assert_eq!(b.wrapping_div(a), WideUint::ZERO);
+        assert_eq!(b.wrapping_rem(a), b);
+
+        let max = !WideUint::<3>::ZERO;
+        let big = WideUint::<3>::from_words([0, 0, 1 << 63]).wrapping_add(b);
+        assert_eq!(max.wrapping_div(big), WideUint::from(1u32));
+        assert_eq!(max.wrapping_rem(big), max.wrapping_sub(big));
+    }
+
+    #[test]
+    fn div_rem_signed() {
+        let minus_seven = WideUint::<3>::ZERO.wrapping_sub(WideUint::from(7u32));
+        let two = WideUint::<3>::from(2u32);
+        let minus_two = WideUint::<3>::ZERO.wrapping_sub(two);
+        let minus_one = !WideUint::<3>::ZERO;
+
+        assert_eq!(
+            minus_seven.wrapping_div_signed(two),
+            WideUint::ZERO.wrapping_sub(WideUint::from(3u32))
+        );
+        assert_eq!(minus_seven.wrapping_rem_signed(two), minus_one);
+        assert_eq!(
+            WideUint::<3>::from(7u32).wrapping_div_signed(minus_two),
+            WideUint::ZERO.wrapping_sub(WideUint::from(3u32))
+        );
+        assert_eq!(
+            WideUint::<3>::from(7u32).wrapping_rem_signed(minus_two),
+            WideUint::from(1u32)
+        );
+        assert_eq!(
+            minus_seven.wrapping_div_signed(minus_two),
+            WideUint::from(3u32)
+        );
+
+        let min = WideUint::<3>::from_words([0, 0, 1 << 63]);
+        assert_eq!(min.wrapping_div_signed(minus_one), min);
+        assert_eq!(min.wrapping_rem_signed(minus_one), WideUint::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn div_by_zero_error() {
+        let _ = WideUint::<3>::from(1u32).wrapping_div(WideUint::ZERO);
+    }
+
+    #[test]
+    fn shifts() {
+        let a = WideUint::<3>::from_words([0x8000_0000_0000_0001, 0, 0]);
+
+        assert_eq!(a << 1, WideUint::from_words([2, 1, 0]));
+        assert_eq!(a << 129, WideUint::from_words([0, 0, 2]));
+        assert_eq!((a << 129) >> 129, WideUint::from_words([1, 0, 0]));
+        assert_eq!(a.checked_shl(192), None);
+        assert_eq!(a.checked_shr(192), None);
+
+        let b = WideUint::<3>::from_words([0, 0, 1 << 63]);
+        assert_eq!(b.shr_arithmetic(64), WideUint::from_words([0, 1 << 63, !0]));
+        assert_eq!(b.shr_arithmetic(1000), !WideUint::ZERO);
+        assert_eq!((b >> 1).shr_arithmetic(1000), WideUint::ZERO);
+    }
+
+    #[test]
+    fn sign_extend() {
+        let a = WideUint::<3>::from(1u128 << 99);
+
+        assert_eq!(a.sign_extend(101), a);
+        assert_eq!(a.sign_extend(100), WideUint::from_words([0, !0 << 35, !0]));
+    }
+
+    #[test]
+    fn cmp_and_conversions() {
+        let a = WideUint::<3>::from_words([!0, !0, 0]);
+        let b = WideUint::<3>::from_words([0, 0, 1]);
+
+        assert!(a < b);
+        assert_eq!(a.low_u128(), u128::MAX);
+        assert_eq!(b.resize::<2>(), WideUint::ZERO);
+        assert_eq!(b.resize::<4>(), WideUint::from_words([0, 0, 1, 0]));
+        assert!(b.bit(128));
+        assert_eq!(a.count_ones(), 128);
+        assert_eq!(b.count_ones(), 1);
+        assert!(!b.bit(127));
+        assert_eq!(format!("{:x}", b), "100000000000000000000000000000000");
+        assert_eq!(format!("{:?}", a), "0xffffffffffffffffffffffffffffffff");
+    }
+
+    #[test]
+    fn bit_counts() {
+        assert_eq!(WideUint::<3>::ZERO.leading_zeros(), 192);
+        assert_eq!(WideUint::<3>::ZERO.trailing_zeros(), 192);
+        assert_eq!((!WideUint::<3>::ZERO).leading_zeros(), 0);
+        assert_eq!((!WideUint::<3>::ZERO).trailing_zeros(), 0);
+
+        let a = WideUint::<3>::from_words([0, 0x10, 0]);
+        assert_eq!(a.leading_zeros(), 123);
+        assert_eq!(a.trailing_zeros(), 68);
+        assert_eq!(a.count_ones(), 1);
+    }
+
+    #[test]
+    fn rotates() {
+        let a = WideUint::<3>::from_words([0x8000_0000_0000_0001, 0, 1 << 63]);
+
+        assert_eq!(a.rotate_left(1), WideUint::from_words([3, 1, 0]));
+        assert_eq!(
+            a.rotate_right(1),
+            WideUint::from_words([1 << 62, 0, 0xc000_0000_0000_0000])
+        );
+        assert_eq!(a.rotate_left(0), a);
+        assert_eq!(a.rotate_left(192), a);
+        assert_eq!(a.rotate_left(65).rotate_right(65), a);
+    }
+}
@@ -1,4 +1,6 @@
 //! Rust simulator code generation.
+//!
+//! The generated simulator is a single struct for the whole `Module` hierarchy, which is flattened into it, except for `Module`s instantiated from a shared definition with [`Module::instance`](crate::Module::instance). Each such definition is generated once as a separate struct, and each of its instances is a member of the struct generated for its parent.
 
 mod compiler;
 mod ir;
@@ -12,11 +14,13 @@ use typed_arena::Arena;
 
 use crate::code_writer;
 use crate::graph;
+use crate::graph::internal_signal;
 use crate::runtime::tracing::*;
 use crate::validation::*;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::io::{Result, Write};
+use std::ptr;
 
 #[derive(Default)]
 pub struct GenerationOptions {
@@ -30,696 +34,1235 @@ pub fn generate<'a, W: Write>(
     options: GenerationOptions,
     w: W,
 ) -> Result<()> {
-    validate_module_hierarchy(m);
+    if let Err(e) = validate_module_hierarchy(m) {
+        panic!("{}", e);
+    }
 
-    // TODO: Consider exposing as a codegen option (and testing both variants)
-    let included_ports = if options.tracing {
-        IncludedPorts::All
-    } else {
-        IncludedPorts::ReachableFromTopLevelOutputs
+    let module_name = options
+        .override_module_name
+        .unwrap_or_else(|| m.name.clone());
+
+    let mut generator = Generator {
+        module_name: module_name.clone(),
+        tracing: options.tracing,
+        definitions: HashMap::new(),
+        num_definitions: 0,
     };
+    let mut w = code_writer::CodeWriter::new(w);
+    generator.generate_struct(m, &module_name, StructKind::TopLevel, &mut w)?;
 
-    let mut signal_reference_counts = HashMap::new();
-    let state_elements = StateElements::new(m, included_ports, &mut signal_reference_counts);
+    Ok(())
+}
 
-    struct TraceSignal {
-        name: String,
-        member_name: String,
-        value_name: String,
-        bit_width: u32,
-        type_: TraceValueType,
-    }
-    let mut trace_signals: HashMap<&'a graph::Module<'a>, Vec<TraceSignal>> = HashMap::new();
-    let mut num_trace_signals = 0;
-    let mut add_trace_signal = |module, name, value_name, bit_width| {
-        if options.tracing {
-            let member_name = format!("__trace_signal_id_{}_{}", name, num_trace_signals);
-            let module_trace_signals = trace_signals.entry(module).or_insert(Vec::new());
-            module_trace_signals.push(TraceSignal {
-                name,
-                member_name,
-                value_name,
-                bit_width,
-                type_: TraceValueType::from_bit_width(bit_width),
-            });
-            num_trace_signals += 1;
-        }
-    };
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum StructKind {
+    // The struct for the `Module` passed to `generate`, which is the generated simulator's public interface
+    TopLevel,
+    // A struct shared by all instances of a definition, each of which is a member of its parent's struct
+    Definition,
+}
 
-    let expr_arena = Arena::new();
-    let mut prop_context = AssignmentContext::new(&expr_arena);
-    let mut c = Compiler::new(&state_elements, &signal_reference_counts, &expr_arena);
-    for (name, input) in m.inputs.borrow().iter() {
-        add_trace_signal(m, name.clone(), name.clone(), input.data.bit_width);
-    }
-    for (name, output) in m.outputs.borrow().iter() {
-        let expr = c.compile_signal(output.data.source, &mut prop_context);
-        prop_context.push(Assignment {
-            target: expr_arena.alloc(Expr::Ref {
-                name: name.clone(),
-                scope: Scope::Member,
-            }),
-            expr,
-        });
+struct TraceSignal {
+    name: String,
+    member_name: String,
+    value_name: String,
+    bit_width: u32,
+    type_: TraceValueType,
+    signed: bool,
+}
 
-        add_trace_signal(m, name.clone(), name.clone(), output.data.bit_width);
-    }
-    struct InnerField {
-        name: String,
-        bit_width: u32,
-    }
-    let mut inner_fields = Vec::new();
-    if options.tracing {
-        fn visit_module<'graph, 'context, 'expr_arena>(
-            module: &'graph graph::Module<'graph>,
-            c: &mut Compiler<'graph, 'context, 'expr_arena>,
-            inner_fields: &mut Vec<InnerField>,
-            prop_context: &mut AssignmentContext<'expr_arena>,
-            expr_arena: &'expr_arena Arena<Expr>,
-            add_trace_signal: &mut impl FnMut(&'graph graph::Module<'graph>, String, String, u32),
-        ) -> Result<()> {
-            // TODO: Identify and fix duplicate signals in traces
-            for (name, &input) in module.inputs.borrow().iter() {
-                // TODO: De-dupe inner field allocs
-                let field_name = format!("__inner_{}_{}", name, inner_fields.len());
-                inner_fields.push(InnerField {
-                    name: field_name.clone(),
-                    bit_width: input.data.bit_width,
+// What the parents of a definition's instances need to know about the definition's generated struct
+struct Definition<'a> {
+    struct_name: String,
+    // The inputs that each output depends on combinationally, which have to be assigned before the output's method is called
+    output_inputs: BTreeMap<String, Vec<String>>,
+    // The inputs that the definition's async resets depend on, if it has any, which have to be assigned before its `async_reset` method is called
+    async_reset_inputs: Option<Vec<String>>,
+    has_reset: bool,
+    clock_names: Vec<String>,
+    trace_signals: HashMap<&'a graph::Module<'a>, Vec<TraceSignal>>,
+}
+
+struct Generator<'a> {
+    module_name: String,
+    tracing: bool,
+    definitions: HashMap<&'a graph::Module<'a>, Definition<'a>>,
+    num_definitions: u32,
+}
+
+impl<'a> Generator<'a> {
+    fn generate_struct<W: Write>(
+        &mut self,
+        m: &'a graph::Module<'a>,
+        struct_name: &str,
+        kind: StructKind,
+        w: &mut code_writer::CodeWriter<W>,
+    ) -> Result<Definition<'a>> {
+        let is_top_level = kind == StructKind::TopLevel;
+        let tracing = self.tracing;
+
+        // TODO: Consider exposing as a codegen option (and testing both variants)
+        let included_ports = if tracing {
+            IncludedPorts::All
+        } else {
+            IncludedPorts::ReachableFromTopLevelOutputs
+        };
+
+        let mut signal_reference_counts = HashMap::new();
+        let state_elements = StateElements::new(m, included_ports, &mut signal_reference_counts);
+
+        // Definitions are generated before the structs containing their instances, as generating those requires the definitions' ports' dependencies
+        let mut instances = state_elements.instances.values().collect::<Vec<_>>();
+        instances.sort_by(|a, b| a.field_name.cmp(&b.field_name));
+        for instance in instances.iter() {
+            if !self.definitions.contains_key(&instance.definition) {
+                let definition_struct_name = format!(
+                    "__{}_{}_{}",
+                    self.module_name, instance.definition.name, self.num_definitions
+                );
+                self.num_definitions += 1;
+                let definition = self.generate_struct(
+                    instance.definition,
+                    &definition_struct_name,
+                    StructKind::Definition,
+                    w,
+                )?;
+                self.definitions.insert(instance.definition, definition);
+            }
+        }
+        let definitions = &self.definitions;
+
+        let mut trace_signals: HashMap<&'a graph::Module<'a>, Vec<TraceSignal>> = HashMap::new();
+        let mut num_trace_signals = 0;
+        let mut add_trace_signal = |module, name, value_name, bit_width, signed| {
+            if tracing {
+                let member_name = format!("__trace_signal_id_{}_{}", name, num_trace_signals);
+                let module_trace_signals = trace_signals.entry(module).or_default();
+                module_trace_signals.push(TraceSignal {
+                    name,
+                    member_name,
+                    value_name,
+                    bit_width,
+                    type_: TraceValueType::from_bit_width(bit_width),
+                    signed,
                 });
-                let expr = c.compile_signal(input.data.driven_value.borrow().unwrap(), prop_context);
+                num_trace_signals += 1;
+            }
+        };
+
+        let expr_arena = Arena::new();
+        let mut prop_context = AssignmentContext::new(&expr_arena);
+        let mut c = Compiler::new(
+            &state_elements,
+            definitions,
+            &signal_reference_counts,
+            &expr_arena,
+        );
+        for (name, input) in m.inputs.borrow().iter() {
+            add_trace_signal(
+                m,
+                name.clone(),
+                name.clone(),
+                input.data.bit_width,
+                input.data.signed,
+            );
+        }
+        // A definition's parent may need its outputs in any order, so each of them is computed by its own method. Definitions only store their outputs in members to trace them.
+        let has_output_members = is_top_level || tracing;
+        let mut output_methods = Vec::new();
+        for (name, output) in m.outputs.borrow().iter() {
+            if has_output_members {
+                let expr = c.compile_signal(output.data.source, &mut prop_context);
                 prop_context.push(Assignment {
                     target: expr_arena.alloc(Expr::Ref {
-                        name: field_name.clone(),
+                        name: name.clone(),
                         scope: Scope::Member,
                     }),
                     expr,
                 });
+            }
+            if !is_top_level {
+                let mut output_context = AssignmentContext::new(&expr_arena);
+                let mut output_compiler = Compiler::new(
+                    &state_elements,
+                    definitions,
+                    &signal_reference_counts,
+                    &expr_arena,
+                );
+                let expr = output_compiler.compile_signal(output.data.source, &mut output_context);
+                output_methods.push((name.clone(), output.data.bit_width, output_context, expr));
+            }
 
-                add_trace_signal(module, name.clone(), field_name, input.data.bit_width);
+            add_trace_signal(
+                m,
+                name.clone(),
+                name.clone(),
+                output.data.bit_width,
+                output.data.signed,
+            );
+        }
+        struct InnerField {
+            name: String,
+            bit_width: u32,
+        }
+        let mut inner_fields = Vec::new();
+        if tracing {
+            fn visit_module<'graph, 'context, 'expr_arena>(
+                module: &'graph graph::Module<'graph>,
+                c: &mut Compiler<'graph, 'context, 'expr_arena>,
+                inner_fields: &mut Vec<InnerField>,
+                prop_context: &mut AssignmentContext<'expr_arena>,
+                expr_arena: &'expr_arena Arena<Expr>,
+                add_trace_signal: &mut impl FnMut(
+                    &'graph graph::Module<'graph>,
+                    String,
+                    String,
+                    u32,
+                    bool,
+                ),
+            ) -> Result<()> {
+                // TODO: Identify and fix duplicate signals in traces
+                for (name, &input) in module.inputs.borrow().iter() {
+                    // TODO: De-dupe inner field allocs
+                    let field_name = format!("__inner_{}_{}", name, inner_fields.len());
+                    inner_fields.push(InnerField {
+                        name: field_name.clone(),
+                        bit_width: input.data.bit_width,
+                    });
+                    let expr =
+                        c.compile_signal(input.data.driven_value.borrow().unwrap(), prop_context);
+                    prop_context.push(Assignment {
+                        target: expr_arena.alloc(Expr::Ref {
+                            name: field_name.clone(),
+                            scope: Scope::Member,
+                        }),
+                        expr,
+                    });
+
+                    add_trace_signal(
+                        module,
+                        name.clone(),
+                        field_name,
+                        input.data.bit_width,
+                        input.data.signed,
+                    );
+                }
+                for (name, &output) in module.outputs.borrow().iter() {
+                    // TODO: De-dupe inner field allocs
+                    let field_name = format!("__inner_{}_{}", name, inner_fields.len());
+                    inner_fields.push(InnerField {
+                        name: field_name.clone(),
+                        bit_width: output.data.bit_width,
+                    });
+                    let expr = c.compile_signal(output.data.source, prop_context);
+                    prop_context.push(Assignment {
+                        target: expr_arena.alloc(Expr::Ref {
+                            name: field_name.clone(),
+                            scope: Scope::Member,
+                        }),
+                        expr,
+                    });
+
+                    add_trace_signal(
+                        module,
+                        name.clone(),
+                        field_name,
+                        output.data.bit_width,
+                        output.data.signed,
+                    );
+                }
+                // Instances are traced by their definitions' structs instead
+                for child in module.modules.borrow().iter() {
+                    if child.definition.is_none() {
+                        visit_module(
+                            child,
+                            c,
+                            inner_fields,
+                            prop_context,
+                            expr_arena,
+                            add_trace_signal,
+                        )?;
+                    }
+                }
+
+                Ok(())
             }
-            for (name, &output) in module.outputs.borrow().iter() {
-                // TODO: De-dupe inner field allocs
-                let field_name = format!("__inner_{}_{}", name, inner_fields.len());
-                inner_fields.push(InnerField {
-                    name: field_name.clone(),
-                    bit_width: output.data.bit_width,
+            for child in m.modules.borrow().iter() {
+                if child.definition.is_none() {
+                    visit_module(
+                        child,
+                        &mut c,
+                        &mut inner_fields,
+                        &mut prop_context,
+                        &expr_arena,
+                        &mut add_trace_signal,
+                    )?;
+                }
+            }
+
+            // Maps the signals traced by a definition's struct onto the corresponding `Module`s of one of its instances
+            fn import_trace_signals<'graph>(
+                definition_module: &'graph graph::Module<'graph>,
+                module: &'graph graph::Module<'graph>,
+                field_name: &str,
+                definition_trace_signals: &HashMap<&'graph graph::Module<'graph>, Vec<TraceSignal>>,
+                add_trace_signal: &mut impl FnMut(
+                    &'graph graph::Module<'graph>,
+                    String,
+                    String,
+                    u32,
+                    bool,
+                ),
+            ) {
+                if let Some(module_trace_signals) = definition_trace_signals.get(&definition_module)
+                {
+                    for trace_signal in module_trace_signals.iter() {
+                        add_trace_signal(
+                            module,
+                            trace_signal.name.clone(),
+                            format!("{}.{}", field_name, trace_signal.value_name),
+                            trace_signal.bit_width,
+                            trace_signal.signed,
+                        );
+                    }
+                }
+                for (&definition_child, &child) in definition_module
+                    .modules
+                    .borrow()
+                    .iter()
+                    .zip(module.modules.borrow().iter())
+                {
+                    import_trace_signals(
+                        definition_child,
+                        child,
+                        field_name,
+                        definition_trace_signals,
+                        add_trace_signal,
+                    );
+                }
+            }
+            for instance in instances.iter() {
+                import_trace_signals(
+                    instance.definition,
+                    instance.module,
+                    &instance.field_name,
+                    &definitions[&instance.definition].trace_signals,
+                    &mut add_trace_signal,
+                );
+            }
+        }
+        for (graph_mem, mem) in state_elements.mems.iter() {
+            for ((address, enable), read_signal_names) in mem.read_signal_names.iter() {
+                let address = c.compile_signal(address, &mut prop_context);
+                prop_context.push(Assignment {
+                    target: expr_arena.alloc(Expr::Ref {
+                        name: read_signal_names.address_name.clone(),
+                        scope: Scope::Member,
+                    }),
+                    expr: address,
                 });
-                let expr = c.compile_signal(output.data.source, prop_context);
+                let enable = c.compile_signal(enable, &mut prop_context);
                 prop_context.push(Assignment {
                     target: expr_arena.alloc(Expr::Ref {
-                        name: field_name.clone(),
+                        name: read_signal_names.enable_name.clone(),
                         scope: Scope::Member,
                     }),
-                    expr,
+                    expr: enable,
                 });
 
-                add_trace_signal(module, name.clone(), field_name, output.data.bit_width);
-            }
-            for child in module.modules.borrow().iter() {
-                visit_module(child, c, inner_fields, prop_context, expr_arena, add_trace_signal)?;
+                add_trace_signal(
+                    graph_mem.module,
+                    read_signal_names.address_name.clone(),
+                    read_signal_names.address_name.clone(),
+                    graph_mem.address_bit_width,
+                    false,
+                );
+                add_trace_signal(
+                    graph_mem.module,
+                    read_signal_names.enable_name.clone(),
+                    read_signal_names.enable_name.clone(),
+                    1,
+                    false,
+                );
             }
+            if let Some((address, value, enable)) = *graph_mem.write_port.borrow() {
+                let address = c.compile_signal(address, &mut prop_context);
+                prop_context.push(Assignment {
+                    target: expr_arena.alloc(Expr::Ref {
+                        name: mem.write_address_name.clone(),
+                        scope: Scope::Member,
+                    }),
+                    expr: address,
+                });
+                let value = c.compile_signal(value, &mut prop_context);
+                prop_context.push(Assignment {
+                    target: expr_arena.alloc(Expr::Ref {
+                        name: mem.write_value_name.clone(),
+                        scope: Scope::Member,
+                    }),
+                    expr: value,
+                });
+                let enable = c.compile_signal(enable, &mut prop_context);
+                prop_context.push(Assignment {
+                    target: expr_arena.alloc(Expr::Ref {
+                        name: mem.write_enable_name.clone(),
+                        scope: Scope::Member,
+                    }),
+                    expr: enable,
+                });
 
-            Ok(())
-        }
-        for child in m.modules.borrow().iter() {
-            visit_module(child, &mut c, &mut inner_fields, &mut prop_context, &expr_arena, &mut add_trace_signal)?;
+                add_trace_signal(
+                    graph_mem.module,
+                    mem.write_address_name.clone(),
+                    mem.write_address_name.clone(),
+                    graph_mem.address_bit_width,
+                    false,
+                );
+                add_trace_signal(
+                    graph_mem.module,
+                    mem.write_value_name.clone(),
+                    mem.write_value_name.clone(),
+                    graph_mem.element_bit_width,
+                    false,
+                );
+                add_trace_signal(
+                    graph_mem.module,
+                    mem.write_enable_name.clone(),
+                    mem.write_enable_name.clone(),
+                    1,
+                    false,
+                );
+            }
         }
-    }
-    for (graph_mem, mem) in state_elements.mems.iter() {
-        for ((address, enable), read_signal_names) in mem.read_signal_names.iter() {
-            let address = c.compile_signal(address, &mut prop_context);
-            prop_context.push(Assignment {
-                target: expr_arena.alloc(Expr::Ref {
-                    name: read_signal_names.address_name.clone(),
-                    scope: Scope::Member,
-                }),
-                expr: address,
-            });
-            let enable = c.compile_signal(enable, &mut prop_context);
+        for (_, reg) in state_elements.regs.iter() {
+            let signal = reg.data.next.borrow().unwrap();
+            let expr = c.compile_signal(signal, &mut prop_context);
             prop_context.push(Assignment {
                 target: expr_arena.alloc(Expr::Ref {
-                    name: read_signal_names.enable_name.clone(),
+                    name: reg.next_name.clone(),
                     scope: Scope::Member,
                 }),
-                expr: enable,
+                expr,
             });
 
             add_trace_signal(
-                graph_mem.module,
-                read_signal_names.address_name.clone(),
-                read_signal_names.address_name.clone(),
-                graph_mem.address_bit_width,
-            );
-            add_trace_signal(
-                graph_mem.module,
-                read_signal_names.enable_name.clone(),
-                read_signal_names.enable_name.clone(),
-                1,
+                signal.module,
+                reg.data.name.clone(),
+                reg.value_name.clone(),
+                signal.bit_width(),
+                reg.data.signed,
             );
         }
-        if let Some((address, value, enable)) = *graph_mem.write_port.borrow() {
-            let address = c.compile_signal(address, &mut prop_context);
-            prop_context.push(Assignment {
-                target: expr_arena.alloc(Expr::Ref {
-                    name: mem.write_address_name.clone(),
-                    scope: Scope::Member,
-                }),
-                expr: address,
-            });
-            let value = c.compile_signal(value, &mut prop_context);
-            prop_context.push(Assignment {
-                target: expr_arena.alloc(Expr::Ref {
-                    name: mem.write_value_name.clone(),
+        // Instances propagate their inputs to their own state elements once all of their inputs have been assigned
+        for instance in instances.iter() {
+            for (name, input) in instance.module.inputs.borrow().iter() {
+                let expr =
+                    c.compile_signal(input.data.driven_value.borrow().unwrap(), &mut prop_context);
+                prop_context.push(Assignment {
+                    target: gen_instance_member(&instance.field_name, name, &expr_arena),
+                    expr,
+                });
+            }
+            prop_context.push_expr(gen_instance_method_call(
+                &instance.field_name,
+                "prop",
+                &expr_arena,
+            ));
+        }
+
+        // Async resets are applied before any other propagation, so they're compiled separately to avoid
+        //  reusing values computed before the affected registers are reset
+        let mut async_reset_context = AssignmentContext::new(&expr_arena);
+        let mut async_reset_compiler = Compiler::new(
+            &state_elements,
+            definitions,
+            &signal_reference_counts,
+            &expr_arena,
+        );
+        let mut async_reset_sources = Vec::new();
+        let mut resets: HashMap<&'a graph::Reset<'a>, String> = HashMap::new();
+        for (_, reg) in state_elements.regs.iter() {
+            if let Some(reset) = *reg.data.reset.borrow() {
+                if resets.contains_key(&reset) {
+                    continue;
+                }
+                let field_name = format!("__reset_{}_{}", reset.name, resets.len());
+                let target = expr_arena.alloc(Expr::Ref {
+                    name: field_name.clone(),
                     scope: Scope::Member,
-                }),
-                expr: value,
+                });
+                match reset.kind {
+                    graph::ResetKind::Async => {
+                        let expr = async_reset_compiler
+                            .compile_signal(reset.source, &mut async_reset_context);
+                        async_reset_context.push(Assignment { target, expr });
+                        async_reset_sources.push(reset.source);
+                    }
+                    graph::ResetKind::Sync => {
+                        let expr = c.compile_signal(reset.source, &mut prop_context);
+                        prop_context.push(Assignment { target, expr });
+                    }
+                }
+                resets.insert(reset, field_name);
+            }
+        }
+        let gen_reset_asserted = |reset: &'a graph::Reset<'a>| {
+            let field = expr_arena.alloc(Expr::Ref {
+                name: resets[&reset].clone(),
+                scope: Scope::Member,
             });
-            let enable = c.compile_signal(enable, &mut prop_context);
-            prop_context.push(Assignment {
-                target: expr_arena.alloc(Expr::Ref {
-                    name: mem.write_enable_name.clone(),
-                    scope: Scope::Member,
+            match reset.polarity {
+                graph::ResetPolarity::ActiveHigh => &*field,
+                graph::ResetPolarity::ActiveLow => expr_arena.alloc(Expr::UnOp {
+                    source: field,
+                    op: UnOp::Not,
                 }),
-                expr: enable,
-            });
-
-            add_trace_signal(
-                graph_mem.module,
-                mem.write_address_name.clone(),
-                mem.write_address_name.clone(),
-                graph_mem.address_bit_width,
-            );
-            add_trace_signal(
-                graph_mem.module,
-                mem.write_value_name.clone(),
-                mem.write_value_name.clone(),
-                graph_mem.element_bit_width,
-            );
-            add_trace_signal(
-                graph_mem.module,
-                mem.write_enable_name.clone(),
-                mem.write_enable_name.clone(),
-                1,
-            );
+            }
+        };
+        for (_, reg) in state_elements.regs.iter() {
+            if let Some(reset) = *reg.data.reset.borrow() {
+                if let graph::ResetKind::Async = reset.kind {
+                    let value = expr_arena.alloc(Expr::Ref {
+                        name: reg.value_name.clone(),
+                        scope: Scope::Member,
+                    });
+                    async_reset_context.push(Assignment {
+                        target: value,
+                        expr: expr_arena.alloc(Expr::Ternary {
+                            cond: gen_reset_asserted(reset),
+                            when_true: Expr::from_constant(
+                                reg.data.initial_value.borrow().as_ref().unwrap(),
+                                reg.data.bit_width,
+                                &expr_arena,
+                            ),
+                            when_false: value,
+                        }),
+                    });
+                }
+            }
+        }
+        for instance in instances.iter() {
+            if let Some(ref async_reset_inputs) =
+                definitions[&instance.definition].async_reset_inputs
+            {
+                let inputs = instance.module.inputs.borrow();
+                for name in async_reset_inputs.iter() {
+                    let source = inputs[name].data.driven_value.borrow().unwrap();
+                    let expr =
+                        async_reset_compiler.compile_signal(source, &mut async_reset_context);
+                    async_reset_context.push(Assignment {
+                        target: gen_instance_member(&instance.field_name, name, &expr_arena),
+                        expr,
+                    });
+                    async_reset_sources.push(source);
+                }
+                async_reset_context.push_expr(gen_instance_method_call(
+                    &instance.field_name,
+                    "async_reset",
+                    &expr_arena,
+                ));
+            }
         }
-    }
-    for (_, reg) in state_elements.regs.iter() {
-        let signal = reg.data.next.borrow().unwrap();
-        let expr = c.compile_signal(signal, &mut prop_context);
-        prop_context.push(Assignment {
-            target: expr_arena.alloc(Expr::Ref {
-                name: reg.next_name.clone(),
-                scope: Scope::Member,
-            }),
-            expr,
-        });
-
-        add_trace_signal(
-            signal.module,
-            reg.data.name.clone(),
-            reg.value_name.clone(),
-            signal.bit_width(),
-        );
-    }
-
-    let mut w = code_writer::CodeWriter::new(w);
 
-    let module_name = options
-        .override_module_name
-        .unwrap_or_else(|| m.name.clone());
+        let vis = if is_top_level { "pub " } else { "" };
 
-    w.append_indent()?;
-    w.append(&format!("pub struct {}", module_name))?;
-    if options.tracing {
-        w.append("<T: kaze::runtime::tracing::Trace>")?;
-    }
-    w.append("{")?;
-    w.append_newline()?;
-    w.indent();
-
-    let inputs = m.inputs.borrow();
-    if !inputs.is_empty() {
-        w.append_line("// Inputs")?;
-        for (name, input) in inputs.iter() {
-            w.append_line(&format!(
-                "pub {}: {}, // {} bit(s)",
-                name,
-                ValueType::from_bit_width(input.data.bit_width).name(),
-                input.data.bit_width
-            ))?;
+        if !is_top_level {
+            w.append_line("#[allow(dead_code, non_camel_case_types)]")?;
         }
-    }
-
-    let outputs = m.outputs.borrow();
-    if !outputs.is_empty() {
-        w.append_line("// Outputs")?;
-        for (name, output) in outputs.iter() {
-            w.append_line(&format!(
-                "pub {}: {}, // {} bit(s)",
-                name,
-                ValueType::from_bit_width(output.data.bit_width).name(),
-                output.data.bit_width
-            ))?;
+        w.append_indent()?;
+        w.append(&format!("{}struct {}", vis, struct_name))?;
+        if is_top_level && tracing {
+            w.append("<T: kaze::runtime::tracing::Trace>")?;
         }
-    }
-
-    if !state_elements.regs.is_empty() {
+        w.append("{")?;
         w.append_newline()?;
-        w.append_line("// Regs")?;
-        for (_, reg) in state_elements.regs.iter() {
-            let type_name = ValueType::from_bit_width(reg.data.bit_width).name();
-            w.append_line(&format!(
-                "{}: {}, // {} bit(s)",
-                reg.value_name, type_name, reg.data.bit_width
-            ))?;
-            w.append_line(&format!("{}: {},", reg.next_name, type_name))?;
-        }
-    }
+        w.indent();
 
-    if !state_elements.mems.is_empty() {
-        w.append_newline()?;
-        w.append_line("// Mems")?;
-        for (_, mem) in state_elements.mems.iter() {
-            let address_type_name = ValueType::from_bit_width(mem.mem.address_bit_width).name();
-            let element_type_name = ValueType::from_bit_width(mem.mem.element_bit_width).name();
-            w.append_line(&format!(
-                "{}: Box<[{}]>, // {} bit elements",
-                mem.mem_name, element_type_name, mem.mem.element_bit_width
-            ))?;
-            for (_, read_signal_names) in mem.read_signal_names.iter() {
+        let inputs = m.inputs.borrow();
+        if !inputs.is_empty() {
+            w.append_line("// Inputs")?;
+            for (name, input) in inputs.iter() {
                 w.append_line(&format!(
-                    "{}: {},",
-                    read_signal_names.address_name, address_type_name
+                    "{}{}: {}, // {} bit(s)",
+                    vis,
+                    name,
+                    ValueType::from_bit_width(input.data.bit_width).name(),
+                    input.data.bit_width
                 ))?;
+            }
+        }
+
+        let outputs = m.outputs.borrow();
+        if has_output_members && !outputs.is_empty() {
+            w.append_line("// Outputs")?;
+            for (name, output) in outputs.iter() {
                 w.append_line(&format!(
-                    "{}: {},",
-                    read_signal_names.enable_name,
-                    ValueType::Bool.name()
+                    "{}{}: {}, // {} bit(s)",
+                    vis,
+                    name,
+                    ValueType::from_bit_width(output.data.bit_width).name(),
+                    output.data.bit_width
                 ))?;
+            }
+        }
+
+        if !state_elements.regs.is_empty() {
+            w.append_newline()?;
+            w.append_line("// Regs")?;
+            for (_, reg) in state_elements.regs.iter() {
+                let type_name = ValueType::from_bit_width(reg.data.bit_width).name();
                 w.append_line(&format!(
-                    "{}: {},",
-                    read_signal_names.value_name, element_type_name
+                    "{}: {}, // {} bit(s)",
+                    reg.value_name, type_name, reg.data.bit_width
                 ))?;
+                w.append_line(&format!("{}: {},", reg.next_name, type_name))?;
             }
-            if mem.mem.write_port.borrow().is_some() {
+        }
+
+        if !resets.is_empty() {
+            w.append_newline()?;
+            w.append_line("// Resets")?;
+            for field_name in resets.values() {
+                w.append_line(&format!("{}: {},", field_name, ValueType::Bool.name()))?;
+            }
+        }
+
+        if !state_elements.mems.is_empty() {
+            w.append_newline()?;
+            w.append_line("// Mems")?;
+            for (_, mem) in state_elements.mems.iter() {
+                let address_type_name = ValueType::from_bit_width(mem.mem.address_bit_width).name();
+                let element_type_name = ValueType::from_bit_width(mem.mem.element_bit_width).name();
                 w.append_line(&format!(
-                    "{}: {},",
-                    mem.write_address_name, address_type_name
+                    "{}: Box<[{}]>, // {} bit elements",
+                    mem.mem_name, element_type_name, mem.mem.element_bit_width
                 ))?;
-                w.append_line(&format!("{}: {},", mem.write_value_name, element_type_name))?;
+                for (_, read_signal_names) in mem.read_signal_names.iter() {
+                    w.append_line(&format!(
+                        "{}: {},",
+                        read_signal_names.address_name, address_type_name
+                    ))?;
+                    w.append_line(&format!(
+                        "{}: {},",
+                        read_signal_names.enable_name,
+                        ValueType::Bool.name()
+                    ))?;
+                    w.append_line(&format!(
+                        "{}: {},",
+                        read_signal_names.value_name, element_type_name
+                    ))?;
+                }
+                if mem.mem.write_port.borrow().is_some() {
+                    w.append_line(&format!(
+                        "{}: {},",
+                        mem.write_address_name, address_type_name
+                    ))?;
+                    w.append_line(&format!("{}: {},", mem.write_value_name, element_type_name))?;
+                    w.append_line(&format!(
+                        "{}: {},",
+                        mem.write_enable_name,
+                        ValueType::Bool.name()
+                    ))?;
+                }
+            }
+        }
+
+        if !instances.is_empty() {
+            w.append_newline()?;
+            w.append_line("// Instances")?;
+            for instance in instances.iter() {
                 w.append_line(&format!(
                     "{}: {},",
-                    mem.write_enable_name,
-                    ValueType::Bool.name()
+                    instance.field_name, definitions[&instance.definition].struct_name
                 ))?;
             }
         }
-    }
-
-    if !inner_fields.is_empty() {
-        w.append_newline()?;
-        w.append_line("// Inner")?;
-        for field in &inner_fields {
-            let type_name = ValueType::from_bit_width(field.bit_width).name();
-            w.append_line(&format!(
-                "{}: {}, // {} bit(s)",
-                field.name, type_name, field.bit_width
-            ))?;
-        }
-    }
 
-    if options.tracing {
-        w.append_newline()?;
-        w.append_line("__trace: T,")?;
-        for module_trace_signals in trace_signals.values() {
-            for trace_signal in module_trace_signals.iter() {
-                w.append_line(&format!("{}: T::SignalId,", trace_signal.member_name))?;
+        if !inner_fields.is_empty() {
+            w.append_newline()?;
+            w.append_line("// Inner")?;
+            for field in &inner_fields {
+                let type_name = ValueType::from_bit_width(field.bit_width).name();
+                w.append_line(&format!(
+                    "{}: {}, // {} bit(s)",
+                    field.name, type_name, field.bit_width
+                ))?;
             }
         }
-    }
-
-    w.unindent();
-    w.append_line("}")?;
-    w.append_newline()?;
-
-    w.append_line("#[allow(unused_parens)]")?;
-    w.append_line("#[automatically_derived]")?;
-    w.append_indent()?;
-    w.append("impl")?;
-    if options.tracing {
-        w.append("<T: kaze::runtime::tracing::Trace>")?;
-    }
-    w.append(&format!(" {}", module_name))?;
-    if options.tracing {
-        w.append("<T>")?;
-    }
-    w.append(" {")?;
-    w.append_newline()?;
-    w.indent();
-
-    w.append_indent()?;
-    w.append("pub fn new(")?;
-    if options.tracing {
-        w.append(&format!(
-            "mut trace: T) -> std::io::Result<{}<T>> {{",
-            module_name
-        ))?;
-    } else {
-        w.append(&format!(") -> {} {{", module_name))?;
-    }
-    w.append_newline()?;
-    w.indent();
-
-    if options.tracing {
-        fn visit_module<'a, W: Write>(
-            module: &'a graph::Module<'a>,
-            trace_signals: &HashMap<&'a graph::Module<'a>, Vec<TraceSignal>>,
-            w: &mut code_writer::CodeWriter<W>,
-        ) -> Result<()> {
-            w.append_line(&format!(
-                "trace.push_module(\"{}\")?;",
-                module.instance_name
-            ))?;
 
-            if let Some(module_trace_signals) = trace_signals.get(&module) {
+        if is_top_level && tracing {
+            w.append_newline()?;
+            w.append_line("__trace: T,")?;
+            for module_trace_signals in trace_signals.values() {
                 for trace_signal in module_trace_signals.iter() {
-                    w.append_line(&format!("let {} = trace.add_signal(\"{}\", {}, kaze::runtime::tracing::TraceValueType::{})?;", trace_signal.member_name, trace_signal.name, trace_signal.bit_width, match trace_signal.type_ {
-                        TraceValueType::Bool => "Bool",
-                        TraceValueType::U32 => "U32",
-                        TraceValueType::U64 => "U64",
-                        TraceValueType::U128 => "U128",
-                    }))?;
+                    w.append_line(&format!("{}: T::SignalId,", trace_signal.member_name))?;
                 }
             }
+        }
 
-            for child in module.modules.borrow().iter() {
-                visit_module(child, trace_signals, w)?;
-            }
-
-            w.append_line("trace.pop_module()?;")?;
+        w.unindent();
+        w.append_line("}")?;
+        w.append_newline()?;
 
-            Ok(())
+        if is_top_level {
+            w.append_line("#[allow(unused_parens, clippy::all)]")?;
+        } else {
+            w.append_line("#[allow(dead_code, unused_parens, clippy::all)]")?;
+        }
+        w.append_indent()?;
+        w.append("impl")?;
+        if is_top_level && tracing {
+            w.append("<T: kaze::runtime::tracing::Trace>")?;
+        }
+        w.append(&format!(" {}", struct_name))?;
+        if is_top_level && tracing {
+            w.append("<T>")?;
         }
-        visit_module(m, &trace_signals, &mut w)?;
+        w.append(" {")?;
         w.append_newline()?;
-    }
-
-    w.append_indent()?;
-    if options.tracing {
-        w.append("Ok(")?;
-    }
-    w.append(&format!("{} {{", module_name))?;
-    w.append_newline()?;
-    w.indent();
+        w.indent();
 
-    if !inputs.is_empty() {
-        w.append_line("// Inputs")?;
-        for (name, input) in inputs.iter() {
-            w.append_line(&format!(
-                "{}: {}, // {} bit(s)",
-                name,
-                ValueType::from_bit_width(input.data.bit_width).zero_str(),
-                input.data.bit_width
+        w.append_indent()?;
+        w.append(&format!("{}fn new(", vis))?;
+        if is_top_level && tracing {
+            w.append(&format!(
+                "mut trace: T) -> std::io::Result<{}<T>> {{",
+                struct_name
             ))?;
+        } else {
+            w.append(&format!(") -> {} {{", struct_name))?;
         }
-    }
+        w.append_newline()?;
+        w.indent();
 
-    if !outputs.is_empty() {
-        w.append_line("// Outputs")?;
-        for (name, output) in outputs.iter() {
-            w.append_line(&format!(
-                "{}: {}, // {} bit(s)",
-                name,
-                ValueType::from_bit_width(output.data.bit_width).zero_str(),
-                output.data.bit_width
-            ))?;
-        }
-    }
+        if is_top_level && tracing {
+            fn visit_module<'a, W: Write>(
+                module: &'a graph::Module<'a>,
+                trace_signals: &HashMap<&'a graph::Module<'a>, Vec<TraceSignal>>,
+                w: &mut code_writer::CodeWriter<W>,
+            ) -> Result<()> {
+                w.append_line(&format!(
+                    "trace.push_module(\"{}\")?;",
+                    module.instance_name
+                ))?;
 
-    if !state_elements.regs.is_empty() {
-        w.append_newline()?;
-        w.append_line("// Regs")?;
-        for (_, reg) in state_elements.regs.iter() {
-            w.append_line(&format!(
-                "{}: {}, // {} bit(s)",
-                reg.value_name,
-                ValueType::from_bit_width(reg.data.bit_width).zero_str(),
-                reg.data.bit_width
-            ))?;
-            w.append_line(&format!(
-                "{}: {},",
-                reg.next_name,
-                ValueType::from_bit_width(reg.data.bit_width).zero_str()
-            ))?;
+                if let Some(module_trace_signals) = trace_signals.get(&module) {
+                    for trace_signal in module_trace_signals.iter() {
+                        w.append_line(&format!("let {} = trace.{}(\"{}\", {}, kaze::runtime::tracing::TraceValueType::{})?;", trace_signal.member_name, if trace_signal.signed { "add_signed_signal" } else { "add_signal" }, trace_signal.name, trace_signal.bit_width, match trace_signal.type_ {
+                            TraceValueType::Bool => "Bool",
+                            TraceValueType::U32 => "U32",
+                            TraceValueType::U64 => "U64",
+                            TraceValueType::U128 => "U128",
+                            TraceValueType::Wide => "Wide",
+                        }))?;
+                    }
+                }
+
+                for child in module.modules.borrow().iter() {
+                    visit_module(child, trace_signals, w)?;
+                }
+
+                w.append_line("trace.pop_module()?;")?;
+
+                Ok(())
+            }
+            visit_module(m, &trace_signals, w)?;
+            w.append_newline()?;
         }
-    }
 
-    if !state_elements.mems.is_empty() {
+        w.append_indent()?;
+        if is_top_level && tracing {
+            w.append("Ok(")?;
+        }
+        w.append(&format!("{} {{", struct_name))?;
         w.append_newline()?;
-        w.append_line("// Mems")?;
-        for (_, mem) in state_elements.mems.iter() {
-            let address_type = ValueType::from_bit_width(mem.mem.address_bit_width);
-            let element_type = ValueType::from_bit_width(mem.mem.element_bit_width);
-            if let Some(ref initial_contents) = *mem.mem.initial_contents.borrow() {
-                w.append_line(&format!("{}: vec![", mem.mem_name))?;
-                w.indent();
-                for element in initial_contents.iter() {
-                    w.append_line(&match *element {
-                        graph::Constant::Bool(value) => format!("{},", value),
-                        graph::Constant::U32(value) => format!("0x{:x},", value),
-                        graph::Constant::U64(value) => format!("0x{:x},", value),
-                        graph::Constant::U128(value) => format!("0x{:x},", value),
-                    })?;
-                }
-                w.unindent();
-                w.append_line("].into_boxed_slice(),")?;
-            } else {
+        w.indent();
+
+        if !inputs.is_empty() {
+            w.append_line("// Inputs")?;
+            for (name, input) in inputs.iter() {
                 w.append_line(&format!(
-                    "{}: vec![{}; {}].into_boxed_slice(),",
-                    mem.mem_name,
-                    element_type.zero_str(),
-                    1 << mem.mem.address_bit_width
+                    "{}: {}, // {} bit(s)",
+                    name,
+                    ValueType::from_bit_width(input.data.bit_width).zero_str(),
+                    input.data.bit_width
                 ))?;
             }
-            for (_, read_signal_names) in mem.read_signal_names.iter() {
-                w.append_line(&format!(
-                    "{}: {},",
-                    read_signal_names.address_name,
-                    address_type.zero_str()
-                ))?;
-                w.append_line(&format!(
-                    "{}: {},",
-                    read_signal_names.enable_name,
-                    ValueType::Bool.zero_str()
-                ))?;
+        }
+
+        if has_output_members && !outputs.is_empty() {
+            w.append_line("// Outputs")?;
+            for (name, output) in outputs.iter() {
                 w.append_line(&format!(
-                    "{}: {},",
-                    read_signal_names.value_name,
-                    element_type.zero_str()
+                    "{}: {}, // {} bit(s)",
+                    name,
+                    ValueType::from_bit_width(output.data.bit_width).zero_str(),
+                    output.data.bit_width
                 ))?;
             }
-            if mem.mem.write_port.borrow().is_some() {
-                w.append_line(&format!(
-                    "{}: {},",
-                    mem.write_address_name,
-                    address_type.zero_str()
-                ))?;
+        }
+
+        if !state_elements.regs.is_empty() {
+            w.append_newline()?;
+            w.append_line("// Regs")?;
+            for (_, reg) in state_elements.regs.iter() {
                 w.append_line(&format!(
-                    "{}: {},",
-                    mem.write_value_name,
-                    element_type.zero_str()
+                    "{}: {}, // {} bit(s)",
+                    reg.value_name,
+                    ValueType::from_bit_width(reg.data.bit_width).zero_str(),
+                    reg.data.bit_width
                 ))?;
                 w.append_line(&format!(
                     "{}: {},",
-                    mem.write_enable_name,
-                    ValueType::Bool.zero_str()
+                    reg.next_name,
+                    ValueType::from_bit_width(reg.data.bit_width).zero_str()
                 ))?;
             }
         }
-    }
 
-    if !inner_fields.is_empty() {
-        w.append_newline()?;
-        for field in &inner_fields {
-            w.append_line(&format!(
-                "{}: {}, // {} bit(s)",
-                field.name,
-                ValueType::from_bit_width(field.bit_width).zero_str(),
-                field.bit_width
-            ))?;
+        if !resets.is_empty() {
+            w.append_newline()?;
+            w.append_line("// Resets")?;
+            for field_name in resets.values() {
+                w.append_line(&format!("{}: {},", field_name, ValueType::Bool.zero_str()))?;
+            }
         }
-    }
 
-    if options.tracing {
-        w.append_newline()?;
-        w.append_line("__trace: trace,")?;
-        for module_trace_signals in trace_signals.values() {
-            for trace_signal in module_trace_signals.iter() {
-                w.append_line(&format!("{},", trace_signal.member_name))?;
+        if !state_elements.mems.is_empty() {
+            w.append_newline()?;
+            w.append_line("// Mems")?;
+            for (_, mem) in state_elements.mems.iter() {
+                let address_type = ValueType::from_bit_width(mem.mem.address_bit_width);
+                let element_type = ValueType::from_bit_width(mem.mem.element_bit_width);
+                if let Some(ref initial_contents) = *mem.mem.initial_contents.borrow() {
+                    w.append_line(&format!("{}: vec![", mem.mem_name))?;
+                    w.indent();
+                    for element in initial_contents.iter() {
+                        w.append_indent()?;
+                        Expr::from_constant(element, mem.mem.element_bit_width, &expr_arena)
+                            .write(w)?;
+                        w.append(",")?;
+                        w.append_newline()?;
+                    }
+                    w.unindent();
+                    w.append_line("].into_boxed_slice(),")?;
+                } else {
+                    w.append_line(&format!(
+                        "{}: vec![{}; {}].into_boxed_slice(),",
+                        mem.mem_name,
+                        element_type.zero_str(),
+                        1 << mem.mem.address_bit_width
+                    ))?;
+                }
+                for (_, read_signal_names) in mem.read_signal_names.iter() {
+                    w.append_line(&format!(
+                        "{}: {},",
+                        read_signal_names.address_name,
+                        address_type.zero_str()
+                    ))?;
+                    w.append_line(&format!(
+                        "{}: {},",
+                        read_signal_names.enable_name,
+                        ValueType::Bool.zero_str()
+                    ))?;
+                    w.append_line(&format!(
+                        "{}: {},",
+                        read_signal_names.value_name,
+                        element_type.zero_str()
+                    ))?;
+                }
+                if mem.mem.write_port.borrow().is_some() {
+                    w.append_line(&format!(
+                        "{}: {},",
+                        mem.write_address_name,
+                        address_type.zero_str()
+                    ))?;
+                    w.append_line(&format!(
+                        "{}: {},",
+                        mem.write_value_name,
+                        element_type.zero_str()
+                    ))?;
+                    w.append_line(&format!(
+                        "{}: {},",
+                        mem.write_enable_name,
+                        ValueType::Bool.zero_str()
+                    ))?;
+                }
             }
         }
-    }
 
-    w.unindent();
-    w.append_indent()?;
-    w.append("}")?;
-    if options.tracing {
-        w.append(")")?;
-    }
-    w.append_newline()?;
-    w.unindent();
-    w.append_line("}")?;
+        if !instances.is_empty() {
+            w.append_newline()?;
+            w.append_line("// Instances")?;
+            for instance in instances.iter() {
+                w.append_line(&format!(
+                    "{}: {}::new(),",
+                    instance.field_name, definitions[&instance.definition].struct_name
+                ))?;
+            }
+        }
 
-    let mut reset_context = AssignmentContext::new(&expr_arena);
-    let mut posedge_clk_context = AssignmentContext::new(&expr_arena);
+        if !inner_fields.is_empty() {
+            w.append_newline()?;
+            for field in &inner_fields {
+                w.append_line(&format!(
+                    "{}: {}, // {} bit(s)",
+                    field.name,
+                    ValueType::from_bit_width(field.bit_width).zero_str(),
+                    field.bit_width
+                ))?;
+            }
+        }
 
-    for (_, reg) in state_elements.regs.iter() {
-        let target = expr_arena.alloc(Expr::Ref {
-            name: reg.value_name.clone(),
-            scope: Scope::Member,
-        });
+        if is_top_level && tracing {
+            w.append_newline()?;
+            w.append_line("__trace: trace,")?;
+            for module_trace_signals in trace_signals.values() {
+                for trace_signal in module_trace_signals.iter() {
+                    w.append_line(&format!("{},", trace_signal.member_name))?;
+                }
+            }
+        }
 
-        if let Some(ref initial_value) = *reg.data.initial_value.borrow() {
-            reset_context.push(Assignment {
-                target,
-                expr: Expr::from_constant(initial_value, reg.data.bit_width, &expr_arena),
-            });
+        w.unindent();
+        w.append_indent()?;
+        w.append("}")?;
+        if is_top_level && tracing {
+            w.append(")")?;
         }
+        w.append_newline()?;
+        w.unindent();
+        w.append_line("}")?;
 
-        posedge_clk_context.push(Assignment {
-            target,
-            expr: expr_arena.alloc(Expr::Ref {
-                name: reg.next_name.clone(),
-                scope: Scope::Member,
-            }),
-        });
-    }
+        let mut reset_context = AssignmentContext::new(&expr_arena);
+        let mut posedge_contexts = BTreeMap::new();
 
-    for (_, mem) in state_elements.mems.iter() {
-        for (_, read_signal_names) in mem.read_signal_names.iter() {
-            let address = expr_arena.alloc(Expr::Ref {
-                name: read_signal_names.address_name.clone(),
-                scope: Scope::Member,
-            });
-            let enable = expr_arena.alloc(Expr::Ref {
-                name: read_signal_names.enable_name.clone(),
+        for (_, reg) in state_elements.regs.iter() {
+            let target = expr_arena.alloc(Expr::Ref {
+                name: reg.value_name.clone(),
                 scope: Scope::Member,
             });
-            let value = expr_arena.alloc(Expr::Ref {
-                name: read_signal_names.value_name.clone(),
+
+            let next = expr_arena.alloc(Expr::Ref {
+                name: reg.next_name.clone(),
                 scope: Scope::Member,
             });
-            let element = expr_arena.alloc(Expr::ArrayIndex {
-                target: expr_arena.alloc(Expr::Ref {
-                    name: mem.mem_name.clone(),
-                    scope: Scope::Member,
-                }),
-                index: address,
-            });
-            // TODO: Conditional assign statement instead of always writing ternary
-            posedge_clk_context.push(Assignment {
-                target: value,
-                expr: expr_arena.alloc(Expr::Ternary {
-                    cond: enable,
-                    when_true: element,
-                    when_false: value,
+            let expr = match *reg.data.reset.borrow() {
+                Some(reset) => &*expr_arena.alloc(Expr::Ternary {
+                    cond: gen_reset_asserted(reset),
+                    when_true: Expr::from_constant(
+                        reg.data.initial_value.borrow().as_ref().unwrap(),
+                        reg.data.bit_width,
+                        &expr_arena,
+                    ),
+                    when_false: next,
                 }),
-            });
+                None => {
+                    if let Some(ref initial_value) = *reg.data.initial_value.borrow() {
+                        reset_context.push(Assignment {
+                            target,
+                            expr: Expr::from_constant(
+                                initial_value,
+                                reg.data.bit_width,
+                                &expr_arena,
+                            ),
+                        });
+                    }
+
+                    next
+                }
+            };
+
+            let clock_name = clock_domain_name(m, *reg.data.clock.borrow());
+            let posedge_context = posedge_contexts
+                .entry(clock_name)
+                .or_insert_with(|| AssignmentContext::new(&expr_arena));
+            posedge_context.push(Assignment { target, expr });
         }
-        if mem.mem.write_port.borrow().is_some() {
-            let address = expr_arena.alloc(Expr::Ref {
-                name: mem.write_address_name.clone(),
-                scope: Scope::Member,
-            });
-            let value = expr_arena.alloc(Expr::Ref {
-                name: mem.write_value_name.clone(),
-                scope: Scope::Member,
-            });
-            let enable = expr_arena.alloc(Expr::Ref {
-                name: mem.write_enable_name.clone(),
-                scope: Scope::Member,
-            });
-            let element = expr_arena.alloc(Expr::ArrayIndex {
-                target: expr_arena.alloc(Expr::Ref {
-                    name: mem.mem_name.clone(),
+
+        for (_, mem) in state_elements.mems.iter() {
+            let clock_name = clock_domain_name(m, *mem.mem.clock.borrow());
+            let posedge_context = posedge_contexts
+                .entry(clock_name)
+                .or_insert_with(|| AssignmentContext::new(&expr_arena));
+            for (_, read_signal_names) in mem.read_signal_names.iter() {
+                let address = expr_arena.alloc(Expr::Ref {
+                    name: read_signal_names.address_name.clone(),
                     scope: Scope::Member,
-                }),
-                index: address,
-            });
-            // TODO: Conditional assign statement instead of always writing ternary
-            posedge_clk_context.push(Assignment {
-                target: element,
-                expr: expr_arena.alloc(Expr::Ternary {
-                    cond: enable,
-                    when_true: value,
-                    when_false: element,
-                }),
-            });
+                });
+                let enable = expr_arena.alloc(Expr::Ref {
+                    name: read_signal_names.enable_name.clone(),
+                    scope: Scope::Member,
+                });
+                let value = expr_arena.alloc(Expr::Ref {
+                    name: read_signal_names.value_name.clone(),
+                    scope: Scope::Member,
+                });
+                let element = expr_arena.alloc(Expr::ArrayIndex {
+                    target: expr_arena.alloc(Expr::Ref {
+                        name: mem.mem_name.clone(),
+                        scope: Scope::Member,
+                    }),
+                    index: address,
+                });
+                // TODO: Conditional assign statement instead of always writing ternary
+                posedge_context.push(Assignment {
+                    target: value,
+                    expr: expr_arena.alloc(Expr::Ternary {
+                        cond: enable,
+                        when_true: element,
+                        when_false: value,
+                    }),
+                });
+            }
+            if mem.mem.write_port.borrow().is_some() {
+                let address = expr_arena.alloc(Expr::Ref {
+                    name: mem.write_address_name.clone(),
+                    scope: Scope::Member,
+                });
+                let value = expr_arena.alloc(Expr::Ref {
+                    name: mem.write_value_name.clone(),
+                    scope: Scope::Member,
+                });
+                let enable = expr_arena.alloc(Expr::Ref {
+                    name: mem.write_enable_name.clone(),
+                    scope: Scope::Member,
+                });
+                let element = expr_arena.alloc(Expr::ArrayIndex {
+                    target: expr_arena.alloc(Expr::Ref {
+                        name: mem.mem_name.clone(),
+                        scope: Scope::Member,
+                    }),
+                    index: address,
+                });
+                // TODO: Conditional assign statement instead of always writing ternary
+                posedge_context.push(Assignment {
+                    target: element,
+                    expr: expr_arena.alloc(Expr::Ternary {
+                        cond: enable,
+                        when_true: value,
+                        when_false: element,
+                    }),
+                });
+            }
         }
-    }
 
-    if !reset_context.is_empty() {
-        w.append_newline()?;
-        w.append_line("pub fn reset(&mut self) {")?;
-        w.indent();
+        for instance in instances.iter() {
+            let definition = &definitions[&instance.definition];
+            if definition.has_reset {
+                reset_context.push_expr(gen_instance_method_call(
+                    &instance.field_name,
+                    "reset",
+                    &expr_arena,
+                ));
+            }
+            for clock_name in definition.clock_names.iter() {
+                // The instance's clock domains are named after its own clocks, which are driven by clocks in this struct's clock domains
+                let clock = instance.module.clocks.borrow().get(clock_name).copied();
+                let posedge_context = posedge_contexts
+                    .entry(clock_domain_name(m, clock))
+                    .or_insert_with(|| AssignmentContext::new(&expr_arena));
+                posedge_context.push_expr(gen_instance_method_call(
+                    &instance.field_name,
+                    &format!("posedge_{}", clock_name),
+                    &expr_arena,
+                ));
+            }
+        }
 
-        reset_context.write(&mut w)?;
+        if !reset_context.is_empty() {
+            w.append_newline()?;
+            w.append_line(&format!("{}fn reset(&mut self) {{", vis))?;
+            w.indent();
 
-        w.unindent();
-        w.append_line("}")?;
-    }
+            reset_context.write(w)?;
+
+            w.unindent();
+            w.append_line("}")?;
+        }
+
+        for (clock_name, posedge_context) in posedge_contexts.iter() {
+            w.append_newline()?;
+            w.append_line(&format!("{}fn posedge_{}(&mut self) {{", vis, clock_name))?;
+            w.indent();
+
+            posedge_context.write(w)?;
+
+            w.unindent();
+            w.append_line("}")?;
+        }
 
-    if !posedge_clk_context.is_empty() {
         w.append_newline()?;
-        w.append_line("pub fn posedge_clk(&mut self) {")?;
+        w.append_line(&format!("{}fn prop(&mut self) {{", vis))?;
         w.indent();
 
-        posedge_clk_context.write(&mut w)?;
+        // A definition's async resets are applied separately by its parent, before the parent propagates anything else
+        if is_top_level {
+            async_reset_context.write(w)?;
+        }
+        prop_context.write(w)?;
 
         w.unindent();
         w.append_line("}")?;
-    }
 
-    w.append_newline()?;
-    w.append_line("pub fn prop(&mut self) {")?;
-    w.indent();
+        if !is_top_level && !async_reset_context.is_empty() {
+            w.append_newline()?;
+            w.append_line("fn async_reset(&mut self) {")?;
+            w.indent();
 
-    prop_context.write(&mut w)?;
+            async_reset_context.write(w)?;
 
-    w.unindent();
-    w.append_line("}")?;
+            w.unindent();
+            w.append_line("}")?;
+        }
 
-    if options.tracing {
-        w.append_newline()?;
-        w.append_line("pub fn update_trace(&mut self, time_stamp: u64) -> std::io::Result<()> {")?;
-        w.indent();
+        for (name, bit_width, output_context, expr) in output_methods.iter() {
+            w.append_newline()?;
+            w.append_line(&format!(
+                "fn output_{}(&mut self) -> {} {{",
+                name,
+                ValueType::from_bit_width(*bit_width).name()
+            ))?;
+            w.indent();
 
-        w.append_line("self.__trace.update_time_stamp(time_stamp)?;")?;
-        w.append_newline()?;
+            output_context.write(w)?;
+            w.append_indent()?;
+            expr.write(w)?;
+            w.append_newline()?;
 
-        for module_trace_signals in trace_signals.values() {
-            for trace_signal in module_trace_signals.iter() {
-                w.append_line(&format!("self.__trace.update_signal(&self.{}, kaze::runtime::tracing::TraceValue::{}(self.{}))?;", trace_signal.member_name, match trace_signal.type_ {
-                    TraceValueType::Bool => "Bool",
-                    TraceValueType::U32 => "U32",
-                    TraceValueType::U64 => "U64",
-                    TraceValueType::U128 => "U128",
-                }, trace_signal.value_name))?;
-            }
+            w.unindent();
+            w.append_line("}")?;
         }
-        w.append_newline()?;
 
-        w.append_line("Ok(())")?;
+        if is_top_level && tracing {
+            w.append_newline()?;
+            w.append_line(
+                "pub fn update_trace(&mut self, time_stamp: u64) -> std::io::Result<()> {",
+            )?;
+            w.indent();
+
+            w.append_line("self.__trace.update_time_stamp(time_stamp)?;")?;
+            w.append_newline()?;
+
+            for module_trace_signals in trace_signals.values() {
+                for trace_signal in module_trace_signals.iter() {
+                    w.append_line(&format!("self.__trace.update_signal(&self.{}, kaze::runtime::tracing::TraceValue::{})?;", trace_signal.member_name, match trace_signal.type_ {
+                        TraceValueType::Bool => format!("Bool(self.{})", trace_signal.value_name),
+                        TraceValueType::U32 => format!("U32(self.{})", trace_signal.value_name),
+                        TraceValueType::U64 => format!("U64(self.{})", trace_signal.value_name),
+                        TraceValueType::U128 => format!("U128(self.{})", trace_signal.value_name),
+                        TraceValueType::Wide => format!("Wide(self.{}.words().to_vec())", trace_signal.value_name),
+                    }))?;
+                }
+            }
+            w.append_newline()?;
+
+            w.append_line("Ok(())")?;
+
+            w.unindent();
+            w.append_line("}")?;
+        }
 
         w.unindent();
         w.append_line("}")?;
+        w.append_newline()?;
+
+        let output_inputs = outputs
+            .iter()
+            .map(|(name, output)| {
+                (
+                    name.clone(),
+                    combinational_inputs(m, vec![output.data.source], definitions),
+                )
+            })
+            .collect();
+        let async_reset_inputs = if async_reset_context.is_empty() {
+            None
+        } else {
+            Some(combinational_inputs(m, async_reset_sources, definitions))
+        };
+
+        Ok(Definition {
+            struct_name: struct_name.into(),
+            output_inputs,
+            async_reset_inputs,
+            has_reset: !reset_context.is_empty(),
+            clock_names: posedge_contexts.into_keys().collect(),
+            trace_signals,
+        })
     }
+}
 
-    w.unindent();
-    w.append_line("}")?;
-    w.append_newline()?;
+// Returns the names of `m`'s inputs that any of `signals` depend on combinationally
+fn combinational_inputs<'a>(
+    m: &'a graph::Module<'a>,
+    mut signals: Vec<&'a internal_signal::InternalSignal<'a>>,
+    definitions: &HashMap<&'a graph::Module<'a>, Definition<'a>>,
+) -> Vec<String> {
+    let mut inputs = BTreeSet::new();
+    let mut visited = HashSet::new();
+    while let Some(signal) = signals.pop() {
+        if !visited.insert(signal) {
+            continue;
+        }
 
-    Ok(())
+        match signal.data {
+            internal_signal::SignalData::Input { data } if ptr::eq(signal.module, m) => {
+                inputs.insert(data.name.clone());
+            }
+            internal_signal::SignalData::Output { data } => match data.module.definition {
+                // An instance's output only depends on the instance's inputs that its definition says it does
+                Some(definition) => {
+                    let instance_inputs = data.module.inputs.borrow();
+                    for name in definitions[&definition].output_inputs[&data.name].iter() {
+                        signals.push(instance_inputs[name].value);
+                    }
+                }
+                None => signals.push(data.source),
+            },
+            _ => signals.extend(combinational_sources(signal)),
+        }
+    }
+    inputs.into_iter().collect()
+}
+
+fn gen_instance_member<'arena>(
+    field_name: &str,
+    member_name: &str,
+    arena: &'arena Arena<Expr<'arena>>,
+) -> &'arena Expr<'arena> {
+    arena.alloc(Expr::Ref {
+        name: format!("{}.{}", field_name, member_name),
+        scope: Scope::Member,
+    })
+}
+
+fn gen_instance_method_call<'arena>(
+    field_name: &str,
+    method_name: &str,
+    arena: &'arena Arena<Expr<'arena>>,
+) -> &'arena Expr<'arena> {
+    arena.alloc(Expr::MemberCall {
+        target: arena.alloc(Expr::Ref {
+            name: field_name.into(),
+            scope: Scope::Member,
+        }),
+        name: method_name.into(),
+        args: Vec::new(),
+    })
+}
+
+fn clock_domain_name<'a>(m: &'a graph::Module<'a>, clock: Option<&'a graph::Clock<'a>>) -> String {
+    match clock {
+        Some(mut clock) => {
+            // Follow driven clocks up the hierarchy until we reach a clock owned by the generated module
+            while !ptr::eq(clock.module, m) {
+                clock = clock.driven_clock.borrow().unwrap();
+            }
+            clock.name.clone()
+        }
+        None => "clk".into(),
+    }
 }
 
 #[cfg(test)]
@@ -743,6 +1286,21 @@ mod tests {
         generate(a, GenerationOptions::default(), Vec::new()).unwrap();
     }
 
+    #[test]
+    #[should_panic(
+        expected = "Cannot generate code for module \"A\" because module \"A\" contains an instance of module \"B\" called \"b\" whose clock \"c\" is not driven."
+    )]
+    fn undriven_instance_clock_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let b = a.module("b", "B");
+        let _ = b.clock("c");
+
+        // Panic
+        generate(a, GenerationOptions::default(), Vec::new()).unwrap();
+    }
+
     #[test]
     #[should_panic(
         expected = "Cannot generate code for module \"A\" because module \"A\" contains a register called \"r\" which is not driven."
@@ -772,6 +1330,28 @@ mod tests {
         generate(a, GenerationOptions::default(), Vec::new()).unwrap();
     }
 
+    #[test]
+    #[should_panic(
+        expected = "Cannot generate code for module \"A\" because module \"A\" contains a register called \"r\" which is assigned to reset \"rst\" but doesn't have a default value."
+    )]
+    fn reset_without_default_value_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let rst = a.reset(
+            "rst",
+            a.input("rst", 1),
+            ResetKind::Sync,
+            ResetPolarity::ActiveHigh,
+        );
+        let r = a.reg("r", 1);
+        r.reset(rst);
+        r.drive_next(!r);
+
+        // Panic
+        generate(a, GenerationOptions::default(), Vec::new()).unwrap();
+    }
+
     #[test]
     #[should_panic(
         expected = "Cannot generate code for module \"A\" because module \"A\" contains a memory called \"m\" which doesn't have any read ports."
@@ -848,4 +1428,34 @@ mod tests {
         // Panic
         generate(b, GenerationOptions::default(), Vec::new()).unwrap();
     }
+
+    #[test]
+    fn shared_module_definitions() {
+        let c = Context::new();
+
+        let inner = c.module("inner", "Inner");
+        let i = inner.input("i", 1);
+        let r = inner.reg("r", 1);
+        r.default_value(false);
+        r.drive_next(i);
+        inner.output("o", r);
+
+        let top = c.module("top", "Top");
+        let i = top.input("i", 1);
+        let inner1 = top.instance("inner1", inner);
+        inner1.get_input("i").unwrap().drive(i);
+        let inner2 = top.instance("inner2", inner);
+        inner2
+            .get_input("i")
+            .unwrap()
+            .drive(inner1.get_output("o").unwrap());
+        top.output("o", inner2.get_output("o").unwrap());
+
+        let mut code = Vec::new();
+        generate(top, GenerationOptions::default(), &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert_eq!(code.matches("struct __Top_Inner_").count(), 1);
+        assert_eq!(code.matches(": __Top_Inner_0,").count(), 2);
+    }
 }
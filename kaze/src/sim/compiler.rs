@@ -1,6 +1,8 @@
 use super::ir::*;
 use super::state_elements::*;
+use super::{gen_instance_member, gen_instance_method_call, Definition};
 
+use crate::graph;
 use crate::graph::internal_signal;
 
 use typed_arena::Arena;
@@ -10,17 +12,22 @@ use std::collections::HashMap;
 // TODO: Can we merge the context and expr_arena lifetimes?
 pub(super) struct Compiler<'graph, 'context, 'expr_arena> {
     state_elements: &'context StateElements<'graph>,
+    definitions: &'context HashMap<&'graph graph::Module<'graph>, Definition<'graph>>,
     signal_reference_counts:
         &'context HashMap<&'graph internal_signal::InternalSignal<'graph>, u32>,
     expr_arena: &'expr_arena Arena<Expr<'expr_arena>>,
 
     signal_exprs:
         HashMap<&'graph internal_signal::InternalSignal<'graph>, &'expr_arena Expr<'expr_arena>>,
+    // Each use of an output creates a new signal, so instance outputs are also cached by output to avoid calling their methods more than once
+    instance_output_exprs:
+        HashMap<*const graph::OutputData<'graph>, &'expr_arena Expr<'expr_arena>>,
 }
 
 impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
     pub fn new(
         state_elements: &'context StateElements<'graph>,
+        definitions: &'context HashMap<&'graph graph::Module<'graph>, Definition<'graph>>,
         signal_reference_counts: &'context HashMap<
             &'graph internal_signal::InternalSignal<'graph>,
             u32,
@@ -29,10 +36,12 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
     ) -> Compiler<'graph, 'context, 'expr_arena> {
         Compiler {
             state_elements,
+            definitions,
             signal_reference_counts,
             expr_arena,
 
             signal_exprs: HashMap::new(),
+            instance_output_exprs: HashMap::new(),
         }
     }
 
@@ -68,10 +77,10 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                         internal_signal::SignalData::Lit {
                             ref value,
                             bit_width,
-                        } => Some((key, Expr::from_constant(value, bit_width, &self.expr_arena))),
+                        } => Some((key, Expr::from_constant(value, bit_width, self.expr_arena))),
 
                         internal_signal::SignalData::Input { data } => {
-                            if let Some(driven_value) = data.driven_value.borrow().clone() {
+                            if let Some(driven_value) = *data.driven_value.borrow() {
                                 frames.push(Frame::Enter {
                                     signal: driven_value,
                                 });
@@ -87,9 +96,37 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             }
                         }
                         internal_signal::SignalData::Output { data } => {
-                            frames.push(Frame::Enter {
-                                signal: data.source,
-                            });
+                            match self.state_elements.instances.get(&data.module) {
+                                Some(instance) => {
+                                    if let Some(expr) =
+                                        self.instance_output_exprs.get(&(data as *const _))
+                                    {
+                                        results.push(*expr);
+                                        continue;
+                                    }
+
+                                    // The inputs this output depends on have to be assigned before its method is called
+                                    frames.push(Frame::Leave { signal });
+                                    let inputs = instance.module.inputs.borrow();
+                                    for name in self.definitions[&instance.definition].output_inputs
+                                        [&data.name]
+                                        .iter()
+                                    {
+                                        frames.push(Frame::Enter {
+                                            signal: inputs[name]
+                                                .data
+                                                .driven_value
+                                                .borrow()
+                                                .unwrap(),
+                                        });
+                                    }
+                                }
+                                None => {
+                                    frames.push(Frame::Enter {
+                                        signal: data.source,
+                                    });
+                                }
+                            }
                             None
                         }
 
@@ -106,6 +143,26 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             frames.push(Frame::Enter { signal: source });
                             None
                         }
+                        internal_signal::SignalData::ReduceOp { source, .. } => {
+                            frames.push(Frame::Leave { signal });
+                            frames.push(Frame::Enter { signal: source });
+                            None
+                        }
+                        internal_signal::SignalData::CountOp { source, .. } => {
+                            frames.push(Frame::Leave { signal });
+                            frames.push(Frame::Enter { signal: source });
+                            None
+                        }
+                        internal_signal::SignalData::OneHot { source, .. } => {
+                            frames.push(Frame::Leave { signal });
+                            frames.push(Frame::Enter { signal: source });
+                            None
+                        }
+                        internal_signal::SignalData::PriorityEncode { source, .. } => {
+                            frames.push(Frame::Leave { signal });
+                            frames.push(Frame::Enter { signal: source });
+                            None
+                        }
                         internal_signal::SignalData::SimpleBinOp { lhs, rhs, .. } => {
                             frames.push(Frame::Leave { signal });
                             frames.push(Frame::Enter { signal: lhs });
@@ -130,6 +187,12 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             frames.push(Frame::Enter { signal: rhs });
                             None
                         }
+                        internal_signal::SignalData::RotateBinOp { lhs, rhs, .. } => {
+                            frames.push(Frame::Leave { signal });
+                            frames.push(Frame::Enter { signal: lhs });
+                            frames.push(Frame::Enter { signal: rhs });
+                            None
+                        }
 
                         internal_signal::SignalData::Mul { lhs, rhs, .. } => {
                             frames.push(Frame::Leave { signal });
@@ -143,12 +206,23 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             frames.push(Frame::Enter { signal: rhs });
                             None
                         }
+                        internal_signal::SignalData::DivisionBinOp { lhs, rhs, .. } => {
+                            frames.push(Frame::Leave { signal });
+                            frames.push(Frame::Enter { signal: lhs });
+                            frames.push(Frame::Enter { signal: rhs });
+                            None
+                        }
 
                         internal_signal::SignalData::Bits { source, .. } => {
                             frames.push(Frame::Leave { signal });
                             frames.push(Frame::Enter { signal: source });
                             None
                         }
+                        internal_signal::SignalData::Extend { source, .. } => {
+                            frames.push(Frame::Leave { signal });
+                            frames.push(Frame::Enter { signal: source });
+                            None
+                        }
 
                         internal_signal::SignalData::Repeat { source, .. } => {
                             frames.push(Frame::Leave { signal });
@@ -174,6 +248,18 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             frames.push(Frame::Enter { signal: when_false });
                             None
                         }
+                        internal_signal::SignalData::Index {
+                            ref elements,
+                            index,
+                            ..
+                        } => {
+                            frames.push(Frame::Leave { signal });
+                            frames.push(Frame::Enter { signal: index });
+                            for &element in elements.iter() {
+                                frames.push(Frame::Enter { signal: element });
+                            }
+                            None
+                        }
 
                         internal_signal::SignalData::MemReadPortOutput {
                             mem,
@@ -199,7 +285,30 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                         internal_signal::SignalData::Lit { .. } => unreachable!(),
 
                         internal_signal::SignalData::Input { .. } => unreachable!(),
-                        internal_signal::SignalData::Output { .. } => unreachable!(),
+                        internal_signal::SignalData::Output { data } => {
+                            let instance = &self.state_elements.instances[&data.module];
+                            for name in self.definitions[&instance.definition].output_inputs
+                                [&data.name]
+                                .iter()
+                            {
+                                let expr = results.pop().unwrap();
+                                a.push(Assignment {
+                                    target: gen_instance_member(
+                                        &instance.field_name,
+                                        name,
+                                        self.expr_arena,
+                                    ),
+                                    expr,
+                                });
+                            }
+                            let expr = a.gen_temp(gen_instance_method_call(
+                                &instance.field_name,
+                                &format!("output_{}", data.name),
+                                self.expr_arena,
+                            ));
+                            self.instance_output_exprs.insert(data, expr);
+                            Some((key, expr))
+                        }
 
                         internal_signal::SignalData::Reg { .. } => unreachable!(),
 
@@ -215,6 +324,156 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             let target_type = ValueType::from_bit_width(bit_width);
                             Some((key, self.gen_mask(expr, bit_width, target_type)))
                         }
+                        internal_signal::SignalData::ReduceOp { source, op } => {
+                            let source_bit_width = source.bit_width();
+                            let source_type = ValueType::from_bit_width(source_bit_width);
+                            let expr = results.pop().unwrap();
+                            let expr = match (source_type, op) {
+                                (ValueType::Bool, _) => expr,
+                                (_, internal_signal::ReduceOp::And) => {
+                                    self.expr_arena.alloc(Expr::InfixBinOp {
+                                        lhs: expr,
+                                        rhs: self.gen_all_ones(source_bit_width, source_type),
+                                        op: InfixBinOp::Equal,
+                                    })
+                                }
+                                (_, internal_signal::ReduceOp::Or) => {
+                                    self.expr_arena.alloc(Expr::InfixBinOp {
+                                        lhs: expr,
+                                        rhs: self.gen_constant(0, source_type),
+                                        op: InfixBinOp::NotEqual,
+                                    })
+                                }
+                                (_, internal_signal::ReduceOp::Xor) => {
+                                    let count = self.expr_arena.alloc(Expr::MemberCall {
+                                        target: expr,
+                                        name: "count_ones".into(),
+                                        args: Vec::new(),
+                                    });
+                                    // Casting to bool tests the count's least significant bit
+                                    self.gen_cast(count, ValueType::U32, ValueType::Bool)
+                                }
+                            };
+                            Some((key, expr))
+                        }
+                        internal_signal::SignalData::CountOp {
+                            source,
+                            op,
+                            bit_width,
+                        } => {
+                            let source_bit_width = source.bit_width();
+                            let source_type = ValueType::from_bit_width(source_bit_width);
+                            let expr = results.pop().unwrap();
+                            let expr = if source_type == ValueType::Bool {
+                                // A single bit is its own count of ones, and its complement is its own count of zeros
+                                match op {
+                                    internal_signal::CountOp::CountOnes => expr,
+                                    internal_signal::CountOp::LeadingZeros
+                                    | internal_signal::CountOp::TrailingZeros => {
+                                        self.expr_arena.alloc(Expr::UnOp {
+                                            source: expr,
+                                            op: UnOp::Not,
+                                        })
+                                    }
+                                }
+                            } else {
+                                let count = self.expr_arena.alloc(Expr::MemberCall {
+                                    target: expr,
+                                    name: match op {
+                                        internal_signal::CountOp::LeadingZeros => {
+                                            "leading_zeros".into()
+                                        }
+                                        internal_signal::CountOp::TrailingZeros => {
+                                            "trailing_zeros".into()
+                                        }
+                                        internal_signal::CountOp::CountOnes => "count_ones".into(),
+                                    },
+                                    args: Vec::new(),
+                                });
+                                let unused_bit_width = source_type.bit_width() - source_bit_width;
+                                let count = match op {
+                                    internal_signal::CountOp::LeadingZeros
+                                        if unused_bit_width > 0 =>
+                                    {
+                                        self.expr_arena.alloc(Expr::InfixBinOp {
+                                            lhs: count,
+                                            rhs: self.gen_constant(
+                                                unused_bit_width as _,
+                                                ValueType::U32,
+                                            ),
+                                            op: InfixBinOp::Sub,
+                                        })
+                                    }
+                                    internal_signal::CountOp::TrailingZeros
+                                        if unused_bit_width > 0 =>
+                                    {
+                                        self.expr_arena.alloc(Expr::FunctionCall {
+                                            name: "std::cmp::min".into(),
+                                            args: vec![
+                                                count,
+                                                self.gen_constant(
+                                                    source_bit_width as _,
+                                                    ValueType::U32,
+                                                ),
+                                            ],
+                                        })
+                                    }
+                                    _ => count,
+                                };
+                                self.gen_cast(
+                                    count,
+                                    ValueType::U32,
+                                    ValueType::from_bit_width(bit_width),
+                                )
+                            };
+                            Some((key, expr))
+                        }
+                        internal_signal::SignalData::OneHot { source, bit_width } => {
+                            let source_type = ValueType::from_bit_width(source.bit_width());
+                            let target_type = ValueType::from_bit_width(bit_width);
+                            let expr = results.pop().unwrap();
+                            let shift = self.gen_cast(expr, source_type, ValueType::U32);
+                            Some((
+                                key,
+                                &*self.expr_arena.alloc(Expr::InfixBinOp {
+                                    lhs: self.gen_constant(1, target_type),
+                                    rhs: shift,
+                                    op: InfixBinOp::Shl,
+                                }),
+                            ))
+                        }
+                        internal_signal::SignalData::PriorityEncode { source, bit_width } => {
+                            let source_type = ValueType::from_bit_width(source.bit_width());
+                            let target_type = ValueType::from_bit_width(bit_width);
+                            let expr = results.pop().unwrap();
+                            if source_type == ValueType::Bool {
+                                Some((key, self.gen_constant(0, target_type)))
+                            } else {
+                                // The source is referenced again when checking for no high bits
+                                let expr = a.gen_temp(expr);
+                                let count = self.expr_arena.alloc(Expr::MemberCall {
+                                    target: expr,
+                                    name: "trailing_zeros".into(),
+                                    args: Vec::new(),
+                                });
+                                Some((
+                                    key,
+                                    &*self.expr_arena.alloc(Expr::Ternary {
+                                        cond: self.expr_arena.alloc(Expr::InfixBinOp {
+                                            lhs: expr,
+                                            rhs: self.gen_constant(0, source_type),
+                                            op: InfixBinOp::Equal,
+                                        }),
+                                        when_true: self.gen_constant(0, target_type),
+                                        when_false: self.gen_cast(
+                                            count,
+                                            ValueType::U32,
+                                            target_type,
+                                        ),
+                                    }),
+                                ))
+                            }
+                        }
                         internal_signal::SignalData::SimpleBinOp { op, .. } => {
                             let lhs = results.pop().unwrap();
                             let rhs = results.pop().unwrap();
@@ -242,13 +501,13 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             };
                             let lhs = self.gen_cast(lhs, source_type, op_input_type);
                             let rhs = self.gen_cast(rhs, source_type, op_input_type);
-                            let expr = self.expr_arena.alloc(Expr::UnaryMemberCall {
+                            let expr = self.expr_arena.alloc(Expr::MemberCall {
                                 target: lhs,
                                 name: match op {
                                     internal_signal::AdditiveBinOp::Add => "wrapping_add".into(),
                                     internal_signal::AdditiveBinOp::Sub => "wrapping_sub".into(),
                                 },
-                                arg: rhs,
+                                args: vec![rhs],
                             });
                             let op_output_type = op_input_type;
                             let target_bit_width = signal.bit_width();
@@ -266,19 +525,35 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                                 | internal_signal::ComparisonBinOp::GreaterThanSigned
                                 | internal_signal::ComparisonBinOp::LessThanEqualSigned
                                 | internal_signal::ComparisonBinOp::LessThanSigned => {
-                                    let source_type_signed = source_type.to_signed();
-                                    lhs = self.gen_cast(lhs, source_type, source_type_signed);
-                                    rhs = self.gen_cast(rhs, source_type, source_type_signed);
-                                    lhs = self.gen_sign_extend_shifts(
-                                        lhs,
-                                        source_bit_width,
-                                        source_type_signed,
-                                    );
-                                    rhs = self.gen_sign_extend_shifts(
-                                        rhs,
-                                        source_bit_width,
-                                        source_type_signed,
-                                    );
+                                    if let ValueType::Wide(_) = source_type {
+                                        // There's no signed wide type, so flip the sign bits instead, which maps signed order onto unsigned order
+                                        let sign_bit =
+                                            self.gen_sign_bit(source_bit_width, source_type);
+                                        lhs = self.expr_arena.alloc(Expr::InfixBinOp {
+                                            lhs,
+                                            rhs: sign_bit,
+                                            op: InfixBinOp::BitXor,
+                                        });
+                                        rhs = self.expr_arena.alloc(Expr::InfixBinOp {
+                                            lhs: rhs,
+                                            rhs: sign_bit,
+                                            op: InfixBinOp::BitXor,
+                                        });
+                                    } else {
+                                        let source_type_signed = source_type.to_signed();
+                                        lhs = self.gen_cast(lhs, source_type, source_type_signed);
+                                        rhs = self.gen_cast(rhs, source_type, source_type_signed);
+                                        lhs = self.gen_sign_extend_shifts(
+                                            lhs,
+                                            source_bit_width,
+                                            source_type_signed,
+                                        );
+                                        rhs = self.gen_sign_extend_shifts(
+                                            rhs,
+                                            source_bit_width,
+                                            source_type_signed,
+                                        );
+                                    }
                                 }
                                 _ => (),
                             }
@@ -327,93 +602,117 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                                 _ => lhs_source_type,
                             };
                             let lhs = self.gen_cast(lhs, lhs_source_type, lhs_op_input_type);
-                            let lhs = match op {
-                                internal_signal::ShiftBinOp::Shl
-                                | internal_signal::ShiftBinOp::Shr => lhs,
-                                internal_signal::ShiftBinOp::ShrArithmetic => {
-                                    let lhs_op_input_type_signed = lhs_op_input_type.to_signed();
-                                    let lhs = self.gen_cast(
-                                        lhs,
-                                        lhs_op_input_type,
-                                        lhs_op_input_type_signed,
-                                    );
-                                    self.gen_sign_extend_shifts(
-                                        lhs,
-                                        lhs_source_bit_width,
-                                        lhs_op_input_type_signed,
-                                    )
-                                }
-                            };
                             let rhs_op_input_type = match rhs_source_type {
                                 ValueType::Bool => ValueType::U32,
                                 _ => rhs_source_type,
                             };
                             let rhs = self.gen_cast(rhs, rhs_source_type, rhs_op_input_type);
-                            let rhs = self.expr_arena.alloc(Expr::BinaryFunctionCall {
+                            let rhs = self.expr_arena.alloc(Expr::FunctionCall {
                                 name: "std::cmp::min".into(),
-                                lhs: rhs,
-                                rhs: self.expr_arena.alloc(Expr::Constant {
-                                    value: match rhs_op_input_type {
-                                        ValueType::Bool
-                                        | ValueType::I32
-                                        | ValueType::I64
-                                        | ValueType::I128 => unreachable!(),
-                                        ValueType::U32 => Constant::U32(std::u32::MAX),
-                                        ValueType::U64 => Constant::U64(std::u32::MAX as _),
-                                        ValueType::U128 => Constant::U128(std::u32::MAX as _),
-                                    },
-                                }),
-                            });
-                            let rhs = self.gen_cast(rhs, lhs_op_input_type, ValueType::U32);
-                            let expr = self.expr_arena.alloc(Expr::UnaryMemberCall {
-                                target: lhs,
-                                name: match op {
-                                    internal_signal::ShiftBinOp::Shl => "checked_shl".into(),
-                                    internal_signal::ShiftBinOp::Shr
-                                    | internal_signal::ShiftBinOp::ShrArithmetic => {
-                                        "checked_shr".into()
-                                    }
-                                },
-                                arg: rhs,
+                                args: vec![
+                                    rhs,
+                                    self.gen_constant(u32::MAX as _, rhs_op_input_type),
+                                ],
                             });
-                            let expr = self.expr_arena.alloc(Expr::UnaryMemberCall {
-                                target: expr,
-                                name: "unwrap_or".into(),
-                                arg: match op {
+                            let rhs = self.gen_cast(rhs, rhs_op_input_type, ValueType::U32);
+                            let op_output_type = lhs_op_input_type;
+                            let expr = if let ValueType::Wide(_) = lhs_op_input_type {
+                                match op {
                                     internal_signal::ShiftBinOp::Shl
                                     | internal_signal::ShiftBinOp::Shr => {
-                                        self.expr_arena.alloc(Expr::Constant {
-                                            value: match lhs_op_input_type {
-                                                ValueType::Bool
-                                                | ValueType::I32
-                                                | ValueType::I64
-                                                | ValueType::I128 => unreachable!(),
-                                                ValueType::U32 => Constant::U32(0),
-                                                ValueType::U64 => Constant::U64(0),
-                                                ValueType::U128 => Constant::U128(0),
+                                        let expr = self.expr_arena.alloc(Expr::MemberCall {
+                                            target: lhs,
+                                            name: match op {
+                                                internal_signal::ShiftBinOp::Shl => {
+                                                    "checked_shl".into()
+                                                }
+                                                _ => "checked_shr".into(),
                                             },
+                                            args: vec![rhs],
+                                        });
+                                        self.expr_arena.alloc(Expr::MemberCall {
+                                            target: expr,
+                                            name: "unwrap_or".into(),
+                                            args: vec![self.gen_constant(0, lhs_op_input_type)],
                                         })
                                     }
                                     internal_signal::ShiftBinOp::ShrArithmetic => {
-                                        self.expr_arena.alloc(Expr::InfixBinOp {
-                                            lhs,
-                                            rhs: self.expr_arena.alloc(Expr::Constant {
-                                                value: Constant::U32(
-                                                    lhs_op_input_type.bit_width() - 1,
-                                                ),
-                                            }),
-                                            op: InfixBinOp::Shr,
+                                        let lhs = self.expr_arena.alloc(Expr::MemberCall {
+                                            target: lhs,
+                                            name: "sign_extend".into(),
+                                            args: vec![self.expr_arena.alloc(Expr::Constant {
+                                                value: Constant::U32(lhs_source_bit_width),
+                                            })],
+                                        });
+                                        self.expr_arena.alloc(Expr::MemberCall {
+                                            target: lhs,
+                                            name: "shr_arithmetic".into(),
+                                            args: vec![rhs],
                                         })
                                     }
-                                },
-                            });
-                            let op_output_type = lhs_op_input_type;
-                            let expr = match op {
-                                internal_signal::ShiftBinOp::Shl
-                                | internal_signal::ShiftBinOp::Shr => expr,
-                                internal_signal::ShiftBinOp::ShrArithmetic => {
-                                    let lhs_op_output_type_signed = op_output_type.to_signed();
-                                    self.gen_cast(expr, lhs_op_output_type_signed, op_output_type)
+                                }
+                            } else {
+                                let lhs = match op {
+                                    internal_signal::ShiftBinOp::Shl
+                                    | internal_signal::ShiftBinOp::Shr => lhs,
+                                    internal_signal::ShiftBinOp::ShrArithmetic => {
+                                        let lhs_op_input_type_signed =
+                                            lhs_op_input_type.to_signed();
+                                        let lhs = self.gen_cast(
+                                            lhs,
+                                            lhs_op_input_type,
+                                            lhs_op_input_type_signed,
+                                        );
+                                        self.gen_sign_extend_shifts(
+                                            lhs,
+                                            lhs_source_bit_width,
+                                            lhs_op_input_type_signed,
+                                        )
+                                    }
+                                };
+                                let expr = self.expr_arena.alloc(Expr::MemberCall {
+                                    target: lhs,
+                                    name: match op {
+                                        internal_signal::ShiftBinOp::Shl => "checked_shl".into(),
+                                        internal_signal::ShiftBinOp::Shr
+                                        | internal_signal::ShiftBinOp::ShrArithmetic => {
+                                            "checked_shr".into()
+                                        }
+                                    },
+                                    args: vec![rhs],
+                                });
+                                let expr = self.expr_arena.alloc(Expr::MemberCall {
+                                    target: expr,
+                                    name: "unwrap_or".into(),
+                                    args: vec![match op {
+                                        internal_signal::ShiftBinOp::Shl
+                                        | internal_signal::ShiftBinOp::Shr => {
+                                            self.gen_constant(0, lhs_op_input_type)
+                                        }
+                                        internal_signal::ShiftBinOp::ShrArithmetic => {
+                                            self.expr_arena.alloc(Expr::InfixBinOp {
+                                                lhs,
+                                                rhs: self.expr_arena.alloc(Expr::Constant {
+                                                    value: Constant::U32(
+                                                        lhs_op_input_type.bit_width() - 1,
+                                                    ),
+                                                }),
+                                                op: InfixBinOp::Shr,
+                                            })
+                                        }
+                                    }],
+                                });
+                                match op {
+                                    internal_signal::ShiftBinOp::Shl
+                                    | internal_signal::ShiftBinOp::Shr => expr,
+                                    internal_signal::ShiftBinOp::ShrArithmetic => {
+                                        let lhs_op_output_type_signed = op_output_type.to_signed();
+                                        self.gen_cast(
+                                            expr,
+                                            lhs_op_output_type_signed,
+                                            op_output_type,
+                                        )
+                                    }
                                 }
                             };
                             let target_bit_width = bit_width;
@@ -422,6 +721,94 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             Some((key, self.gen_mask(expr, target_bit_width, target_type)))
                         }
 
+                        internal_signal::SignalData::RotateBinOp {
+                            rhs, op, bit_width, ..
+                        } => {
+                            let lhs_type = ValueType::from_bit_width(bit_width);
+                            let rhs_bit_width = rhs.bit_width();
+                            let rhs_source_type = ValueType::from_bit_width(rhs_bit_width);
+                            let lhs = results.pop().unwrap();
+                            let rhs = results.pop().unwrap();
+                            // Rotating a single bit has no effect
+                            if lhs_type == ValueType::Bool {
+                                Some((key, lhs))
+                            } else {
+                                let rhs_op_input_type = match rhs_source_type {
+                                    ValueType::Bool => ValueType::U32,
+                                    _ => rhs_source_type,
+                                };
+                                let rhs = self.gen_cast(rhs, rhs_source_type, rhs_op_input_type);
+                                // The rotation amount only needs to be reduced if it can reach the bit width
+                                let rhs = if rhs_bit_width < 32 && (1 << rhs_bit_width) <= bit_width
+                                {
+                                    rhs
+                                } else if let ValueType::Wide(_) = rhs_op_input_type {
+                                    self.expr_arena.alloc(Expr::MemberCall {
+                                        target: rhs,
+                                        name: "wrapping_rem".into(),
+                                        args: vec![
+                                            self.gen_constant(bit_width as _, rhs_op_input_type)
+                                        ],
+                                    })
+                                } else {
+                                    self.expr_arena.alloc(Expr::InfixBinOp {
+                                        lhs: rhs,
+                                        rhs: self.gen_constant(bit_width as _, rhs_op_input_type),
+                                        op: InfixBinOp::Rem,
+                                    })
+                                };
+                                let rhs = self.gen_cast(rhs, rhs_op_input_type, ValueType::U32);
+                                if bit_width == lhs_type.bit_width() {
+                                    Some((
+                                        key,
+                                        &*self.expr_arena.alloc(Expr::MemberCall {
+                                            target: lhs,
+                                            name: match op {
+                                                internal_signal::RotateBinOp::Left => {
+                                                    "rotate_left".into()
+                                                }
+                                                internal_signal::RotateBinOp::Right => {
+                                                    "rotate_right".into()
+                                                }
+                                            },
+                                            args: vec![rhs],
+                                        }),
+                                    ))
+                                } else {
+                                    // Both operands are referenced twice when combining the shifted halves
+                                    let lhs = a.gen_temp(lhs);
+                                    let rhs = a.gen_temp(rhs);
+                                    let (op, complement_op) = match op {
+                                        internal_signal::RotateBinOp::Left => {
+                                            (InfixBinOp::Shl, InfixBinOp::Shr)
+                                        }
+                                        internal_signal::RotateBinOp::Right => {
+                                            (InfixBinOp::Shr, InfixBinOp::Shl)
+                                        }
+                                    };
+                                    let complement = self.expr_arena.alloc(Expr::InfixBinOp {
+                                        lhs: self.gen_constant(bit_width as _, ValueType::U32),
+                                        rhs,
+                                        op: InfixBinOp::Sub,
+                                    });
+                                    let expr = self.expr_arena.alloc(Expr::InfixBinOp {
+                                        lhs: self.expr_arena.alloc(Expr::InfixBinOp {
+                                            lhs,
+                                            rhs,
+                                            op,
+                                        }),
+                                        rhs: self.expr_arena.alloc(Expr::InfixBinOp {
+                                            lhs,
+                                            rhs: complement,
+                                            op: complement_op,
+                                        }),
+                                        op: InfixBinOp::BitOr,
+                                    });
+                                    Some((key, self.gen_mask(expr, bit_width, lhs_type)))
+                                }
+                            }
+                        }
+
                         internal_signal::SignalData::Mul {
                             lhs,
                             rhs,
@@ -434,14 +821,7 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             let target_type = ValueType::from_bit_width(bit_width);
                             let lhs = self.gen_cast(lhs, lhs_type, target_type);
                             let rhs = self.gen_cast(rhs, rhs_type, target_type);
-                            Some((
-                                key,
-                                &*self.expr_arena.alloc(Expr::InfixBinOp {
-                                    lhs,
-                                    rhs,
-                                    op: InfixBinOp::Mul,
-                                }),
-                            ))
+                            Some((key, self.gen_mul(lhs, rhs, target_type)))
                         }
                         internal_signal::SignalData::MulSigned {
                             lhs,
@@ -456,22 +836,118 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             let rhs = results.pop().unwrap();
                             let target_bit_width = bit_width;
                             let target_type = ValueType::from_bit_width(target_bit_width);
-                            let target_type_signed = target_type.to_signed();
-                            let lhs = self.gen_cast(lhs, lhs_type, target_type_signed);
-                            let rhs = self.gen_cast(rhs, rhs_type, target_type_signed);
-                            let lhs =
-                                self.gen_sign_extend_shifts(lhs, lhs_bit_width, target_type_signed);
-                            let rhs =
-                                self.gen_sign_extend_shifts(rhs, rhs_bit_width, target_type_signed);
-                            let expr = self.expr_arena.alloc(Expr::InfixBinOp {
-                                lhs,
-                                rhs,
-                                op: InfixBinOp::Mul,
-                            });
-                            let expr = self.gen_cast(expr, target_type_signed, target_type);
+                            let expr = if let ValueType::Wide(_) = target_type {
+                                let lhs = self.gen_cast(lhs, lhs_type, target_type);
+                                let rhs = self.gen_cast(rhs, rhs_type, target_type);
+                                let lhs = self.gen_sign_extend(lhs, lhs_bit_width);
+                                let rhs = self.gen_sign_extend(rhs, rhs_bit_width);
+                                self.gen_mul(lhs, rhs, target_type)
+                            } else {
+                                let target_type_signed = target_type.to_signed();
+                                let lhs = self.gen_cast(lhs, lhs_type, target_type_signed);
+                                let rhs = self.gen_cast(rhs, rhs_type, target_type_signed);
+                                let lhs = self.gen_sign_extend_shifts(
+                                    lhs,
+                                    lhs_bit_width,
+                                    target_type_signed,
+                                );
+                                let rhs = self.gen_sign_extend_shifts(
+                                    rhs,
+                                    rhs_bit_width,
+                                    target_type_signed,
+                                );
+                                let expr = self.gen_mul(lhs, rhs, target_type_signed);
+                                self.gen_cast(expr, target_type_signed, target_type)
+                            };
                             Some((key, self.gen_mask(expr, target_bit_width, target_type)))
                         }
 
+                        internal_signal::SignalData::DivisionBinOp { op, bit_width, .. } => {
+                            let lhs = results.pop().unwrap();
+                            let rhs = results.pop().unwrap();
+                            // Both operands are referenced again when selecting the result for a zero divisor
+                            let lhs = a.gen_temp(lhs);
+                            let rhs = a.gen_temp(rhs);
+                            let (signed, name) = match op {
+                                internal_signal::DivisionBinOp::Div => (false, "wrapping_div"),
+                                internal_signal::DivisionBinOp::DivSigned => (true, "wrapping_div"),
+                                internal_signal::DivisionBinOp::Rem => (false, "wrapping_rem"),
+                                internal_signal::DivisionBinOp::RemSigned => (true, "wrapping_rem"),
+                            };
+                            let source_type = ValueType::from_bit_width(bit_width);
+                            let expr = if let ValueType::Wide(_) = source_type {
+                                if signed {
+                                    let lhs = self.gen_sign_extend(lhs, bit_width);
+                                    let rhs = self.gen_sign_extend(rhs, bit_width);
+                                    let expr = self.expr_arena.alloc(Expr::MemberCall {
+                                        target: lhs,
+                                        name: format!("{}_signed", name),
+                                        args: vec![rhs],
+                                    });
+                                    self.gen_mask(expr, bit_width, source_type)
+                                } else {
+                                    self.expr_arena.alloc(Expr::MemberCall {
+                                        target: lhs,
+                                        name: name.into(),
+                                        args: vec![rhs],
+                                    })
+                                }
+                            } else {
+                                let op_type = match source_type {
+                                    ValueType::Bool => ValueType::U32,
+                                    _ => source_type,
+                                };
+                                let lhs = self.gen_cast(lhs, source_type, op_type);
+                                let rhs = self.gen_cast(rhs, source_type, op_type);
+                                let expr = if signed {
+                                    let op_type_signed = op_type.to_signed();
+                                    let lhs = self.gen_cast(lhs, op_type, op_type_signed);
+                                    let rhs = self.gen_cast(rhs, op_type, op_type_signed);
+                                    let lhs =
+                                        self.gen_sign_extend_shifts(lhs, bit_width, op_type_signed);
+                                    let rhs =
+                                        self.gen_sign_extend_shifts(rhs, bit_width, op_type_signed);
+                                    let expr = self.expr_arena.alloc(Expr::MemberCall {
+                                        target: lhs,
+                                        name: name.into(),
+                                        args: vec![rhs],
+                                    });
+                                    let expr = self.gen_cast(expr, op_type_signed, op_type);
+                                    self.gen_mask(expr, bit_width, op_type)
+                                } else {
+                                    self.expr_arena.alloc(Expr::InfixBinOp {
+                                        lhs,
+                                        rhs,
+                                        op: match op {
+                                            internal_signal::DivisionBinOp::Div => InfixBinOp::Div,
+                                            _ => InfixBinOp::Rem,
+                                        },
+                                    })
+                                };
+                                self.gen_cast(expr, op_type, source_type)
+                            };
+                            let when_rhs_zero = match op {
+                                internal_signal::DivisionBinOp::Div
+                                | internal_signal::DivisionBinOp::DivSigned => {
+                                    self.gen_all_ones(bit_width, source_type)
+                                }
+                                internal_signal::DivisionBinOp::Rem
+                                | internal_signal::DivisionBinOp::RemSigned => lhs,
+                            };
+                            Some((
+                                key,
+                                &*self.expr_arena.alloc(Expr::Ternary {
+                                    cond: self.expr_arena.alloc(Expr::InfixBinOp {
+                                        lhs: rhs,
+                                        rhs: self.gen_constant(0, source_type),
+                                        op: InfixBinOp::Equal,
+                                    }),
+                                    when_true: when_rhs_zero,
+                                    when_false: expr,
+                                }),
+                            ))
+                        }
+
                         internal_signal::SignalData::Bits {
                             source, range_low, ..
                         } => {
@@ -486,6 +962,39 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                             );
                             Some((key, self.gen_mask(expr, target_bit_width, target_type)))
                         }
+                        internal_signal::SignalData::Extend {
+                            source,
+                            op,
+                            bit_width,
+                        } => {
+                            let source_bit_width = source.bit_width();
+                            let source_type = ValueType::from_bit_width(source_bit_width);
+                            let target_type = ValueType::from_bit_width(bit_width);
+                            let expr = results.pop().unwrap();
+                            let expr = match op {
+                                internal_signal::ExtendOp::Zero => {
+                                    self.gen_cast(expr, source_type, target_type)
+                                }
+                                internal_signal::ExtendOp::Sign => {
+                                    let expr = if let ValueType::Wide(_) = target_type {
+                                        let expr = self.gen_cast(expr, source_type, target_type);
+                                        self.gen_sign_extend(expr, source_bit_width)
+                                    } else {
+                                        let target_type_signed = target_type.to_signed();
+                                        let expr =
+                                            self.gen_cast(expr, source_type, target_type_signed);
+                                        let expr = self.gen_sign_extend_shifts(
+                                            expr,
+                                            source_bit_width,
+                                            target_type_signed,
+                                        );
+                                        self.gen_cast(expr, target_type_signed, target_type)
+                                    };
+                                    self.gen_mask(expr, bit_width, target_type)
+                                }
+                            };
+                            Some((key, expr))
+                        }
 
                         internal_signal::SignalData::Repeat {
                             source,
@@ -552,6 +1061,44 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
                                 }),
                             ))
                         }
+                        internal_signal::SignalData::Index {
+                            ref elements,
+                            index,
+                            ..
+                        } => {
+                            let index_bit_width = index.bit_width();
+                            let index_type = ValueType::from_bit_width(index_bit_width);
+                            let mut index = results.pop().unwrap();
+                            let elements = (0..elements.len())
+                                .map(|_| results.pop().unwrap())
+                                .collect::<Vec<_>>();
+                            // Indices past the last element select the last element
+                            let last = elements.len() as u128 - 1;
+                            if index_bit_width >= 128
+                                || last != u128::MAX >> (128 - index_bit_width)
+                            {
+                                let last = self.gen_constant(last, index_type);
+                                index = self.expr_arena.alloc(Expr::MemberCall {
+                                    target: a.gen_temp(index),
+                                    name: "min".into(),
+                                    args: vec![last],
+                                });
+                            }
+                            if let ValueType::Wide(_) = index_type {
+                                index = self.expr_arena.alloc(Expr::MemberCall {
+                                    target: index,
+                                    name: "low_u128".into(),
+                                    args: Vec::new(),
+                                });
+                            }
+                            Some((
+                                key,
+                                &*self.expr_arena.alloc(Expr::ArrayIndex {
+                                    target: self.expr_arena.alloc(Expr::Array { elements }),
+                                    index,
+                                }),
+                            ))
+                        }
 
                         internal_signal::SignalData::MemReadPortOutput { .. } => unreachable!(),
                     }
@@ -569,6 +1116,16 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
         results.pop().unwrap()
     }
 
+    fn gen_constant(
+        &mut self,
+        value: u128,
+        value_type: ValueType,
+    ) -> &'expr_arena Expr<'expr_arena> {
+        self.expr_arena.alloc(Expr::Constant {
+            value: Constant::from_u128(value, value_type),
+        })
+    }
+
     fn gen_mask(
         &mut self,
         expr: &'expr_arena Expr<'expr_arena>,
@@ -579,23 +1136,67 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
             return expr;
         }
 
-        let mask = (1u128 << bit_width) - 1;
+        let mask = self.gen_all_ones(bit_width, target_type);
         self.expr_arena.alloc(Expr::InfixBinOp {
             lhs: expr,
-            rhs: self.expr_arena.alloc(Expr::Constant {
-                value: match target_type {
-                    ValueType::Bool | ValueType::I32 | ValueType::I64 | ValueType::I128 => {
-                        unreachable!()
-                    }
-                    ValueType::U32 => Constant::U32(mask as _),
-                    ValueType::U64 => Constant::U64(mask as _),
-                    ValueType::U128 => Constant::U128(mask),
-                },
-            }),
+            rhs: mask,
             op: InfixBinOp::BitAnd,
         })
     }
 
+    fn gen_all_ones(
+        &mut self,
+        bit_width: u32,
+        target_type: ValueType,
+    ) -> &'expr_arena Expr<'expr_arena> {
+        match target_type {
+            ValueType::I32 | ValueType::I64 | ValueType::I128 => unreachable!(),
+            ValueType::Bool | ValueType::U32 | ValueType::U64 | ValueType::U128 => {
+                self.gen_constant(u128::MAX >> (128 - bit_width), target_type)
+            }
+            ValueType::Wide(word_count) => self.expr_arena.alloc(Expr::Constant {
+                value: Constant::Wide(
+                    (0..word_count)
+                        .map(|i| match bit_width.saturating_sub(i * 64) {
+                            0 => 0,
+                            word_bit_width if word_bit_width >= 64 => !0,
+                            word_bit_width => (1u64 << word_bit_width) - 1,
+                        })
+                        .collect(),
+                ),
+            }),
+        }
+    }
+
+    fn gen_mul(
+        &mut self,
+        lhs: &'expr_arena Expr<'expr_arena>,
+        rhs: &'expr_arena Expr<'expr_arena>,
+        target_type: ValueType,
+    ) -> &'expr_arena Expr<'expr_arena> {
+        match target_type {
+            ValueType::Wide(_) => self.expr_arena.alloc(Expr::MemberCall {
+                target: lhs,
+                name: "wrapping_mul".into(),
+                args: vec![rhs],
+            }),
+            _ => self.expr_arena.alloc(Expr::InfixBinOp {
+                lhs,
+                rhs,
+                op: InfixBinOp::Mul,
+            }),
+        }
+    }
+
+    fn gen_sign_bit(
+        &mut self,
+        bit_width: u32,
+        target_type: ValueType,
+    ) -> &'expr_arena Expr<'expr_arena> {
+        let sign_bit = self.gen_constant(1, target_type);
+        self.gen_shift_left(sign_bit, bit_width - 1)
+    }
+
     fn gen_shift_left(
         &mut self,
         expr: &'expr_arena Expr<'expr_arena>,
@@ -642,20 +1243,36 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
             return expr;
         }
 
+        match (source_type, target_type) {
+            (ValueType::Wide(_), ValueType::Wide(word_count)) => {
+                return self.expr_arena.alloc(Expr::MemberCall {
+                    target: expr,
+                    name: format!("resize::<{}>", word_count),
+                    args: Vec::new(),
+                });
+            }
+            (ValueType::Wide(_), _) => {
+                let expr = self.expr_arena.alloc(Expr::MemberCall {
+                    target: expr,
+                    name: "low_u128".into(),
+                    args: Vec::new(),
+                });
+                return self.gen_cast(expr, ValueType::U128, target_type);
+            }
+            (_, ValueType::Wide(_)) => {
+                return self.expr_arena.alloc(Expr::FunctionCall {
+                    name: format!("{}::from", target_type.name()),
+                    args: vec![expr],
+                });
+            }
+            _ => (),
+        }
+
         if target_type == ValueType::Bool {
             let expr = self.gen_mask(expr, 1, source_type);
             return self.expr_arena.alloc(Expr::InfixBinOp {
                 lhs: expr,
-                rhs: self.expr_arena.alloc(Expr::Constant {
-                    value: match source_type {
-                        ValueType::Bool | ValueType::I32 | ValueType::I64 | ValueType::I128 => {
-                            unreachable!()
-                        }
-                        ValueType::U32 => Constant::U32(0),
-                        ValueType::U64 => Constant::U64(0),
-                        ValueType::U128 => Constant::U128(0),
-                    },
-                }),
+                rhs: self.gen_constant(0, source_type),
                 op: InfixBinOp::NotEqual,
             });
         }
@@ -666,6 +1283,20 @@ impl<'graph, 'context, 'expr_arena> Compiler<'graph, 'context, 'expr_arena> {
         })
     }
 
+    fn gen_sign_extend(
+        &mut self,
+        expr: &'expr_arena Expr<'expr_arena>,
+        source_bit_width: u32,
+    ) -> &'expr_arena Expr<'expr_arena> {
+        self.expr_arena.alloc(Expr::MemberCall {
+            target: expr,
+            name: "sign_extend".into(),
+            args: vec![self.expr_arena.alloc(Expr::Constant {
+                value: Constant::U32(source_bit_width),
+            })],
+        })
+    }
+
     fn gen_sign_extend_shifts(
         &mut self,
         expr: &'expr_arena Expr,
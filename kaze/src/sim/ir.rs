@@ -7,7 +7,7 @@ use std::io::{Result, Write};
 
 pub struct AssignmentContext<'arena> {
     arena: &'arena Arena<Expr<'arena>>,
-    assignments: Vec<Assignment<'arena>>,
+    statements: Vec<Statement<'arena>>,
     local_count: u32,
 }
 
@@ -15,7 +15,7 @@ impl<'arena> AssignmentContext<'arena> {
     pub fn new(arena: &'arena Arena<Expr<'arena>>) -> AssignmentContext<'arena> {
         AssignmentContext {
             arena,
-            assignments: Vec::new(),
+            statements: Vec::new(),
             local_count: 0,
         }
     }
@@ -28,7 +28,7 @@ impl<'arena> AssignmentContext<'arena> {
                 let name = format!("__temp_{}", self.local_count);
                 self.local_count += 1;
 
-                self.assignments.push(Assignment {
+                self.push(Assignment {
                     target: self.arena.alloc(Expr::Ref {
                         name: name.clone(),
                         scope: Scope::Local,
@@ -45,22 +45,40 @@ impl<'arena> AssignmentContext<'arena> {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.assignments.is_empty()
+        self.statements.is_empty()
     }
 
     pub fn push(&mut self, assignment: Assignment<'arena>) {
-        self.assignments.push(assignment);
+        self.statements.push(Statement::Assignment(assignment));
+    }
+
+    // Pushes an expression that's evaluated only for its side effects, such as a method call
+    pub fn push_expr(&mut self, expr: &'arena Expr<'arena>) {
+        self.statements.push(Statement::Expr(expr));
     }
 
     pub fn write<W: Write>(&self, w: &mut code_writer::CodeWriter<W>) -> Result<()> {
-        for assignment in self.assignments.iter() {
-            assignment.write(w)?;
+        for statement in self.statements.iter() {
+            match statement {
+                Statement::Assignment(assignment) => assignment.write(w)?,
+                Statement::Expr(expr) => {
+                    w.append_indent()?;
+                    expr.write(w)?;
+                    w.append(";")?;
+                    w.append_newline()?;
+                }
+            }
         }
 
         Ok(())
     }
 }
 
+enum Statement<'arena> {
+    Assignment(Assignment<'arena>),
+    Expr(&'arena Expr<'arena>),
+}
+
 pub struct Assignment<'arena> {
     pub target: &'arena Expr<'arena>,
     pub expr: &'arena Expr<'arena>,
@@ -89,14 +107,16 @@ impl<'arena> Assignment<'arena> {
 }
 
 pub enum Expr<'arena> {
+    Array {
+        elements: Vec<&'arena Expr<'arena>>,
+    },
     ArrayIndex {
         target: &'arena Expr<'arena>,
         index: &'arena Expr<'arena>,
     },
-    BinaryFunctionCall {
+    FunctionCall {
         name: String,
-        lhs: &'arena Expr<'arena>,
-        rhs: &'arena Expr<'arena>,
+        args: Vec<&'arena Expr<'arena>>,
     },
     Cast {
         source: &'arena Expr<'arena>,
@@ -119,10 +139,10 @@ pub enum Expr<'arena> {
         when_true: &'arena Expr<'arena>,
         when_false: &'arena Expr<'arena>,
     },
-    UnaryMemberCall {
+    MemberCall {
         target: &'arena Expr<'arena>,
         name: String,
-        arg: &'arena Expr<'arena>,
+        args: Vec<&'arena Expr<'arena>>,
     },
     UnOp {
         source: &'arena Expr<'arena>,
@@ -136,16 +156,11 @@ impl<'arena> Expr<'arena> {
         bit_width: u32,
         arena: &'arena Arena<Expr<'arena>>,
     ) -> &'arena Expr<'arena> {
-        let value = value.numeric_value();
-
         let target_type = ValueType::from_bit_width(bit_width);
         arena.alloc(Expr::Constant {
             value: match target_type {
-                ValueType::Bool => Constant::Bool(value != 0),
-                ValueType::I32 | ValueType::I64 | ValueType::I128 => unreachable!(),
-                ValueType::U32 => Constant::U32(value as _),
-                ValueType::U64 => Constant::U64(value as _),
-                ValueType::U128 => Constant::U128(value),
+                ValueType::Wide(word_count) => Constant::Wide(value.words(word_count as _)),
+                _ => Constant::from_u128(value.low_u128(), target_type),
             },
         })
     }
@@ -154,6 +169,7 @@ impl<'arena> Expr<'arena> {
         enum Command<'arena> {
             Expr { expr: &'arena Expr<'arena> },
             Str { s: &'arena str },
+            String { s: String },
         }
 
         let mut commands = Vec::new();
@@ -162,33 +178,39 @@ impl<'arena> Expr<'arena> {
         while let Some(command) = commands.pop() {
             match command {
                 Command::Expr { expr } => match *expr {
-                    Expr::ArrayIndex {
-                        ref target,
-                        ref index,
-                    } => {
+                    Expr::Array { ref elements } => {
+                        commands.push(Command::Str { s: "]" });
+                        for (i, element) in elements.iter().enumerate().rev() {
+                            commands.push(Command::Expr { expr: element });
+                            if i > 0 {
+                                commands.push(Command::Str { s: ", " });
+                            }
+                        }
+                        w.append("[")?;
+                    }
+                    Expr::ArrayIndex { target, index } => {
                         commands.push(Command::Str { s: " as usize]" });
                         commands.push(Command::Expr { expr: index });
                         commands.push(Command::Str { s: "[" });
                         commands.push(Command::Expr { expr: target });
                     }
-                    Expr::BinaryFunctionCall {
-                        ref name,
-                        ref lhs,
-                        ref rhs,
-                    } => {
+                    Expr::FunctionCall { ref name, ref args } => {
                         commands.push(Command::Str { s: ")" });
-                        commands.push(Command::Expr { expr: rhs });
-                        commands.push(Command::Str { s: ", " });
-                        commands.push(Command::Expr { expr: lhs });
+                        for (i, arg) in args.iter().enumerate().rev() {
+                            commands.push(Command::Expr { expr: arg });
+                            if i > 0 {
+                                commands.push(Command::Str { s: ", " });
+                            }
+                        }
                         w.append(&format!("{}(", name))?;
                     }
                     Expr::Cast {
-                        ref source,
+                        source,
                         target_type,
                     } => {
                         commands.push(Command::Str { s: ")" });
-                        commands.push(Command::Str {
-                            s: &target_type.name(),
+                        commands.push(Command::String {
+                            s: target_type.name(),
                         });
                         commands.push(Command::Str { s: " as " });
                         commands.push(Command::Expr { expr: source });
@@ -200,13 +222,18 @@ impl<'arena> Expr<'arena> {
                             Constant::U32(value) => format!("0x{:x}u32", value),
                             Constant::U64(value) => format!("0x{:x}u64", value),
                             Constant::U128(value) => format!("0x{:x}u128", value),
+                            Constant::Wide(ref words) => format!(
+                                "{}::from_words([{}])",
+                                ValueType::Wide(words.len() as _).name(),
+                                words
+                                    .iter()
+                                    .map(|word| format!("0x{:x}u64", word))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ),
                         })?;
                     }
-                    Expr::InfixBinOp {
-                        ref lhs,
-                        ref rhs,
-                        op,
-                    } => {
+                    Expr::InfixBinOp { lhs, rhs, op } => {
                         commands.push(Command::Str { s: ")" });
                         commands.push(Command::Expr { expr: rhs });
                         commands.push(Command::Str { s: " " });
@@ -223,7 +250,10 @@ impl<'arena> Expr<'arena> {
                                 InfixBinOp::GreaterThanEqual => ">=",
                                 InfixBinOp::Shl => "<<",
                                 InfixBinOp::Shr => ">>",
+                                InfixBinOp::Sub => "-",
                                 InfixBinOp::Mul => "*",
+                                InfixBinOp::Div => "/",
+                                InfixBinOp::Rem => "%",
                             },
                         });
                         commands.push(Command::Str { s: " " });
@@ -237,9 +267,9 @@ impl<'arena> Expr<'arena> {
                         w.append(name)?;
                     }
                     Expr::Ternary {
-                        ref cond,
-                        ref when_true,
-                        ref when_false,
+                        cond,
+                        when_true,
+                        when_false,
                     } => {
                         commands.push(Command::Str { s: "}" });
                         commands.push(Command::Expr { expr: when_false });
@@ -249,19 +279,24 @@ impl<'arena> Expr<'arena> {
                         commands.push(Command::Expr { expr: cond });
                         w.append("if ")?;
                     }
-                    Expr::UnaryMemberCall {
-                        ref target,
+                    Expr::MemberCall {
+                        target,
                         ref name,
-                        ref arg,
+                        ref args,
                     } => {
                         commands.push(Command::Str { s: ")" });
-                        commands.push(Command::Expr { expr: arg });
+                        for (i, arg) in args.iter().enumerate().rev() {
+                            commands.push(Command::Expr { expr: arg });
+                            if i > 0 {
+                                commands.push(Command::Str { s: ", " });
+                            }
+                        }
                         commands.push(Command::Str { s: "(" });
                         commands.push(Command::Str { s: name });
                         commands.push(Command::Str { s: "." });
                         commands.push(Command::Expr { expr: target });
                     }
-                    Expr::UnOp { ref source, op } => {
+                    Expr::UnOp { source, op } => {
                         w.append(match op {
                             UnOp::Not => "!",
                         })?;
@@ -271,6 +306,9 @@ impl<'arena> Expr<'arena> {
                 Command::Str { s } => {
                     w.append(s)?;
                 }
+                Command::String { s } => {
+                    w.append(&s)?;
+                }
             }
         }
 
@@ -283,6 +321,25 @@ pub enum Constant {
     U32(u32),
     U64(u64),
     U128(u128),
+    Wide(Vec<u64>),
+}
+
+impl Constant {
+    pub fn from_u128(value: u128, value_type: ValueType) -> Constant {
+        match value_type {
+            ValueType::Bool => Constant::Bool(value != 0),
+            ValueType::I32 | ValueType::I64 | ValueType::I128 => unreachable!(),
+            ValueType::U32 => Constant::U32(value as _),
+            ValueType::U64 => Constant::U64(value as _),
+            ValueType::U128 => Constant::U128(value),
+            ValueType::Wide(word_count) => {
+                let mut words = vec![0; word_count as usize];
+                words[0] = value as _;
+                words[1] = (value >> 64) as _;
+                Constant::Wide(words)
+            }
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -298,7 +355,10 @@ pub enum InfixBinOp {
     GreaterThanEqual,
     Shl,
     Shr,
+    Sub,
     Mul,
+    Div,
+    Rem,
 }
 
 #[derive(Clone, Copy)]
@@ -321,6 +381,7 @@ pub enum ValueType {
     U32,
     U64,
     U128,
+    Wide(u32),
 }
 
 impl ValueType {
@@ -334,28 +395,35 @@ impl ValueType {
         } else if bit_width <= 128 {
             ValueType::U128
         } else {
-            unreachable!()
+            ValueType::Wide(bit_width.div_ceil(64))
         }
     }
 
-    pub fn to_signed(&self) -> ValueType {
+    pub fn to_signed(self) -> ValueType {
         match self {
-            ValueType::Bool | ValueType::I32 | ValueType::I64 | ValueType::I128 => unreachable!(),
+            ValueType::Bool
+            | ValueType::I32
+            | ValueType::I64
+            | ValueType::I128
+            | ValueType::Wide(_) => unreachable!(),
             ValueType::U32 => ValueType::I32,
             ValueType::U64 => ValueType::I64,
             ValueType::U128 => ValueType::I128,
         }
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> String {
         match self {
-            ValueType::Bool => "bool",
-            ValueType::I32 => "i32",
-            ValueType::I64 => "i64",
-            ValueType::I128 => "i128",
-            ValueType::U32 => "u32",
-            ValueType::U64 => "u64",
-            ValueType::U128 => "u128",
+            ValueType::Bool => "bool".into(),
+            ValueType::I32 => "i32".into(),
+            ValueType::I64 => "i64".into(),
+            ValueType::I128 => "i128".into(),
+            ValueType::U32 => "u32".into(),
+            ValueType::U64 => "u64".into(),
+            ValueType::U128 => "u128".into(),
+            ValueType::Wide(word_count) => {
+                format!("kaze::runtime::WideUint::<{}>", word_count)
+            }
         }
     }
 
@@ -365,13 +433,15 @@ impl ValueType {
             ValueType::I32 | ValueType::U32 => 32,
             ValueType::I64 | ValueType::U64 => 64,
             ValueType::I128 | ValueType::U128 => 128,
+            ValueType::Wide(word_count) => word_count * 64,
         }
     }
 
-    pub fn zero_str(&self) -> &'static str {
+    pub fn zero_str(&self) -> String {
         match self {
-            ValueType::Bool => "false",
-            _ => "0",
+            ValueType::Bool => "false".into(),
+            ValueType::Wide(_) => format!("{}::ZERO", self.name()),
+            _ => "0".into(),
         }
     }
 }
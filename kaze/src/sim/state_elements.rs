@@ -24,6 +24,13 @@ pub(super) struct Mem<'a> {
     pub write_enable_name: String,
 }
 
+// An instance of a shared definition, which is simulated by a member of the definition's generated struct rather than being flattened
+pub(super) struct Instance<'a> {
+    pub module: &'a graph::Module<'a>,
+    pub definition: &'a graph::Module<'a>,
+    pub field_name: String,
+}
+
 pub struct ReadSignalNames {
     pub address_name: String,
     pub enable_name: String,
@@ -41,6 +48,7 @@ pub(super) enum IncludedPorts {
 pub(super) struct StateElements<'a> {
     pub mems: HashMap<&'a graph::Mem<'a>, Mem<'a>>,
     pub regs: HashMap<&'a internal_signal::InternalSignal<'a>, Register<'a>>,
+    pub instances: HashMap<&'a graph::Module<'a>, Instance<'a>>,
 }
 
 impl<'a> StateElements<'a> {
@@ -50,23 +58,27 @@ impl<'a> StateElements<'a> {
         included_ports: IncludedPorts,
         signal_reference_counts: &mut HashMap<&'a internal_signal::InternalSignal<'a>, u32>,
     ) -> StateElements<'a> {
-        let mut mems = HashMap::new();
-        let mut regs = HashMap::new();
+        let mut state_elements = StateElements {
+            mems: HashMap::new(),
+            regs: HashMap::new(),
+            instances: HashMap::new(),
+        };
 
-        visit_module(m, included_ports, &mut mems, &mut regs, signal_reference_counts);
+        visit_module(
+            m,
+            included_ports,
+            &mut state_elements,
+            signal_reference_counts,
+        );
 
-        StateElements {
-            mems,
-            regs,
-        }
+        state_elements
     }
 }
 
 fn visit_module<'a>(
     m: &'a graph::Module<'a>,
     included_ports: IncludedPorts,
-    mems: &mut HashMap<&'a graph::Mem<'a>, Mem<'a>>,
-    regs: &mut HashMap<&'a internal_signal::InternalSignal<'a>, Register<'a>>,
+    state_elements: &mut StateElements<'a>,
     signal_reference_counts: &mut HashMap<&'a internal_signal::InternalSignal<'a>, u32>,
 ) {
     match included_ports {
@@ -74,27 +86,48 @@ fn visit_module<'a>(
         // TODO: Test
         IncludedPorts::All => {
             for (_, &input) in m.inputs.borrow().iter() {
-                visit_signal(input.value, mems, regs, signal_reference_counts);
+                visit_signal(input.value, state_elements, signal_reference_counts);
             }
             for (_, &output) in m.outputs.borrow().iter() {
-                visit_signal(output.data.source, mems, regs, signal_reference_counts);
+                visit_signal(output.data.source, state_elements, signal_reference_counts);
             }
             for &register in m.registers.borrow().iter() {
                 match register.data {
-                    internal_signal::SignalData::Reg { ref data } => {
-                        visit_signal(data.next.borrow().unwrap(), mems, regs, signal_reference_counts);
+                    internal_signal::SignalData::Reg { data } => {
+                        visit_signal(
+                            data.next.borrow().unwrap(),
+                            state_elements,
+                            signal_reference_counts,
+                        );
+                        if let Some(reset) = *data.reset.borrow() {
+                            visit_signal(reset.source, state_elements, signal_reference_counts);
+                        }
                     }
                     _ => unreachable!(),
                 }
             }
             for &module in m.modules.borrow().iter() {
-                visit_module(module, included_ports, mems, regs, signal_reference_counts);
+                match module.definition {
+                    Some(definition) => {
+                        if add_instance(module, definition, &mut state_elements.instances) {
+                            for (_, &input) in module.inputs.borrow().iter() {
+                                visit_signal(input.value, state_elements, signal_reference_counts);
+                            }
+                        }
+                    }
+                    None => visit_module(
+                        module,
+                        included_ports,
+                        state_elements,
+                        signal_reference_counts,
+                    ),
+                }
             }
             // TODO: Cover all mems as well
         }
         IncludedPorts::ReachableFromTopLevelOutputs => {
             for (_, &output) in m.outputs.borrow().iter() {
-                visit_signal(output.data.source, mems, regs, signal_reference_counts);
+                visit_signal(output.data.source, state_elements, signal_reference_counts);
             }
         }
     }
@@ -103,10 +136,15 @@ fn visit_module<'a>(
 // TODO: Move this to ctor and iterate over input module outputs there?
 fn visit_signal<'a>(
     signal: &'a internal_signal::InternalSignal<'a>,
-    mems: &mut HashMap<&'a graph::Mem<'a>, Mem<'a>>,
-    regs: &mut HashMap<&'a internal_signal::InternalSignal<'a>, Register<'a>>,
+    state_elements: &mut StateElements<'a>,
     signal_reference_counts: &mut HashMap<&'a internal_signal::InternalSignal<'a>, u32>,
 ) {
+    let StateElements {
+        mems,
+        regs,
+        instances,
+    } = state_elements;
+
     // TODO: Do we even need this with just the one member?
     struct Frame<'a> {
         signal: &'a internal_signal::InternalSignal<'a>,
@@ -129,17 +167,29 @@ fn visit_signal<'a>(
             internal_signal::SignalData::Lit { .. } => (),
 
             internal_signal::SignalData::Input { data } => {
-                if let Some(driven_value) = data.driven_value.borrow().clone() {
+                if let Some(driven_value) = *data.driven_value.borrow() {
                     frames.push(Frame {
                         signal: driven_value,
                     });
                 }
             }
-            internal_signal::SignalData::Output { data } => {
-                frames.push(Frame {
-                    signal: data.source,
-                });
-            }
+            internal_signal::SignalData::Output { data } => match data.module.definition {
+                // An instance's outputs are computed by its definition's struct, which needs all of the instance's inputs
+                Some(definition) => {
+                    if add_instance(data.module, definition, instances) {
+                        for (_, &input) in data.module.inputs.borrow().iter() {
+                            frames.push(Frame {
+                                signal: input.value,
+                            });
+                        }
+                    }
+                }
+                None => {
+                    frames.push(Frame {
+                        signal: data.source,
+                    });
+                }
+            },
 
             internal_signal::SignalData::Reg { data } => {
                 let key = signal;
@@ -156,11 +206,28 @@ fn visit_signal<'a>(
                 frames.push(Frame {
                     signal: data.next.borrow().unwrap(),
                 });
+                if let Some(reset) = *data.reset.borrow() {
+                    frames.push(Frame {
+                        signal: reset.source,
+                    });
+                }
             }
 
             internal_signal::SignalData::UnOp { source, .. } => {
                 frames.push(Frame { signal: source });
             }
+            internal_signal::SignalData::ReduceOp { source, .. } => {
+                frames.push(Frame { signal: source });
+            }
+            internal_signal::SignalData::CountOp { source, .. } => {
+                frames.push(Frame { signal: source });
+            }
+            internal_signal::SignalData::OneHot { source, .. } => {
+                frames.push(Frame { signal: source });
+            }
+            internal_signal::SignalData::PriorityEncode { source, .. } => {
+                frames.push(Frame { signal: source });
+            }
             internal_signal::SignalData::SimpleBinOp { lhs, rhs, .. } => {
                 frames.push(Frame { signal: lhs });
                 frames.push(Frame { signal: rhs });
@@ -177,6 +244,10 @@ fn visit_signal<'a>(
                 frames.push(Frame { signal: lhs });
                 frames.push(Frame { signal: rhs });
             }
+            internal_signal::SignalData::RotateBinOp { lhs, rhs, .. } => {
+                frames.push(Frame { signal: lhs });
+                frames.push(Frame { signal: rhs });
+            }
 
             internal_signal::SignalData::Mul { lhs, rhs, .. } => {
                 frames.push(Frame { signal: lhs });
@@ -186,10 +257,17 @@ fn visit_signal<'a>(
                 frames.push(Frame { signal: lhs });
                 frames.push(Frame { signal: rhs });
             }
+            internal_signal::SignalData::DivisionBinOp { lhs, rhs, .. } => {
+                frames.push(Frame { signal: lhs });
+                frames.push(Frame { signal: rhs });
+            }
 
             internal_signal::SignalData::Bits { source, .. } => {
                 frames.push(Frame { signal: source });
             }
+            internal_signal::SignalData::Extend { source, .. } => {
+                frames.push(Frame { signal: source });
+            }
 
             internal_signal::SignalData::Repeat { source, .. } => {
                 frames.push(Frame { signal: source });
@@ -209,6 +287,16 @@ fn visit_signal<'a>(
                 frames.push(Frame { signal: when_true });
                 frames.push(Frame { signal: when_false });
             }
+            internal_signal::SignalData::Index {
+                ref elements,
+                index,
+                ..
+            } => {
+                frames.push(Frame { signal: index });
+                for &element in elements.iter() {
+                    frames.push(Frame { signal: element });
+                }
+            }
 
             internal_signal::SignalData::MemReadPortOutput { mem, .. } => {
                 let key = mem;
@@ -257,3 +345,24 @@ fn visit_signal<'a>(
         }
     }
 }
+
+// Returns `true` if `module` hadn't been added yet
+fn add_instance<'a>(
+    module: &'a graph::Module<'a>,
+    definition: &'a graph::Module<'a>,
+    instances: &mut HashMap<&'a graph::Module<'a>, Instance<'a>>,
+) -> bool {
+    if instances.contains_key(&module) {
+        return false;
+    }
+    let field_name = format!("__instance_{}_{}", module.instance_name, instances.len());
+    instances.insert(
+        module,
+        Instance {
+            module,
+            definition,
+            field_name,
+        },
+    );
+    true
+}
@@ -1,18 +1,42 @@
+use crate::error::Error;
 use crate::graph;
 use crate::graph::internal_signal;
 
-pub fn validate_module_hierarchy<'a>(m: &'a graph::Module<'a>) {
-    detect_undriven_registers_and_inputs(m, m);
-    detect_mem_errors(m, m);
-    detect_combinational_loops(m, m);
+use std::collections::HashMap;
+
+pub fn validate_module_hierarchy<'a>(m: &'a graph::Module<'a>) -> Result<(), Error> {
+    detect_undriven_registers_and_inputs(m, m)?;
+    detect_mem_errors(m, m)?;
+    detect_combinational_loops(m, m, &mut HashMap::new())
 }
 
-fn detect_undriven_registers_and_inputs<'a>(m: &graph::Module<'a>, root: &graph::Module<'a>) {
+fn detect_undriven_registers_and_inputs<'a>(
+    m: &graph::Module<'a>,
+    root: &graph::Module<'a>,
+) -> Result<(), Error> {
     for register in m.registers.borrow().iter() {
         match register.data {
-            internal_signal::SignalData::Reg { ref data } => {
+            internal_signal::SignalData::Reg { data } => {
                 if data.next.borrow().is_none() {
-                    panic!("Cannot generate code for module \"{}\" because module \"{}\" contains a register called \"{}\" which is not driven.", root.name, m.name, data.name);
+                    return Err(Error::UndrivenRegister {
+                        root_module_name: root.name.clone(),
+                        module_path: m.path(),
+                        module_name: m.name.clone(),
+                        register_name: data.name.clone(),
+                        location: data.location,
+                    });
+                }
+                if let Some(reset) = *data.reset.borrow() {
+                    if data.initial_value.borrow().is_none() {
+                        return Err(Error::ResetRegisterWithoutDefaultValue {
+                            root_module_name: root.name.clone(),
+                            module_path: m.path(),
+                            module_name: m.name.clone(),
+                            register_name: data.name.clone(),
+                            reset_name: reset.name.clone(),
+                            location: data.location,
+                        });
+                    }
                 }
             }
             _ => unreachable!(),
@@ -22,143 +46,260 @@ fn detect_undriven_registers_and_inputs<'a>(m: &graph::Module<'a>, root: &graph:
     for module in m.modules.borrow().iter() {
         for (name, input) in module.inputs.borrow().iter() {
             if input.data.driven_value.borrow().is_none() {
-                panic!("Cannot generate code for module \"{}\" because module \"{}\" contains an instance of module \"{}\" called \"{}\" whose input \"{}\" is not driven.", root.name, m.name, module.name, module.instance_name, name);
+                return Err(Error::UndrivenInstanceInput {
+                    root_module_name: root.name.clone(),
+                    module_path: m.path(),
+                    module_name: m.name.clone(),
+                    instance_module_name: module.name.clone(),
+                    instance_name: module.instance_name.clone(),
+                    input_name: name.clone(),
+                    location: input.data.location,
+                });
+            }
+        }
+
+        for (name, clock) in module.clocks.borrow().iter() {
+            if clock.driven_clock.borrow().is_none() {
+                return Err(Error::UndrivenInstanceClock {
+                    root_module_name: root.name.clone(),
+                    module_path: m.path(),
+                    module_name: m.name.clone(),
+                    instance_module_name: module.name.clone(),
+                    instance_name: module.instance_name.clone(),
+                    clock_name: name.clone(),
+                });
             }
         }
 
-        detect_undriven_registers_and_inputs(module, root);
+        detect_undriven_registers_and_inputs(module, root)?;
     }
+
+    Ok(())
 }
 
-fn detect_mem_errors<'a>(m: &graph::Module<'a>, root: &graph::Module<'a>) {
+fn detect_mem_errors<'a>(m: &graph::Module<'a>, root: &graph::Module<'a>) -> Result<(), Error> {
     for mem in m.mems.borrow().iter() {
         if mem.read_ports.borrow().is_empty() {
-            panic!("Cannot generate code for module \"{}\" because module \"{}\" contains a memory called \"{}\" which doesn't have any read ports.", root.name, m.name, mem.name);
+            return Err(Error::MemWithoutReadPorts {
+                root_module_name: root.name.clone(),
+                module_path: m.path(),
+                module_name: m.name.clone(),
+                mem_name: mem.name.clone(),
+                location: mem.location,
+            });
         }
 
         if mem.initial_contents.borrow().is_none() && mem.write_port.borrow().is_none() {
-            panic!("Cannot generate code for module \"{}\" because module \"{}\" contains a memory called \"{}\" which doesn't have initial contents or a write port specified. At least one of the two is required.", root.name, m.name, mem.name);
+            return Err(Error::MemWithoutInitialContentsOrWritePort {
+                root_module_name: root.name.clone(),
+                module_path: m.path(),
+                module_name: m.name.clone(),
+                mem_name: mem.name.clone(),
+                location: mem.location,
+            });
         }
     }
 
     for module in m.modules.borrow().iter() {
-        detect_mem_errors(module, root);
+        detect_mem_errors(module, root)?;
     }
+
+    Ok(())
 }
 
-fn detect_combinational_loops<'a>(m: &graph::Module<'a>, root: &graph::Module<'a>) {
-    for module in m.modules.borrow().iter() {
-        for (_, output) in module.outputs.borrow().iter() {
-            trace_signal(output.data.source, output.data.source, root);
+fn detect_combinational_loops<'a>(
+    m: &graph::Module<'a>,
+    root: &graph::Module<'a>,
+    visit_states: &mut HashMap<&'a internal_signal::InternalSignal<'a>, VisitState>,
+) -> Result<(), Error> {
+    for (_, input) in m.inputs.borrow().iter() {
+        trace_signal(input.value, root, visit_states)?;
+    }
+    for (_, output) in m.outputs.borrow().iter() {
+        trace_signal(output.data.source, root, visit_states)?;
+    }
+    for register in m.registers.borrow().iter() {
+        if let internal_signal::SignalData::Reg { data } = register.data {
+            if let Some(next) = *data.next.borrow() {
+                trace_signal(next, root, visit_states)?;
+            }
+        }
+    }
+    for reset in m.resets.borrow().iter() {
+        trace_signal(reset.source, root, visit_states)?;
+    }
+    for mem in m.mems.borrow().iter() {
+        for &(address, enable) in mem.read_ports.borrow().iter() {
+            trace_signal(address, root, visit_states)?;
+            trace_signal(enable, root, visit_states)?;
+        }
+        if let Some((address, value, enable)) = *mem.write_port.borrow() {
+            trace_signal(address, root, visit_states)?;
+            trace_signal(value, root, visit_states)?;
+            trace_signal(enable, root, visit_states)?;
         }
+    }
 
-        detect_combinational_loops(module, root);
+    for module in m.modules.borrow().iter() {
+        detect_combinational_loops(module, root, visit_states)?;
     }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum VisitState {
+    Visiting,
+    Visited,
 }
 
+// Depth-first search over combinational dependencies; a dependency on a signal that's still on the stack closes a loop
 fn trace_signal<'a>(
     signal: &'a internal_signal::InternalSignal<'a>,
-    source_output: &'a internal_signal::InternalSignal<'a>,
     root: &graph::Module<'a>,
-) {
+    visit_states: &mut HashMap<&'a internal_signal::InternalSignal<'a>, VisitState>,
+) -> Result<(), Error> {
     struct Frame<'a> {
         signal: &'a internal_signal::InternalSignal<'a>,
+        sources: Vec<&'a internal_signal::InternalSignal<'a>>,
     }
 
-    let mut frames = Vec::new();
-    frames.push(Frame { signal });
-
-    while let Some(frame) = frames.pop() {
-        let signal = frame.signal;
+    if visit_states.contains_key(&signal) {
+        return Ok(());
+    }
 
-        match signal.data {
-            internal_signal::SignalData::Lit { .. } => (),
+    let mut frames = vec![Frame {
+        signal,
+        sources: combinational_sources(signal),
+    }];
+    visit_states.insert(signal, VisitState::Visiting);
 
-            internal_signal::SignalData::Input { data } => {
-                if let Some(driven_value) = data.driven_value.borrow().clone() {
+    while let Some(frame) = frames.last_mut() {
+        match frame.sources.pop() {
+            Some(source) => match visit_states.get(&source) {
+                Some(VisitState::Visiting) => {
+                    let start = frames
+                        .iter()
+                        .position(|frame| frame.signal == source)
+                        .unwrap();
+                    let path = frames[start..].iter().map(|frame| frame.signal).collect();
+                    return Err(combinational_loop_error(path, root));
+                }
+                Some(VisitState::Visited) => (),
+                None => {
+                    visit_states.insert(source, VisitState::Visiting);
                     frames.push(Frame {
-                        signal: driven_value,
+                        signal: source,
+                        sources: combinational_sources(source),
                     });
                 }
+            },
+            None => {
+                visit_states.insert(frame.signal, VisitState::Visited);
+                frames.pop();
             }
-            internal_signal::SignalData::Output { data } => {
-                if data.source == source_output {
-                    panic!("Cannot generate code for module \"{}\" because module \"{}\" contains an output called \"{}\" which forms a combinational loop with itself.", root.name, data.module.name, data.name);
-                }
-                frames.push(Frame {
-                    signal: data.source,
-                });
-            }
+        }
+    }
 
-            internal_signal::SignalData::Reg { .. } => (),
+    Ok(())
+}
 
-            internal_signal::SignalData::UnOp { ref source, .. } => {
-                frames.push(Frame { signal: source });
-            }
-            internal_signal::SignalData::SimpleBinOp {
-                ref lhs, ref rhs, ..
-            } => {
-                frames.push(Frame { signal: lhs });
-                frames.push(Frame { signal: rhs });
-            }
-            internal_signal::SignalData::AdditiveBinOp {
-                ref lhs, ref rhs, ..
-            } => {
-                frames.push(Frame { signal: lhs });
-                frames.push(Frame { signal: rhs });
-            }
-            internal_signal::SignalData::ComparisonBinOp {
-                ref lhs, ref rhs, ..
-            } => {
-                frames.push(Frame { signal: lhs });
-                frames.push(Frame { signal: rhs });
-            }
-            internal_signal::SignalData::ShiftBinOp {
-                ref lhs, ref rhs, ..
-            } => {
-                frames.push(Frame { signal: lhs });
-                frames.push(Frame { signal: rhs });
-            }
+pub(crate) fn combinational_sources<'a>(
+    signal: &'a internal_signal::InternalSignal<'a>,
+) -> Vec<&'a internal_signal::InternalSignal<'a>> {
+    match signal.data {
+        internal_signal::SignalData::Lit { .. } => Vec::new(),
 
-            internal_signal::SignalData::Mul {
-                ref lhs, ref rhs, ..
-            } => {
-                frames.push(Frame { signal: lhs });
-                frames.push(Frame { signal: rhs });
-            }
-            internal_signal::SignalData::MulSigned {
-                ref lhs, ref rhs, ..
-            } => {
-                frames.push(Frame { signal: lhs });
-                frames.push(Frame { signal: rhs });
-            }
+        internal_signal::SignalData::Input { data } => {
+            data.driven_value.borrow().iter().copied().collect()
+        }
+        internal_signal::SignalData::Output { data } => vec![data.source],
 
-            internal_signal::SignalData::Bits { ref source, .. } => {
-                frames.push(Frame { signal: source });
-            }
+        internal_signal::SignalData::Reg { .. } => Vec::new(),
 
-            internal_signal::SignalData::Repeat { ref source, .. } => {
-                frames.push(Frame { signal: source });
-            }
-            internal_signal::SignalData::Concat {
-                ref lhs, ref rhs, ..
-            } => {
-                frames.push(Frame { signal: lhs });
-                frames.push(Frame { signal: rhs });
-            }
+        internal_signal::SignalData::UnOp { source, .. } => vec![source],
+        internal_signal::SignalData::ReduceOp { source, .. } => vec![source],
+        internal_signal::SignalData::CountOp { source, .. } => vec![source],
+        internal_signal::SignalData::OneHot { source, .. } => vec![source],
+        internal_signal::SignalData::PriorityEncode { source, .. } => vec![source],
+        internal_signal::SignalData::SimpleBinOp { lhs, rhs, .. } => vec![lhs, rhs],
+        internal_signal::SignalData::AdditiveBinOp { lhs, rhs, .. } => vec![lhs, rhs],
+        internal_signal::SignalData::ComparisonBinOp { lhs, rhs, .. } => vec![lhs, rhs],
+        internal_signal::SignalData::ShiftBinOp { lhs, rhs, .. } => vec![lhs, rhs],
+        internal_signal::SignalData::RotateBinOp { lhs, rhs, .. } => vec![lhs, rhs],
+
+        internal_signal::SignalData::Mul { lhs, rhs, .. } => vec![lhs, rhs],
+        internal_signal::SignalData::MulSigned { lhs, rhs, .. } => vec![lhs, rhs],
+        internal_signal::SignalData::DivisionBinOp { lhs, rhs, .. } => vec![lhs, rhs],
+
+        internal_signal::SignalData::Bits { source, .. } => vec![source],
+        internal_signal::SignalData::Extend { source, .. } => vec![source],
+
+        internal_signal::SignalData::Repeat { source, .. } => vec![source],
+        internal_signal::SignalData::Concat { lhs, rhs, .. } => vec![lhs, rhs],
+
+        internal_signal::SignalData::Mux {
+            cond,
+            when_true,
+            when_false,
+            ..
+        } => vec![cond, when_true, when_false],
+        internal_signal::SignalData::Index {
+            ref elements,
+            index,
+            ..
+        } => elements.iter().copied().chain([index]).collect(),
+
+        internal_signal::SignalData::MemReadPortOutput { .. } => Vec::new(),
+    }
+}
+
+// `path` lists signals in dependency order (each signal depends on the next, and the last depends on the first)
+fn combinational_loop_error<'a>(
+    path: Vec<&'a internal_signal::InternalSignal<'a>>,
+    root: &graph::Module<'a>,
+) -> Error {
+    // Only inputs and instance outputs are named, and every loop crosses at least one instance boundary
+    let mut named_signals = path
+        .into_iter()
+        .rev()
+        .filter(|signal| {
+            matches!(
+                signal.data,
+                internal_signal::SignalData::Input { .. }
+                    | internal_signal::SignalData::Output { .. }
+            )
+        })
+        .collect::<Vec<_>>();
+    let output_index = named_signals
+        .iter()
+        .position(|signal| matches!(signal.data, internal_signal::SignalData::Output { .. }))
+        .unwrap();
+    named_signals.rotate_left(output_index);
+    named_signals.push(named_signals[0]);
 
-            internal_signal::SignalData::Mux {
-                ref cond,
-                ref when_true,
-                ref when_false,
-                ..
-            } => {
-                frames.push(Frame { signal: cond });
-                frames.push(Frame { signal: when_true });
-                frames.push(Frame { signal: when_false });
+    let cycle = named_signals
+        .iter()
+        .map(|signal| match signal.data {
+            internal_signal::SignalData::Input { data } => {
+                format!("{}.{}", signal.module.path(), data.name)
             }
+            internal_signal::SignalData::Output { data } => {
+                format!("{}.{}", data.module.path(), data.name)
+            }
+            _ => unreachable!(),
+        })
+        .collect();
 
-            internal_signal::SignalData::MemReadPortOutput { .. } => (),
-        }
+    match named_signals[0].data {
+        internal_signal::SignalData::Output { data } => Error::CombinationalLoop {
+            root_module_name: root.name.clone(),
+            module_path: data.module.path(),
+            module_name: data.module.name.clone(),
+            output_name: data.name.clone(),
+            location: data.source.location,
+            cycle,
+        },
+        _ => unreachable!(),
     }
 }
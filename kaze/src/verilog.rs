@@ -9,26 +9,109 @@ use ir::*;
 use module_decls::*;
 
 use crate::code_writer;
+use crate::error::Error;
 use crate::graph;
 use crate::validation::*;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::{Result, Write};
 
 // TODO: Note that mutable writer reference can be passed, see https://rust-lang.github.io/api-guidelines/interoperability.html#c-rw-value
+/// Generates Verilog code for `m` and every module instantiated (directly or indirectly) in `m`.
+///
+/// One Verilog module is emitted per module definition, starting with `m` and followed by its descendants in depth-first order. All instances created from the same definition with [`Module::instance`](graph::Module::instance) share a single Verilog module, which is only generated once; every other `Module` is its own definition. As Verilog modules are named after their definitions' [names](graph::ModuleParent::module), different definitions in the hierarchy must have different names.
+///
+/// # Errors
+///
+/// Returns any error returned by `w`.
+///
+/// # Panics
+///
+/// Panics if `m` or any of its descendants fail validation (see [`Module::validate`](graph::Module::validate)), or if two different definitions in the hierarchy have the same name (see [`Error::ConflictingModuleDefinitions`](crate::Error::ConflictingModuleDefinitions)).
+///
+/// # Examples
+///
+/// ```
+/// use kaze::*;
+///
+/// let c = Context::new();
+///
+/// let top = c.module("top", "Top");
+/// let inverter = top.module("inverter", "Inverter");
+/// let i = inverter.input("i", 1);
+/// let o = inverter.output("o", !i);
+/// i.drive(top.input("i", 1));
+/// top.output("o", o);
+///
+/// verilog::generate(top, std::io::stdout()).unwrap();
+/// ```
 pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
-    validate_module_hierarchy(m);
+    if let Err(e) = validate_module_hierarchy(m) {
+        panic!("{}", e);
+    }
+
+    // Instances share the Verilog module generated for their definition, so each definition's hierarchy only needs to be visited once (through whichever of its instances is found first)
+    let mut definitions = Vec::new();
+    let mut visited_definitions = HashSet::new();
+    let mut definition_paths = HashMap::new();
+    let mut modules = vec![(m, m.path())];
+    while let Some((module, path)) = modules.pop() {
+        let definition = module.definition.unwrap_or(module);
+        if !visited_definitions.insert(definition) {
+            continue;
+        }
+
+        if let Some(existing_path) = definition_paths.insert(&definition.name, path.clone()) {
+            panic!(
+                "{}",
+                Error::ConflictingModuleDefinitions {
+                    root_module_name: m.name.clone(),
+                    module_name: definition.name.clone(),
+                    module_path: path,
+                    existing_module_path: existing_path,
+                }
+            );
+        }
+        definitions.push(definition);
+
+        modules.extend(
+            definition
+                .modules
+                .borrow()
+                .iter()
+                .rev()
+                .map(|&child| (child, format!("{}.{}", path, child.instance_name))),
+        );
+    }
+
+    let mut w = code_writer::CodeWriter::new(w);
+    for definition in definitions {
+        generate_module(definition, &mut w)?;
+    }
+
+    Ok(())
+}
 
+fn generate_module<'a, W: Write>(
+    m: &'a graph::Module<'a>,
+    w: &mut code_writer::CodeWriter<W>,
+) -> Result<()> {
     let mut instances = HashMap::new();
-    for instance in m.instances.borrow().iter() {
-        let mut input_names = HashMap::new();
-        for (name, _) in instance.instantiated_module.inputs.borrow().iter() {
-            input_names.insert(name.clone(), format!("__{}_input_{}", instance.name, name));
+    for instance in m.modules.borrow().iter() {
+        let mut input_names = BTreeMap::new();
+        for name in instance.inputs.borrow().keys() {
+            input_names.insert(
+                name.clone(),
+                format!("__{}_input_{}", instance.instance_name, name),
+            );
         }
 
-        let mut output_names = HashMap::new();
-        for (name, _) in instance.instantiated_module.outputs.borrow().iter() {
-            output_names.insert(name.clone(), format!("__{}_output_{}", instance.name, name));
+        let mut output_names = BTreeMap::new();
+        for name in instance.outputs.borrow().keys() {
+            output_names.insert(
+                name.clone(),
+                format!("__{}_output_{}", instance.instance_name, name),
+            );
         }
 
         instances.insert(
@@ -73,7 +156,7 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
     let mut regs = HashMap::new();
     for reg in m.registers.borrow().iter() {
         match reg.data {
-            graph::SignalData::Reg { data } => {
+            graph::internal_signal::SignalData::Reg { data } => {
                 let value_name = format!("__reg_{}_{}", data.name, regs.len());
                 let next_name = format!("{}_next", value_name);
                 regs.insert(
@@ -99,7 +182,7 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
 
     let mut assignments = AssignmentContext::new();
     for (name, output) in m.outputs.borrow().iter() {
-        let expr = c.compile_signal(&output, &module_decls, &mut assignments);
+        let expr = c.compile_signal(output.data.source, &module_decls, &mut assignments);
         assignments.push(Assignment {
             target_name: name.clone(),
             expr,
@@ -108,16 +191,21 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
 
     let mut node_decls = Vec::new();
 
-    for (instance, instance_decls) in module_decls.instances.iter() {
+    for instance in m.modules.borrow().iter() {
+        let instance_decls = &module_decls.instances[instance];
+
+        let inputs = instance.inputs.borrow();
         for (name, decl_name) in instance_decls.input_names.iter() {
+            let input = inputs[name];
             node_decls.push(NodeDecl {
                 net_type: NetType::Wire,
                 name: decl_name.clone(),
-                bit_width: instance.instantiated_module.inputs.borrow()[name].bit_width(),
+                bit_width: input.data.bit_width,
+                signed: false,
             });
 
             let expr = c.compile_signal(
-                instance.driven_inputs.borrow()[name],
+                input.data.driven_value.borrow().unwrap(),
                 &module_decls,
                 &mut assignments,
             );
@@ -127,22 +215,27 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
             });
         }
 
+        let outputs = instance.outputs.borrow();
         for (name, decl_name) in instance_decls.output_names.iter() {
             node_decls.push(NodeDecl {
                 net_type: NetType::Wire,
                 name: decl_name.clone(),
-                bit_width: instance.instantiated_module.outputs.borrow()[name].bit_width(),
+                bit_width: outputs[name].data.bit_width,
+                signed: false,
             });
         }
     }
 
-    for (mem, mem_decls) in module_decls.mems.iter() {
-        for ((address, enable), read_signal_names) in mem_decls.read_signal_names.iter() {
+    for mem in m.mems.borrow().iter() {
+        let mem_decls = &module_decls.mems[mem];
+        for (address, enable) in mem.read_ports.borrow().iter() {
+            let read_signal_names = &mem_decls.read_signal_names[&(*address, *enable)];
             let expr = c.compile_signal(address, &module_decls, &mut assignments);
             node_decls.push(NodeDecl {
                 net_type: NetType::Wire,
                 name: read_signal_names.address_name.clone(),
                 bit_width: address.bit_width(),
+                signed: false,
             });
             assignments.push(Assignment {
                 target_name: read_signal_names.address_name.clone(),
@@ -153,6 +246,7 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
                 net_type: NetType::Wire,
                 name: read_signal_names.enable_name.clone(),
                 bit_width: enable.bit_width(),
+                signed: false,
             });
             assignments.push(Assignment {
                 target_name: read_signal_names.enable_name.clone(),
@@ -162,6 +256,7 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
                 net_type: NetType::Reg,
                 name: read_signal_names.value_name.clone(),
                 bit_width: mem.element_bit_width,
+                signed: false,
             });
         }
         if let Some((address, value, enable)) = *mem.write_port.borrow() {
@@ -170,6 +265,7 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
                 net_type: NetType::Wire,
                 name: mem_decls.write_address_name.clone(),
                 bit_width: address.bit_width(),
+                signed: false,
             });
             assignments.push(Assignment {
                 target_name: mem_decls.write_address_name.clone(),
@@ -180,6 +276,7 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
                 net_type: NetType::Wire,
                 name: mem_decls.write_value_name.clone(),
                 bit_width: value.bit_width(),
+                signed: false,
             });
             assignments.push(Assignment {
                 target_name: mem_decls.write_value_name.clone(),
@@ -190,6 +287,7 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
                 net_type: NetType::Wire,
                 name: mem_decls.write_enable_name.clone(),
                 bit_width: enable.bit_width(),
+                signed: false,
             });
             assignments.push(Assignment {
                 target_name: mem_decls.write_enable_name.clone(),
@@ -198,16 +296,35 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
         }
     }
 
-    for reg in module_decls.regs.values() {
+    for reset in m.resets.borrow().iter() {
+        let name = reset_name(reset);
+        node_decls.push(NodeDecl {
+            net_type: NetType::Wire,
+            name: name.clone(),
+            bit_width: 1,
+            signed: false,
+        });
+
+        let expr = c.compile_signal(reset.source, &module_decls, &mut assignments);
+        assignments.push(Assignment {
+            target_name: name,
+            expr,
+        });
+    }
+
+    for reg in m.registers.borrow().iter() {
+        let reg = &module_decls.regs[reg];
         node_decls.push(NodeDecl {
             net_type: NetType::Reg,
             name: reg.value_name.clone(),
             bit_width: reg.data.bit_width,
+            signed: reg.data.signed,
         });
         node_decls.push(NodeDecl {
             net_type: NetType::Wire,
             name: reg.next_name.clone(),
             bit_width: reg.data.bit_width,
+            signed: false,
         });
 
         let expr = c.compile_signal(
@@ -221,8 +338,18 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
         });
     }
 
-    let mut w = code_writer::CodeWriter::new(w);
-
+    let parameters = m.parameters.borrow();
+    if !parameters.is_empty() {
+        w.append_line("// Parameters:")?;
+        for (name, value) in parameters.iter() {
+            // Parameter names and values are arbitrary strings, so they're escaped to keep them on a single comment line
+            w.append_line(&format!(
+                "//   {} = {}",
+                name.escape_debug(),
+                value.escape_debug()
+            ))?;
+        }
+    }
     w.append_line(&format!("module {}(", m.name))?;
     w.indent();
 
@@ -230,6 +357,12 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
     w.append_line("input wire reset_n,")?;
     w.append_indent()?;
     w.append("input wire clk")?;
+    for name in m.clocks.borrow().keys() {
+        w.append(",")?;
+        w.append_newline()?;
+        w.append_indent()?;
+        w.append(&format!("input wire {}", name))?;
+    }
     if !m.inputs.borrow().is_empty() || !m.outputs.borrow().is_empty() {
         w.append(",")?;
         w.append_newline()?;
@@ -240,8 +373,11 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
     for (i, (name, source)) in inputs.iter().enumerate() {
         w.append_indent()?;
         w.append("input wire ")?;
-        if source.bit_width() > 1 {
-            w.append(&format!("[{}:{}] ", source.bit_width() - 1, 0))?;
+        if source.data.signed {
+            w.append("signed ")?;
+        }
+        if source.data.bit_width > 1 {
+            w.append(&format!("[{}:{}] ", source.data.bit_width - 1, 0))?;
         }
         w.append(name)?;
         if !m.outputs.borrow().is_empty() || i < num_inputs - 1 {
@@ -254,8 +390,11 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
     for (i, (name, output)) in outputs.iter().enumerate() {
         w.append_indent()?;
         w.append("output wire ")?;
-        if output.bit_width() > 1 {
-            w.append(&format!("[{}:{}] ", output.bit_width() - 1, 0))?;
+        if output.data.signed {
+            w.append("signed ")?;
+        }
+        if output.data.bit_width > 1 {
+            w.append(&format!("[{}:{}] ", output.data.bit_width - 1, 0))?;
         }
         w.append(name)?;
         if i < num_outputs - 1 {
@@ -268,21 +407,29 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
 
     if !node_decls.is_empty() {
         for node_decl in node_decls {
-            node_decl.write(&mut w)?;
+            node_decl.write(w)?;
         }
         w.append_newline()?;
     }
 
-    for (instance, instance_decls) in module_decls.instances.iter() {
-        w.append_line(&format!(
-            "{} {}(",
-            instance.instantiated_module.name, instance.name
-        ))?;
+    for instance in m.modules.borrow().iter() {
+        let instance_decls = &module_decls.instances[instance];
+        w.append_line(&format!("{} {}(", instance.name, instance.instance_name))?;
         w.indent();
         // TODO: Make conditional based on the presence of (resetable) state elements
         w.append_line(".reset_n(reset_n),")?;
         w.append_indent()?;
         w.append(".clk(clk)")?;
+        for (name, clock) in instance.clocks.borrow().iter() {
+            w.append(",")?;
+            w.append_newline()?;
+            w.append_indent()?;
+            w.append(&format!(
+                ".{}({})",
+                name,
+                clock.driven_clock.borrow().unwrap().name
+            ))?;
+        }
         if !instance_decls.input_names.is_empty() {
             for (name, decl_name) in instance_decls.input_names.iter() {
                 w.append(",")?;
@@ -305,7 +452,8 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
         w.append_newline()?;
     }
 
-    for (mem, mem_decls) in module_decls.mems.iter() {
+    for mem in m.mems.borrow().iter() {
+        let mem_decls = &module_decls.mems[mem];
         w.append_indent()?;
         w.append("reg ")?;
         if mem.element_bit_width > 1 {
@@ -324,11 +472,11 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
             w.indent();
             for (i, element) in initial_contents.iter().enumerate() {
                 w.append_line(&format!(
-                    "{}[{}] = {}'h{:x};",
+                    "{}[{}] = {}'h{};",
                     mem.name,
                     i,
                     mem.element_bit_width,
-                    element.numeric_value()
+                    element.hex_string()
                 ))?;
             }
             w.unindent();
@@ -336,10 +484,14 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
             w.append_newline()?;
         }
         if !mem_decls.read_signal_names.is_empty() || mem.write_port.borrow().is_some() {
-            w.append_line("always @(posedge clk) begin")?;
+            w.append_line(&format!(
+                "always @(posedge {}) begin",
+                clock_name(*mem.clock.borrow())
+            ))?;
             w.indent();
         }
-        for (_, read_signal_names) in mem_decls.read_signal_names.iter() {
+        for read_port in mem.read_ports.borrow().iter() {
+            let read_signal_names = &mem_decls.read_signal_names[read_port];
             w.append_line(&format!("if ({}) begin", read_signal_names.enable_name))?;
             w.indent();
             w.append_line(&format!(
@@ -366,23 +518,59 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
         }
     }
 
-    for reg in module_decls.regs.values() {
+    for reg in m.registers.borrow().iter() {
+        let reg = &module_decls.regs[reg];
+        // Registers without an explicit reset use the implicit async active-low reset_n, but only if they have a default value
+        let (reset_name, reset_kind, reset_polarity) = match *reg.data.reset.borrow() {
+            Some(reset) => (reset_name(reset), Some(reset.kind), reset.polarity),
+            None => (
+                "reset_n".into(),
+                reg.data
+                    .initial_value
+                    .borrow()
+                    .as_ref()
+                    .map(|_| graph::ResetKind::Async),
+                graph::ResetPolarity::ActiveLow,
+            ),
+        };
         w.append_indent()?;
-        w.append("always @(posedge clk")?;
-        if reg.data.initial_value.borrow().is_some() {
-            w.append(", negedge reset_n")?;
+        w.append(&format!(
+            "always @(posedge {}",
+            clock_name(*reg.data.clock.borrow())
+        ))?;
+        if let Some(graph::ResetKind::Async) = reset_kind {
+            w.append(&format!(
+                ", {} {}",
+                match reset_polarity {
+                    graph::ResetPolarity::ActiveHigh => "posedge",
+                    graph::ResetPolarity::ActiveLow => "negedge",
+                },
+                reset_name
+            ))?;
         }
         w.append(") begin")?;
         w.append_newline()?;
         w.indent();
-        if let Some(ref initial_value) = *reg.data.initial_value.borrow() {
-            w.append_line("if (~reset_n) begin")?;
+        if reset_kind.is_some() {
+            w.append_line(&format!(
+                "if ({}{}) begin",
+                match reset_polarity {
+                    graph::ResetPolarity::ActiveHigh => "",
+                    graph::ResetPolarity::ActiveLow => "~",
+                },
+                reset_name
+            ))?;
             w.indent();
             w.append_line(&format!(
-                "{} <= {}'h{:x};",
+                "{} <= {}'h{};",
                 reg.value_name,
                 reg.data.bit_width,
-                initial_value.numeric_value()
+                reg.data
+                    .initial_value
+                    .borrow()
+                    .as_ref()
+                    .unwrap()
+                    .hex_string()
             ))?;
             w.unindent();
             w.append_line("end")?;
@@ -390,7 +578,7 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
             w.indent();
         }
         w.append_line(&format!("{} <= {};", reg.value_name, reg.next_name))?;
-        if reg.data.initial_value.borrow().is_some() {
+        if reset_kind.is_some() {
             w.unindent();
             w.append_line("end")?;
         }
@@ -400,7 +588,7 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
     }
 
     if !assignments.is_empty() {
-        assignments.write(&mut w)?;
+        assignments.write(w)?;
         w.append_newline()?;
     }
 
@@ -411,44 +599,20 @@ pub fn generate<'a, W: Write>(m: &'a graph::Module<'a>, w: W) -> Result<()> {
     Ok(())
 }
 
+fn reset_name(reset: &graph::Reset) -> String {
+    format!("__reset_{}", reset.name)
+}
+
+fn clock_name<'a>(clock: Option<&'a graph::Clock<'a>>) -> &'a str {
+    clock.map_or("clk", |clock| &clock.name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use crate::*;
 
-    #[test]
-    #[should_panic(
-        expected = "Cannot generate code for module \"A\" because it has a recursive definition formed by an instance of itself called \"a\"."
-    )]
-    fn recursive_module_definition_error1() {
-        let c = Context::new();
-
-        let a = c.module("A");
-
-        let _ = a.instance("a", "A");
-
-        // Panic
-        generate(a, Vec::new()).unwrap();
-    }
-
-    #[test]
-    #[should_panic(
-        expected = "Cannot generate code for module \"A\" because it has a recursive definition formed by an instance of itself called \"a\" in module \"B\"."
-    )]
-    fn recursive_module_definition_error2() {
-        let c = Context::new();
-
-        let a = c.module("A");
-        let b = c.module("B");
-
-        let _ = a.instance("b", "B");
-        let _ = b.instance("a", "A");
-
-        // Panic
-        generate(a, Vec::new()).unwrap();
-    }
-
     #[test]
     #[should_panic(
         expected = "Cannot generate code for module \"A\" because module \"A\" contains an instance of module \"B\" called \"b\" whose input \"i\" is not driven."
@@ -456,12 +620,10 @@ mod tests {
     fn undriven_instance_input_error() {
         let c = Context::new();
 
-        let a = c.module("A");
-        let b = c.module("B");
+        let a = c.module("a", "A");
+        let b = a.module("b", "B");
         let _ = b.input("i", 1);
 
-        let _ = a.instance("b", "B");
-
         // Panic
         generate(a, Vec::new()).unwrap();
     }
@@ -473,7 +635,7 @@ mod tests {
     fn undriven_register_error1() {
         let c = Context::new();
 
-        let a = c.module("A");
+        let a = c.module("a", "A");
         let _ = a.reg("r", 1);
 
         // Panic
@@ -487,11 +649,31 @@ mod tests {
     fn undriven_register_error2() {
         let c = Context::new();
 
-        let a = c.module("A");
-        let b = c.module("B");
+        let a = c.module("a", "A");
+        let b = a.module("b", "B");
         let _ = b.reg("r", 1);
 
-        let _ = a.instance("b", "B");
+        // Panic
+        generate(a, Vec::new()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot generate code for module \"A\" because module \"A\" contains a register called \"r\" which is assigned to reset \"rst\" but doesn't have a default value."
+    )]
+    fn reset_without_default_value_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let rst = a.reset(
+            "rst",
+            a.input("rst", 1),
+            ResetKind::Sync,
+            ResetPolarity::ActiveHigh,
+        );
+        let r = a.reg("r", 1);
+        r.reset(rst);
+        r.drive_next(!r);
 
         // Panic
         generate(a, Vec::new()).unwrap();
@@ -504,7 +686,7 @@ mod tests {
     fn mem_without_read_ports_error1() {
         let c = Context::new();
 
-        let a = c.module("A");
+        let a = c.module("a", "A");
         let _ = a.mem("m", 1, 1);
 
         // Panic
@@ -518,12 +700,10 @@ mod tests {
     fn mem_without_read_ports_error2() {
         let c = Context::new();
 
-        let a = c.module("A");
-        let b = c.module("B");
+        let a = c.module("a", "A");
+        let b = a.module("b", "B");
         let _ = b.mem("m", 1, 1);
 
-        let _ = a.instance("b", "B");
-
         // Panic
         generate(a, Vec::new()).unwrap();
     }
@@ -535,7 +715,7 @@ mod tests {
     fn mem_without_initial_contents_or_write_port_error1() {
         let c = Context::new();
 
-        let a = c.module("A");
+        let a = c.module("a", "A");
         let m = a.mem("m", 1, 1);
         let _ = m.read_port(a.low(), a.low());
 
@@ -550,13 +730,11 @@ mod tests {
     fn mem_without_initial_contents_or_write_port_error2() {
         let c = Context::new();
 
-        let a = c.module("A");
-        let b = c.module("B");
+        let a = c.module("a", "A");
+        let b = a.module("b", "B");
         let m = b.mem("m", 1, 1);
         let _ = m.read_port(b.low(), b.low());
 
-        let _ = a.instance("b", "B");
-
         // Panic
         generate(a, Vec::new()).unwrap();
     }
@@ -568,15 +746,370 @@ mod tests {
     fn combinational_loop_error() {
         let c = Context::new();
 
-        let a = c.module("a");
-        a.output("o", a.input("i", 1));
-
-        let b = c.module("b");
-        let a_inst = b.instance("a_inst", "a");
-        let a_inst_o = a_inst.output("o");
-        a_inst.drive_input("i", a_inst_o);
+        let b = c.module("b", "b");
+        let a = b.module("a", "a");
+        let a_i = a.input("i", 1);
+        let a_o = a.output("o", a_i);
+        a_i.drive(a_o);
 
         // Panic
         generate(b, Vec::new()).unwrap();
     }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot generate code for module \"A\" because module \"A\" contains an instance of module \"B\" called \"b\" whose clock \"c\" is not driven."
+    )]
+    fn undriven_instance_clock_error() {
+        let c = Context::new();
+
+        let a = c.module("a", "A");
+        let b = a.module("b", "B");
+        let _ = b.clock("c");
+
+        // Panic
+        generate(a, Vec::new()).unwrap();
+    }
+
+    #[test]
+    fn clock_domains() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let pixel_clk = top.clock("pixel_clk");
+        let r = top.reg("r", 1);
+        r.clock(pixel_clk);
+        r.drive_next(!r);
+        let inner = top.module("inner", "Inner");
+        let inner_clk = inner.clock("inner_clk");
+        inner_clk.drive(pixel_clk);
+        let inner_r = inner.reg("r", 1);
+        inner_r.clock(inner_clk);
+        inner_r.drive_next(!inner_r);
+        let inner_o = inner.output("o", inner_r);
+        top.output("o", r & inner_o);
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert!(code.contains("input wire pixel_clk,"));
+        assert!(code.contains("input wire inner_clk,"));
+        assert!(code.contains(".inner_clk(pixel_clk)"));
+        assert!(code.contains("always @(posedge pixel_clk) begin"));
+        assert!(code.contains("always @(posedge inner_clk) begin"));
+    }
+
+    #[test]
+    fn resets() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let arst_n = top.reset(
+            "arst_n",
+            top.input("arst_n", 1),
+            ResetKind::Async,
+            ResetPolarity::ActiveLow,
+        );
+        let srst = top.reset(
+            "srst",
+            top.input("srst", 1),
+            ResetKind::Sync,
+            ResetPolarity::ActiveHigh,
+        );
+        let r1 = top.reg("r1", 1);
+        r1.default_value(false);
+        r1.reset(arst_n);
+        r1.drive_next(!r1);
+        let r2 = top.reg("r2", 1);
+        r2.default_value(true);
+        r2.reset(srst);
+        r2.drive_next(!r2);
+        top.output("o", r1 & r2);
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert!(code.contains("assign __reset_arst_n = arst_n;"));
+        assert!(code.contains("assign __reset_srst = srst;"));
+        assert!(code.contains("always @(posedge clk, negedge __reset_arst_n) begin"));
+        assert!(code.contains("if (~__reset_arst_n) begin"));
+        assert!(code.contains("if (__reset_srst) begin"));
+        assert_eq!(code.matches("always @(posedge clk) begin").count(), 1);
+    }
+
+    #[test]
+    fn wide_constants() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let r = top.reg("r", 256);
+        r.default_value([0xdeadbeefu64, 0, 0, 0xfadebabe]);
+        r.drive_next(r ^ top.lit([1u64, 0, 0, 1], 256));
+        top.output("o", r);
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert!(code.contains("output wire [255:0] o"));
+        assert!(code.contains("256'hfadebabe0000000000000000000000000000000000000000deadbeef"));
+        assert!(code.contains("256'h1000000000000000000000000000000000000000000000001"));
+    }
+
+    #[test]
+    fn signed_outputs() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let i = top.input("i", 8).as_signed();
+        top.output("o1", i * i);
+        top.output("o2", i.lt(i));
+        top.output("o3", i.bits(0, 0).as_signed());
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert!(code.contains("input wire [7:0] i"));
+        assert!(code.contains("output wire signed [15:0] o1"));
+        assert!(code.contains("output wire o2"));
+        assert!(code.contains("output wire signed o3"));
+    }
+
+    #[test]
+    fn signed_inputs_and_registers() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let i = top.signed_input("i", 8);
+        let r = top.signed_reg("r", 8);
+        r.drive_next(i);
+        top.output("o1", r);
+        top.output("o2", i.lt(r));
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert!(code.contains("input wire signed [7:0] i"));
+        assert!(code.contains("output wire signed [7:0] o1"));
+        assert!(code.contains("output wire o2"));
+        assert!(code.contains("reg signed [7:0] __reg_r_0;"));
+        // Signed nets are read through unsigned temporaries, so unsigned operations stay unsigned
+        assert!(code.contains("assign __temp_0 = __reg_r_0;"));
+        assert!(code.contains("assign __temp_1 = i;"));
+        assert!(code.contains("assign __temp_2 = __temp_1 < __temp_0;"));
+    }
+
+    #[test]
+    fn shr_arithmetic_is_signed() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let x = top.input("x", 8);
+        top.output("o1", x.as_signed() >> 1u32);
+        top.output("o2", x.shr_arithmetic(top.input("s", 3)));
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert!(code.contains("assign __temp_0 = $signed(x) >>> 1'h1;"));
+        assert!(code.contains("assign __temp_1 = $signed(x) >>> s;"));
+    }
+
+    #[test]
+    fn signal_vec_get() {
+        let c = Context::new();
+
+        let top = c.module("top", "Top");
+        let v = SignalVec::new(&[top.input("a", 8), top.input("b", 8), top.input("c", 8)]);
+        top.output("o", v.get(top.input("s", 2)));
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert!(code.contains("assign __temp_0 = s[0] ? b : a;"));
+        assert!(code.contains("assign __temp_1 = s[1] ? c : __temp_0;"));
+        assert!(code.contains("assign __temp_2 = s > 2'h2 ? c : __temp_1;"));
+    }
+
+    #[test]
+    fn shared_module_definitions() {
+        let c = Context::new();
+
+        let inner = c.module("inner", "Inner");
+        let inner_i = inner.input("i", 8);
+        inner.output("o", !inner_i);
+
+        let top = c.module("top", "Top");
+        let i = top.input("i", 8);
+        let inner1 = top.instance("inner1", inner);
+        inner1.get_input("i").unwrap().drive(i);
+        let inner2 = top.instance("inner2", inner);
+        inner2
+            .get_input("i")
+            .unwrap()
+            .drive(inner1.get_output("o").unwrap());
+        top.output("o", inner2.get_output("o").unwrap());
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert_eq!(code.matches("module Top(").count(), 1);
+        assert_eq!(code.matches("module Inner(").count(), 1);
+        assert!(code.find("module Top(").unwrap() < code.find("module Inner(").unwrap());
+        assert!(code.contains("Inner inner1("));
+        assert!(code.contains("Inner inner2("));
+        assert!(code.contains(".i(__inner1_input_i)"));
+        assert!(code.contains(".o(__inner2_output_o)"));
+        assert!(code.contains("assign __inner1_input_i = i;"));
+        assert!(code.contains("assign __inner2_input_i = __inner1_output_o;"));
+        assert!(code.contains("assign o = __inner2_output_o;"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot generate Verilog code for module \"Top\" because module instances \"top.inner0\" and \"top.inner1\" are both called \"Inner\" but aren't instances of the same definition. Modules with the same name must be instantiated from a single definition with `Module::instance`."
+    )]
+    fn conflicting_module_definitions_error() {
+        let c = Context::new();
+
+        // Identical modules that are built separately are still separate definitions
+        let top = c.module("top", "Top");
+        for index in 0..2 {
+            let inner = top.module(format!("inner{}", index), "Inner");
+            let inner_i = inner.input("i", 8);
+            inner_i.drive(top.input(format!("i{}", index), 8));
+            top.output(format!("o{}", index), inner.output("o", !inner_i));
+        }
+
+        // Panic
+        generate(top, std::io::sink()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot generate Verilog code for module \"Top\" because module instances \"top.outer.inner\" and \"top.inner\" are both called \"Inner\" but aren't instances of the same definition. Modules with the same name must be instantiated from a single definition with `Module::instance`."
+    )]
+    fn conflicting_nested_module_definitions_error() {
+        let c = Context::new();
+
+        let outer = c.module("outer", "Outer");
+        let inner = outer.module("inner", "Inner");
+        let inner_i = inner.input("i", 8);
+        inner_i.drive(outer.input("i", 8));
+        outer.output("o", inner.output("o", inner_i));
+
+        let top = c.module("top", "Top");
+        let i = top.input("i", 8);
+        let outer = top.instance("outer", outer);
+        outer.get_input("i").unwrap().drive(i);
+        top.output("o0", outer.get_output("o").unwrap());
+        let inner = top.module("inner", "Inner");
+        let inner_i = inner.input("i", 8);
+        inner_i.drive(i);
+        top.output("o1", inner.output("o", !inner_i));
+
+        // Panic
+        generate(top, std::io::sink()).unwrap();
+    }
+
+    #[test]
+    fn instance_shared_definition() {
+        let c = Context::new();
+
+        let def = c.module("def", "Def");
+        let i = def.input("i", 8);
+        let inner = def.module("inner", "Inner");
+        let inner_i = inner.input("i", 8);
+        let inner_o = inner.output("o", !inner_i);
+        inner_i.drive(i);
+        def.output("o", inner_o);
+
+        let top = c.module("top", "Top");
+        let mut value: &dyn Signal = top.input("i", 8);
+        for index in 0..4 {
+            let instance = top.instance(format!("def{}", index), def);
+            instance.get_input("i").unwrap().drive(value);
+            value = instance.get_output("o").unwrap();
+        }
+        top.output("o", value);
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert_eq!(code.matches("module Def(").count(), 1);
+        assert_eq!(code.matches("module Inner(").count(), 1);
+        for index in 0..4 {
+            assert!(code.contains(&format!("Def def{}(", index)));
+        }
+    }
+
+    #[test]
+    fn generator_parameter_comments() {
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        struct Width(u32);
+
+        impl ModuleParams for Width {
+            fn params(&self) -> Vec<(&'static str, String)> {
+                vec![("W", self.0.to_string())]
+            }
+        }
+
+        let c = Context::new();
+
+        let inverter = ModuleGenerator::new(&c, "Inverter", |m, p: &Width| {
+            m.output("o", !m.input("i", p.0));
+        });
+
+        let top = c.module("top", "Top");
+        let a = inverter.instance(top, "a", Width(8));
+        let b = inverter.instance(top, "b", Width(8));
+        a.get_input("i").unwrap().drive(top.input("i", 8));
+        b.get_input("i").unwrap().drive(a.get_output("o").unwrap());
+        top.output("o", b.get_output("o").unwrap());
+
+        let mut code = Vec::new();
+        generate(top, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert_eq!(code.matches("module Inverter_W8(").count(), 1);
+        assert!(code.contains("// Parameters:\n//   W = 8\nmodule Inverter_W8("));
+        assert!(!code.contains("// Parameters:\nmodule Top("));
+    }
+
+    #[test]
+    fn generator_parameter_comments_escaped() {
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        struct Label(&'static str);
+
+        impl ModuleParams for Label {
+            fn params(&self) -> Vec<(&'static str, String)> {
+                vec![("L", self.0.to_string())]
+            }
+        }
+
+        let c = Context::new();
+
+        let generator = ModuleGenerator::new(&c, "Labelled", |m, _: &Label| {
+            m.output("o", m.input("i", 1));
+        });
+
+        let m = generator.definition(Label("a\nmodule Evil();\r\\"));
+
+        let mut code = Vec::new();
+        generate(m, &mut code).unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert!(code.contains(
+            "// Parameters:\n//   L = a\\nmodule Evil();\\r\\\\\nmodule Labelled_La_module_Evil_____("
+        ));
+        assert!(!code.contains("\nmodule Evil();"));
+    }
 }
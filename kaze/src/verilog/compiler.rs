@@ -1,12 +1,12 @@
 use super::ir::*;
 use super::module_decls::*;
 
-use crate::graph;
+use crate::graph::internal_signal;
 
 use std::collections::HashMap;
 
 pub struct Compiler<'graph> {
-    signal_exprs: HashMap<&'graph graph::Signal<'graph>, Expr>,
+    signal_exprs: HashMap<&'graph internal_signal::InternalSignal<'graph>, Expr>,
 }
 
 impl<'graph> Compiler<'graph> {
@@ -18,13 +18,13 @@ impl<'graph> Compiler<'graph> {
 
     pub fn compile_signal(
         &mut self,
-        signal: &'graph graph::Signal<'graph>,
+        signal: &'graph internal_signal::InternalSignal<'graph>,
         module_decls: &ModuleDecls<'graph>,
         a: &mut AssignmentContext,
     ) -> Expr {
         enum Frame<'graph> {
-            Enter(&'graph graph::Signal<'graph>),
-            Leave(&'graph graph::Signal<'graph>),
+            Enter(&'graph internal_signal::InternalSignal<'graph>),
+            Leave(&'graph internal_signal::InternalSignal<'graph>),
         }
 
         let mut frames = Vec::new();
@@ -41,81 +41,140 @@ impl<'graph> Compiler<'graph> {
                     }
 
                     match signal.data {
-                        graph::SignalData::Lit {
+                        internal_signal::SignalData::Lit {
                             ref value,
                             bit_width,
                         } => Some(Expr::from_constant(value, bit_width)),
 
-                        graph::SignalData::Input { ref name, .. } => {
-                            Some(Expr::Ref { name: name.clone() })
+                        internal_signal::SignalData::Input { data } => {
+                            let expr = Expr::Ref {
+                                name: data.name.clone(),
+                            };
+                            // Signed nets are read through unsigned temporaries so that they don't make the expressions they're used in signed
+                            Some(if data.signed {
+                                a.gen_temp(expr, data.bit_width)
+                            } else {
+                                expr
+                            })
+                        }
+                        internal_signal::SignalData::Output { data } => {
+                            let instance_decls = &module_decls.instances[&data.module];
+                            Some(Expr::Ref {
+                                name: instance_decls.output_names[&data.name].clone(),
+                            })
                         }
 
-                        graph::SignalData::Reg { .. } => Some(Expr::Ref {
-                            name: module_decls.regs[&signal].value_name.clone(),
-                        }),
+                        internal_signal::SignalData::Reg { data } => {
+                            let expr = Expr::Ref {
+                                name: module_decls.regs[&signal].value_name.clone(),
+                            };
+                            // Signed nets are read through unsigned temporaries so that they don't make the expressions they're used in signed
+                            Some(if data.signed {
+                                a.gen_temp(expr, data.bit_width)
+                            } else {
+                                expr
+                            })
+                        }
 
-                        graph::SignalData::UnOp { source, .. } => {
+                        internal_signal::SignalData::UnOp { source, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(source));
                             None
                         }
-                        graph::SignalData::SimpleBinOp { lhs, rhs, .. } => {
+                        internal_signal::SignalData::ReduceOp { source, .. } => {
+                            frames.push(Frame::Leave(signal));
+                            frames.push(Frame::Enter(source));
+                            None
+                        }
+                        internal_signal::SignalData::CountOp { source, .. } => {
+                            frames.push(Frame::Leave(signal));
+                            frames.push(Frame::Enter(source));
+                            None
+                        }
+                        internal_signal::SignalData::OneHot { source, .. } => {
+                            frames.push(Frame::Leave(signal));
+                            frames.push(Frame::Enter(source));
+                            None
+                        }
+                        internal_signal::SignalData::PriorityEncode { source, .. } => {
+                            frames.push(Frame::Leave(signal));
+                            frames.push(Frame::Enter(source));
+                            None
+                        }
+                        internal_signal::SignalData::SimpleBinOp { lhs, rhs, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(lhs));
                             frames.push(Frame::Enter(rhs));
                             None
                         }
-                        graph::SignalData::AdditiveBinOp { lhs, rhs, .. } => {
+                        internal_signal::SignalData::AdditiveBinOp { lhs, rhs, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(lhs));
                             frames.push(Frame::Enter(rhs));
                             None
                         }
-                        graph::SignalData::ComparisonBinOp { lhs, rhs, .. } => {
+                        internal_signal::SignalData::ComparisonBinOp { lhs, rhs, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(lhs));
                             frames.push(Frame::Enter(rhs));
                             None
                         }
-                        graph::SignalData::ShiftBinOp { lhs, rhs, .. } => {
+                        internal_signal::SignalData::ShiftBinOp { lhs, rhs, .. } => {
+                            frames.push(Frame::Leave(signal));
+                            frames.push(Frame::Enter(lhs));
+                            frames.push(Frame::Enter(rhs));
+                            None
+                        }
+                        internal_signal::SignalData::RotateBinOp { lhs, rhs, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(lhs));
                             frames.push(Frame::Enter(rhs));
                             None
                         }
 
-                        graph::SignalData::Mul { lhs, rhs, .. } => {
+                        internal_signal::SignalData::Mul { lhs, rhs, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(lhs));
                             frames.push(Frame::Enter(rhs));
                             None
                         }
-                        graph::SignalData::MulSigned { lhs, rhs, .. } => {
+                        internal_signal::SignalData::MulSigned { lhs, rhs, .. } => {
+                            frames.push(Frame::Leave(signal));
+                            frames.push(Frame::Enter(lhs));
+                            frames.push(Frame::Enter(rhs));
+                            None
+                        }
+                        internal_signal::SignalData::DivisionBinOp { lhs, rhs, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(lhs));
                             frames.push(Frame::Enter(rhs));
                             None
                         }
 
-                        graph::SignalData::Bits { source, .. } => {
+                        internal_signal::SignalData::Bits { source, .. } => {
+                            frames.push(Frame::Leave(signal));
+                            frames.push(Frame::Enter(source));
+                            None
+                        }
+                        internal_signal::SignalData::Extend { source, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(source));
                             None
                         }
 
-                        graph::SignalData::Repeat { source, .. } => {
+                        internal_signal::SignalData::Repeat { source, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(source));
                             None
                         }
-                        graph::SignalData::Concat { lhs, rhs, .. } => {
+                        internal_signal::SignalData::Concat { lhs, rhs, .. } => {
                             frames.push(Frame::Leave(signal));
                             frames.push(Frame::Enter(lhs));
                             frames.push(Frame::Enter(rhs));
                             None
                         }
 
-                        graph::SignalData::Mux {
+                        internal_signal::SignalData::Mux {
                             cond,
                             when_true,
                             when_false,
@@ -127,17 +186,20 @@ impl<'graph> Compiler<'graph> {
                             frames.push(Frame::Enter(when_false));
                             None
                         }
-
-                        graph::SignalData::InstanceOutput {
-                            instance, ref name, ..
+                        internal_signal::SignalData::Index {
+                            ref elements,
+                            index,
+                            ..
                         } => {
-                            let instance_decls = &module_decls.instances[&instance];
-                            Some(Expr::Ref {
-                                name: instance_decls.output_names[name].clone(),
-                            })
+                            frames.push(Frame::Leave(signal));
+                            frames.push(Frame::Enter(index));
+                            for &element in elements.iter() {
+                                frames.push(Frame::Enter(element));
+                            }
+                            None
                         }
 
-                        graph::SignalData::MemReadPortOutput {
+                        internal_signal::SignalData::MemReadPortOutput {
                             mem,
                             address,
                             enable,
@@ -152,25 +214,113 @@ impl<'graph> Compiler<'graph> {
                 }
                 Frame::Leave(signal) => {
                     match signal.data {
-                        graph::SignalData::Lit { .. } => unreachable!(),
+                        internal_signal::SignalData::Lit { .. } => unreachable!(),
 
-                        graph::SignalData::Input { .. } => unreachable!(),
+                        internal_signal::SignalData::Input { .. } => unreachable!(),
+                        internal_signal::SignalData::Output { .. } => unreachable!(),
 
-                        graph::SignalData::Reg { .. } => unreachable!(),
+                        internal_signal::SignalData::Reg { .. } => unreachable!(),
 
-                        graph::SignalData::UnOp { op, bit_width, .. } => {
+                        internal_signal::SignalData::UnOp { op, bit_width, .. } => {
                             let source = results.pop().unwrap();
                             Some(a.gen_temp(
                                 Expr::UnOp {
                                     source: Box::new(source),
                                     op: match op {
-                                        graph::UnOp::Not => UnOp::Not,
+                                        internal_signal::UnOp::Not => UnOp::Not,
                                     },
                                 },
                                 bit_width,
                             ))
                         }
-                        graph::SignalData::SimpleBinOp { op, bit_width, .. } => {
+                        internal_signal::SignalData::ReduceOp { op, .. } => {
+                            let source = results.pop().unwrap();
+                            Some(a.gen_temp(
+                                Expr::UnOp {
+                                    source: Box::new(source),
+                                    op: match op {
+                                        internal_signal::ReduceOp::And => UnOp::ReduceAnd,
+                                        internal_signal::ReduceOp::Or => UnOp::ReduceOr,
+                                        internal_signal::ReduceOp::Xor => UnOp::ReduceXor,
+                                    },
+                                },
+                                1,
+                            ))
+                        }
+                        internal_signal::SignalData::CountOp {
+                            source,
+                            op,
+                            bit_width,
+                        } => {
+                            let source_bit_width = source.bit_width();
+                            let source = results.pop().unwrap();
+                            // Verilog doesn't allow indexing scalars, but a single bit is its own count of ones, and its complement is its own count of zeros
+                            Some(if source_bit_width == 1 {
+                                match op {
+                                    internal_signal::CountOp::CountOnes => source,
+                                    internal_signal::CountOp::LeadingZeros
+                                    | internal_signal::CountOp::TrailingZeros => a.gen_temp(
+                                        Expr::UnOp {
+                                            source: Box::new(source),
+                                            op: UnOp::Not,
+                                        },
+                                        bit_width,
+                                    ),
+                                }
+                            } else {
+                                gen_count(&source, op, 0, source_bit_width, bit_width, a)
+                            })
+                        }
+                        internal_signal::SignalData::OneHot { bit_width, .. } => {
+                            let source = results.pop().unwrap();
+                            Some(a.gen_temp(
+                                Expr::BinOp {
+                                    lhs: Box::new(Expr::Constant {
+                                        bit_width,
+                                        value: "1".into(),
+                                    }),
+                                    rhs: Box::new(source),
+                                    op: BinOp::Shl,
+                                },
+                                bit_width,
+                            ))
+                        }
+                        internal_signal::SignalData::PriorityEncode { source, bit_width } => {
+                            let source_bit_width = source.bit_width();
+                            let source = results.pop().unwrap();
+                            let zero = Expr::Constant {
+                                bit_width,
+                                value: "0".into(),
+                            };
+                            Some(if source_bit_width == 1 {
+                                zero
+                            } else {
+                                let any = a.gen_temp(
+                                    Expr::UnOp {
+                                        source: Box::new(source.clone()),
+                                        op: UnOp::ReduceOr,
+                                    },
+                                    1,
+                                );
+                                let count = gen_count(
+                                    &source,
+                                    internal_signal::CountOp::TrailingZeros,
+                                    0,
+                                    source_bit_width,
+                                    bit_width,
+                                    a,
+                                );
+                                a.gen_temp(
+                                    Expr::Ternary {
+                                        cond: Box::new(any),
+                                        when_true: Box::new(count),
+                                        when_false: Box::new(zero),
+                                    },
+                                    bit_width,
+                                )
+                            })
+                        }
+                        internal_signal::SignalData::SimpleBinOp { op, bit_width, .. } => {
                             let lhs = results.pop().unwrap();
                             let rhs = results.pop().unwrap();
                             Some(a.gen_temp(
@@ -178,15 +328,15 @@ impl<'graph> Compiler<'graph> {
                                     lhs: Box::new(lhs),
                                     rhs: Box::new(rhs),
                                     op: match op {
-                                        graph::SimpleBinOp::BitAnd => BinOp::BitAnd,
-                                        graph::SimpleBinOp::BitOr => BinOp::BitOr,
-                                        graph::SimpleBinOp::BitXor => BinOp::BitXor,
+                                        internal_signal::SimpleBinOp::BitAnd => BinOp::BitAnd,
+                                        internal_signal::SimpleBinOp::BitOr => BinOp::BitOr,
+                                        internal_signal::SimpleBinOp::BitXor => BinOp::BitXor,
                                     },
                                 },
                                 bit_width,
                             ))
                         }
-                        graph::SignalData::AdditiveBinOp { op, bit_width, .. } => {
+                        internal_signal::SignalData::AdditiveBinOp { op, bit_width, .. } => {
                             let lhs = results.pop().unwrap();
                             let rhs = results.pop().unwrap();
                             Some(a.gen_temp(
@@ -194,22 +344,22 @@ impl<'graph> Compiler<'graph> {
                                     lhs: Box::new(lhs),
                                     rhs: Box::new(rhs),
                                     op: match op {
-                                        graph::AdditiveBinOp::Add => BinOp::Add,
-                                        graph::AdditiveBinOp::Sub => BinOp::Sub,
+                                        internal_signal::AdditiveBinOp::Add => BinOp::Add,
+                                        internal_signal::AdditiveBinOp::Sub => BinOp::Sub,
                                     },
                                 },
                                 bit_width,
                             ))
                         }
-                        graph::SignalData::ComparisonBinOp { op, .. } => {
+                        internal_signal::SignalData::ComparisonBinOp { op, .. } => {
                             let bit_width = signal.bit_width();
                             let mut lhs = results.pop().unwrap();
                             let mut rhs = results.pop().unwrap();
                             match op {
-                                graph::ComparisonBinOp::GreaterThanEqualSigned
-                                | graph::ComparisonBinOp::GreaterThanSigned
-                                | graph::ComparisonBinOp::LessThanEqualSigned
-                                | graph::ComparisonBinOp::LessThanSigned => {
+                                internal_signal::ComparisonBinOp::GreaterThanEqualSigned
+                                | internal_signal::ComparisonBinOp::GreaterThanSigned
+                                | internal_signal::ComparisonBinOp::LessThanEqualSigned
+                                | internal_signal::ComparisonBinOp::LessThanSigned => {
                                     lhs = Expr::Signed {
                                         source: Box::new(lhs),
                                     };
@@ -224,20 +374,20 @@ impl<'graph> Compiler<'graph> {
                                     lhs: Box::new(lhs),
                                     rhs: Box::new(rhs),
                                     op: match op {
-                                        graph::ComparisonBinOp::Equal => BinOp::Equal,
-                                        graph::ComparisonBinOp::NotEqual => BinOp::NotEqual,
-                                        graph::ComparisonBinOp::LessThan
-                                        | graph::ComparisonBinOp::LessThanSigned => BinOp::LessThan,
-                                        graph::ComparisonBinOp::LessThanEqual
-                                        | graph::ComparisonBinOp::LessThanEqualSigned => {
+                                        internal_signal::ComparisonBinOp::Equal => BinOp::Equal,
+                                        internal_signal::ComparisonBinOp::NotEqual => BinOp::NotEqual,
+                                        internal_signal::ComparisonBinOp::LessThan
+                                        | internal_signal::ComparisonBinOp::LessThanSigned => BinOp::LessThan,
+                                        internal_signal::ComparisonBinOp::LessThanEqual
+                                        | internal_signal::ComparisonBinOp::LessThanEqualSigned => {
                                             BinOp::LessThanEqual
                                         }
-                                        graph::ComparisonBinOp::GreaterThan
-                                        | graph::ComparisonBinOp::GreaterThanSigned => {
+                                        internal_signal::ComparisonBinOp::GreaterThan
+                                        | internal_signal::ComparisonBinOp::GreaterThanSigned => {
                                             BinOp::GreaterThan
                                         }
-                                        graph::ComparisonBinOp::GreaterThanEqual
-                                        | graph::ComparisonBinOp::GreaterThanEqualSigned => {
+                                        internal_signal::ComparisonBinOp::GreaterThanEqual
+                                        | internal_signal::ComparisonBinOp::GreaterThanEqualSigned => {
                                             BinOp::GreaterThanEqual
                                         }
                                     },
@@ -245,24 +395,101 @@ impl<'graph> Compiler<'graph> {
                                 bit_width,
                             ))
                         }
-                        graph::SignalData::ShiftBinOp { op, bit_width, .. } => {
-                            let lhs = results.pop().unwrap();
+                        internal_signal::SignalData::ShiftBinOp { op, bit_width, .. } => {
+                            let mut lhs = results.pop().unwrap();
                             let rhs = results.pop().unwrap();
+                            // Temps are declared unsigned, so `>>>` only sign-fills if its lhs is explicitly signed
+                            if let internal_signal::ShiftBinOp::ShrArithmetic = op {
+                                lhs = Expr::Signed {
+                                    source: Box::new(lhs),
+                                };
+                            }
                             Some(a.gen_temp(
                                 Expr::BinOp {
                                     lhs: Box::new(lhs),
                                     rhs: Box::new(rhs),
                                     op: match op {
-                                        graph::ShiftBinOp::Shl => BinOp::Shl,
-                                        graph::ShiftBinOp::Shr => BinOp::Shr,
-                                        graph::ShiftBinOp::ShrArithmetic => BinOp::ShrArithmetic,
+                                        internal_signal::ShiftBinOp::Shl => BinOp::Shl,
+                                        internal_signal::ShiftBinOp::Shr => BinOp::Shr,
+                                        internal_signal::ShiftBinOp::ShrArithmetic => {
+                                            BinOp::ShrArithmetic
+                                        }
                                     },
                                 },
                                 bit_width,
                             ))
                         }
 
-                        graph::SignalData::Mul { bit_width, .. } => {
+                        internal_signal::SignalData::RotateBinOp {
+                            rhs, op, bit_width, ..
+                        } => {
+                            let rhs_bit_width = rhs.bit_width();
+                            let lhs = results.pop().unwrap();
+                            let rhs = results.pop().unwrap();
+                            // Rotating a single bit has no effect
+                            Some(if bit_width == 1 {
+                                lhs
+                            } else {
+                                // The rotation amount only needs to be reduced if it can reach the bit width
+                                let rhs = if rhs_bit_width < 32 && (1 << rhs_bit_width) <= bit_width
+                                {
+                                    rhs
+                                } else {
+                                    a.gen_temp(
+                                        Expr::BinOp {
+                                            lhs: Box::new(rhs),
+                                            rhs: Box::new(Expr::Constant {
+                                                bit_width: rhs_bit_width,
+                                                value: format!("{:x}", bit_width),
+                                            }),
+                                            op: BinOp::Rem,
+                                        },
+                                        rhs_bit_width,
+                                    )
+                                };
+                                let (op, complement_op) = match op {
+                                    internal_signal::RotateBinOp::Left => (BinOp::Shl, BinOp::Shr),
+                                    internal_signal::RotateBinOp::Right => (BinOp::Shr, BinOp::Shl),
+                                };
+                                let complement = a.gen_temp(
+                                    Expr::BinOp {
+                                        lhs: Box::new(Expr::Constant {
+                                            bit_width: 32,
+                                            value: format!("{:x}", bit_width),
+                                        }),
+                                        rhs: Box::new(rhs.clone()),
+                                        op: BinOp::Sub,
+                                    },
+                                    32,
+                                );
+                                let shifted = a.gen_temp(
+                                    Expr::BinOp {
+                                        lhs: Box::new(lhs.clone()),
+                                        rhs: Box::new(rhs),
+                                        op,
+                                    },
+                                    bit_width,
+                                );
+                                let wrapped = a.gen_temp(
+                                    Expr::BinOp {
+                                        lhs: Box::new(lhs),
+                                        rhs: Box::new(complement),
+                                        op: complement_op,
+                                    },
+                                    bit_width,
+                                );
+                                a.gen_temp(
+                                    Expr::BinOp {
+                                        lhs: Box::new(shifted),
+                                        rhs: Box::new(wrapped),
+                                        op: BinOp::BitOr,
+                                    },
+                                    bit_width,
+                                )
+                            })
+                        }
+
+                        internal_signal::SignalData::Mul { bit_width, .. } => {
                             let lhs = results.pop().unwrap();
                             let rhs = results.pop().unwrap();
                             Some(a.gen_temp(
@@ -274,7 +501,7 @@ impl<'graph> Compiler<'graph> {
                                 bit_width,
                             ))
                         }
-                        graph::SignalData::MulSigned { bit_width, .. } => {
+                        internal_signal::SignalData::MulSigned { bit_width, .. } => {
                             let lhs = results.pop().unwrap();
                             let rhs = results.pop().unwrap();
                             let lhs = Expr::Signed {
@@ -293,7 +520,65 @@ impl<'graph> Compiler<'graph> {
                             ))
                         }
 
-                        graph::SignalData::Bits {
+                        internal_signal::SignalData::DivisionBinOp { op, bit_width, .. } => {
+                            let lhs = results.pop().unwrap();
+                            let rhs = results.pop().unwrap();
+                            let (op_lhs, op_rhs) = match op {
+                                internal_signal::DivisionBinOp::DivSigned
+                                | internal_signal::DivisionBinOp::RemSigned => (
+                                    Expr::Signed {
+                                        source: Box::new(lhs.clone()),
+                                    },
+                                    Expr::Signed {
+                                        source: Box::new(rhs.clone()),
+                                    },
+                                ),
+                                _ => (lhs.clone(), rhs.clone()),
+                            };
+                            let expr = a.gen_temp(
+                                Expr::BinOp {
+                                    lhs: Box::new(op_lhs),
+                                    rhs: Box::new(op_rhs),
+                                    op: match op {
+                                        internal_signal::DivisionBinOp::Div
+                                        | internal_signal::DivisionBinOp::DivSigned => BinOp::Div,
+                                        internal_signal::DivisionBinOp::Rem
+                                        | internal_signal::DivisionBinOp::RemSigned => BinOp::Rem,
+                                    },
+                                },
+                                bit_width,
+                            );
+                            // Division by zero is undefined in Verilog, so the result for a zero divisor is selected explicitly
+                            let when_rhs_zero = match op {
+                                internal_signal::DivisionBinOp::Div
+                                | internal_signal::DivisionBinOp::DivSigned => Expr::Repeat {
+                                    source: Box::new(Expr::Constant {
+                                        bit_width: 1,
+                                        value: "1".into(),
+                                    }),
+                                    count: bit_width,
+                                },
+                                internal_signal::DivisionBinOp::Rem
+                                | internal_signal::DivisionBinOp::RemSigned => lhs,
+                            };
+                            Some(a.gen_temp(
+                                Expr::Ternary {
+                                    cond: Box::new(Expr::BinOp {
+                                        lhs: Box::new(rhs),
+                                        rhs: Box::new(Expr::Constant {
+                                            bit_width,
+                                            value: "0".into(),
+                                        }),
+                                        op: BinOp::Equal,
+                                    }),
+                                    when_true: Box::new(when_rhs_zero),
+                                    when_false: Box::new(expr),
+                                },
+                                bit_width,
+                            ))
+                        }
+
+                        internal_signal::SignalData::Bits {
                             source,
                             range_high,
                             range_low,
@@ -315,8 +600,41 @@ impl<'graph> Compiler<'graph> {
                                 )
                             })
                         }
+                        internal_signal::SignalData::Extend {
+                            source,
+                            op,
+                            bit_width,
+                        } => {
+                            let source_bit_width = source.bit_width();
+                            let source = results.pop().unwrap();
+                            let fill = match op {
+                                internal_signal::ExtendOp::Zero => Expr::Constant {
+                                    bit_width: 1,
+                                    value: "0".into(),
+                                },
+                                // Verilog doesn't allow indexing scalars
+                                internal_signal::ExtendOp::Sign if source_bit_width == 1 => {
+                                    source.clone()
+                                }
+                                internal_signal::ExtendOp::Sign => Expr::Bits {
+                                    source: Box::new(source.clone()),
+                                    range_high: source_bit_width - 1,
+                                    range_low: source_bit_width - 1,
+                                },
+                            };
+                            Some(a.gen_temp(
+                                Expr::Concat {
+                                    lhs: Box::new(Expr::Repeat {
+                                        source: Box::new(fill),
+                                        count: bit_width - source_bit_width,
+                                    }),
+                                    rhs: Box::new(source),
+                                },
+                                bit_width,
+                            ))
+                        }
 
-                        graph::SignalData::Repeat {
+                        internal_signal::SignalData::Repeat {
                             count, bit_width, ..
                         } => {
                             let source = results.pop().unwrap();
@@ -328,7 +646,7 @@ impl<'graph> Compiler<'graph> {
                                 bit_width,
                             ))
                         }
-                        graph::SignalData::Concat { bit_width, .. } => {
+                        internal_signal::SignalData::Concat { bit_width, .. } => {
                             let lhs = results.pop().unwrap();
                             let rhs = results.pop().unwrap();
                             Some(a.gen_temp(
@@ -340,7 +658,7 @@ impl<'graph> Compiler<'graph> {
                             ))
                         }
 
-                        graph::SignalData::Mux { bit_width, .. } => {
+                        internal_signal::SignalData::Mux { bit_width, .. } => {
                             let cond = results.pop().unwrap();
                             let when_true = results.pop().unwrap();
                             let when_false = results.pop().unwrap();
@@ -353,10 +671,20 @@ impl<'graph> Compiler<'graph> {
                                 bit_width,
                             ))
                         }
+                        internal_signal::SignalData::Index {
+                            ref elements,
+                            index,
+                            bit_width,
+                        } => {
+                            let index_bit_width = index.bit_width();
+                            let index = results.pop().unwrap();
+                            let elements = (0..elements.len())
+                                .map(|_| results.pop().unwrap())
+                                .collect();
+                            Some(gen_index(&index, index_bit_width, elements, bit_width, a))
+                        }
 
-                        graph::SignalData::InstanceOutput { .. } => unreachable!(),
-
-                        graph::SignalData::MemReadPortOutput { .. } => unreachable!(),
+                        internal_signal::SignalData::MemReadPortOutput { .. } => unreachable!(),
                     }
                 }
             } {
@@ -368,3 +696,143 @@ impl<'graph> Compiler<'graph> {
         results.pop().unwrap()
     }
 }
+
+// Builds a balanced tree of multiplexers selecting one of `elements` with the low bits of `index`, where indices past the last element select the last element
+fn gen_index(
+    index: &Expr,
+    index_bit_width: u32,
+    mut elements: Vec<Expr>,
+    bit_width: u32,
+    a: &mut AssignmentContext,
+) -> Expr {
+    let element_count = elements.len();
+    let last = elements[element_count - 1].clone();
+    let select_bit_width = usize::BITS - (element_count - 1).leading_zeros();
+    for bit in 0..select_bit_width {
+        // Verilog doesn't allow indexing scalars
+        let cond = if index_bit_width == 1 {
+            index.clone()
+        } else {
+            Expr::Bits {
+                source: Box::new(index.clone()),
+                range_high: bit,
+                range_low: bit,
+            }
+        };
+        // An unpaired element can only be selected by indices up to the last element, as larger indices are handled below
+        elements = elements
+            .chunks(2)
+            .map(|pair| match pair {
+                [when_false, when_true] => a.gen_temp(
+                    Expr::Ternary {
+                        cond: Box::new(cond.clone()),
+                        when_true: Box::new(when_true.clone()),
+                        when_false: Box::new(when_false.clone()),
+                    },
+                    bit_width,
+                ),
+                _ => pair[0].clone(),
+            })
+            .collect();
+    }
+    let selected = elements.pop().unwrap();
+    if select_bit_width == index_bit_width && element_count == 1 << select_bit_width {
+        return selected;
+    }
+    a.gen_temp(
+        Expr::Ternary {
+            cond: Box::new(Expr::BinOp {
+                lhs: Box::new(index.clone()),
+                rhs: Box::new(Expr::Constant {
+                    bit_width: index_bit_width,
+                    value: format!("{:x}", element_count - 1),
+                }),
+                op: BinOp::GreaterThan,
+            }),
+            when_true: Box::new(last),
+            when_false: Box::new(selected),
+        },
+        bit_width,
+    )
+}
+
+// Builds a balanced tree of temps counting bits in the range `[range_low, range_high)` of `source`, so that wide signals don't produce deeply-nested expressions
+fn gen_count(
+    source: &Expr,
+    op: internal_signal::CountOp,
+    range_low: u32,
+    range_high: u32,
+    bit_width: u32,
+    a: &mut AssignmentContext,
+) -> Expr {
+    let bits = |range_high, range_low| Expr::Bits {
+        source: Box::new(source.clone()),
+        range_high,
+        range_low,
+    };
+    let constant = |value: u32| Expr::Constant {
+        bit_width,
+        value: format!("{:x}", value),
+    };
+
+    if range_high - range_low == 1 {
+        return match op {
+            internal_signal::CountOp::CountOnes => bits(range_low, range_low),
+            internal_signal::CountOp::LeadingZeros | internal_signal::CountOp::TrailingZeros => a
+                .gen_temp(
+                    Expr::Ternary {
+                        cond: Box::new(bits(range_low, range_low)),
+                        when_true: Box::new(constant(0)),
+                        when_false: Box::new(constant(1)),
+                    },
+                    bit_width,
+                ),
+        };
+    }
+
+    let range_mid = range_low + (range_high - range_low) / 2;
+    let low = gen_count(source, op, range_low, range_mid, bit_width, a);
+    let high = gen_count(source, op, range_mid, range_high, bit_width, a);
+    // Zeros are only counted into the far half if the near half has no high bits
+    let (near, far, near_range_high, near_range_low) = match op {
+        internal_signal::CountOp::CountOnes => {
+            return a.gen_temp(
+                Expr::BinOp {
+                    lhs: Box::new(low),
+                    rhs: Box::new(high),
+                    op: BinOp::Add,
+                },
+                bit_width,
+            );
+        }
+        internal_signal::CountOp::LeadingZeros => (high, low, range_high - 1, range_mid),
+        internal_signal::CountOp::TrailingZeros => (low, high, range_mid - 1, range_low),
+    };
+    let near_any = if near_range_high == near_range_low {
+        bits(near_range_high, near_range_low)
+    } else {
+        a.gen_temp(
+            Expr::UnOp {
+                source: Box::new(bits(near_range_high, near_range_low)),
+                op: UnOp::ReduceOr,
+            },
+            1,
+        )
+    };
+    let far = a.gen_temp(
+        Expr::BinOp {
+            lhs: Box::new(constant(near_range_high - near_range_low + 1)),
+            rhs: Box::new(far),
+            op: BinOp::Add,
+        },
+        bit_width,
+    );
+    a.gen_temp(
+        Expr::Ternary {
+            cond: Box::new(near_any),
+            when_true: Box::new(near),
+            when_false: Box::new(far),
+        },
+        bit_width,
+    )
+}
@@ -7,6 +7,7 @@ pub struct NodeDecl {
     pub net_type: NetType,
     pub name: String,
     pub bit_width: u32,
+    pub signed: bool,
 }
 
 impl NodeDecl {
@@ -14,6 +15,9 @@ impl NodeDecl {
         w.append_indent()?;
         self.net_type.write(w)?;
         w.append(" ")?;
+        if self.signed {
+            w.append("signed ")?;
+        }
         if self.bit_width > 1 {
             w.append(&format!("[{}:{}] ", self.bit_width - 1, 0))?;
         }
@@ -58,6 +62,7 @@ impl AssignmentContext {
             net_type: NetType::Wire,
             name: name.clone(),
             bit_width,
+            signed: false,
         });
 
         self.assignments.push(Assignment {
@@ -128,7 +133,7 @@ pub enum Expr {
     },
     Constant {
         bit_width: u32,
-        value: u128,
+        value: String,
     },
     Ref {
         name: String,
@@ -155,7 +160,7 @@ impl Expr {
     pub fn from_constant(value: &graph::Constant, bit_width: u32) -> Expr {
         Expr::Constant {
             bit_width,
-            value: value.numeric_value(),
+            value: value.hex_string(),
         }
     }
 
@@ -181,6 +186,8 @@ impl Expr {
                         BinOp::ShrArithmetic => ">>>",
                         BinOp::Sub => "-",
                         BinOp::Mul => "*",
+                        BinOp::Div => "/",
+                        BinOp::Rem => "%",
                     }
                 ))?;
                 rhs.write(w)?;
@@ -205,7 +212,7 @@ impl Expr {
                 w.append("}")?;
             }
             Expr::Constant { bit_width, value } => {
-                w.append(&format!("{}'h{:x}", bit_width, value))?;
+                w.append(&format!("{}'h{}", bit_width, value))?;
             }
             Expr::Ref { name } => {
                 w.append(name)?;
@@ -234,6 +241,9 @@ impl Expr {
             Expr::UnOp { source, op } => {
                 w.append(match op {
                     UnOp::Not => "~",
+                    UnOp::ReduceAnd => "&",
+                    UnOp::ReduceOr => "|",
+                    UnOp::ReduceXor => "^",
                 })?;
                 source.write(w)?;
             }
@@ -260,9 +270,14 @@ pub enum BinOp {
     ShrArithmetic,
     Sub,
     Mul,
+    Div,
+    Rem,
 }
 
 #[derive(Clone)]
 pub enum UnOp {
     Not,
+    ReduceAnd,
+    ReduceOr,
+    ReduceXor,
 }
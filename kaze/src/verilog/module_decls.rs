@@ -1,14 +1,21 @@
 use crate::graph;
+use crate::graph::internal_signal;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub struct InstanceDecls {
-    pub input_names: HashMap<String, String>,
-    pub output_names: HashMap<String, String>,
+    pub input_names: BTreeMap<String, String>,
+    pub output_names: BTreeMap<String, String>,
 }
 
 pub struct MemDecls<'a> {
-    pub read_signal_names: HashMap<(&'a graph::Signal<'a>, &'a graph::Signal<'a>), ReadSignalNames>,
+    pub read_signal_names: HashMap<
+        (
+            &'a internal_signal::InternalSignal<'a>,
+            &'a internal_signal::InternalSignal<'a>,
+        ),
+        ReadSignalNames,
+    >,
     pub write_address_name: String,
     pub write_value_name: String,
     pub write_enable_name: String,
@@ -27,7 +34,7 @@ pub struct RegisterDecls<'a> {
 }
 
 pub struct ModuleDecls<'a> {
-    pub instances: HashMap<&'a graph::Instance<'a>, InstanceDecls>,
+    pub instances: HashMap<&'a graph::Module<'a>, InstanceDecls>,
     pub mems: HashMap<&'a graph::Mem<'a>, MemDecls<'a>>,
-    pub regs: HashMap<&'a graph::Signal<'a>, RegisterDecls<'a>>,
+    pub regs: HashMap<&'a internal_signal::InternalSignal<'a>, RegisterDecls<'a>>,
 }
@@ -42,6 +42,107 @@ fn main() -> Result<()> {
         sim::GenerationOptions::default(),
         &mut file,
     )?;
+    sim::generate(
+        div_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        div_signed_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        reduce_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        dyn_select_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        extend_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        rotate_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        count_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        saturating_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        signed_test_module(&p),
+        sim::GenerationOptions {
+            tracing: true,
+            ..sim::GenerationOptions::default()
+        },
+        &mut file,
+    )?;
+    sim::generate(
+        lit_operand_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        switch_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        when_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        bundle_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        signal_vec_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        stream_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        stream_forward_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        instance_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        generator_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        instance_clock_reset_test_module(&p),
+        sim::GenerationOptions {
+            tracing: true,
+            ..sim::GenerationOptions::default()
+        },
+        &mut file,
+    )?;
     sim::generate(
         shl_test_module(&p),
         sim::GenerationOptions::default(),
@@ -249,11 +350,34 @@ fn main() -> Result<()> {
         sim::GenerationOptions::default(),
         &mut file,
     )?;
+    sim::generate(
+        clock_domain_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        reset_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        wide_test_module(&p),
+        sim::GenerationOptions::default(),
+        &mut file,
+    )?;
+    sim::generate(
+        wide_trace_test_module(&p),
+        sim::GenerationOptions {
+            tracing: true,
+            ..sim::GenerationOptions::default()
+        },
+        &mut file,
+    )?;
 
     Ok(())
 }
 
-fn input_masking<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn input_masking<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("input_masking", "InputMasking");
 
     m.output("o", m.input("i", 27));
@@ -261,7 +385,7 @@ fn input_masking<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn widest_input<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn widest_input<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("widest_input", "WidestInput");
 
     m.output("o", m.input("i", 128));
@@ -269,7 +393,7 @@ fn widest_input<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn add_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn add_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("add_test_module", "AddTestModule");
 
     let i1 = m.input("i1", 1);
@@ -299,7 +423,7 @@ fn add_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn sub_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn sub_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("sub_test_module", "SubTestModule");
 
     let i1 = m.input("i1", 1);
@@ -329,7 +453,7 @@ fn sub_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn mul_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn mul_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("mul_test_module", "MulTestModule");
 
     let i1 = m.input("i1", 1);
@@ -363,7 +487,7 @@ fn mul_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn mul_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn mul_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("mul_signed_test_module", "MulSignedTestModule");
 
     let i1 = m.input("i1", 1);
@@ -397,7 +521,688 @@ fn mul_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn shl_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn div_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("div_test_module", "DivTestModule");
+
+    let i1 = m.input("i1", 1);
+    let i2 = m.input("i2", 1);
+    m.output("o1", i1 / i2);
+    m.output("o2", i1 % i2);
+
+    let i3 = m.input("i3", 3);
+    let i4 = m.input("i4", 3);
+    m.output("o3", i3 / i4);
+    m.output("o4", i3 % i4);
+
+    let i5 = m.input("i5", 32);
+    let i6 = m.input("i6", 32);
+    m.output("o5", i5 / i6);
+    m.output("o6", i5 % i6);
+
+    let i7 = m.input("i7", 64);
+    let i8_ = m.input("i8", 64);
+    m.output("o7", i7 / i8_);
+    m.output("o8", i7 % i8_);
+
+    let i9 = m.input("i9", 128);
+    let i10 = m.input("i10", 128);
+    m.output("o9", i9 / i10);
+    m.output("o10", i9 % i10);
+
+    m
+}
+
+fn div_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("div_signed_test_module", "DivSignedTestModule");
+
+    let i1 = m.input("i1", 1);
+    let i2 = m.input("i2", 1);
+    m.output("o1", i1.div_signed(i2));
+    m.output("o2", i1.rem_signed(i2));
+
+    let i3 = m.input("i3", 3);
+    let i4 = m.input("i4", 3);
+    m.output("o3", i3.div_signed(i4));
+    m.output("o4", i3.rem_signed(i4));
+
+    let i5 = m.input("i5", 32);
+    let i6 = m.input("i6", 32);
+    m.output("o5", i5.div_signed(i6));
+    m.output("o6", i5.rem_signed(i6));
+
+    let i7 = m.input("i7", 64);
+    let i8_ = m.input("i8", 64);
+    m.output("o7", i7.div_signed(i8_));
+    m.output("o8", i7.rem_signed(i8_));
+
+    let i9 = m.input("i9", 128);
+    let i10 = m.input("i10", 128);
+    m.output("o9", i9.div_signed(i10));
+    m.output("o10", i9.rem_signed(i10));
+
+    m
+}
+
+fn reduce_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("reduce_test_module", "ReduceTestModule");
+
+    let i1 = m.input("i1", 1);
+    m.output("and1", i1.and_reduce());
+    m.output("or1", i1.or_reduce());
+    m.output("xor1", i1.xor_reduce());
+
+    let i2 = m.input("i2", 3);
+    m.output("and2", i2.and_reduce());
+    m.output("or2", i2.or_reduce());
+    m.output("xor2", i2.xor_reduce());
+
+    let i3 = m.input("i3", 32);
+    m.output("and3", i3.and_reduce());
+    m.output("or3", i3.or_reduce());
+    m.output("xor3", i3.xor_reduce());
+
+    let i4 = m.input("i4", 64);
+    m.output("and4", i4.and_reduce());
+    m.output("or4", i4.or_reduce());
+    m.output("xor4", i4.xor_reduce());
+
+    let i5 = m.input("i5", 128);
+    m.output("and5", i5.and_reduce());
+    m.output("or5", i5.or_reduce());
+    m.output("xor5", i5.xor_reduce());
+
+    let i6 = m.input("i6", 200);
+    m.output("and6", i6.and_reduce());
+    m.output("or6", i6.or_reduce());
+    m.output("xor6", i6.xor_reduce());
+
+    m
+}
+
+fn dyn_select_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("dyn_select_test_module", "DynSelectTestModule");
+
+    let i1 = m.input("i1", 1);
+    let index1 = m.input("index1", 2);
+    m.output("bit1", i1.bit_dyn(index1));
+
+    let i2 = m.input("i2", 8);
+    let index2 = m.input("index2", 4);
+    m.output("bit2", i2.bit_dyn(index2));
+    m.output("bits2", i2.bits_dyn(index2, 3));
+    m.output("all_bits2", i2.bits_dyn(index2, 8));
+
+    let i3 = m.input("i3", 100);
+    let index3 = m.input("index3", 7);
+    m.output("bits3", i3.bits_dyn(index3, 40));
+
+    let i4 = m.input("i4", 200);
+    let index4 = m.input("index4", 8);
+    m.output("bit4", i4.bit_dyn(index4));
+    m.output("bits4", i4.bits_dyn(index4, 64));
+    m.output("wide_bits4", i4.bits_dyn(index4, 150));
+
+    m
+}
+
+fn extend_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("extend_test_module", "ExtendTestModule");
+
+    let i1 = m.input("i1", 1);
+    m.output("zext1", i1.zext(8));
+    m.output("sext1", i1.sext(8));
+
+    let i2 = m.input("i2", 4);
+    m.output("zext2", i2.zext(32));
+    m.output("sext2", i2.sext(32));
+    m.output("sext2_same", i2.sext(4));
+
+    let i3 = m.input("i3", 32);
+    m.output("zext3", i3.zext(33));
+    m.output("sext3", i3.sext(33));
+
+    let i4 = m.input("i4", 64);
+    m.output("sext4", i4.sext(128));
+
+    let i5 = m.input("i5", 100);
+    m.output("zext5", i5.zext(200));
+    m.output("sext5", i5.sext(200));
+
+    let i6 = m.input("i6", 150);
+    m.output("zext6", i6.zext(200));
+    m.output("sext6", i6.sext(200));
+
+    let i7 = m.input("i7", 8);
+    m.output("resize7_narrow", i7.resize(4));
+    m.output("resize7_same", i7.resize(8));
+    m.output("resize7_wide", i7.resize(12));
+
+    m
+}
+
+fn rotate_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("rotate_test_module", "RotateTestModule");
+
+    let i1 = m.input("i1", 1);
+    let amount1 = m.input("amount1", 2);
+    m.output("rotl1", i1.rotate_left(amount1));
+
+    let i2 = m.input("i2", 8);
+    let amount2 = m.input("amount2", 3);
+    m.output("rotl2", i2.rotate_left(amount2));
+    m.output("rotr2", i2.rotate_right(amount2));
+    let amount2_wide = m.input("amount2_wide", 8);
+    m.output("rotl2_wide", i2.rotate_left(amount2_wide));
+    let amount2_wider = m.input("amount2_wider", 200);
+    m.output("rotr2_wider", i2.rotate_right(amount2_wider));
+
+    let i3 = m.input("i3", 32);
+    let amount3 = m.input("amount3", 5);
+    m.output("rotl3", i3.rotate_left(amount3));
+    m.output("rotr3", i3.rotate_right(amount3));
+
+    let i4 = m.input("i4", 100);
+    let amount4 = m.input("amount4", 7);
+    m.output("rotl4", i4.rotate_left(amount4));
+    m.output("rotr4", i4.rotate_right(amount4));
+
+    let i5 = m.input("i5", 128);
+    let amount5 = m.input("amount5", 8);
+    m.output("rotl5", i5.rotate_left(amount5));
+
+    let i6 = m.input("i6", 200);
+    let amount6 = m.input("amount6", 8);
+    m.output("rotl6", i6.rotate_left(amount6));
+    m.output("rotr6", i6.rotate_right(amount6));
+
+    let i7 = m.input("i7", 256);
+    let amount7 = m.input("amount7", 100);
+    m.output("rotl7", i7.rotate_left(amount7));
+    m.output("rotr7", i7.rotate_right(amount7));
+
+    m
+}
+
+fn count_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("count_test_module", "CountTestModule");
+
+    let i1 = m.input("i1", 1);
+    m.output("leading_zeros1", i1.leading_zeros());
+    m.output("trailing_zeros1", i1.trailing_zeros());
+    m.output("count_ones1", i1.count_ones());
+    m.output("priority_encode1", i1.priority_encode());
+    m.output("one_hot1", i1.one_hot());
+
+    let i2 = m.input("i2", 8);
+    m.output("leading_zeros2", i2.leading_zeros());
+    m.output("trailing_zeros2", i2.trailing_zeros());
+    m.output("count_ones2", i2.count_ones());
+    m.output("priority_encode2", i2.priority_encode());
+    m.output("one_hot2", i2.bits(2, 0).one_hot());
+
+    let i3 = m.input("i3", 32);
+    m.output("leading_zeros3", i3.leading_zeros());
+    m.output("trailing_zeros3", i3.trailing_zeros());
+    m.output("count_ones3", i3.count_ones());
+    m.output("priority_encode3", i3.priority_encode());
+    m.output("one_hot3", i3.bits(5, 0).one_hot());
+
+    let i4 = m.input("i4", 100);
+    m.output("leading_zeros4", i4.leading_zeros());
+    m.output("trailing_zeros4", i4.trailing_zeros());
+    m.output("count_ones4", i4.count_ones());
+    m.output("priority_encode4", i4.priority_encode());
+    m.output("one_hot4", i4.bits(6, 0).one_hot());
+
+    let i5 = m.input("i5", 200);
+    m.output("leading_zeros5", i5.leading_zeros());
+    m.output("trailing_zeros5", i5.trailing_zeros());
+    m.output("count_ones5", i5.count_ones());
+    m.output("priority_encode5", i5.priority_encode());
+    m.output("one_hot5", i5.bits(7, 0).one_hot());
+
+    m
+}
+
+fn saturating_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("saturating_test_module", "SaturatingTestModule");
+
+    for (i, &bit_width) in [1, 8, 32, 64, 128].iter().enumerate() {
+        let lhs = m.input(format!("lhs{}", i + 1), bit_width);
+        let rhs = m.input(format!("rhs{}", i + 1), bit_width);
+        m.output(format!("add_carry{}", i + 1), lhs.add_carry(rhs));
+        m.output(format!("sub_borrow{}", i + 1), lhs.sub_borrow(rhs));
+        m.output(format!("add_sat{}", i + 1), lhs.add_sat(rhs));
+        m.output(format!("add_sat_signed{}", i + 1), lhs.add_sat_signed(rhs));
+        m.output(format!("sub_sat{}", i + 1), lhs.sub_sat(rhs));
+        m.output(format!("sub_sat_signed{}", i + 1), lhs.sub_sat_signed(rhs));
+    }
+
+    m
+}
+
+fn signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("signed_test_module", "SignedTestModule");
+
+    let lhs = m.signed_input("lhs", 8).as_signed();
+    let rhs = m.input("rhs", 8);
+    let shift = m.input("shift", 3);
+
+    let prev_lhs = m.signed_reg("prev_lhs", 8);
+    prev_lhs.default_value(0u32);
+    prev_lhs.drive_next(lhs);
+    m.output("prev_lhs_out", prev_lhs);
+
+    m.output("lt", lhs.lt(rhs));
+    m.output("le", lhs.le(rhs));
+    m.output("gt", lhs.gt(rhs));
+    m.output("ge", lhs.ge(rhs));
+    m.output("lt_unsigned", lhs.as_unsigned().lt(rhs));
+    m.output("mul", lhs * rhs);
+    m.output("div", lhs / rhs);
+    m.output("rem", lhs % rhs);
+    m.output("shr", lhs >> shift);
+    m.output("shl", lhs << shift);
+    m.output("add", lhs + rhs);
+    m.output("sub", lhs - rhs);
+    m.output("inverted", !lhs);
+    m.output("mul_add", (lhs + rhs) * lhs);
+    m.output("as_unsigned", lhs.as_unsigned());
+
+    m
+}
+
+fn lit_operand_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("lit_operand_test_module", "LitOperandTestModule");
+
+    let i1 = m.input("i1", 8);
+    m.output("add", i1 + 1);
+    m.output("sub", i1 - 1u8);
+    m.output("bit_and", i1 & 0x0fu32);
+    m.output("shl", i1 << 2u64);
+    m.output("mul", i1 * 3u16);
+    m.output("eq", i1.eq(3));
+    m.output("eq_neg", i1.eq(-1));
+    m.output("lt", i1.lt(0x80u32));
+    m.output("lt_signed", i1.lt_signed(-3));
+    m.output("signed_add", i1.as_signed() + (-2));
+
+    let i2 = m.input("i2", 100);
+    m.output("wide_add", i2 + (-2));
+    m.output("wide_eq", i2.eq(-1));
+    m.output("wide_ge", i2.ge(0xffffffffffffffffu128));
+
+    m
+}
+
+fn switch_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("switch_test_module", "SwitchTestModule");
+
+    // Dense; built as a multiplexer tree
+    let sel1 = m.input("sel1", 3);
+    let a = m.input("a", 8);
+    let b = m.input("b", 8);
+    let (o1, o2) = switch_(sel1)
+        .case(0u32, (a, b))
+        .case(1u32, (b, a))
+        .case(2u32, (a + b, a - b))
+        .case(3u32, (a & b, a | b))
+        .case(5u32, (!a, !b))
+        .default((m.lit(0xffu32, 8), m.lit(0u32, 8)));
+    m.output("o1", o1);
+    m.output("o2", o2);
+    let o3 = switch_(sel1)
+        .case(7u32, a)
+        .case(6u32, b)
+        .case(5u32, a ^ b)
+        .case(4u32, m.lit(4u32, 8))
+        .case(3u32, m.lit(3u32, 8))
+        .case(2u32, m.lit(2u32, 8))
+        .case(1u32, m.lit(1u32, 8))
+        .case(0u32, m.lit(0u32, 8))
+        .end();
+    m.output("o3", o3);
+
+    // Sparse; built as a chain of comparisons
+    let sel2 = m.input("sel2", 8);
+    let o4 = switch_(sel2)
+        .case(0x10u32, a)
+        .case(0x20u32, b)
+        .case(0xffu32, a + b)
+        .default(m.lit(0x55u32, 8));
+    m.output("o4", o4);
+    let o5 = switch_(sel2).case(0x10u32, a).case(0x20u32, b).end();
+    m.output("o5", o5);
+
+    let sel3 = m.input("sel3", 100);
+    let o6 = switch_(sel3)
+        .case(0u32, a)
+        .case(1u128 << 99, b)
+        .case([0u64, 1], a ^ b)
+        .default(m.lit(0xaau32, 8));
+    m.output("o6", o6);
+
+    m
+}
+
+fn when_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("when_test_module", "WhenTestModule");
+
+    let start = m.input("start", 1);
+    let stop = m.input("stop", 1);
+    let load = m.input("load", 1);
+    let load_value = m.input("load_value", 8);
+
+    let busy = m.reg("busy_reg", 1);
+    busy.default_value(false);
+    let count = m.reg("count_reg", 8);
+    count.default_value(0u32);
+    when(start, || {
+        busy.assign(m.high());
+        count.assign(m.lit(0u32, 8));
+    })
+    .else_when(busy, || {
+        count.assign(count + 1);
+        when(stop, || {
+            busy.assign(m.low());
+        });
+    })
+    .otherwise(|| {
+        when(load, || {
+            count.assign(load_value);
+        });
+    });
+    m.output("busy", busy);
+    m.output("count", count);
+
+    let write_addr = m.input("write_addr", 2);
+    let write_value = m.input("write_value", 8);
+    let write_enable = m.input("write_enable", 1);
+    let clear = m.input("clear", 1);
+    let read_addr = m.input("read_addr", 2);
+
+    let mem = m.mem("mem", 2, 8);
+    mem.initial_contents(&[0u32, 0, 0, 0]);
+    when(write_enable, || {
+        mem.write(write_addr, write_value);
+    });
+    when(clear, || {
+        mem.write(write_addr, m.lit(0u32, 8));
+    });
+    m.output("read_data", mem.read_port(read_addr, m.high()));
+
+    m
+}
+
+bundle! {
+    struct BundleTestStage {
+        valid: 1,
+        pc: 16,
+        insn: 8,
+    }
+}
+
+fn bundle_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("bundle_test_module", "BundleTestModule");
+
+    let inner = m.module("inner", "BundleTestModuleInner");
+    let inner_stage_in = inner.bundle_input::<BundleTestStage>("stage_in");
+    let inner_stage_in_value = inner_stage_in.value();
+    let inner_stage_out = inner.bundle_output(
+        "stage_out",
+        &BundleTestStage {
+            valid: inner_stage_in_value.valid,
+            pc: inner_stage_in_value.pc + 1u32,
+            insn: !inner_stage_in_value.insn,
+        },
+    );
+
+    let stage_in = m.bundle_input::<BundleTestStage>("stage_in");
+    inner_stage_in.drive(stage_in.value());
+
+    let packed = inner_stage_out.pack();
+    m.output("packed", packed);
+    let stage_reg = packed.reg_next("stage_reg");
+    m.bundle_output("stage_out", &BundleTestStage::unpack(stage_reg));
+
+    m
+}
+
+fn signal_vec_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("signal_vec_test_module", "SignalVecTestModule");
+
+    let lanes = SignalVec::new(&[
+        m.input("lane0", 8),
+        m.input("lane1", 8),
+        m.input("lane2", 8),
+        m.input("lane3", 8),
+    ]);
+    m.output("o1", lanes.get(m.input("sel1", 2)));
+    m.output("o2", lanes.fold(m.lit(0u32, 8), |acc, lane| acc + lane));
+    m.output("o3", lanes.map(|lane| !lane).get(m.input("sel2", 2)));
+
+    // Indices past the last element select the last element
+    let short_lanes = SignalVec::new(&lanes.elements()[..3]);
+    m.output("o4", short_lanes.get(m.input("sel3", 2)));
+    m.output("o5", short_lanes.get(m.input("sel4", 1)));
+    m.output("o6", short_lanes.get(m.input("sel5", 130)));
+
+    // Register file
+    let regs = (0..4)
+        .map(|i| {
+            let reg = m.reg(format!("r{}", i), 8);
+            reg.default_value(0u32);
+            reg
+        })
+        .collect::<Vec<_>>();
+    let reg_values = regs
+        .iter()
+        .map(|&reg| reg as &dyn Signal)
+        .collect::<Vec<_>>();
+    let reg_file = SignalVec::new(&reg_values);
+    let write_enable = m.input("write_enable", 1);
+    let next = reg_file.set(m.input("write_index", 2), m.input("write_value", 8));
+    for (reg, &next) in regs.iter().zip(next.elements()) {
+        reg.drive_next(write_enable.mux(next, *reg));
+    }
+    m.output("read_value", reg_file.get(m.input("read_index", 2)));
+
+    m
+}
+
+bundle! {
+    struct StreamTestPacket {
+        data: 8,
+        last: 1,
+    }
+}
+
+fn stream_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("stream_test_module", "StreamTestModule");
+
+    // Emits an incrementing counter while enabled
+    let producer = m.module("producer", "StreamTestModuleProducer");
+    let out = producer.stream_producer::<StreamTestPacket>("out");
+    let enable = producer.input("enable", 1);
+    let count = producer.reg("count_reg", 8);
+    count.default_value(0u32);
+    let fire = enable & out.ready();
+    count.drive_next(fire.mux(count + 1u32, count));
+    out.drive(
+        enable,
+        &StreamTestPacket {
+            data: count,
+            last: count.eq(3u32),
+        },
+    );
+    let producer_fire = producer.output("fire", fire);
+    enable.drive(m.input("enable", 1));
+
+    // Sums packets until the last one while accepting
+    let consumer = m.module("consumer", "StreamTestModuleConsumer");
+    let input = consumer.stream_consumer::<StreamTestPacket>("in");
+    let accept = consumer.input("accept", 1);
+    let sum = consumer.reg("sum_reg", 8);
+    sum.default_value(0u32);
+    let done = consumer.reg("done_reg", 1);
+    done.default_value(false);
+    let fire = input.valid() & accept & !done;
+    sum.drive_next(fire.mux(sum + input.payload().data, sum));
+    done.drive_next(done | (fire & input.payload().last));
+    input.drive_ready(accept & !done);
+    let consumer_sum = consumer.output("sum", sum);
+    let consumer_done = consumer.output("done", done);
+    accept.drive(m.input("accept", 1));
+
+    connect(&out, &input);
+
+    m.output("fire", producer_fire);
+    m.output("sum", consumer_sum);
+    m.output("done", consumer_done);
+
+    m
+}
+
+fn stream_forward_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("stream_forward_test_module", "StreamForwardTestModule");
+    let input = m.stream_consumer::<StreamTestPacket>("in");
+    let out = m.stream_producer::<StreamTestPacket>("out");
+
+    // Increments each packet's data on its way through
+    let inner = m.module("inner", "StreamForwardTestModuleInner");
+    let inner_input = inner.stream_consumer::<StreamTestPacket>("in");
+    let inner_out = inner.stream_producer::<StreamTestPacket>("out");
+    inner_input.drive_ready(inner_out.ready());
+    inner_out.drive(
+        inner_input.valid(),
+        &StreamTestPacket {
+            data: inner_input.payload().data + 1u32,
+            last: inner_input.payload().last,
+        },
+    );
+
+    forward_consumer(&input, &inner_input);
+    forward_producer(&inner_out, &out);
+
+    m
+}
+
+fn instance_test_module<'a>(c: &'a Context<'a>) -> &'a Module<'a> {
+    // Accumulates twice its input, which it also outputs combinationally
+    let def = c.module(
+        "instance_test_module_accumulator",
+        "InstanceTestModuleAccumulator",
+    );
+    let i = def.input("i", 8);
+    let doubler = def.module("doubler", "InstanceTestModuleDoubler");
+    let doubler_i = doubler.input("i", 8);
+    let doubled = doubler.output("o", doubler_i + doubler_i);
+    doubler_i.drive(i);
+    let acc = def.reg("acc_reg", 8);
+    acc.default_value(0u32);
+    acc.drive_next(acc + doubled);
+    def.output("sum", acc);
+    def.output("doubled", doubled);
+
+    let m = c.module("instance_test_module", "InstanceTestModule");
+
+    let mut value: &dyn Signal = m.input("i", 8);
+    for name in ["a", "b", "c"] {
+        let instance = m.instance(name, def);
+        instance.get_input("i").unwrap().drive(value);
+        value = instance.get_output("doubled").unwrap();
+        m.output(format!("sum_{}", name), instance.get_output("sum").unwrap());
+    }
+    m.output("doubled", value);
+
+    m
+}
+
+fn instance_clock_reset_test_module<'a>(c: &'a Context<'a>) -> &'a Module<'a> {
+    // Counts edges of its own clock, and is held at 0 by an async reset
+    let def = c.module(
+        "instance_clock_reset_test_module_counter",
+        "InstanceClockResetTestModuleCounter",
+    );
+    let count_clk = def.clock("count_clk");
+    let arst_n = def.reset(
+        "arst_n",
+        def.input("arst_n", 1),
+        ResetKind::Async,
+        ResetPolarity::ActiveLow,
+    );
+    let incr = def.module("incr", "InstanceClockResetTestModuleIncr");
+    let incr_i = incr.input("i", 8);
+    let incr_o = incr.output("o", incr_i + incr.lit(1u32, 8));
+    let count = def.reg("count_reg", 8);
+    count.default_value(0u32);
+    count.clock(count_clk);
+    count.reset(arst_n);
+    incr_i.drive(count);
+    count.drive_next(incr_o);
+    def.output("count", count);
+
+    let m = c.module(
+        "instance_clock_reset_test_module",
+        "InstanceClockResetTestModule",
+    );
+
+    let arst_n = m.input("arst_n", 1);
+    for name in ["a", "b"] {
+        let clock = m.clock(format!("{}_clk", name));
+        let instance = m.instance(name, def);
+        instance.clocks()[0].drive(clock);
+        instance.get_input("arst_n").unwrap().drive(arst_n);
+        m.output(
+            format!("count_{}", name),
+            instance.get_output("count").unwrap(),
+        );
+    }
+
+    m
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct DelayParams {
+    width: u32,
+    depth: u32,
+}
+
+impl ModuleParams for DelayParams {
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![("W", self.width.to_string()), ("D", self.depth.to_string())]
+    }
+}
+
+fn generator_test_module<'a>(c: &'a Context<'a>) -> &'a Module<'a> {
+    let delay = ModuleGenerator::new(c, "GeneratorTestModuleDelay", |m, p: &DelayParams| {
+        let mut value: &dyn Signal = m.input("i", p.width);
+        for stage in 0..p.depth {
+            let r = m.reg(format!("stage{}", stage), p.width);
+            r.default_value(0u32);
+            r.drive_next(value);
+            value = r;
+        }
+        m.output("o", value);
+    });
+
+    let m = c.module("generator_test_module", "GeneratorTestModule");
+
+    let i = m.input("i", 8);
+    for (name, depth) in [("a", 1), ("b", 2), ("c", 2)] {
+        let instance = delay.instance(
+            m,
+            format!("delay_{}", name),
+            DelayParams { width: 8, depth },
+        );
+        instance.get_input("i").unwrap().drive(i);
+        m.output(name, instance.get_output("o").unwrap());
+    }
+
+    m
+}
+
+fn shl_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("shl_test_module", "ShlTestModule");
 
     let i1 = m.input("i1", 1);
@@ -439,7 +1244,7 @@ fn shl_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn shr_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn shr_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("shr_test_module", "ShrTestModule");
 
     let i1 = m.input("i1", 1);
@@ -481,7 +1286,7 @@ fn shr_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn shr_arithmetic_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn shr_arithmetic_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("shr_arithmetic_test_module", "ShrArithmeticTestModule");
 
     let i1 = m.input("i1", 1);
@@ -523,7 +1328,7 @@ fn shr_arithmetic_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn bit_and_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn bit_and_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("bit_and_test_module", "BitAndTestModule");
 
     let i1 = m.input("i1", 1);
@@ -533,7 +1338,7 @@ fn bit_and_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn bit_or_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn bit_or_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("bit_or_test_module", "BitOrTestModule");
 
     let i1 = m.input("i1", 1);
@@ -543,7 +1348,7 @@ fn bit_or_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn bit_xor_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn bit_xor_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("bit_xor_test_module", "BitXorTestModule");
 
     let i1 = m.input("i1", 1);
@@ -553,7 +1358,7 @@ fn bit_xor_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn not_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn not_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("not_test_module", "NotTestModule");
 
     let i = m.input("i", 4);
@@ -562,7 +1367,7 @@ fn not_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn reg_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn reg_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("reg_test_module", "RegTestModule");
 
     let r1 = m.reg("r1", 32);
@@ -577,7 +1382,7 @@ fn reg_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn simple_reg_delay<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn simple_reg_delay<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("simple_reg_delay", "SimpleRegDelay");
 
     let r1 = m.reg("r1", 100);
@@ -594,7 +1399,7 @@ fn simple_reg_delay<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn bit_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn bit_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("bit_test_module_0", "BitTestModule0");
 
     let i = m.input("i", 1);
@@ -603,7 +1408,7 @@ fn bit_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn bit_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn bit_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("bit_test_module_1", "BitTestModule1");
 
     let i = m.input("i", 4);
@@ -615,7 +1420,7 @@ fn bit_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn bits_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn bits_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("bits_test_module_0", "BitsTestModule0");
 
     let i = m.input("i", 4);
@@ -629,7 +1434,7 @@ fn bits_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn bits_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn bits_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("bits_test_module_1", "BitsTestModule1");
 
     let i = m.input("i", 128);
@@ -650,7 +1455,7 @@ fn bits_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn repeat_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn repeat_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("repeat_test_module", "RepeatTestModule");
 
     let i = m.input("i", 4);
@@ -668,7 +1473,7 @@ fn repeat_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn concat_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn concat_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("concat_test_module", "ConcatTestModule");
 
     let i1 = m.input("i1", 4);
@@ -686,7 +1491,7 @@ fn concat_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn eq_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn eq_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("eq_test_module", "EqTestModule");
 
     let i1 = m.input("i1", 4);
@@ -697,7 +1502,7 @@ fn eq_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn ne_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn ne_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("ne_test_module", "NeTestModule");
 
     let i1 = m.input("i1", 4);
@@ -708,7 +1513,7 @@ fn ne_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn lt_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn lt_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("lt_test_module", "LtTestModule");
 
     let i1 = m.input("i1", 4);
@@ -719,7 +1524,7 @@ fn lt_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn le_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn le_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("le_test_module", "LeTestModule");
 
     let i1 = m.input("i1", 4);
@@ -730,7 +1535,7 @@ fn le_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn gt_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn gt_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("gt_test_module", "GtTestModule");
 
     let i1 = m.input("i1", 4);
@@ -741,7 +1546,7 @@ fn gt_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn ge_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn ge_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("ge_test_module", "GeTestModule");
 
     let i1 = m.input("i1", 4);
@@ -752,7 +1557,7 @@ fn ge_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn lt_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn lt_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("lt_signed_test_module", "LtSignedTestModule");
 
     let i1 = m.input("i1", 4);
@@ -763,7 +1568,7 @@ fn lt_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn le_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn le_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("le_signed_test_module", "LeSignedTestModule");
 
     let i1 = m.input("i1", 4);
@@ -774,7 +1579,7 @@ fn le_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn gt_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn gt_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("gt_signed_test_module", "GtSignedTestModule");
 
     let i1 = m.input("i1", 4);
@@ -785,7 +1590,7 @@ fn gt_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn ge_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn ge_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("ge_signed_test_module", "GeSignedTestModule");
 
     let i1 = m.input("i1", 4);
@@ -796,7 +1601,8 @@ fn ge_signed_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn mux_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+#[allow(clippy::redundant_locals)]
+fn mux_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("mux_test_module", "MuxTestModule");
 
     let invert = m.input("invert", 1);
@@ -828,7 +1634,7 @@ fn mux_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn reg_next_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn reg_next_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("reg_next_test_module", "RegNextTestModule");
 
     let i = m.input("i", 1);
@@ -839,7 +1645,7 @@ fn reg_next_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn reg_next_with_default_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn reg_next_with_default_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module(
         "reg_next_with_default_test_module",
         "RegNextWithDefaultTestModule",
@@ -853,7 +1659,7 @@ fn reg_next_with_default_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Modul
     m
 }
 
-fn instantiation_test_module_comb<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn instantiation_test_module_comb<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     // TODO: Do we want to restructure all of these test modules to follow this pattern?
     struct InstantiationTestModuleCombInner<'a> {
         i1: &'a Input<'a>,
@@ -892,7 +1698,7 @@ fn instantiation_test_module_comb<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'
     m
 }
 
-fn instantiation_test_module_reg<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn instantiation_test_module_reg<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     struct InstantiationTestModuleRegInner<'a> {
         i1: &'a Input<'a>,
         i2: &'a Input<'a>,
@@ -933,7 +1739,7 @@ fn instantiation_test_module_reg<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a
     m
 }
 
-fn nested_instantiation_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn nested_instantiation_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     struct NestedInstantiationTestModuleInnerInner<'a> {
         i: &'a Input<'a>,
         o: &'a Output<'a>,
@@ -990,7 +1796,7 @@ fn nested_instantiation_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module
     m
 }
 
-fn mem_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn mem_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("mem_test_module_0", "MemTestModule0");
 
     // No initial contents, single write port, single read port
@@ -1008,7 +1814,7 @@ fn mem_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn mem_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn mem_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("mem_test_module_1", "MemTestModule1");
 
     // Initial contents, no write ports, single read port
@@ -1022,7 +1828,7 @@ fn mem_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn mem_test_module_2<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn mem_test_module_2<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("mem_test_module_2", "MemTestModule2");
 
     // No initial contents, single write port, single read port
@@ -1040,7 +1846,7 @@ fn mem_test_module_2<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn trace_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn trace_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("trace_test_module_0", "TraceTestModule0");
 
     m.output("o0", m.input("i0", 1));
@@ -1052,7 +1858,7 @@ fn trace_test_module_0<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn trace_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn trace_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("trace_test_module_1", "TraceTestModule1");
 
     let r1 = m.reg("r1", 32);
@@ -1067,7 +1873,7 @@ fn trace_test_module_1<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn trace_test_module_2<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn trace_test_module_2<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     struct TraceTestModule2Inner<'a> {
         i1: &'a Input<'a>,
         i2: &'a Input<'a>,
@@ -1105,7 +1911,7 @@ fn trace_test_module_2<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn trace_test_module_3<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn trace_test_module_3<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("trace_test_module_3", "TraceTestModule3");
 
     // No initial contents, single write port, single read port
@@ -1123,7 +1929,7 @@ fn trace_test_module_3<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
     m
 }
 
-fn deep_graph_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
+fn deep_graph_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
     let m = p.module("deep_graph_test_module", "DeepGraphTestModule");
 
     let mut x: &'a dyn Signal<'a> = m.input("i", 1);
@@ -1136,3 +1942,151 @@ fn deep_graph_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &Module<'a> {
 
     m
 }
+
+fn clock_domain_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("clock_domain_test_module", "ClockDomainTestModule");
+
+    let pixel_clk = m.clock("pixel_clk");
+
+    let r1 = m.reg("r1", 32);
+    r1.default_value(0u32);
+    r1.drive_next(m.input("i1", 32));
+    m.output("o1", r1);
+
+    let r2 = m.reg("r2", 32);
+    r2.default_value(0u32);
+    r2.clock(pixel_clk);
+    r2.drive_next(m.input("i2", 32));
+    m.output("o2", r2);
+
+    let inner = m.module("inner", "ClockDomainTestModuleInner");
+    let inner_clk = inner.clock("inner_clk");
+    inner_clk.drive(pixel_clk);
+    let inner_i = inner.input("i", 32);
+    inner_i.drive(m.input("i3", 32));
+    let mem = inner.mem("mem", 1, 32);
+    mem.clock(inner_clk);
+    mem.initial_contents(&[0u32, 0u32]);
+    mem.write_port(inner.low(), inner_i, inner.high());
+    let inner_o = inner.output("o", mem.read_port(inner.low(), inner.high()));
+    m.output("o3", inner_o);
+
+    m
+}
+
+fn reset_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("reset_test_module", "ResetTestModule");
+
+    let arst_n = m.reset(
+        "arst_n",
+        m.input("arst_n", 1),
+        ResetKind::Async,
+        ResetPolarity::ActiveLow,
+    );
+    let srst = m.reset(
+        "srst",
+        m.input("srst", 1),
+        ResetKind::Sync,
+        ResetPolarity::ActiveHigh,
+    );
+
+    let r1 = m.reg("r1", 32);
+    r1.default_value(0xfadebabeu32);
+    r1.reset(arst_n);
+    r1.drive_next(m.input("i1", 32));
+    m.output("o1", r1);
+
+    let r2 = m.reg("r2", 32);
+    r2.default_value(0xdeadbeefu32);
+    r2.reset(srst);
+    r2.drive_next(m.input("i2", 32));
+    m.output("o2", r2);
+
+    let r3 = m.reg("r3", 32);
+    r3.default_value(0u32);
+    r3.drive_next(m.input("i3", 32));
+    m.output("o3", r3);
+
+    // Sync reset driven by a register, as in a simple reset synchronizer
+    let srst_sync = m.reg("srst_sync", 1);
+    srst_sync.default_value(true);
+    srst_sync.reset(arst_n);
+    srst_sync.drive_next(m.low());
+    let srst_sync = m.reset(
+        "srst_sync",
+        srst_sync,
+        ResetKind::Sync,
+        ResetPolarity::ActiveHigh,
+    );
+
+    let r4 = m.reg("r4", 32);
+    r4.default_value(0u32);
+    r4.reset(srst_sync);
+    r4.drive_next(m.input("i4", 32));
+    m.output("o4", r4);
+
+    m
+}
+
+fn wide_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("wide_test_module", "WideTestModule");
+
+    let i1 = m.input("i1", 256);
+    let i2 = m.input("i2", 256);
+    m.output("add", i1 + i2);
+    m.output("sub", i1 - i2);
+    m.output("bit_and", i1 & i2);
+    m.output("bit_or", i1 | i2);
+    m.output("bit_xor", i1 ^ i2);
+    m.output("eq", i1.eq(i2));
+    m.output("lt", i1.lt(i2));
+    m.output("lt_signed", i1.lt_signed(i2));
+    m.output("ge_signed", i1.ge_signed(i2));
+    m.output("mux", m.input("sel", 1).mux(i1, i2));
+    m.output("shl_wide_rhs", i1 << i2);
+    m.output("div", i1 / i2);
+    m.output("rem", i1 % i2);
+    m.output("div_signed", i1.div_signed(i2));
+    m.output("rem_signed", i1.rem_signed(i2));
+
+    let i3 = m.input("i3", 200);
+    let i4 = m.input("i4", 100);
+    let shift = m.input("shift", 9);
+    m.output("inverted", !i3);
+    m.output("shl", i3 << shift);
+    m.output("shr", i3 >> shift);
+    m.output("shr_arithmetic", i3.shr_arithmetic(shift));
+    m.output("mul", i3 * i4);
+    m.output("mul_signed", i3.mul_signed(i4));
+    m.output("bits_narrow", i3.bits(199, 136));
+    m.output("bits_wide", i3.bits(199, 8));
+    m.output("concat", m.input("i5", 128).concat(m.input("i6", 128)));
+    m.output("repeated", i4.repeat(3));
+    m.output(
+        "lit",
+        m.lit([0xdeadbeefu64, 0xfadebabe, 0xabad1dea, 0xc0cac01a], 256),
+    );
+
+    let r = m.reg("r", 256);
+    r.default_value([1u64, 2, 3, 4]);
+    r.drive_next(i1);
+    m.output("reg_value", r);
+
+    let mem = m.mem("mem", 1, 256);
+    mem.initial_contents(&[[0u64, 0, 0, 0], [5, 6, 7, 8]]);
+    mem.write_port(m.input("write_addr", 1), i2, m.input("write_enable", 1));
+    m.output(
+        "mem_value",
+        mem.read_port(m.input("read_addr", 1), m.high()),
+    );
+
+    m
+}
+
+fn wide_trace_test_module<'a>(p: &'a impl ModuleParent<'a>) -> &'a Module<'a> {
+    let m = p.module("wide_trace_test_module", "WideTraceTestModule");
+
+    m.output("o", m.input("i", 192));
+
+    m
+}
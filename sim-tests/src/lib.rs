@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     extern crate kaze;
 
@@ -9,6 +10,7 @@ mod tests {
     use modules::*;
 
     use kaze::runtime::tracing::*;
+    use kaze::runtime::WideUint;
 
     use std::cell::RefCell;
     use std::collections::BTreeMap;
@@ -45,7 +47,7 @@ mod tests {
         }
 
         fn append_newline(&mut self) -> fmt::Result {
-            writeln!(self.f, "")?;
+            writeln!(self.f)?;
             Ok(())
         }
 
@@ -77,19 +79,22 @@ mod tests {
                 w.append_line(&format!("module {}:", name))?;
                 w.indent();
 
-                w.append_line(&format!("children:"))?;
+                w.append_line("children:")?;
                 w.indent();
                 for (name, module) in module.children.iter() {
                     print_module(w, name, module)?;
                 }
                 w.unindent();
 
-                w.append_line(&format!("signals:"))?;
+                w.append_line("signals:")?;
                 w.indent();
                 for (name, signal) in module.signals.iter() {
                     w.append_line(&format!(
-                        "{}: {} bit(s) ({:?})",
-                        name, signal.bit_width, signal.type_
+                        "{}: {} bit(s) ({:?}{})",
+                        name,
+                        signal.bit_width,
+                        signal.type_,
+                        if signal.signed { ", signed" } else { "" }
                     ))?;
                     w.indent();
                     for (timestamp, value) in signal.values.borrow().iter() {
@@ -125,6 +130,7 @@ mod tests {
     struct CaptureSignal {
         bit_width: u32,
         type_: TraceValueType,
+        signed: bool,
         values: RefCell<Vec<(u64, TraceValue)>>,
     }
 
@@ -146,6 +152,27 @@ mod tests {
                 time_stamp: 0,
             }
         }
+
+        fn add_capture_signal(
+            &mut self,
+            name: &'static str,
+            bit_width: u32,
+            type_: TraceValueType,
+            signed: bool,
+        ) -> io::Result<Rc<CaptureSignal>> {
+            let (_, current_module) = self.module_stack.last_mut().unwrap();
+
+            let ret = Rc::new(CaptureSignal {
+                bit_width,
+                type_,
+                signed,
+                values: RefCell::new(Vec::new()),
+            });
+
+            current_module.signals.insert(name, ret.clone());
+
+            Ok(ret)
+        }
     }
 
     impl<'a> Trace for CaptureTrace<'a> {
@@ -186,17 +213,16 @@ mod tests {
             bit_width: u32,
             type_: TraceValueType,
         ) -> io::Result<Self::SignalId> {
-            let (_, current_module) = self.module_stack.last_mut().unwrap();
-
-            let ret = Rc::new(CaptureSignal {
-                bit_width,
-                type_,
-                values: RefCell::new(Vec::new()),
-            });
-
-            current_module.signals.insert(name, ret.clone());
+            self.add_capture_signal(name, bit_width, type_, false)
+        }
 
-            Ok(ret)
+        fn add_signed_signal(
+            &mut self,
+            name: &'static str,
+            bit_width: u32,
+            type_: TraceValueType,
+        ) -> io::Result<Self::SignalId> {
+            self.add_capture_signal(name, bit_width, type_, true)
         }
 
         fn update_time_stamp(&mut self, time_stamp: u64) -> io::Result<()> {
@@ -413,81 +439,1257 @@ mod tests {
         m.prop();
         assert_eq!(m.o4, 0xfffffffe00000001);
 
-        m.i9 = 0xfadebabedeadbeef;
-        m.i10 = true;
+        m.i9 = 0xfadebabedeadbeef;
+        m.i10 = true;
+        m.prop();
+        assert_eq!(m.o5, 0xfadebabedeadbeef);
+
+        m.i11 = 0xfadebabedeadbeef;
+        m.i12 = 0xabad1deacafeb00b;
+        m.prop();
+        assert_eq!(m.o6, 0xa83c_6c93_0366_3080_b2ff_e1cd_0bdd_8445);
+
+        m.i13 = 0x7adebabedeadbeefabad1deacafeb00b;
+        m.i14 = false;
+        m.prop();
+        assert_eq!(m.o7, 0);
+        m.i14 = true;
+        m.prop();
+        assert_eq!(m.o7, 0x7adebabedeadbeefabad1deacafeb00b);
+    }
+
+    #[test]
+    fn mul_signed_test_module() {
+        let mut m = MulSignedTestModule::new();
+
+        m.i1 = false;
+        m.i2 = false;
+        m.prop();
+        assert_eq!(m.o1, 0);
+
+        m.i1 = true; // -1
+        m.i2 = false;
+        m.prop();
+        assert_eq!(m.o1, 0);
+
+        m.i1 = false;
+        m.i2 = true; // -1
+        m.prop();
+        assert_eq!(m.o1, 0);
+
+        m.i1 = true; // -1
+        m.i2 = true; // -1
+        m.prop();
+        assert_eq!(m.o1, 1);
+
+        m.i3 = 7; // -1
+        m.i4 = 15; // -1
+        m.prop();
+        assert_eq!(m.o2, 1);
+
+        m.i5 = 0xffffffff; // -1
+        m.i6 = true; // 1
+        m.prop();
+        assert_eq!(m.o3, 1);
+
+        m.i7 = 0xffffffff; // -1
+        m.i8 = 0x7fffffff;
+        m.prop();
+        assert_eq!(m.o4, 0xffffffff80000001);
+
+        m.i9 = 0xfadebabedeadbeef; // negative something
+        m.i10 = true; // -1
+        m.prop();
+        assert_eq!(m.o5, 0x0521454121524111);
+
+        m.i11 = 0xfadebabedeadbeef; // negative something
+        m.i12 = 0xabad1deacafeb00b; // negative something
+        m.prop();
+        assert_eq!(m.o6, 0x1b093e959b9c186b2ffe1cd0bdd8445);
+
+        m.i13 = 0x7adebabedeadbeefabad1deacafeb00b;
+        m.i14 = false;
+        m.prop();
+        assert_eq!(m.o7, 0);
+        m.i14 = true; // -1
+        m.prop();
+        assert_eq!(m.o7, 0x5214541215241105452e21535014ff5);
+    }
+
+    #[test]
+    fn div_test_module() {
+        let mut m = DivTestModule::new();
+
+        m.i1 = true;
+        m.i2 = true;
+        m.prop();
+        assert_eq!(m.o1, true);
+        assert_eq!(m.o2, false);
+
+        m.i2 = false;
+        m.prop();
+        assert_eq!(m.o1, true);
+        assert_eq!(m.o2, true);
+
+        m.i1 = false;
+        m.prop();
+        assert_eq!(m.o1, true);
+        assert_eq!(m.o2, false);
+
+        m.i3 = 7;
+        m.i4 = 2;
+        m.prop();
+        assert_eq!(m.o3, 3);
+        assert_eq!(m.o4, 1);
+
+        m.i3 = 5;
+        m.i4 = 0;
+        m.prop();
+        assert_eq!(m.o3, 7);
+        assert_eq!(m.o4, 5);
+
+        m.i5 = 0xffffffff;
+        m.i6 = 0x10;
+        m.prop();
+        assert_eq!(m.o5, 0x0fffffff);
+        assert_eq!(m.o6, 0xf);
+
+        m.i6 = 0;
+        m.prop();
+        assert_eq!(m.o5, 0xffffffff);
+        assert_eq!(m.o6, 0xffffffff);
+
+        m.i7 = 0xfadebabedeadbeef;
+        m.i8 = 0x100000000;
+        m.prop();
+        assert_eq!(m.o7, 0xfadebabe);
+        assert_eq!(m.o8, 0xdeadbeef);
+
+        m.i8 = 0;
+        m.prop();
+        assert_eq!(m.o7, 0xffffffffffffffff);
+        assert_eq!(m.o8, 0xfadebabedeadbeef);
+
+        m.i9 = u128::MAX;
+        m.i10 = 3;
+        m.prop();
+        assert_eq!(m.o9, 0x55555555555555555555555555555555);
+        assert_eq!(m.o10, 0);
+
+        m.i9 = 10;
+        m.i10 = 4;
+        m.prop();
+        assert_eq!(m.o9, 2);
+        assert_eq!(m.o10, 2);
+
+        m.i10 = 0;
+        m.prop();
+        assert_eq!(m.o9, u128::MAX);
+        assert_eq!(m.o10, 10);
+    }
+
+    #[test]
+    fn div_signed_test_module() {
+        let mut m = DivSignedTestModule::new();
+
+        m.i1 = true; // -1
+        m.i2 = true; // -1
+        m.prop();
+        assert_eq!(m.o1, true); // Wraps
+        assert_eq!(m.o2, false);
+
+        m.i2 = false;
+        m.prop();
+        assert_eq!(m.o1, true);
+        assert_eq!(m.o2, true);
+
+        m.i1 = false;
+        m.i2 = true; // -1
+        m.prop();
+        assert_eq!(m.o1, false);
+        assert_eq!(m.o2, false);
+
+        m.i3 = 5; // -3
+        m.i4 = 2;
+        m.prop();
+        assert_eq!(m.o3, 7); // -1
+        assert_eq!(m.o4, 7); // -1
+
+        m.i3 = 3;
+        m.i4 = 6; // -2
+        m.prop();
+        assert_eq!(m.o3, 7); // -1
+        assert_eq!(m.o4, 1);
+
+        m.i3 = 4; // -4
+        m.i4 = 7; // -1
+        m.prop();
+        assert_eq!(m.o3, 4); // Wraps
+        assert_eq!(m.o4, 0);
+
+        m.i4 = 0;
+        m.prop();
+        assert_eq!(m.o3, 7);
+        assert_eq!(m.o4, 4);
+
+        m.i5 = 0xfffffff9; // -7
+        m.i6 = 2;
+        m.prop();
+        assert_eq!(m.o5, 0xfffffffd); // -3
+        assert_eq!(m.o6, 0xffffffff); // -1
+
+        m.i5 = 0x80000000;
+        m.i6 = 0xffffffff; // -1
+        m.prop();
+        assert_eq!(m.o5, 0x80000000); // Wraps
+        assert_eq!(m.o6, 0);
+
+        m.i6 = 0;
+        m.prop();
+        assert_eq!(m.o5, 0xffffffff);
+        assert_eq!(m.o6, 0x80000000);
+
+        m.i7 = 100;
+        m.i8 = 0xfffffffffffffff9; // -7
+        m.prop();
+        assert_eq!(m.o7, 0xfffffffffffffff2); // -14
+        assert_eq!(m.o8, 2);
+
+        m.i7 = 0x8000000000000000;
+        m.i8 = 0xffffffffffffffff; // -1
+        m.prop();
+        assert_eq!(m.o7, 0x8000000000000000); // Wraps
+        assert_eq!(m.o8, 0);
+
+        m.i9 = 0xffffffffffffffffffffffffffffff9c; // -100
+        m.i10 = 0xfffffffffffffffffffffffffffffff9; // -7
+        m.prop();
+        assert_eq!(m.o9, 14);
+        assert_eq!(m.o10, 0xfffffffffffffffffffffffffffffffe); // -2
+
+        m.i9 = 1 << 127;
+        m.i10 = u128::MAX; // -1
+        m.prop();
+        assert_eq!(m.o9, 1 << 127); // Wraps
+        assert_eq!(m.o10, 0);
+
+        m.i10 = 0;
+        m.prop();
+        assert_eq!(m.o9, u128::MAX);
+        assert_eq!(m.o10, 1 << 127);
+    }
+
+    #[test]
+    fn reduce_test_module() {
+        let mut m = ReduceTestModule::new();
+
+        m.i1 = false;
+        m.prop();
+        assert_eq!(m.and1, false);
+        assert_eq!(m.or1, false);
+        assert_eq!(m.xor1, false);
+        m.i1 = true;
+        m.prop();
+        assert_eq!(m.and1, true);
+        assert_eq!(m.or1, true);
+        assert_eq!(m.xor1, true);
+
+        m.i2 = 0;
+        m.prop();
+        assert_eq!(m.and2, false);
+        assert_eq!(m.or2, false);
+        assert_eq!(m.xor2, false);
+        m.i2 = 5;
+        m.prop();
+        assert_eq!(m.and2, false);
+        assert_eq!(m.or2, true);
+        assert_eq!(m.xor2, false);
+        m.i2 = 7;
+        m.prop();
+        assert_eq!(m.and2, true);
+        assert_eq!(m.or2, true);
+        assert_eq!(m.xor2, true);
+
+        m.i3 = 0xffffffff;
+        m.prop();
+        assert_eq!(m.and3, true);
+        assert_eq!(m.or3, true);
+        assert_eq!(m.xor3, false);
+        m.i3 = 0x80000000;
+        m.prop();
+        assert_eq!(m.and3, false);
+        assert_eq!(m.or3, true);
+        assert_eq!(m.xor3, true);
+
+        m.i4 = 0xffffffffffffffff;
+        m.prop();
+        assert_eq!(m.and4, true);
+        assert_eq!(m.xor4, false);
+        m.i4 = 0x7fffffffffffffff;
+        m.prop();
+        assert_eq!(m.and4, false);
+        assert_eq!(m.or4, true);
+        assert_eq!(m.xor4, true);
+
+        m.i5 = u128::MAX;
+        m.prop();
+        assert_eq!(m.and5, true);
+        assert_eq!(m.xor5, false);
+        m.i5 = 1 << 127;
+        m.prop();
+        assert_eq!(m.and5, false);
+        assert_eq!(m.or5, true);
+        assert_eq!(m.xor5, true);
+        m.i5 = 0;
+        m.prop();
+        assert_eq!(m.or5, false);
+
+        m.i6 = WideUint::from_words([!0, !0, !0, 0xff]);
+        m.prop();
+        assert_eq!(m.and6, true);
+        assert_eq!(m.or6, true);
+        assert_eq!(m.xor6, false);
+        m.i6 = WideUint::from_words([!0, !0, !0, 0x7f]);
+        m.prop();
+        assert_eq!(m.and6, false);
+        assert_eq!(m.or6, true);
+        assert_eq!(m.xor6, true);
+        m.i6 = WideUint::ZERO;
+        m.prop();
+        assert_eq!(m.and6, false);
+        assert_eq!(m.or6, false);
+        assert_eq!(m.xor6, false);
+    }
+
+    #[test]
+    fn dyn_select_test_module() {
+        let mut m = DynSelectTestModule::new();
+
+        m.i1 = true;
+        m.index1 = 0;
+        m.prop();
+        assert_eq!(m.bit1, true);
+        m.index1 = 1;
+        m.prop();
+        assert_eq!(m.bit1, false);
+
+        m.i2 = 0b1011_0110;
+        for index in 0..16 {
+            m.index2 = index;
+            m.prop();
+            let expected = if index < 8 { 0b1011_0110 >> index } else { 0 };
+            assert_eq!(m.bit2, expected & 1 != 0);
+            assert_eq!(m.bits2, expected & 0b111);
+            assert_eq!(m.all_bits2, expected);
+        }
+
+        m.i3 = 0xf_fadebabe_deadbeef_abad1dea;
+        m.index3 = 32;
+        m.prop();
+        assert_eq!(m.bits3, 0xbe_deadbeef);
+        m.index3 = 80;
+        m.prop();
+        assert_eq!(m.bits3, 0xf_fade);
+        m.index3 = 127;
+        m.prop();
+        assert_eq!(m.bits3, 0);
+
+        m.i4 = WideUint::from_words([
+            0x0123456789abcdef,
+            0xfedcba9876543210,
+            0x1122334455667788,
+            0x99,
+        ]);
+        m.index4 = 0;
+        m.prop();
+        assert_eq!(m.bit4, true);
+        assert_eq!(m.bits4, 0x0123456789abcdef);
+        m.index4 = 100;
+        m.prop();
+        assert_eq!(m.bit4, true);
+        assert_eq!(m.bits4, 0x455667788fedcba9);
+        assert_eq!(
+            m.wide_bits4,
+            WideUint::from_words([0x455667788fedcba9, 0x991122334, 0])
+        );
+        m.index4 = 199;
+        m.prop();
+        assert_eq!(m.bit4, true);
+        assert_eq!(m.bits4, 1);
+        m.index4 = 200;
+        m.prop();
+        assert_eq!(m.bit4, false);
+        assert_eq!(m.bits4, 0);
+        assert_eq!(m.wide_bits4, WideUint::ZERO);
+    }
+
+    #[test]
+    fn extend_test_module() {
+        let mut m = ExtendTestModule::new();
+
+        m.i1 = false;
+        m.i2 = 0x5;
+        m.i3 = 0x7fffffff;
+        m.i4 = 0x7fffffffffffffff;
+        m.i5 = 0x7_ffffffff_ffffffff_ffffffff;
+        m.i6 = WideUint::from_words([!0, !0, 0x1fffff]);
+        m.i7 = 0xfa;
+        m.prop();
+        assert_eq!(m.zext1, 0x00);
+        assert_eq!(m.sext1, 0x00);
+        assert_eq!(m.zext2, 0x5);
+        assert_eq!(m.sext2, 0x5);
+        assert_eq!(m.sext2_same, 0x5);
+        assert_eq!(m.zext3, 0x7fffffff);
+        assert_eq!(m.sext3, 0x7fffffff);
+        assert_eq!(m.sext4, 0x7fffffffffffffff);
+        assert_eq!(m.zext5, WideUint::from_words([!0, 0x7_ffffffff, 0, 0]));
+        assert_eq!(m.sext5, WideUint::from_words([!0, 0x7_ffffffff, 0, 0]));
+        assert_eq!(m.zext6, WideUint::from_words([!0, !0, 0x1fffff, 0]));
+        assert_eq!(m.sext6, WideUint::from_words([!0, !0, 0x1fffff, 0]));
+        assert_eq!(m.resize7_narrow, 0xa);
+        assert_eq!(m.resize7_same, 0xfa);
+        assert_eq!(m.resize7_wide, 0x0fa);
+
+        m.i1 = true;
+        m.i2 = 0xa;
+        m.i3 = 0x80000000;
+        m.i4 = 0x8000000000000000;
+        m.i5 = 0x8_00000000_00000000_00000000;
+        m.i6 = WideUint::from_words([0, 0, 0x200000]);
+        m.prop();
+        assert_eq!(m.zext1, 0x01);
+        assert_eq!(m.sext1, 0xff);
+        assert_eq!(m.zext2, 0x0000000a);
+        assert_eq!(m.sext2, 0xfffffffa);
+        assert_eq!(m.sext2_same, 0xa);
+        assert_eq!(m.zext3, 0x0_80000000);
+        assert_eq!(m.sext3, 0x1_80000000);
+        assert_eq!(m.sext4, 0xffffffffffffffff_8000000000000000);
+        assert_eq!(m.zext5, WideUint::from_words([0, 0x8_00000000, 0, 0]));
+        assert_eq!(
+            m.sext5,
+            WideUint::from_words([0, 0xfffffff8_00000000, !0, 0xff])
+        );
+        assert_eq!(m.zext6, WideUint::from_words([0, 0, 0x200000, 0]));
+        assert_eq!(
+            m.sext6,
+            WideUint::from_words([0, 0, 0xffffffff_ffe00000, 0xff])
+        );
+    }
+
+    #[test]
+    fn rotate_test_module() {
+        let mut m = RotateTestModule::new();
+
+        m.i1 = true;
+        for amount in 0..4 {
+            m.amount1 = amount;
+            m.prop();
+            assert_eq!(m.rotl1, true);
+        }
+
+        m.i2 = 0b1000_0110;
+        for amount in 0..8 {
+            m.amount2 = amount;
+            m.prop();
+            assert_eq!(m.rotl2, 0b1000_0110u8.rotate_left(amount) as u32);
+            assert_eq!(m.rotr2, 0b1000_0110u8.rotate_right(amount) as u32);
+        }
+        for amount in 0..256 {
+            m.amount2_wide = amount;
+            m.prop();
+            assert_eq!(m.rotl2_wide, 0b1000_0110u8.rotate_left(amount) as u32);
+        }
+        m.amount2_wider = WideUint::from_words([1, 0, 0, 0x80]);
+        m.prop();
+        // 2^199 + 1 = 1 (mod 8)
+        assert_eq!(m.rotr2_wider, 0b0100_0011);
+
+        m.i3 = 0x8000_0001;
+        for amount in 0..32 {
+            m.amount3 = amount;
+            m.prop();
+            assert_eq!(m.rotl3, 0x8000_0001u32.rotate_left(amount));
+            assert_eq!(m.rotr3, 0x8000_0001u32.rotate_right(amount));
+        }
+
+        m.i4 = 0x8_00000000_00000000_00000003;
+        m.amount4 = 0;
+        m.prop();
+        assert_eq!(m.rotl4, 0x8_00000000_00000000_00000003);
+        assert_eq!(m.rotr4, 0x8_00000000_00000000_00000003);
+        m.amount4 = 1;
+        m.prop();
+        assert_eq!(m.rotl4, 0x00000000_00000000_00000007);
+        assert_eq!(m.rotr4, 0xc_00000000_00000000_00000001);
+        m.amount4 = 101;
+        m.prop();
+        assert_eq!(m.rotl4, 0x00000000_00000000_00000007);
+        assert_eq!(m.rotr4, 0xc_00000000_00000000_00000001);
+
+        m.i5 = 0x80000000_00000000_00000000_00000001;
+        m.amount5 = 129;
+        m.prop();
+        assert_eq!(m.rotl5, 0x00000000_00000000_00000000_00000003);
+
+        m.i6 = WideUint::from_words([1, 0, 0, 0x80]);
+        m.amount6 = 1;
+        m.prop();
+        assert_eq!(m.rotl6, WideUint::from_words([3, 0, 0, 0]));
+        assert_eq!(m.rotr6, WideUint::from_words([0, 0, 0, 0xc0]));
+        m.amount6 = 201;
+        m.prop();
+        assert_eq!(m.rotl6, WideUint::from_words([3, 0, 0, 0]));
+        assert_eq!(m.rotr6, WideUint::from_words([0, 0, 0, 0xc0]));
+
+        m.i7 = WideUint::from_words([1, 0, 0, 1 << 63]);
+        m.amount7 = 257;
+        m.prop();
+        assert_eq!(m.rotl7, WideUint::from_words([3, 0, 0, 0]));
+        assert_eq!(
+            m.rotr7,
+            WideUint::from_words([0, 0, 0, 0xc000_0000_0000_0000])
+        );
+    }
+
+    #[test]
+    fn count_test_module() {
+        let mut m = CountTestModule::new();
+
+        m.i1 = false;
+        m.prop();
+        assert_eq!(m.leading_zeros1, true);
+        assert_eq!(m.trailing_zeros1, true);
+        assert_eq!(m.count_ones1, false);
+        assert_eq!(m.priority_encode1, false);
+        assert_eq!(m.one_hot1, 0b01);
+        m.i1 = true;
+        m.prop();
+        assert_eq!(m.leading_zeros1, false);
+        assert_eq!(m.trailing_zeros1, false);
+        assert_eq!(m.count_ones1, true);
+        assert_eq!(m.priority_encode1, false);
+        assert_eq!(m.one_hot1, 0b10);
+
+        for i in 0..256u32 {
+            m.i2 = i;
+            m.prop();
+            assert_eq!(m.leading_zeros2, (i as u8).leading_zeros());
+            assert_eq!(m.trailing_zeros2, (i as u8).trailing_zeros());
+            assert_eq!(m.count_ones2, i.count_ones());
+            assert_eq!(m.priority_encode2, (i as u8).trailing_zeros() & 0x7);
+            assert_eq!(m.one_hot2, 1 << (i & 0x7));
+        }
+
+        for &i in &[0, 1, 0x8000_0000, 0x0001_0100, 0xffff_ffff, 0x1234_5678] {
+            m.i3 = i;
+            m.prop();
+            assert_eq!(m.leading_zeros3, i.leading_zeros());
+            assert_eq!(m.trailing_zeros3, i.trailing_zeros());
+            assert_eq!(m.count_ones3, i.count_ones());
+            assert_eq!(m.priority_encode3, i.trailing_zeros() & 0x1f);
+            assert_eq!(m.one_hot3, 1u64 << (i & 0x3f));
+        }
+
+        m.i4 = 0;
+        m.prop();
+        assert_eq!(m.leading_zeros4, 100);
+        assert_eq!(m.trailing_zeros4, 100);
+        assert_eq!(m.count_ones4, 0);
+        assert_eq!(m.priority_encode4, 0);
+        assert_eq!(m.one_hot4, 1);
+        m.i4 = 0x1_00000000_00000000_0000007f;
+        m.prop();
+        assert_eq!(m.leading_zeros4, 3);
+        assert_eq!(m.trailing_zeros4, 0);
+        assert_eq!(m.count_ones4, 8);
+        assert_eq!(m.priority_encode4, 0);
+        assert_eq!(m.one_hot4, 1 << 127);
+        m.i4 = 0x8_00000000_00000000_00000000;
+        m.prop();
+        assert_eq!(m.leading_zeros4, 0);
+        assert_eq!(m.trailing_zeros4, 99);
+        assert_eq!(m.count_ones4, 1);
+        assert_eq!(m.priority_encode4, 99);
+
+        m.i5 = WideUint::ZERO;
+        m.prop();
+        assert_eq!(m.leading_zeros5, 200);
+        assert_eq!(m.trailing_zeros5, 200);
+        assert_eq!(m.count_ones5, 0);
+        assert_eq!(m.priority_encode5, 0);
+        assert_eq!(m.one_hot5, WideUint::from(1u32));
+        m.i5 = WideUint::from_words([0, 0xff00, 0, 0x10]);
+        m.prop();
+        assert_eq!(m.leading_zeros5, 3);
+        assert_eq!(m.trailing_zeros5, 72);
+        assert_eq!(m.count_ones5, 9);
+        assert_eq!(m.priority_encode5, 72);
+        assert_eq!(m.one_hot5, WideUint::from(1u32));
+        m.i5 = WideUint::from_words([0xff, 0, 0, 0]);
+        m.prop();
+        assert_eq!(m.one_hot5, WideUint::from_words([0, 0, 0, 1 << 63]));
+    }
+
+    #[test]
+    fn saturating_test_module() {
+        let mut m = SaturatingTestModule::new();
+
+        for &(lhs, rhs) in &[(false, false), (false, true), (true, false), (true, true)] {
+            m.lhs1 = lhs;
+            m.rhs1 = rhs;
+            m.prop();
+            assert_eq!(m.add_carry1, lhs as u32 + rhs as u32);
+            assert_eq!(m.sub_borrow1, (lhs as u32).wrapping_sub(rhs as u32) & 0x3);
+            assert_eq!(m.add_sat1, lhs | rhs);
+            assert_eq!(m.sub_sat1, lhs & !rhs);
+            // A 1-bit signed value is either 0 or -1
+            assert_eq!(m.add_sat_signed1, lhs | rhs);
+            assert_eq!(m.sub_sat_signed1, lhs & !rhs);
+        }
+
+        for lhs in 0..256u32 {
+            for rhs in 0..256u32 {
+                m.lhs2 = lhs;
+                m.rhs2 = rhs;
+                m.prop();
+                assert_eq!(m.add_carry2, lhs + rhs);
+                assert_eq!(m.sub_borrow2, lhs.wrapping_sub(rhs) & 0x1ff);
+                assert_eq!(m.add_sat2, (lhs as u8).saturating_add(rhs as u8) as u32);
+                assert_eq!(
+                    m.add_sat_signed2,
+                    (lhs as i8).saturating_add(rhs as i8) as u8 as u32
+                );
+                assert_eq!(m.sub_sat2, (lhs as u8).saturating_sub(rhs as u8) as u32);
+                assert_eq!(
+                    m.sub_sat_signed2,
+                    (lhs as i8).saturating_sub(rhs as i8) as u8 as u32
+                );
+            }
+        }
+
+        let values32 = [
+            0,
+            1,
+            0x7fff_ffff,
+            0x8000_0000,
+            0x8000_0001,
+            0xffff_ffff,
+            0x1234_5678,
+        ];
+        for &lhs in &values32 {
+            for &rhs in &values32 {
+                m.lhs3 = lhs;
+                m.rhs3 = rhs;
+                m.prop();
+                assert_eq!(m.add_carry3, lhs as u64 + rhs as u64);
+                assert_eq!(
+                    m.sub_borrow3,
+                    (lhs as u64).wrapping_sub(rhs as u64) & 0x1_ffff_ffff
+                );
+                assert_eq!(m.add_sat3, lhs.saturating_add(rhs));
+                assert_eq!(
+                    m.add_sat_signed3,
+                    (lhs as i32).saturating_add(rhs as i32) as u32
+                );
+                assert_eq!(m.sub_sat3, lhs.saturating_sub(rhs));
+                assert_eq!(
+                    m.sub_sat_signed3,
+                    (lhs as i32).saturating_sub(rhs as i32) as u32
+                );
+            }
+        }
+
+        let values64 = [
+            0,
+            1,
+            i64::MAX as u64,
+            i64::MIN as u64,
+            !0,
+            0x1234_5678_9abc_def0,
+        ];
+        for &lhs in &values64 {
+            for &rhs in &values64 {
+                m.lhs4 = lhs;
+                m.rhs4 = rhs;
+                m.prop();
+                assert_eq!(m.add_carry4, lhs as u128 + rhs as u128);
+                assert_eq!(
+                    m.sub_borrow4,
+                    (lhs as u128).wrapping_sub(rhs as u128) & 0x1_ffff_ffff_ffff_ffff
+                );
+                assert_eq!(m.add_sat4, lhs.saturating_add(rhs));
+                assert_eq!(
+                    m.add_sat_signed4,
+                    (lhs as i64).saturating_add(rhs as i64) as u64
+                );
+                assert_eq!(m.sub_sat4, lhs.saturating_sub(rhs));
+                assert_eq!(
+                    m.sub_sat_signed4,
+                    (lhs as i64).saturating_sub(rhs as i64) as u64
+                );
+            }
+        }
+
+        let values128 = [0, 1, i128::MAX as u128, i128::MIN as u128, !0];
+        for &lhs in &values128 {
+            for &rhs in &values128 {
+                m.lhs5 = lhs;
+                m.rhs5 = rhs;
+                m.prop();
+                let (sum, carry) = lhs.overflowing_add(rhs);
+                assert_eq!(
+                    m.add_carry5,
+                    WideUint::from(sum) | (WideUint::from(carry) << 128)
+                );
+                let (difference, borrow) = lhs.overflowing_sub(rhs);
+                assert_eq!(
+                    m.sub_borrow5,
+                    WideUint::from(difference) | (WideUint::from(borrow) << 128)
+                );
+                assert_eq!(m.add_sat5, lhs.saturating_add(rhs));
+                assert_eq!(
+                    m.add_sat_signed5,
+                    (lhs as i128).saturating_add(rhs as i128) as u128
+                );
+                assert_eq!(m.sub_sat5, lhs.saturating_sub(rhs));
+                assert_eq!(
+                    m.sub_sat_signed5,
+                    (lhs as i128).saturating_sub(rhs as i128) as u128
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn signed_test_module() -> io::Result<()> {
+        let mut capture = Capture::new();
+        let trace = CaptureTrace::new(&mut capture);
+
+        let mut m = SignedTestModule::new(trace)?;
+
+        m.reset();
+
+        for (time_stamp, &(lhs, rhs, shift)) in [
+            (0x00u8, 0x00u8, 0u8),
+            (0x05, 0x03, 1),
+            (0xfb, 0x03, 2),
+            (0x05, 0xfd, 3),
+            (0x80, 0xff, 7),
+            (0x80, 0x7f, 4),
+            (0x7f, 0x80, 5),
+            (0xff, 0x00, 6),
+        ]
+        .iter()
+        .enumerate()
+        {
+            m.lhs = lhs as u32;
+            m.rhs = rhs as u32;
+            m.shift = shift as u32;
+            m.prop();
+            m.update_trace(time_stamp as u64)?;
+
+            let (lhs_s, rhs_s) = (lhs as i8, rhs as i8);
+            assert_eq!(m.lt, lhs_s < rhs_s);
+            assert_eq!(m.le, lhs_s <= rhs_s);
+            assert_eq!(m.gt, lhs_s > rhs_s);
+            assert_eq!(m.ge, lhs_s >= rhs_s);
+            assert_eq!(m.lt_unsigned, lhs < rhs);
+            assert_eq!(m.mul, (lhs_s as i32 * rhs_s as i32) as u32 & 0xffff);
+            if rhs_s != 0 {
+                assert_eq!(m.div, lhs_s.wrapping_div(rhs_s) as u8 as u32);
+                assert_eq!(m.rem, lhs_s.wrapping_rem(rhs_s) as u8 as u32);
+            }
+            assert_eq!(m.shr, (lhs_s >> shift) as u8 as u32);
+            assert_eq!(m.shl, lhs.wrapping_shl(shift as u32) as u32);
+            assert_eq!(m.add, lhs.wrapping_add(rhs) as u32);
+            assert_eq!(m.sub, lhs.wrapping_sub(rhs) as u32);
+            assert_eq!(m.inverted, !lhs as u32);
+            assert_eq!(
+                m.mul_add,
+                ((lhs_s.wrapping_add(rhs_s) as i32) * lhs_s as i32) as u32 & 0xffff
+            );
+            assert_eq!(m.as_unsigned, lhs as u32);
+
+            m.posedge_clk();
+            m.prop();
+            assert_eq!(m.prev_lhs_out, lhs as u32);
+        }
+
+        let (_, root) = capture.root.as_ref().unwrap();
+        for (name, signal) in root.signals.iter() {
+            let signed = matches!(
+                *name,
+                "lhs"
+                    | "prev_lhs"
+                    | "prev_lhs_out"
+                    | "mul"
+                    | "div"
+                    | "rem"
+                    | "shr"
+                    | "shl"
+                    | "add"
+                    | "sub"
+                    | "inverted"
+                    | "mul_add"
+            );
+            assert_eq!(signal.signed, signed, "{}", name);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn lit_operand_test_module() {
+        let mut m = LitOperandTestModule::new();
+
+        for i1 in [0x00u8, 0x01, 0x03, 0x7f, 0x80, 0xfc, 0xfd, 0xff] {
+            m.i1 = i1 as u32;
+            m.prop();
+
+            assert_eq!(m.add, i1.wrapping_add(1) as u32);
+            assert_eq!(m.sub, i1.wrapping_sub(1) as u32);
+            assert_eq!(m.bit_and, (i1 & 0x0f) as u32);
+            assert_eq!(m.shl, i1.wrapping_shl(2) as u32);
+            assert_eq!(m.mul, i1 as u32 * 3);
+            assert_eq!(m.eq, i1 == 3);
+            assert_eq!(m.eq_neg, i1 == 0xff);
+            assert_eq!(m.lt, i1 < 0x80);
+            assert_eq!(m.lt_signed, (i1 as i8) < -3);
+            assert_eq!(m.signed_add, i1.wrapping_sub(2) as u32);
+        }
+
+        let max = (1u128 << 100) - 1;
+        for i2 in [0u128, 1, 2, 0xffffffffffffffff, 1 << 64, max - 1, max] {
+            m.i2 = i2;
+            m.prop();
+
+            assert_eq!(m.wide_add, i2.wrapping_sub(2) & max);
+            assert_eq!(m.wide_eq, i2 == max);
+            assert_eq!(m.wide_ge, i2 >= 0xffffffffffffffff);
+        }
+    }
+
+    #[test]
+    fn switch_test_module() {
+        let mut m = SwitchTestModule::new();
+
+        let (a, b) = (0x3cu8, 0xa5u8);
+        m.a = a as u32;
+        m.b = b as u32;
+
+        for sel1 in 0..8u32 {
+            m.sel1 = sel1;
+            m.prop();
+            let (o1, o2) = match sel1 {
+                0 => (a, b),
+                1 => (b, a),
+                2 => (a.wrapping_add(b), a.wrapping_sub(b)),
+                3 => (a & b, a | b),
+                5 => (!a, !b),
+                _ => (0xff, 0),
+            };
+            assert_eq!(m.o1, o1 as u32);
+            assert_eq!(m.o2, o2 as u32);
+            let o3 = match sel1 {
+                7 => a,
+                6 => b,
+                5 => a ^ b,
+                _ => sel1 as u8,
+            };
+            assert_eq!(m.o3, o3 as u32);
+        }
+
+        for sel2 in [0x00u32, 0x10, 0x20, 0x30, 0xff] {
+            m.sel2 = sel2;
+            m.prop();
+            let o4 = match sel2 {
+                0x10 => a,
+                0x20 => b,
+                0xff => a.wrapping_add(b),
+                _ => 0x55,
+            };
+            assert_eq!(m.o4, o4 as u32);
+            assert_eq!(m.o5, if sel2 == 0x10 { a } else { b } as u32);
+        }
+
+        for sel3 in [0u128, 1, 1 << 64, 1 << 99, (1 << 99) | 1] {
+            m.sel3 = sel3;
+            m.prop();
+            let o6 = match sel3 {
+                0 => a,
+                s if s == 1 << 99 => b,
+                s if s == 1 << 64 => a ^ b,
+                _ => 0xaa,
+            };
+            assert_eq!(m.o6, o6 as u32);
+        }
+    }
+
+    #[test]
+    fn when_test_module() {
+        let mut m = WhenTestModule::new();
+
+        m.reset();
+        m.prop();
+        assert_eq!(m.busy, false);
+        assert_eq!(m.count, 0);
+
+        // Load while idle
+        m.load = true;
+        m.load_value = 0x40;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.busy, false);
+        assert_eq!(m.count, 0x40);
+
+        // Hold while idle
+        m.load = false;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.busy, false);
+        assert_eq!(m.count, 0x40);
+
+        // Start
+        m.start = true;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.busy, true);
+        assert_eq!(m.count, 0);
+
+        // Count, ignoring loads while busy
+        m.start = false;
+        m.load = true;
+        for i in 1..=3 {
+            m.prop();
+            m.posedge_clk();
+            m.prop();
+            assert_eq!(m.busy, true);
+            assert_eq!(m.count, i);
+        }
+
+        // Stop, counting one last time
+        m.load = false;
+        m.stop = true;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.busy, false);
+        assert_eq!(m.count, 4);
+
+        // Stop has no effect while idle
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.busy, false);
+        assert_eq!(m.count, 4);
+
+        // Write
+        m.write_addr = 1;
+        m.write_value = 0xaa;
+        m.write_enable = true;
+        m.prop();
+        m.posedge_clk();
+        m.write_enable = false;
+        m.read_addr = 1;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.read_data, 0xaa);
+
+        // Clear takes priority over write
+        m.write_value = 0xbb;
+        m.write_enable = true;
+        m.clear = true;
+        m.prop();
+        m.posedge_clk();
+        m.write_enable = false;
+        m.clear = false;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.read_data, 0);
+
+        // No write
+        m.write_value = 0xcc;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.read_data, 0);
+    }
+
+    #[test]
+    fn bundle_test_module() {
+        let mut m = BundleTestModule::new();
+
+        m.stage_in_valid = true;
+        m.stage_in_pc = 0x1234;
+        m.stage_in_insn = 0x0f;
+        m.prop();
+        assert_eq!(m.packed, 1 << 24 | 0x1235 << 8 | 0xf0);
+
+        m.posedge_clk();
+        m.stage_in_valid = false;
+        m.stage_in_pc = 0xffff;
+        m.stage_in_insn = 0xff;
+        m.prop();
+        assert_eq!(m.packed, 0);
+        assert_eq!(m.stage_out_valid, true);
+        assert_eq!(m.stage_out_pc, 0x1235);
+        assert_eq!(m.stage_out_insn, 0xf0);
+
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.stage_out_valid, false);
+        assert_eq!(m.stage_out_pc, 0x0000);
+        assert_eq!(m.stage_out_insn, 0x00);
+    }
+
+    #[test]
+    fn signal_vec_test_module() {
+        let mut m = SignalVecTestModule::new();
+
+        let lanes = [0x12, 0x34, 0x56, 0x78];
+        m.lane0 = lanes[0];
+        m.lane1 = lanes[1];
+        m.lane2 = lanes[2];
+        m.lane3 = lanes[3];
+        for i in 0..4 {
+            m.sel1 = i;
+            m.sel2 = i;
+            m.sel3 = i;
+            m.sel4 = i & 1 != 0;
+            m.sel5 = (i as u128).into();
+            m.prop();
+            assert_eq!(m.o1, lanes[i as usize]);
+            assert_eq!(m.o2, 0x14);
+            assert_eq!(m.o3, !lanes[i as usize] & 0xff);
+            assert_eq!(m.o4, lanes[i.min(2) as usize]);
+            assert_eq!(m.o5, lanes[(i & 1) as usize]);
+            assert_eq!(m.o6, lanes[i.min(2) as usize]);
+        }
+        m.sel5 = (1u128 << 100).into();
+        m.prop();
+        assert_eq!(m.o6, lanes[2]);
+
+        m.reset();
+        for i in 0..4 {
+            m.write_enable = true;
+            m.write_index = i;
+            m.write_value = 0x10 + i;
+            m.prop();
+            m.posedge_clk();
+        }
+        m.write_enable = false;
+        m.write_value = 0xff;
+        m.prop();
+        m.posedge_clk();
+        for i in 0..4 {
+            m.read_index = i;
+            m.prop();
+            assert_eq!(m.read_value, 0x10 + i);
+        }
+    }
+
+    #[test]
+    fn stream_test_module() {
+        let mut m = StreamTestModule::new();
+
+        m.reset();
+
+        // No transfer without both valid and ready
+        m.enable = true;
+        m.accept = false;
+        m.prop();
+        assert_eq!(m.fire, false);
+        m.posedge_clk();
+        m.enable = false;
+        m.accept = true;
+        m.prop();
+        assert_eq!(m.fire, false);
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.sum, 0);
+
+        // Transfer 0, 1, 2, 3 (last)
+        m.enable = true;
+        for _ in 0..4 {
+            m.prop();
+            assert_eq!(m.fire, true);
+            m.posedge_clk();
+        }
+        m.prop();
+        assert_eq!(m.sum, 6);
+        assert_eq!(m.done, true);
+
+        // Consumer no longer ready
+        assert_eq!(m.fire, false);
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.sum, 6);
+    }
+
+    #[test]
+    fn stream_forward_test_module() {
+        let mut m = StreamForwardTestModule::new();
+
+        m.in_valid = true;
+        m.in_data = 5;
+        m.in_last = true;
+        m.out_ready = false;
+        m.prop();
+        assert_eq!(m.out_valid, true);
+        assert_eq!(m.out_data, 6);
+        assert_eq!(m.out_last, true);
+        assert_eq!(m.in_ready, false);
+
+        m.in_valid = false;
+        m.in_data = 0xff;
+        m.in_last = false;
+        m.out_ready = true;
+        m.prop();
+        assert_eq!(m.out_valid, false);
+        assert_eq!(m.out_data, 0);
+        assert_eq!(m.out_last, false);
+        assert_eq!(m.in_ready, true);
+    }
+
+    #[test]
+    fn instance_test_module() {
+        let mut m = InstanceTestModule::new();
+
+        m.reset();
+
+        m.i = 1;
         m.prop();
-        assert_eq!(m.o5, 0xfadebabedeadbeef);
+        assert_eq!(m.doubled, 8);
+        assert_eq!(m.sum_a, 0);
+        assert_eq!(m.sum_b, 0);
+        assert_eq!(m.sum_c, 0);
 
-        m.i11 = 0xfadebabedeadbeef;
-        m.i12 = 0xabad1deacafeb00b;
+        // Each instance keeps its own state
+        m.posedge_clk();
         m.prop();
-        assert_eq!(m.o6, 0xa83c_6c93_0366_3080_b2ff_e1cd_0bdd_8445);
+        assert_eq!(m.sum_a, 2);
+        assert_eq!(m.sum_b, 4);
+        assert_eq!(m.sum_c, 8);
 
-        m.i13 = 0x7adebabedeadbeefabad1deacafeb00b;
-        m.i14 = false;
+        m.i = 3;
         m.prop();
-        assert_eq!(m.o7, 0);
-        m.i14 = true;
+        assert_eq!(m.doubled, 24);
+        m.posedge_clk();
         m.prop();
-        assert_eq!(m.o7, 0x7adebabedeadbeefabad1deacafeb00b);
+        assert_eq!(m.sum_a, 8);
+        assert_eq!(m.sum_b, 16);
+        assert_eq!(m.sum_c, 32);
     }
 
     #[test]
-    fn mul_signed_test_module() {
-        let mut m = MulSignedTestModule::new();
+    fn instance_clock_reset_test_module() -> io::Result<()> {
+        let mut capture = Capture::new();
+        let trace = CaptureTrace::new(&mut capture);
 
-        m.i1 = false;
-        m.i2 = false;
-        m.prop();
-        assert_eq!(m.o1, 0);
+        let mut m = InstanceClockResetTestModule::new(trace)?;
 
-        m.i1 = true; // -1
-        m.i2 = false;
+        m.arst_n = true;
         m.prop();
-        assert_eq!(m.o1, 0);
+        m.update_trace(0)?;
+        assert_eq!(m.count_a, 0);
+        assert_eq!(m.count_b, 0);
 
-        m.i1 = false;
-        m.i2 = true; // -1
+        // Each instance is clocked by its own clock
+        m.posedge_a_clk();
         m.prop();
-        assert_eq!(m.o1, 0);
-
-        m.i1 = true; // -1
-        m.i2 = true; // -1
+        m.posedge_a_clk();
         m.prop();
-        assert_eq!(m.o1, 1);
-
-        m.i3 = 7; // -1
-        m.i4 = 15; // -1
+        m.posedge_b_clk();
         m.prop();
-        assert_eq!(m.o2, 1);
+        m.update_trace(1)?;
+        assert_eq!(m.count_a, 2);
+        assert_eq!(m.count_b, 1);
 
-        m.i5 = 0xffffffff; // -1
-        m.i6 = true; // 1
+        // Async reset takes effect in every instance without a clock edge
+        m.arst_n = false;
         m.prop();
-        assert_eq!(m.o3, 1);
-
-        m.i7 = 0xffffffff; // -1
-        m.i8 = 0x7fffffff;
+        m.update_trace(2)?;
+        assert_eq!(m.count_a, 0);
+        assert_eq!(m.count_b, 0);
+        m.posedge_a_clk();
+        m.posedge_b_clk();
         m.prop();
-        assert_eq!(m.o4, 0xffffffff80000001);
+        assert_eq!(m.count_a, 0);
+        assert_eq!(m.count_b, 0);
 
-        m.i9 = 0xfadebabedeadbeef; // negative something
-        m.i10 = true; // -1
+        m.arst_n = true;
         m.prop();
-        assert_eq!(m.o5, 0x0521454121524111);
+        m.posedge_b_clk();
+        m.prop();
+        assert_eq!(m.count_a, 0);
+        assert_eq!(m.count_b, 1);
 
-        m.i11 = 0xfadebabedeadbeef; // negative something
-        m.i12 = 0xabad1deacafeb00b; // negative something
+        // Signals of instances and their nested modules are traced per instance
+        let (name, root) = capture.root.as_ref().unwrap();
+        assert_eq!(*name, "instance_clock_reset_test_module");
+        assert_eq!(
+            *root.children["a"].signals["count"].values.borrow(),
+            vec![
+                (0, TraceValue::U32(0)),
+                (1, TraceValue::U32(2)),
+                (2, TraceValue::U32(0)),
+            ]
+        );
+        assert_eq!(
+            *root.children["b"].signals["count"].values.borrow(),
+            vec![
+                (0, TraceValue::U32(0)),
+                (1, TraceValue::U32(1)),
+                (2, TraceValue::U32(0)),
+            ]
+        );
+        assert_eq!(
+            *root.children["a"].children["incr"].signals["o"]
+                .values
+                .borrow(),
+            vec![
+                (0, TraceValue::U32(1)),
+                (1, TraceValue::U32(3)),
+                (2, TraceValue::U32(1)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn generator_test_module() {
+        let mut m = GeneratorTestModule::new();
+
+        m.reset();
+
+        m.i = 1;
         m.prop();
-        assert_eq!(m.o6, 0x1b093e959b9c186b2ffe1cd0bdd8445);
+        assert_eq!(m.a, 0);
+        assert_eq!(m.b, 0);
+        assert_eq!(m.c, 0);
 
-        m.i13 = 0x7adebabedeadbeefabad1deacafeb00b;
-        m.i14 = false;
+        m.posedge_clk();
+        m.i = 2;
         m.prop();
-        assert_eq!(m.o7, 0);
-        m.i14 = true; // -1
+        assert_eq!(m.a, 1);
+        assert_eq!(m.b, 0);
+        assert_eq!(m.c, 0);
+
+        m.posedge_clk();
         m.prop();
-        assert_eq!(m.o7, 0x5214541215241105452e21535014ff5);
+        assert_eq!(m.a, 2);
+        assert_eq!(m.b, 1);
+        assert_eq!(m.c, 1);
     }
 
     #[test]
@@ -560,7 +1762,7 @@ mod tests {
         assert_eq!(m.o3, 0xbeef0000);
 
         m.i5 = 0xdeadbeef;
-        m.i6 = std::u32::MAX;
+        m.i6 = u32::MAX;
         m.prop();
         assert_eq!(m.o3, 0);
 
@@ -575,7 +1777,7 @@ mod tests {
         assert_eq!(m.o4, 0xbabedeadbeef0000);
 
         m.i7 = 0xfadebabedeadbeef;
-        m.i8 = std::u64::MAX;
+        m.i8 = u64::MAX;
         m.prop();
         assert_eq!(m.o4, 0);
 
@@ -590,7 +1792,7 @@ mod tests {
         assert_eq!(m.o5, 0xaaaa55555555fadebabedeadbeef0000);
 
         m.i9 = 0xaaaaaaaa55555555fadebabedeadbeef;
-        m.i10 = std::u128::MAX;
+        m.i10 = u128::MAX;
         m.prop();
         assert_eq!(m.o5, 0);
 
@@ -715,7 +1917,7 @@ mod tests {
         assert_eq!(m.o3, 0x0000dead);
 
         m.i5 = 0xdeadbeef;
-        m.i6 = std::u32::MAX;
+        m.i6 = u32::MAX;
         m.prop();
         assert_eq!(m.o3, 0);
 
@@ -730,7 +1932,7 @@ mod tests {
         assert_eq!(m.o4, 0x0000fadebabedead);
 
         m.i7 = 0xfadebabedeadbeef;
-        m.i8 = std::u64::MAX;
+        m.i8 = u64::MAX;
         m.prop();
         assert_eq!(m.o4, 0);
 
@@ -745,7 +1947,7 @@ mod tests {
         assert_eq!(m.o5, 0x0000aaaaaaaa55555555fadebabedead);
 
         m.i9 = 0xaaaaaaaa55555555fadebabedeadbeef;
-        m.i10 = std::u128::MAX;
+        m.i10 = u128::MAX;
         m.prop();
         assert_eq!(m.o5, 0);
 
@@ -905,7 +2107,7 @@ mod tests {
         assert_eq!(m.o3, 0xffffdead);
 
         m.i5 = 0xdeadbeef;
-        m.i6 = std::u32::MAX;
+        m.i6 = u32::MAX;
         m.prop();
         assert_eq!(m.o3, 0xffffffff);
 
@@ -920,7 +2122,7 @@ mod tests {
         assert_eq!(m.o4, 0xfffffadebabedead);
 
         m.i7 = 0xfadebabedeadbeef;
-        m.i8 = std::u64::MAX;
+        m.i8 = u64::MAX;
         m.prop();
         assert_eq!(m.o4, 0xffffffffffffffff);
 
@@ -935,7 +2137,7 @@ mod tests {
         assert_eq!(m.o5, 0xffffaaaaaaaa55555555fadebabedead);
 
         m.i9 = 0xaaaaaaaa55555555fadebabedeadbeef;
-        m.i10 = std::u128::MAX;
+        m.i10 = u128::MAX;
         m.prop();
         assert_eq!(m.o5, 0xffffffffffffffffffffffffffffffff);
 
@@ -2070,6 +3272,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(true)),
@@ -2082,6 +3285,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 2,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (1, TraceValue::U32(0)),
@@ -2094,6 +3298,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (1, TraceValue::U32(0)),
@@ -2106,6 +3311,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 64,
                                     type_: TraceValueType::U64,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U64(0)),
                                         (1, TraceValue::U64(0)),
@@ -2118,6 +3324,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 128,
                                     type_: TraceValueType::U128,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U128(0)),
                                         (1, TraceValue::U128(0)),
@@ -2130,6 +3337,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(true)),
@@ -2142,6 +3350,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 2,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (1, TraceValue::U32(0)),
@@ -2154,6 +3363,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (1, TraceValue::U32(0)),
@@ -2166,6 +3376,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 64,
                                     type_: TraceValueType::U64,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U64(0)),
                                         (1, TraceValue::U64(0)),
@@ -2178,6 +3389,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 128,
                                     type_: TraceValueType::U128,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U128(0)),
                                         (1, TraceValue::U128(0)),
@@ -2246,6 +3458,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0xdeadbeef)),
@@ -2261,6 +3474,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0)),
@@ -2276,6 +3490,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0)),
@@ -2291,6 +3506,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0)),
@@ -2306,6 +3522,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0)),
@@ -2321,6 +3538,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0)),
@@ -2386,19 +3604,64 @@ mod tests {
                                 "inner1",
                                 CaptureModule {
                                     children: BTreeMap::new(),
-                                    signals: vec![(
-                                        "r",
-                                        Rc::new(CaptureSignal {
-                                            bit_width: 32,
-                                            type_: TraceValueType::U32,
-                                            values: RefCell::new(vec![
-                                                (0, TraceValue::U32(0)),
-                                                (0, TraceValue::U32(0)),
-                                                (1, TraceValue::U32(0xffff0000)),
-                                                (2, TraceValue::U32(0xffff0000)),
-                                            ]),
-                                        })
-                                    ),]
+                                    signals: vec![
+                                        (
+                                            "i1",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0xffffffff)),
+                                                    (1, TraceValue::U32(0xffffffff)),
+                                                    (2, TraceValue::U32(0xffffffff)),
+                                                ]),
+                                            })
+                                        ),
+                                        (
+                                            "i2",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0xffff0000)),
+                                                    (1, TraceValue::U32(0xffff0000)),
+                                                    (2, TraceValue::U32(0xffff0000)),
+                                                ]),
+                                            })
+                                        ),
+                                        (
+                                            "o",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0)),
+                                                    (1, TraceValue::U32(0xffff0000)),
+                                                    (2, TraceValue::U32(0xffff0000)),
+                                                ]),
+                                            })
+                                        ),
+                                        (
+                                            "r",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0)),
+                                                    (1, TraceValue::U32(0xffff0000)),
+                                                    (2, TraceValue::U32(0xffff0000)),
+                                                ]),
+                                            })
+                                        ),
+                                    ]
                                     .into_iter()
                                     .collect(),
                                 }
@@ -2407,19 +3670,64 @@ mod tests {
                                 "inner2",
                                 CaptureModule {
                                     children: BTreeMap::new(),
-                                    signals: vec![(
-                                        "r",
-                                        Rc::new(CaptureSignal {
-                                            bit_width: 32,
-                                            type_: TraceValueType::U32,
-                                            values: RefCell::new(vec![
-                                                (0, TraceValue::U32(0)),
-                                                (0, TraceValue::U32(0)),
-                                                (1, TraceValue::U32(0x000f0000)),
-                                                (2, TraceValue::U32(0x000f0000)),
-                                            ]),
-                                        })
-                                    ),]
+                                    signals: vec![
+                                        (
+                                            "i1",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0x00ff0000)),
+                                                    (1, TraceValue::U32(0x00ff0000)),
+                                                    (2, TraceValue::U32(0x00ff0000)),
+                                                ]),
+                                            })
+                                        ),
+                                        (
+                                            "i2",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0x000f0000)),
+                                                    (1, TraceValue::U32(0x000f0000)),
+                                                    (2, TraceValue::U32(0x000f0000)),
+                                                ]),
+                                            })
+                                        ),
+                                        (
+                                            "o",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0)),
+                                                    (1, TraceValue::U32(0x000f0000)),
+                                                    (2, TraceValue::U32(0x000f0000)),
+                                                ]),
+                                            })
+                                        ),
+                                        (
+                                            "r",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0)),
+                                                    (1, TraceValue::U32(0x000f0000)),
+                                                    (2, TraceValue::U32(0x000f0000)),
+                                                ]),
+                                            })
+                                        ),
+                                    ]
                                     .into_iter()
                                     .collect(),
                                 }
@@ -2428,19 +3736,64 @@ mod tests {
                                 "inner3",
                                 CaptureModule {
                                     children: BTreeMap::new(),
-                                    signals: vec![(
-                                        "r",
-                                        Rc::new(CaptureSignal {
-                                            bit_width: 32,
-                                            type_: TraceValueType::U32,
-                                            values: RefCell::new(vec![
-                                                (0, TraceValue::U32(0)),
-                                                (0, TraceValue::U32(0)),
-                                                (1, TraceValue::U32(0)),
-                                                (2, TraceValue::U32(0x000f0000)),
-                                            ]),
-                                        })
-                                    ),]
+                                    signals: vec![
+                                        (
+                                            "i1",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0)),
+                                                    (1, TraceValue::U32(0xffff0000)),
+                                                    (2, TraceValue::U32(0xffff0000)),
+                                                ]),
+                                            })
+                                        ),
+                                        (
+                                            "i2",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0)),
+                                                    (1, TraceValue::U32(0x000f0000)),
+                                                    (2, TraceValue::U32(0x000f0000)),
+                                                ]),
+                                            })
+                                        ),
+                                        (
+                                            "o",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0)),
+                                                    (1, TraceValue::U32(0)),
+                                                    (2, TraceValue::U32(0x000f0000)),
+                                                ]),
+                                            })
+                                        ),
+                                        (
+                                            "r",
+                                            Rc::new(CaptureSignal {
+                                                bit_width: 32,
+                                                type_: TraceValueType::U32,
+                                                signed: false,
+                                                values: RefCell::new(vec![
+                                                    (0, TraceValue::U32(0)),
+                                                    (0, TraceValue::U32(0)),
+                                                    (1, TraceValue::U32(0)),
+                                                    (2, TraceValue::U32(0x000f0000)),
+                                                ]),
+                                            })
+                                        ),
+                                    ]
                                     .into_iter()
                                     .collect(),
                                 }
@@ -2454,6 +3807,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0xffffffff)),
@@ -2467,6 +3821,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0xffff0000)),
@@ -2480,6 +3835,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0x00ff0000)),
@@ -2493,6 +3849,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0x000f0000)),
@@ -2506,6 +3863,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 32,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (0, TraceValue::U32(0)),
@@ -2621,6 +3979,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(false)),
@@ -2640,6 +3999,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(false)),
@@ -2659,6 +4019,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(false)),
@@ -2678,6 +4039,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(false)),
@@ -2697,6 +4059,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 4,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (1, TraceValue::U32(0)),
@@ -2716,6 +4079,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(false)),
@@ -2735,6 +4099,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 4,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (1, TraceValue::U32(0)),
@@ -2754,6 +4119,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(false)),
@@ -2773,6 +4139,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(false)),
@@ -2792,6 +4159,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 1,
                                     type_: TraceValueType::Bool,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::Bool(false)),
                                         (1, TraceValue::Bool(false)),
@@ -2811,6 +4179,7 @@ mod tests {
                                 Rc::new(CaptureSignal {
                                     bit_width: 4,
                                     type_: TraceValueType::U32,
+                                    signed: false,
                                     values: RefCell::new(vec![
                                         (0, TraceValue::U32(0)),
                                         (1, TraceValue::U32(0)),
@@ -2848,4 +4217,354 @@ mod tests {
         m.prop();
         assert_eq!(m.o, false);
     }
+
+    #[test]
+    fn clock_domain_test_module() {
+        let mut m = ClockDomainTestModule::new();
+
+        // Check initial value
+        m.reset();
+        m.prop();
+        assert_eq!(m.o1, 0);
+        assert_eq!(m.o2, 0);
+
+        // Implicit clock only affects implicit clock domain
+        m.i1 = 0xdeadbeef;
+        m.i2 = 0xfadebabe;
+        m.i3 = 0xabad1dea;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.o1, 0xdeadbeef);
+        assert_eq!(m.o2, 0);
+
+        // Explicit clock only affects its own domain, including instances driven by it
+        m.i1 = 0xfadebabe;
+        m.prop();
+        m.posedge_pixel_clk();
+        m.prop();
+        assert_eq!(m.o1, 0xdeadbeef);
+        assert_eq!(m.o2, 0xfadebabe);
+        m.posedge_pixel_clk();
+        m.prop();
+        assert_eq!(m.o3, 0xabad1dea);
+    }
+
+    #[test]
+    fn reset_test_module() {
+        let mut m = ResetTestModule::new();
+
+        // Async reset takes effect without a clock edge
+        m.arst_n = false;
+        m.prop();
+        assert_eq!(m.o1, 0xfadebabe);
+
+        // Async reset holds registers in reset across clock edges
+        m.i1 = 0xabad1dea;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.o1, 0xfadebabe);
+
+        // Released async reset lets registers update again
+        m.arst_n = true;
+        m.prop();
+        assert_eq!(m.o1, 0xfadebabe);
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.o1, 0xabad1dea);
+
+        // Sync reset only takes effect on a clock edge
+        m.i2 = 0xabad1dea;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.o2, 0xabad1dea);
+        m.srst = true;
+        m.prop();
+        assert_eq!(m.o2, 0xabad1dea);
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.o2, 0xdeadbeef);
+        m.srst = false;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.o2, 0xabad1dea);
+
+        // Implicit reset only affects registers without an explicit reset
+        m.i3 = 0xabad1dea;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.o3, 0xabad1dea);
+        m.reset();
+        m.prop();
+        assert_eq!(m.o1, 0xabad1dea);
+        assert_eq!(m.o2, 0xabad1dea);
+        assert_eq!(m.o3, 0);
+
+        // Synchronized reset is released one cycle after the async reset is released
+        m.i4 = 0xabad1dea;
+        m.arst_n = false;
+        m.prop();
+        m.arst_n = true;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.o4, 0);
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.o4, 0xabad1dea);
+    }
+
+    #[test]
+    fn wide_test_module() {
+        let mut m = WideTestModule::new();
+
+        m.i1 = WideUint::from_words([!0, !0, !0, 0]);
+        m.i2 = WideUint::from(1u32);
+        m.prop();
+        assert_eq!(m.add, WideUint::from_words([0, 0, 0, 1]));
+        assert_eq!(m.sub, WideUint::from_words([!0 - 1, !0, !0, 0]));
+        assert_eq!(m.bit_and, WideUint::from(1u32));
+        assert_eq!(m.bit_or, m.i1);
+        assert_eq!(m.bit_xor, WideUint::from_words([!0 - 1, !0, !0, 0]));
+        assert_eq!(m.eq, false);
+        assert_eq!(m.lt, false);
+        assert_eq!(m.lt_signed, false);
+        assert_eq!(m.ge_signed, true);
+
+        m.i1 = !WideUint::ZERO;
+        m.prop();
+        assert_eq!(m.add, WideUint::ZERO);
+        assert_eq!(m.lt, false);
+        assert_eq!(m.lt_signed, true);
+        assert_eq!(m.ge_signed, false);
+
+        m.i1 = WideUint::ZERO;
+        m.prop();
+        assert_eq!(m.sub, !WideUint::ZERO);
+        assert_eq!(m.lt, true);
+        assert_eq!(m.lt_signed, true);
+
+        m.i2 = WideUint::ZERO;
+        m.prop();
+        assert_eq!(m.eq, true);
+
+        m.i1 = WideUint::from_words([1, 2, 3, 4]);
+        m.i2 = WideUint::from_words([5, 6, 7, 8]);
+        m.sel = true;
+        m.prop();
+        assert_eq!(m.mux, m.i1);
+        m.sel = false;
+        m.prop();
+        assert_eq!(m.mux, m.i2);
+
+        m.i1 = WideUint::from(1u32);
+        m.i2 = WideUint::from(255u32);
+        m.prop();
+        assert_eq!(m.shl_wide_rhs, WideUint::from_words([0, 0, 0, 1 << 63]));
+        m.i2 = WideUint::from(256u32);
+        m.prop();
+        assert_eq!(m.shl_wide_rhs, WideUint::ZERO);
+        m.i2 = WideUint::from_words([0, 0, 0, 1]);
+        m.prop();
+        assert_eq!(m.shl_wide_rhs, WideUint::ZERO);
+
+        m.i1 = WideUint::from_words([0, 0, 0, 1]);
+        m.i2 = WideUint::from(3u32);
+        m.prop();
+        assert_eq!(
+            m.div,
+            WideUint::from_words([0x5555555555555555; 3]).resize()
+        );
+        assert_eq!(m.rem, WideUint::from(1u32));
+        assert_eq!(m.div_signed, m.div);
+        assert_eq!(m.rem_signed, m.rem);
+
+        m.i1 = WideUint::ZERO.wrapping_sub(WideUint::from(7u32)); // -7
+        m.i2 = WideUint::from(2u32);
+        m.prop();
+        assert_eq!(m.div, WideUint::from_words([!0 - 3, !0, !0, !0 >> 1]));
+        assert_eq!(m.rem, WideUint::from(1u32));
+        assert_eq!(
+            m.div_signed,
+            WideUint::ZERO.wrapping_sub(WideUint::from(3u32))
+        );
+        assert_eq!(m.rem_signed, !WideUint::ZERO);
+
+        m.i2 = WideUint::ZERO;
+        m.prop();
+        assert_eq!(m.div, !WideUint::ZERO);
+        assert_eq!(m.rem, m.i1);
+        assert_eq!(m.div_signed, !WideUint::ZERO);
+        assert_eq!(m.rem_signed, m.i1);
+
+        m.i3 = WideUint::ZERO;
+        m.prop();
+        assert_eq!(m.inverted, WideUint::from_words([!0, !0, !0, 0xff]));
+
+        m.i3 = WideUint::from(1u32);
+        m.shift = 199;
+        m.prop();
+        assert_eq!(m.shl, WideUint::from_words([0, 0, 0, 0x80]));
+        m.shift = 200;
+        m.prop();
+        assert_eq!(m.shl, WideUint::ZERO);
+        m.shift = 511;
+        m.prop();
+        assert_eq!(m.shl, WideUint::ZERO);
+
+        m.i3 = WideUint::from_words([0, 0, 0, 0x80]);
+        m.shift = 199;
+        m.prop();
+        assert_eq!(m.shr, WideUint::from(1u32));
+        m.shift = 300;
+        m.prop();
+        assert_eq!(m.shr, WideUint::ZERO);
+
+        m.shift = 1;
+        m.prop();
+        assert_eq!(m.shr_arithmetic, WideUint::from_words([0, 0, 0, 0xc0]));
+        m.shift = 300;
+        m.prop();
+        assert_eq!(m.shr_arithmetic, WideUint::from_words([!0, !0, !0, 0xff]));
+        m.i3 = WideUint::from_words([0, 0, 0, 0x40]);
+        m.shift = 300;
+        m.prop();
+        assert_eq!(m.shr_arithmetic, WideUint::ZERO);
+
+        m.i3 = WideUint::from(u128::MAX);
+        m.i4 = u64::MAX as u128;
+        m.prop();
+        assert_eq!(m.mul, WideUint::from_words([1, !0, !0 - 1, 0, 0]));
+
+        m.i3 = WideUint::from_words([!0, !0, !0, 0xff]);
+        m.i4 = 3;
+        m.prop();
+        assert_eq!(
+            m.mul_signed,
+            WideUint::from_words([!0 - 2, !0, !0, !0, 0xfff_ffff_ffff])
+        );
+
+        m.i3 = WideUint::from_words([
+            0x0123456789abcdef,
+            0xfedcba9876543210,
+            0x1122334455667788,
+            0x99,
+        ]);
+        m.prop();
+        assert_eq!(m.bits_narrow, 0x9911223344556677);
+        assert_eq!(
+            m.bits_wide,
+            WideUint::from_words([0x100123456789abcd, 0x88fedcba98765432, 0x9911223344556677])
+        );
+
+        m.i5 = 0xfadebabedeadbeefabad1deabadc0de5;
+        m.i6 = 0x0123456789abcdeffedcba9876543210;
+        m.prop();
+        assert_eq!(
+            m.concat,
+            WideUint::from_words([
+                0xfedcba9876543210,
+                0x0123456789abcdef,
+                0xabad1deabadc0de5,
+                0xfadebabedeadbeef,
+            ])
+        );
+
+        m.i4 = 1;
+        m.prop();
+        assert_eq!(m.repeated, WideUint::from_words([1, 1 << 36, 0, 1 << 8, 0]));
+
+        assert_eq!(
+            m.lit,
+            WideUint::from_words([0xdeadbeef, 0xfadebabe, 0xabad1dea, 0xc0cac01a])
+        );
+
+        m.reset();
+        m.prop();
+        assert_eq!(m.reg_value, WideUint::from_words([1, 2, 3, 4]));
+        m.i1 = WideUint::from_words([5, 6, 7, 8]);
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.reg_value, WideUint::from_words([5, 6, 7, 8]));
+
+        m.read_addr = true;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.mem_value, WideUint::from_words([5, 6, 7, 8]));
+
+        m.write_addr = false;
+        m.write_enable = true;
+        m.i2 = WideUint::from_words([9, 10, 11, 12]);
+        m.read_addr = false;
+        m.prop();
+        m.posedge_clk();
+        m.write_enable = false;
+        m.prop();
+        m.posedge_clk();
+        m.prop();
+        assert_eq!(m.mem_value, WideUint::from_words([9, 10, 11, 12]));
+    }
+
+    #[test]
+    fn wide_trace_test_module() -> io::Result<()> {
+        let mut capture = Capture::new();
+        let trace = CaptureTrace::new(&mut capture);
+
+        let mut m = WideTraceTestModule::new(trace)?;
+
+        m.prop();
+        m.update_trace(0)?;
+
+        m.i = WideUint::from_words([1, 2, 3]);
+        m.prop();
+        m.update_trace(1)?;
+
+        assert_eq!(
+            capture,
+            Capture {
+                root: Some((
+                    "wide_trace_test_module",
+                    CaptureModule {
+                        children: BTreeMap::new(),
+                        signals: vec![
+                            (
+                                "i",
+                                Rc::new(CaptureSignal {
+                                    bit_width: 192,
+                                    type_: TraceValueType::Wide,
+                                    signed: false,
+                                    values: RefCell::new(vec![
+                                        (0, TraceValue::Wide(vec![0, 0, 0])),
+                                        (1, TraceValue::Wide(vec![1, 2, 3])),
+                                    ]),
+                                })
+                            ),
+                            (
+                                "o",
+                                Rc::new(CaptureSignal {
+                                    bit_width: 192,
+                                    type_: TraceValueType::Wide,
+                                    signed: false,
+                                    values: RefCell::new(vec![
+                                        (0, TraceValue::Wide(vec![0, 0, 0])),
+                                        (1, TraceValue::Wide(vec![1, 2, 3])),
+                                    ]),
+                                })
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    }
+                )),
+            }
+        );
+
+        Ok(())
+    }
 }